@@ -0,0 +1,28 @@
+// Compares BLAKE2B against BLAKE2BP and BLAKE2SP on a large in-memory buffer. The parallel modes should scale with the number of cores, up to 4 (BLAKE2BP) or 8 (BLAKE2SP).
+//
+// Run with `cargo bench --bench blake2_parallel --features blake2-parallel`
+
+use paranoid_hash::{Algo, ParanoidHashMulti};
+use std::thread;
+use std::time::Instant;
+
+const SIZE: usize = 256 * 1024 * 1024;
+
+fn main(){
+    let data: Vec<u8> = (0..SIZE).map(|i| (i % 251) as u8).collect();
+    let cores = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    println!("cores: {}",cores);
+
+    let mut baseline = None;
+    for (name,algo) in [("BLAKE2B",Algo::Blake2b(64)),("BLAKE2BP",Algo::Blake2bp(64)),("BLAKE2SP",Algo::Blake2sp(32))] {
+        let context = ParanoidHashMulti::new(&[algo]);
+
+        let start = Instant::now();
+        context.read_bytes(&data);
+        let elapsed = start.elapsed();
+        let throughput = SIZE as f64 / elapsed.as_secs_f64() / (1024.0 * 1024.0 * 1024.0);
+
+        let baseline = *baseline.get_or_insert(elapsed);
+        println!("{:<9} {:>10.2?} {:>6.2} GiB/s {:>6.2}x",name,elapsed,throughput,baseline.as_secs_f64() / elapsed.as_secs_f64());
+    }
+}
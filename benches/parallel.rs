@@ -0,0 +1,27 @@
+// Compares `read_bytes()` against `read_bytes_parallel()` on a large in-memory buffer.
+//
+// Run with `cargo bench --bench parallel`
+
+use paranoid_hash::ParanoidHash;
+use std::time::Instant;
+
+const SIZE: usize = 256 * 1024 * 1024;
+
+fn main(){
+    let data: Vec<u8> = (0..SIZE).map(|i| (i % 251) as u8).collect();
+    let context = ParanoidHash::default();
+
+    let start = Instant::now();
+    let sequential = context.read_bytes(&data);
+    let sequential_time = start.elapsed();
+
+    let start = Instant::now();
+    let parallel = context.read_bytes_parallel(&data);
+    let parallel_time = start.elapsed();
+
+    assert_eq!(sequential,parallel);
+
+    println!("sequential: {:?}",sequential_time);
+    println!("parallel:   {:?}",parallel_time);
+    println!("speedup:    {:.2}x",sequential_time.as_secs_f64() / parallel_time.as_secs_f64());
+}
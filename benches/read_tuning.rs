@@ -0,0 +1,41 @@
+// Compares `read_using_std()` on a cold file with and without `ReadTuning` hints.
+//
+// The hints only do something on Linux and Android with the `fadvise` feature. The file is evicted from the page cache before each run by hashing it with `drop_cache`, which needs the same feature, so without it every run reads a warm file and the times should be the same.
+//
+// Run with `cargo bench --bench read_tuning --features fadvise`
+
+use paranoid_hash::{ParanoidHash, ReadTuning};
+use std::io::Write;
+use std::time::Instant;
+
+const FILE_SIZE: usize = 512 * 1024 * 1024;
+
+fn main(){
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    let block: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+    for _ in 0..FILE_SIZE / block.len() {
+        file.write_all(&block).unwrap();
+    }
+    file.flush().unwrap();
+
+    let evict = ParanoidHash::default().read_tuning(ReadTuning { sequential: false, drop_cache: true });
+    let runs = [
+        ("no hints", ReadTuning::default()),
+        ("sequential", ReadTuning { sequential: true, drop_cache: false }),
+        ("sequential + drop_cache", ReadTuning { sequential: true, drop_cache: true }),
+    ];
+
+    let mut expected = None;
+    for (name, tuning) in runs.iter() {
+        evict.read_using_std(file.path()).unwrap();
+
+        let context = ParanoidHash::default().read_tuning(*tuning);
+        let start = Instant::now();
+        let digests = context.read_using_std(file.path()).unwrap();
+        let elapsed = start.elapsed();
+
+        // The hints never change the digests
+        assert_eq!(expected.get_or_insert_with(|| digests.clone()),&digests);
+        println!("{:<24} {:?} ({:.0} MB/s)",name,elapsed,FILE_SIZE as f64 / elapsed.as_secs_f64() / 1_000_000.0);
+    }
+}
@@ -0,0 +1,55 @@
+// Compares allocations and time per call of `read_bytes()` against `ParanoidHashSession::hash_bytes_into()` on many small inputs.
+//
+// Run with `cargo bench --bench session`
+
+use paranoid_hash::ParanoidHash;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+const CALLS: usize = 1_000_000;
+
+// Counts every allocation made by the process
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main(){
+    let messages: Vec<[u8; 32]> = (0..CALLS).map(|i| [(i % 251) as u8; 32]).collect();
+    let context = ParanoidHash::default();
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for message in messages.iter() {
+        let digests = context.read_bytes(message);
+        std::hint::black_box(digests);
+    }
+    let read_bytes_time = start.elapsed();
+    let read_bytes_allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    let mut session = context.session();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for message in messages.iter() {
+        let digests = session.hash_bytes_into(message).unwrap();
+        std::hint::black_box(digests);
+    }
+    let session_time = start.elapsed();
+    let session_allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!("read_bytes:      {:?} ({:.1} allocations per call)",read_bytes_time,read_bytes_allocations as f64 / CALLS as f64);
+    println!("hash_bytes_into: {:?} ({:.1} allocations per call)",session_time,session_allocations as f64 / CALLS as f64);
+}
@@ -0,0 +1,170 @@
+#ifndef PARANOID_HASH_H
+#define PARANOID_HASH_H
+
+/* Generated with cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * SHA1 as the Operating System Hash Function
+ */
+#define PARANOID_HASH_SHA1 1
+
+/**
+ * SHA256 as the Operating System Hash Function
+ */
+#define PARANOID_HASH_SHA256 2
+
+/**
+ * SHA512 as the Operating System Hash Function
+ */
+#define PARANOID_HASH_SHA512 3
+
+/**
+ * MD5 as the Operating System Hash Function, only accepted when the crate is built with the `insecure-md5` feature
+ */
+#define PARANOID_HASH_MD5 4
+
+/**
+ * SHA512_256 as the Operating System Hash Function
+ */
+#define PARANOID_HASH_SHA512_256 5
+
+/**
+ * SHA224 as the Operating System Hash Function
+ */
+#define PARANOID_HASH_SHA224 6
+
+/**
+ * # Status
+ *
+ * Returned by every function that can fail. The values up to 99 mirror `FileError`, with `FileError::AtPath` reported as the error it wraps.
+ */
+typedef enum ParanoidHashStatus {
+  PARANOID_HASH_STATUS_OK = 0,
+  PARANOID_HASH_STATUS_FILE_NOT_FOUND = 1,
+  PARANOID_HASH_STATUS_OS_HASHING_ERROR = 2,
+  PARANOID_HASH_STATUS_IO = 3,
+  PARANOID_HASH_STATUS_EMPTY_FILE = 4,
+  PARANOID_HASH_STATUS_CANCELLED = 5,
+  PARANOID_HASH_STATUS_DIGEST_DISAGREEMENT = 6,
+  PARANOID_HASH_STATUS_INVALID_DIGEST = 7,
+  PARANOID_HASH_STATUS_KEY_FILE = 8,
+  PARANOID_HASH_STATUS_INVALID_KEY = 9,
+  PARANOID_HASH_STATUS_INVALID_MANIFEST = 10,
+  PARANOID_HASH_STATUS_UNSUPPORTED_MANIFEST_VERSION = 11,
+  PARANOID_HASH_STATUS_CONFLICTING_CHECKSUMS = 12,
+  PARANOID_HASH_STATUS_FILE_TOO_LARGE = 13,
+  PARANOID_HASH_STATUS_VERIFICATION_FAILED = 14,
+  PARANOID_HASH_STATUS_IMPLEMENTATION_MISMATCH = 15,
+  PARANOID_HASH_STATUS_INVALID_GZIP = 16,
+  PARANOID_HASH_STATUS_INVALID_RANGE = 17,
+  PARANOID_HASH_STATUS_READ_INCONSISTENCY = 18,
+  PARANOID_HASH_STATUS_RETRIES_EXHAUSTED = 19,
+  PARANOID_HASH_STATUS_SYMLINK_LOOP = 20,
+  PARANOID_HASH_STATUS_INVALID_DIGEST_SIZE = 21,
+  PARANOID_HASH_STATUS_OS_ALGORITHM_UNAVAILABLE = 22,
+  PARANOID_HASH_STATUS_XATTR_UNSUPPORTED = 23,
+  PARANOID_HASH_STATUS_NO_EXPECTED_DIGEST = 24,
+  PARANOID_HASH_STATUS_WEAK_ALGORITHM = 25,
+  PARANOID_HASH_STATUS_NOT_RESUMABLE = 26,
+  PARANOID_HASH_STATUS_INVALID_HASHER_STATE = 27,
+  PARANOID_HASH_STATUS_UNSUPPORTED_STATE_VERSION = 28,
+  PARANOID_HASH_STATUS_TIMED_OUT = 29,
+  PARANOID_HASH_STATUS_MODIFIED_DURING_HASH = 30,
+  /**
+   * A required pointer was null
+   */
+  PARANOID_HASH_STATUS_NULL_POINTER = 100,
+  /**
+   * A path is not valid for this platform (it must be UTF-8 on Windows)
+   */
+  PARANOID_HASH_STATUS_INVALID_ARGUMENT = 101,
+  /**
+   * The buffer cannot hold the digest and its terminating NUL
+   */
+  PARANOID_HASH_STATUS_BUFFER_TOO_SMALL = 102,
+  /**
+   * Nothing has been hashed successfully with this handle yet
+   */
+  PARANOID_HASH_STATUS_NO_DIGEST = 103,
+  /**
+   * The crate panicked. This is a bug.
+   */
+  PARANOID_HASH_STATUS_PANIC = 104,
+} ParanoidHashStatus;
+
+/**
+ * # Handle
+ *
+ * A configuration along with the digests of the last successful call. Created with `paranoid_hash_new()` and released with `paranoid_hash_free()`.
+ *
+ * A handle must not be used from two threads at the same time.
+ */
+typedef struct ParanoidHashHandle ParanoidHashHandle;
+
+/**
+ * Creates a handle hashing with BLAKE2B at `digest_size` bytes (1-64) and the Operating System Hash Function `os_algorithm` (one of the `PARANOID_HASH_SHA256`, `PARANOID_HASH_SHA512`, `PARANOID_HASH_SHA512_256`, `PARANOID_HASH_SHA224` or `PARANOID_HASH_MD5` constants). `PARANOID_HASH_MD5` needs the `insecure-md5` feature and returns null without it.
+ *
+ * Returns null if either argument is out of range. `PARANOID_HASH_SHA1` is broken and also returns null; use `paranoid_hash_new_allowing_weak()` for it.
+ */
+struct ParanoidHashHandle *paranoid_hash_new(size_t digest_size, uint32_t os_algorithm);
+
+/**
+ * Same as `paranoid_hash_new()`, but also accepts `PARANOID_HASH_SHA1`, for verifying digests that were recorded with it.
+ */
+struct ParanoidHashHandle *paranoid_hash_new_allowing_weak(size_t digest_size, uint32_t os_algorithm);
+
+/**
+ * Releases a handle created with `paranoid_hash_new()`. Passing null does nothing.
+ *
+ * # Safety
+ *
+ * `handle` must be null or a handle from `paranoid_hash_new()` that has not been freed.
+ */
+void paranoid_hash_free(struct ParanoidHashHandle *handle);
+
+/**
+ * Hashes the file at `path` (a NUL-terminated string) and keeps the digests in the handle. On failure the previous digests are cleared.
+ *
+ * # Safety
+ *
+ * `handle` must be a valid handle and `path` a NUL-terminated string.
+ */
+enum ParanoidHashStatus paranoid_hash_file(struct ParanoidHashHandle *handle, const char *path);
+
+/**
+ * Hashes `length` bytes at `data` and keeps the digests in the handle. `data` may be null when `length` is 0.
+ *
+ * # Safety
+ *
+ * `handle` must be a valid handle and `data` must point to `length` readable bytes.
+ */
+enum ParanoidHashStatus paranoid_hash_bytes(struct ParanoidHashHandle *handle,
+                                            const uint8_t *data,
+                                            size_t length);
+
+/**
+ * Copies the BLAKE2B digest of the last successful call into `buffer` as a NUL-terminated upper hexadecimal string. `buffer_length` must be at least twice the digest size plus one.
+ *
+ * # Safety
+ *
+ * `handle` must be a valid handle and `buffer` must point to `buffer_length` writable bytes.
+ */
+enum ParanoidHashStatus paranoid_hash_blake2b_hex(const struct ParanoidHashHandle *handle,
+                                                  char *buffer,
+                                                  size_t buffer_length);
+
+/**
+ * Copies the Operating System digest of the last successful call into `buffer` as a NUL-terminated upper hexadecimal string. `buffer_length` must be at least twice the digest size plus one (129 always suffices).
+ *
+ * # Safety
+ *
+ * `handle` must be a valid handle and `buffer` must point to `buffer_length` writable bytes.
+ */
+enum ParanoidHashStatus paranoid_hash_os_hex(const struct ParanoidHashHandle *handle,
+                                             char *buffer,
+                                             size_t buffer_length);
+
+#endif /* PARANOID_HASH_H */
@@ -0,0 +1,172 @@
+// Hardware Acceleration
+//
+// Reports whether each Operating System Hash Function is likely to run on the SHA extensions of the processor (SHA-NI on x86, the ARMv8 cryptography extensions on AArch64). Detection combines the CPU features reported by the standard library with what is known about each backend, and an optional short benchmark measures the throughput actually reached.
+
+use std::time::{Duration, Instant};
+
+use crate::hashers::os_hasher;
+use crate::probe::OS_ALGORITHMS;
+use crate::{OsAlgorithm, ParanoidHash};
+
+// The in-memory buffer hashed by the benchmark, in bytes
+const BENCH_BUFFER: usize = 64 * 1024;
+
+/// # Acceleration Backend
+///
+/// Which implementation computes an Operating System Hash Function.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AccelBackend {
+    /// The crypto library of the operating system through `crypto-hash` (OpenSSL, CommonCrypto or CNG), which uses the SHA extensions whenever the processor has them
+    OperatingSystem,
+    /// The `sha2` crate, which detects the SHA extensions at runtime (used for SHA224 and SHA512_256)
+    PureRust,
+}
+
+/// # Algorithm Acceleration
+///
+/// The acceleration of a single Operating System Hash Function.
+#[derive(Debug,Clone,PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AlgorithmAccel {
+    pub algorithm: OsAlgorithm,
+    pub backend: AccelBackend,
+    /// Whether the operating system can compute it (see `OsAlgorithm::is_available()`)
+    pub available: bool,
+    /// Whether the processor has instructions for it that the backend is likely to use
+    pub hardware: bool,
+    /// The throughput measured by `AccelInfo::benchmark()` in MiB per second, or `None` without a benchmark
+    pub throughput: Option<f64>,
+    /// The measured throughput as a fraction of the fastest algorithm (which has `1.0`), or `None` without a benchmark
+    pub relative_throughput: Option<f64>,
+}
+
+/// # Acceleration Info
+///
+/// Returned by `ParanoidHash::acceleration_info()`.
+#[derive(Debug,Clone,PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AccelInfo {
+    /// The SHA related CPU features detected at runtime, by their `std::arch` names (such as `"sha"` on x86 or `"sha2"` and `"sha3"` on AArch64)
+    pub cpu_features: Vec<&'static str>,
+    /// Every Operating System Hash Function, in the order SHA1, SHA224, SHA256, SHA512, SHA512_256, MD5 (with the `insecure-md5` feature)
+    pub algorithms: Vec<AlgorithmAccel>,
+}
+
+impl AccelInfo {
+    /// ## Get
+    ///
+    /// The entry of an Operating System Hash Function.
+    pub fn get(&self, algorithm: &OsAlgorithm) -> Option<&AlgorithmAccel> {
+        return self.algorithms.iter().find(|accel| accel.algorithm == *algorithm)
+    }
+    /// ## Benchmark
+    ///
+    /// Measures the throughput of every available algorithm by hashing a 64 KiB buffer in memory over and over, and fills in `throughput` and `relative_throughput`. The budget is split evenly between the algorithms and each one stops at the end of the pass that exceeds its share, so the whole benchmark takes about `budget` plus at most one pass per algorithm.
+    ///
+    /// This keeps a core busy for the whole budget, which is why it is never run by `acceleration_info()` itself. A budget of a few hundred milliseconds is enough for a rough comparison.
+    pub fn benchmark(mut self, budget: Duration) -> Self {
+        let share = budget / self.algorithms.len() as u32;
+        let buffer = vec![0u8; BENCH_BUFFER];
+
+        for accel in self.algorithms.iter_mut().filter(|accel| accel.available) {
+            accel.throughput = measure(&accel.algorithm, &buffer, share);
+        }
+
+        let fastest = self.algorithms.iter().filter_map(|accel| accel.throughput).fold(0.0, f64::max);
+        if fastest > 0.0 {
+            for accel in self.algorithms.iter_mut() {
+                accel.relative_throughput = accel.throughput.map(|throughput| throughput / fastest);
+            }
+        }
+        return self
+    }
+}
+
+impl ParanoidHash {
+    /// # Acceleration Info
+    ///
+    /// Reports for every Operating System Hash Function whether hardware acceleration is likely to be in use. Nothing is hashed apart from the availability probes; call `benchmark()` on the result to also measure the throughput.
+    ///
+    /// `hardware` is a prediction from the CPU features and the backend:
+    ///
+    /// * SHA1, SHA224 and SHA256 use SHA-NI on x86 and the SHA1/SHA2 extensions on AArch64
+    /// * SHA512 and SHA512_256 use the SHA512 extensions on AArch64 (part of `"sha3"`), and run in software on x86
+    /// * MD5 never has hardware support
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    /// use std::time::Duration;
+    ///
+    /// let info = ParanoidHash::acceleration_info().benchmark(Duration::from_millis(60));
+    /// let sha256 = info.get(&OsAlgorithm::SHA256).unwrap();
+    /// println!("SHA256 hardware: {} ({:.0} MiB/s)",sha256.hardware,sha256.throughput.unwrap());
+    /// ```
+    pub fn acceleration_info() -> AccelInfo {
+        let cpu_features = cpu_features();
+        let algorithms = OS_ALGORITHMS.iter().map(|algorithm| AlgorithmAccel {
+            algorithm: algorithm.clone(),
+            backend: backend(algorithm),
+            available: algorithm.is_available(),
+            hardware: hardware(algorithm, &cpu_features),
+            throughput: None,
+            relative_throughput: None,
+        }).collect();
+        return AccelInfo { cpu_features, algorithms }
+    }
+}
+
+fn backend(algorithm: &OsAlgorithm) -> AccelBackend {
+    match algorithm {
+        OsAlgorithm::SHA224 | OsAlgorithm::SHA512_256 => return AccelBackend::PureRust,
+        _ => return AccelBackend::OperatingSystem,
+    }
+}
+
+fn hardware(algorithm: &OsAlgorithm, cpu_features: &[&'static str]) -> bool {
+    let has = |feature: &str| cpu_features.contains(&feature);
+    match algorithm {
+        OsAlgorithm::SHA1 | OsAlgorithm::SHA224 | OsAlgorithm::SHA256 => return has("sha") || has("sha2"),
+        OsAlgorithm::SHA512 | OsAlgorithm::SHA512_256 => return has("sha3"),
+        #[cfg(feature = "insecure-md5")]
+        OsAlgorithm::MD5 => return false,
+    }
+}
+
+#[allow(unused_mut)]
+fn cpu_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::arch::is_x86_feature_detected!("sha") {
+            features.push("sha");
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("sha2") {
+            features.push("sha2");
+        }
+        if std::arch::is_aarch64_feature_detected!("sha3") {
+            features.push("sha3");
+        }
+    }
+    return features
+}
+
+// Hashes `buffer` until `share` has passed (at least once) and returns the throughput in MiB per second
+fn measure(algorithm: &OsAlgorithm, buffer: &[u8], share: Duration) -> Option<f64> {
+    let mut backend = os_hasher(algorithm).ok()?;
+    let start = Instant::now();
+    let mut bytes = 0u64;
+    loop {
+        backend.update(buffer).ok()?;
+        bytes += buffer.len() as u64;
+        if start.elapsed() >= share {
+            break
+        }
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    backend.finish().ok()?;
+    return Some(bytes as f64 / (1024.0 * 1024.0) / elapsed.max(f64::MIN_POSITIVE))
+}
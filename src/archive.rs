@@ -0,0 +1,69 @@
+// Archive Members
+//
+// Hashes the files inside a tar archive as it is streamed, without extracting anything to the filesystem. With the `gzip` feature, gzip compressed archives (`.tar.gz`) are recognized by their first bytes and decompressed on the fly.
+
+use std::io::{BufReader, Read};
+#[cfg(feature = "gzip")]
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use ::tar::{Archive, EntryType};
+
+#[cfg(feature = "gzip")]
+use crate::gzip::{gzip_decoder, gzip_error};
+use crate::retry::RetryReader;
+use crate::{long_path, FileError, ParanoidHash};
+
+// The first two bytes of every gzip member
+#[cfg(feature = "gzip")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+impl ParanoidHash {
+    /// # Read Tar Members
+    ///
+    /// Requires the `tar` feature.
+    ///
+    /// Hashes every regular file in the tar archive at `tar_path`, in archive order, and returns each member's path (as stored in the archive, lossily converted to UTF-8) with its digests. The archive is read once and nothing is extracted.
+    ///
+    /// Directories, symbolic links, hard links and special files (devices, FIFOs) have no contents of their own and are skipped. A member that appears more than once is returned every time it appears.
+    ///
+    /// With the `gzip` feature, a gzip compressed archive is decompressed as it is read.
+    ///
+    /// A malformed archive returns `FileError::Io` (or `FileError::InvalidGzip` if its compression is corrupt). An error hashing a member (such as `FileError::FileTooLarge` with `max_file_size()`) is returned as `FileError::AtPath` with the member's path.
+    pub fn read_tar_members<T: AsRef<Path>>(&self, tar_path: T) -> Result<Vec<(String,String,String)>,FileError> {
+
+        let tar_path = long_path::extended(tar_path.as_ref());
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !tar_path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+
+        let file = self.open_file(&tar_path)?;
+        #[allow(unused_mut)]
+        let mut reader = BufReader::new(RetryReader::new(file, self.retry_policy));
+
+        #[cfg(feature = "gzip")]
+        if reader.fill_buf().map_err(|e| FileError::Io(e.kind()))?.starts_with(&GZIP_MAGIC) {
+            return self.hash_tar(gzip_decoder(reader)).map_err(gzip_error)
+        }
+        return self.hash_tar(reader)
+    }
+    fn hash_tar<R: Read>(&self, reader: R) -> Result<Vec<(String,String,String)>,FileError> {
+        let mut archive = Archive::new(reader);
+        let mut members = Vec::new();
+
+        for entry in archive.entries().map_err(|e| FileError::Io(e.kind()))? {
+            let entry = entry.map_err(|e| FileError::Io(e.kind()))?;
+            match entry.header().entry_type() {
+                EntryType::Regular | EntryType::Continuous => {}
+                _ => continue,
+            }
+
+            let path = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+            let ((blake2b, os), _) = self.hash_reader(entry).map_err(|error| FileError::AtPath(PathBuf::from(&path), Box::new(error)))?;
+            members.push((path, blake2b, os));
+        }
+        return Ok(members)
+    }
+}
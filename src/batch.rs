@@ -0,0 +1,77 @@
+// Batch Hashing
+//
+// Hashes a known list of files, keeping going after a failure so every path gets its own result. The files are shared out between up to `threads()` threads, and the results are put back in the order of the paths.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::{FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Files
+    ///
+    /// Hashes every path and returns one result per path, in the same order as `paths`. An error (such as a missing file or denied permission) is kept in that path's result and the remaining files are still hashed.
+    ///
+    /// Up to `threads()` files are hashed at once. Each thread allocates one buffer for the files that are not memory mapped and reuses it for every file it hashes.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,FileError};
+    ///
+    /// let results = ParanoidHash::default().read_files(&["examples/example_file.txt","missing.txt"]);
+    /// assert!(results[0].1.is_ok());
+    /// assert_eq!(results[1].1,Err(FileError::FileNotFound));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn read_files<P: AsRef<Path>>(&self, paths: &[P]) -> Vec<(PathBuf,Result<(String,String),FileError>)> {
+        let paths: Vec<&Path> = paths.iter().map(|path| path.as_ref()).collect();
+
+        return map_threaded(&paths, self.thread_count(), Vec::new, |window, path| {
+            let result = self.hash_path_buffered(path, None, window).map(|(digests, _)| self.encode_pair(digests));
+            (path.to_path_buf(), result)
+        })
+    }
+}
+
+// Maps every item on up to `threads` threads (including the calling thread) and returns the results in the order of `items`. Each thread takes the next unclaimed item and keeps its own state, created by `init`. With 1 thread, or if no thread can be spawned, the items are mapped in order on the calling thread.
+pub(crate) fn map_threaded<T, R, S, I, F>(items: &[T], threads: usize, init: I, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    I: Fn() -> S + Sync,
+    F: Fn(&mut S, &T) -> R + Sync,
+{
+    let threads = threads.min(items.len());
+    if threads < 2 {
+        let mut state = init();
+        return items.iter().map(|item| f(&mut state, item)).collect()
+    }
+
+    let next = AtomicUsize::new(0);
+    let work = || {
+        let mut state = init();
+        let mut results: Vec<(usize,R)> = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            if index >= items.len() {
+                return results
+            }
+            results.push((index, f(&mut state, &items[index])));
+        }
+    };
+
+    let mut results: Vec<(usize,R)> = thread::scope(|scope| {
+        // A thread that could not be spawned is not needed, the calling thread takes its share of the items
+        let workers: Vec<_> = (1..threads).filter_map(|_| thread::Builder::new().spawn_scoped(scope, work).ok()).collect();
+        let mut results = work();
+        for worker in workers {
+            match worker.join() {
+                Ok(mapped) => results.extend(mapped),
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+        results
+    });
+    results.sort_by_key(|(index, _)| *index);
+    return results.into_iter().map(|(_, result)| result).collect()
+}
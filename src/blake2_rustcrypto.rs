@@ -0,0 +1,62 @@
+// RustCrypto BLAKE2B
+//
+// BLAKE2B from the maintained RustCrypto `blake2` crate, used instead of `blake2-rfc` with the `blake2-rustcrypto` feature. It has the same interface as `blake2_rfc::blake2b::Blake2b` and produces the same digests.
+//
+// `blake2::Blake2bVar` cannot be keyed and `blake2::Blake2bMac` fixes the digest size at compile time (and hashes an empty key as a block of zeros), so the core is driven directly.
+
+use blake2::digest::core_api::{Block, Buffer, UpdateCore, VariableOutputCore};
+use blake2::digest::Output;
+use blake2::Blake2bVarCore;
+
+#[derive(Clone)]
+pub(crate) struct Blake2b {
+    core: Blake2bVarCore,
+    // Holds back the last block, which is compressed with the final flag
+    buffer: Buffer<Blake2bVarCore>,
+    digest_size: usize,
+}
+
+impl Blake2b {
+    /// Panics if `digest_size` is not between 1 and 64 bytes.
+    pub(crate) fn new(digest_size: usize) -> Self {
+        return Blake2b::with_key(digest_size, &[])
+    }
+    /// Panics if `digest_size` is not between 1 and 64 bytes or `key` is longer than 64 bytes.
+    pub(crate) fn with_key(digest_size: usize, key: &[u8]) -> Self {
+        assert!((1..=64).contains(&digest_size) && key.len() <= 64);
+        let mut blake2b = Blake2b {
+            core: Blake2bVarCore::new_with_params(&[], &[], key.len(), digest_size),
+            buffer: Buffer::<Blake2bVarCore>::default(),
+            digest_size,
+        };
+
+        // A keyed hash starts with the key padded to a full block
+        if !key.is_empty() {
+            let mut block = Block::<Blake2bVarCore>::default();
+            block[..key.len()].copy_from_slice(key);
+            blake2b.update(&block);
+        }
+        return blake2b
+    }
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        let core = &mut self.core;
+        self.buffer.digest_blocks(bytes, |blocks| core.update_blocks(blocks));
+    }
+    pub(crate) fn finalize(mut self) -> Blake2bResult {
+        let mut output = Output::<Blake2bVarCore>::default();
+        self.core.finalize_variable_core(&mut self.buffer, &mut output);
+        return Blake2bResult { output, digest_size: self.digest_size }
+    }
+}
+
+// The untruncated output and the digest size, like `blake2_rfc::blake2b::Blake2bResult`
+pub(crate) struct Blake2bResult {
+    output: Output<Blake2bVarCore>,
+    digest_size: usize,
+}
+
+impl Blake2bResult {
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        return &self.output[..self.digest_size]
+    }
+}
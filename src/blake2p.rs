@@ -0,0 +1,234 @@
+// Parallel BLAKE2
+//
+// BLAKE2bp and BLAKE2sp from the BLAKE2 specification. The input is split into blocks that are dealt out in turn to 4 (BLAKE2bp) or 8 (BLAKE2sp) leaves, and a root node hashes the untruncated leaf digests. The leaves are independent, so large inputs hash each leaf on its own thread.
+//
+// Only full-length unkeyed digests are hashed on threads. A truncated leaf still has to pass on its untruncated output and the root of a keyed tree includes the key length in its parameters without hashing the key, neither of which can be set up from separate `blake2b_simd` states, so those use the sequential implementation of the same construction.
+
+#[cfg(test)]
+use std::cell::Cell;
+use std::thread;
+
+use crate::LibAlgorithm;
+
+// Input is hashed in batches of this many bytes so each batch is worth spawning threads for. A multiple of every stripe size.
+const BATCH_SIZE: usize = 1024 * 1024;
+// Smaller inputs are hashed on the calling thread
+const THREAD_THRESHOLD: usize = 256 * 1024;
+
+// The number of leaf threads spawned by the current thread, so tests can check the thread limit is honored
+#[cfg(test)]
+thread_local! {
+    static LEAF_THREADS: Cell<usize> = const { Cell::new(0) };
+}
+
+enum Leaf {
+    B(blake2b_simd::State),
+    S(blake2s_simd::State),
+}
+
+impl Leaf {
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Leaf::B(state) => { state.update(bytes); }
+            Leaf::S(state) => { state.update(bytes); }
+        }
+    }
+    fn finalize(&self) -> Vec<u8> {
+        match self {
+            Leaf::B(state) => return state.finalize().as_bytes().to_vec(),
+            Leaf::S(state) => return state.finalize().as_bytes().to_vec(),
+        }
+    }
+}
+
+enum Mode {
+    // The leaves and the bytes that do not fill a batch yet
+    Threaded { leaves: Vec<Leaf>, pending: Vec<u8> },
+    SequentialB(Box<blake2b_simd::blake2bp::State>),
+    SequentialS(Box<blake2s_simd::blake2sp::State>),
+}
+
+/// A running BLAKE2bp or BLAKE2sp hash.
+pub(crate) struct ParallelBlake2 {
+    algorithm: LibAlgorithm,
+    digest_size: usize,
+    mode: Mode,
+    threads: usize,
+}
+
+impl ParallelBlake2 {
+    /// `digest_size` and the length of `key` must be within the limits of `algorithm`. At most `threads` threads are used, including the calling thread.
+    pub(crate) fn new(algorithm: LibAlgorithm, digest_size: usize, key: Option<&[u8]>, threads: usize) -> Self {
+        let key = key.unwrap_or_default();
+        let sequential = !key.is_empty() || digest_size != algorithm.max_digest_size();
+        let mode = match &algorithm {
+            LibAlgorithm::BLAKE2BP if sequential => Mode::SequentialB(Box::new(blake2b_simd::blake2bp::Params::new().hash_length(digest_size).key(key).to_state())),
+            LibAlgorithm::BLAKE2SP if sequential => Mode::SequentialS(Box::new(blake2s_simd::blake2sp::Params::new().hash_length(digest_size).key(key).to_state())),
+            _ => {
+                let lanes = algorithm.lanes();
+                let leaves = (0..lanes).map(|lane| leaf(&algorithm, lane as u64, lane == lanes - 1)).collect();
+                Mode::Threaded { leaves, pending: Vec::new() }
+            }
+        };
+        return ParallelBlake2 { algorithm, digest_size, mode, threads }
+    }
+    pub(crate) fn update(&mut self, mut bytes: &[u8]) {
+        let (leaves, pending) = match &mut self.mode {
+            Mode::Threaded { leaves, pending } => (leaves, pending),
+            Mode::SequentialB(state) => { state.update(bytes); return }
+            Mode::SequentialS(state) => { state.update(bytes); return }
+        };
+        let block = self.algorithm.block_len();
+
+        // Completes a partial batch first so the leaves always receive whole stripes
+        if !pending.is_empty() {
+            let length = (BATCH_SIZE - pending.len()).min(bytes.len());
+            pending.extend_from_slice(&bytes[..length]);
+            bytes = &bytes[length..];
+            if pending.len() < BATCH_SIZE {
+                return
+            }
+            hash_stripes(leaves, pending, block, self.threads);
+            pending.clear();
+        }
+
+        let whole = bytes.len() / BATCH_SIZE * BATCH_SIZE;
+        hash_stripes(leaves, &bytes[..whole], block, self.threads);
+        pending.extend_from_slice(&bytes[whole..]);
+    }
+    pub(crate) fn finalize(self) -> Vec<u8> {
+        let (mut leaves, pending) = match self.mode {
+            Mode::Threaded { leaves, pending } => (leaves, pending),
+            Mode::SequentialB(state) => return state.finalize().as_bytes().to_vec(),
+            Mode::SequentialS(state) => return state.finalize().as_bytes().to_vec(),
+        };
+        let block = self.algorithm.block_len();
+        let stripe = block * leaves.len();
+
+        // Whole stripes, then each leaf takes its block (if any) of the last partial stripe
+        let whole = pending.len() / stripe * stripe;
+        hash_stripes(&mut leaves, &pending[..whole], block, self.threads);
+        for (lane, chunk) in pending[whole..].chunks(block).enumerate() {
+            leaves[lane].update(chunk);
+        }
+
+        match self.algorithm {
+            LibAlgorithm::BLAKE2SP => {
+                let mut root = blake2s_simd::Params::new();
+                root.hash_length(self.digest_size).fanout(leaves.len() as u8).max_depth(2).max_leaf_length(0).node_offset(0).node_depth(1).inner_hash_length(blake2s_simd::OUTBYTES).last_node(true);
+                let mut root = root.to_state();
+                for leaf in leaves.iter() {
+                    root.update(&leaf.finalize());
+                }
+                return root.finalize().as_bytes().to_vec()
+            }
+            _ => {
+                let mut root = blake2b_simd::Params::new();
+                root.hash_length(self.digest_size).fanout(leaves.len() as u8).max_depth(2).max_leaf_length(0).node_offset(0).node_depth(1).inner_hash_length(blake2b_simd::OUTBYTES).last_node(true);
+                let mut root = root.to_state();
+                for leaf in leaves.iter() {
+                    root.update(&leaf.finalize());
+                }
+                return root.finalize().as_bytes().to_vec()
+            }
+        }
+    }
+}
+
+// A full-length leaf
+fn leaf(algorithm: &LibAlgorithm, offset: u64, last: bool) -> Leaf {
+    match algorithm {
+        LibAlgorithm::BLAKE2SP => {
+            let mut params = blake2s_simd::Params::new();
+            params.hash_length(blake2s_simd::OUTBYTES).fanout(algorithm.lanes() as u8).max_depth(2).max_leaf_length(0).node_offset(offset).node_depth(0).inner_hash_length(blake2s_simd::OUTBYTES).last_node(last);
+            return Leaf::S(params.to_state())
+        }
+        _ => {
+            let mut params = blake2b_simd::Params::new();
+            params.hash_length(blake2b_simd::OUTBYTES).fanout(algorithm.lanes() as u8).max_depth(2).max_leaf_length(0).node_offset(offset).node_depth(0).inner_hash_length(blake2b_simd::OUTBYTES).last_node(last);
+            return Leaf::B(params.to_state())
+        }
+    }
+}
+
+// Feeds whole stripes (one block for each leaf) to the leaves. When the input is large enough the leaves are shared out between up to `threads` threads, one thread per leaf if there are enough.
+fn hash_stripes(leaves: &mut [Leaf], bytes: &[u8], block: usize, threads: usize) {
+    let stripe = block * leaves.len();
+    let hash_lane = |leaf: &mut Leaf, lane: usize| {
+        for chunk in bytes.chunks_exact(stripe) {
+            leaf.update(&chunk[lane * block..(lane + 1) * block]);
+        }
+    };
+
+    if bytes.len() < THREAD_THRESHOLD || threads < 2 {
+        for (lane, leaf) in leaves.iter_mut().enumerate() {
+            hash_lane(leaf, lane);
+        }
+        return
+    }
+
+    // Each thread takes a run of neighbouring leaves. A run whose thread could not be spawned is hashed on this thread afterwards.
+    let per_thread = leaves.len().div_ceil(threads);
+    let mut failed: Vec<usize> = Vec::new();
+    thread::scope(|scope| {
+        for (run, leaves) in leaves.chunks_mut(per_thread).enumerate() {
+            let hash_run = move || {
+                for (offset, leaf) in leaves.iter_mut().enumerate() {
+                    hash_lane(leaf, run * per_thread + offset);
+                }
+            };
+            if thread::Builder::new().spawn_scoped(scope, hash_run).is_err() {
+                failed.push(run);
+            }
+            #[cfg(test)]
+            LEAF_THREADS.with(|count| count.set(count.get() + 1));
+        }
+    });
+    for run in failed {
+        for lane in run * per_thread..((run + 1) * per_thread).min(leaves.len()) {
+            hash_lane(&mut leaves[lane], lane);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LEAF_THREADS;
+    use crate::{LibAlgorithm, OsAlgorithm, ParanoidHash};
+
+    // Large enough to be hashed on threads
+    const SIZE: usize = 4 * 1024 * 1024;
+
+    fn leaf_threads<F: FnOnce()>(hash: F) -> usize {
+        LEAF_THREADS.with(|count| count.set(0));
+        hash();
+        return LEAF_THREADS.with(|count| count.get())
+    }
+
+    fn blake2bp(threads: usize) -> ParanoidHash {
+        return ParanoidHash::new(64,OsAlgorithm::SHA256).library_algorithm(LibAlgorithm::BLAKE2BP).threads(threads)
+    }
+
+    #[test]
+    fn one_thread_spawns_no_leaf_threads(){
+        let data = vec![0x5A; SIZE];
+        let context = blake2bp(1);
+
+        assert_eq!(leaf_threads(|| { context.read_bytes(&data); }),0);
+        assert_eq!(leaf_threads(|| { context.read_bytes_parallel(&data); }),0);
+        assert_eq!(leaf_threads(|| { context.session().hash_bytes_into(&data).unwrap(); }),0);
+        assert_eq!(leaf_threads(|| { context.read_from_reader(&data[..]).unwrap(); }),0);
+    }
+
+    #[test]
+    fn leaf_threads_follow_the_setting(){
+        let data = vec![0x5A; SIZE];
+        let expected = blake2bp(1).read_bytes(&data);
+
+        // The 4 leaves of BLAKE2BP are shared between 2 threads, however many cores there are
+        let context = blake2bp(2);
+        assert_eq!(leaf_threads(|| assert_eq!(context.read_bytes(&data),expected)),2);
+        assert_eq!(leaf_threads(|| assert_eq!(context.read_bytes_parallel(&data),expected)),2);
+        assert_eq!(leaf_threads(|| { blake2bp(8).read_bytes(&data); }),4);
+    }
+}
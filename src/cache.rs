@@ -0,0 +1,226 @@
+// Hash Cache
+//
+// Digests of files that have already been hashed, keyed by canonical path and checked against the size and modification time so an unchanged file is not read again.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::hashers::DigestPair;
+use crate::{FileError, ParanoidHash};
+
+/// The version of the format written by [`HashCache::save()`].
+pub const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// # Hash Cache
+///
+/// Digests of previously hashed files, used by `read_files_cached()`, `manifest_dir_cached()` and `Manifest::verify_cached()`.
+///
+/// A file is looked up by its canonical path and the stored digests are reused if its size and modification time (to the nanosecond) have not changed. Otherwise it is hashed and its entry replaced.
+///
+/// The cache records the library hash function, digest size and Operating System Hash Function it was filled with. Using it with a different configuration discards every entry first.
+///
+/// **Warning:** A file changed without changing its size or modification time (such as by a tool that restores the modification time, or on a filesystem with coarse timestamps) is not detected. Use `force()` to rehash everything when that matters.
+///
+/// With the `serde` feature the cache can be kept between runs with `save()` and `load()`.
+///
+/// ```rust
+/// use paranoid_hash::{HashCache,ParanoidHash};
+///
+/// let context = ParanoidHash::default();
+/// let mut cache = HashCache::new();
+///
+/// let first = context.read_files_cached(&["examples/example_file.txt"],&mut cache);
+/// let second = context.read_files_cached(&["examples/example_file.txt"],&mut cache);
+/// assert_eq!(first,second);
+/// assert_eq!(cache.return_stats().hits,1);
+/// ```
+#[derive(Debug,Clone,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HashCache {
+    format_version: u32,
+    // The configuration the entries were hashed with, such as `blake2b-64+sha512`. Empty until the cache is first used.
+    algorithms: String,
+    entries: BTreeMap<String,CacheEntry>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    force: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stats: CacheStats,
+}
+
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct CacheEntry {
+    size: u64,
+    // Nanoseconds since the Unix epoch
+    modified: u64,
+    // Upper hexadecimal, regardless of the Output Encoding
+    library: String,
+    os: String,
+}
+
+/// # Cache Stats
+///
+/// Counts of cache lookups since the cache was created or loaded, or since `reset_stats()`.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash,Default)]
+pub struct CacheStats {
+    /// Files whose digests were reused
+    pub hits: u64,
+    /// Files that were hashed, because they were not in the cache, had changed or `force()` is set
+    pub misses: u64,
+    /// The number of bytes read to hash the misses
+    pub bytes_hashed: u64,
+}
+
+impl Default for HashCache {
+    fn default() -> Self { HashCache::new() }
+}
+
+impl HashCache {
+    /// ## New
+    ///
+    /// An empty cache. It takes the configuration of the first `ParanoidHash` it is used with.
+    pub fn new() -> Self {
+        return HashCache {
+            format_version: CACHE_FORMAT_VERSION,
+            algorithms: String::new(),
+            entries: BTreeMap::new(),
+            force: false,
+            stats: CacheStats::default(),
+        }
+    }
+    /// ## Force
+    ///
+    /// Rehashes every file instead of reusing stored digests. The fresh digests are still stored.
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        return self
+    }
+    /// ## Return Stats
+    pub fn return_stats(&self) -> CacheStats {
+        return self.stats
+    }
+    /// ## Reset Stats
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+    /// ## Length
+    ///
+    /// The number of files in the cache.
+    pub fn len(&self) -> usize {
+        return self.entries.len()
+    }
+    /// ## Is Empty
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty()
+    }
+    /// ## Clear
+    ///
+    /// Removes every entry, so every file is hashed again.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+    /// ## Remove
+    ///
+    /// Removes the entry for `path`, so it is hashed again the next time. Returns false if it was not in the cache.
+    pub fn remove<T: AsRef<Path>>(&mut self, path: T) -> bool {
+        match cache_key(path.as_ref()) {
+            Some(key) => return self.entries.remove(&key).is_some(),
+            None => return false,
+        }
+    }
+    /// ## Save
+    ///
+    /// Writes the cache to `path` as JSON. Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<(),FileError> {
+        let json = serde_json::to_vec(self).map_err(|_| FileError::Io(std::io::ErrorKind::InvalidData))?;
+        return fs::write(path, json).map_err(|e| FileError::Io(e.kind()))
+    }
+    /// ## Load
+    ///
+    /// Reads a cache written by `save()`. Requires the `serde` feature.
+    ///
+    /// Returns `FileError::FileNotFound` if there is no file at `path` and `FileError::Io(ErrorKind::InvalidData)` if it is not a cache in the current format.
+    #[cfg(feature = "serde")]
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<HashCache,FileError> {
+        if !path.as_ref().exists() {
+            return Err(FileError::FileNotFound)
+        }
+        let json = fs::read(path).map_err(|e| FileError::Io(e.kind()))?;
+        match serde_json::from_slice::<HashCache>(&json) {
+            Ok(cache) if cache.format_version == CACHE_FORMAT_VERSION => return Ok(cache),
+            _ => return Err(FileError::Io(std::io::ErrorKind::InvalidData)),
+        }
+    }
+    // Discards the entries if they were hashed with another configuration
+    fn check_config(&mut self, algorithms: String) {
+        if self.algorithms != algorithms {
+            self.entries.clear();
+            self.algorithms = algorithms;
+        }
+    }
+}
+
+impl ParanoidHash {
+    /// # Read Files Cached
+    ///
+    /// Same as `read_files()`, but reuses the digests in `cache` for files that have not changed and stores the digests of the rest. See [`HashCache`].
+    #[allow(clippy::type_complexity)]
+    pub fn read_files_cached<P: AsRef<Path>>(&self, paths: &[P], cache: &mut HashCache) -> Vec<(PathBuf,Result<(String,String),FileError>)> {
+        let mut window = Vec::new();
+
+        return paths.iter().map(|path| {
+            let result = self.hash_path_cached(path.as_ref(), cache, &mut window).map(|(digests, _)| self.encode_pair(digests));
+            (path.as_ref().to_path_buf(), result)
+        }).collect()
+    }
+    // `hash_path_cached()` if there is a cache, otherwise `hash_path_buffered()`
+    pub(crate) fn hash_path_with_cache(&self, path: &Path, cache: Option<&mut HashCache>, window: &mut Vec<u8>) -> Result<(DigestPair,u64),FileError> {
+        match cache {
+            Some(cache) => return self.hash_path_cached(path, cache, window),
+            None => return self.hash_path_buffered(path, None, window).map(|(digests, stats)| (digests, stats.bytes)),
+        }
+    }
+    // `hash_path_buffered()` through the cache. Also returns the size of the file.
+    pub(crate) fn hash_path_cached(&self, path: &Path, cache: &mut HashCache, window: &mut Vec<u8>) -> Result<(DigestPair,u64),FileError> {
+        cache.check_config(self.algorithms_tag());
+
+        // Special files, and files without a canonical UTF-8 path or a modification time, are hashed without the cache
+        let key = cache_key(path);
+        let stamp = fs::metadata(path).ok().filter(|metadata| metadata.is_file()).and_then(|metadata| Some((metadata.len(), modified(&metadata)?)));
+
+        if let (Some(key), Some((size, modified)), false) = (&key, stamp, cache.force) {
+            if let Some(entry) = cache.entries.get(key).filter(|entry| entry.size == size && entry.modified == modified) {
+                if let (Ok(library), Ok(os)) = (hex::decode(&entry.library), hex::decode(&entry.os)) {
+                    self.check_size(size)?;
+                    self.check_empty(size)?;
+                    cache.stats.hits += 1;
+                    return Ok(((library, os), size))
+                }
+            }
+        }
+
+        let ((library, os), stats) = self.hash_path_buffered(path, None, window)?;
+        cache.stats.misses += 1;
+        cache.stats.bytes_hashed += stats.bytes;
+
+        // The size and time from before hashing are stored, so a change while hashing is caught next time
+        if let (Some(key), Some((size, modified))) = (key, stamp) {
+            cache.entries.insert(key, CacheEntry { size, modified, library: hex::encode_upper(&library), os: hex::encode_upper(&os) });
+        }
+        return Ok(((library, os), stats.bytes))
+    }
+}
+
+fn cache_key(path: &Path) -> Option<String> {
+    return fs::canonicalize(path).ok()?.into_os_string().into_string().ok()
+}
+
+// Nanoseconds since the Unix epoch, or `None` if the modification time is not available or earlier than the epoch
+pub(crate) fn modified(metadata: &fs::Metadata) -> Option<u64> {
+    let since_epoch = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    return u64::try_from(since_epoch.as_nanos()).ok()
+}
@@ -0,0 +1,48 @@
+// Cancellable Hashing
+//
+// Long running hashes (such as a 100 GB file) can be stopped from another thread by setting an `AtomicBool`. The flag is checked between chunks, so cancellation happens within one chunk of being requested.
+
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::hashers::CHUNK_SIZE;
+use crate::retry::RetryReader;
+use crate::{FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Cancellable
+    ///
+    /// Streams the file through both hash functions, returning `FileError::Cancelled` as soon as `cancel` is set to `true`. The partial hash state is discarded.
+    ///
+    /// The file is opened and read like `read_using_std()`, with the same size limit, Retry Policy, Read Tuning and Modification Check.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,FileError};
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// let cancel = AtomicBool::new(true);
+    /// let context = ParanoidHash::default();
+    /// assert_eq!(context.read_cancellable("examples/example_file.txt",&cancel),Err(FileError::Cancelled));
+    /// ```
+    pub fn read_cancellable<T: AsRef<Path>>(&self, path: T, cancel: &AtomicBool) -> Result<(String,String),FileError> {
+        return self.read_file_with(path.as_ref(), |reader| self.read_from_reader_cancellable(reader, cancel)).map(|(digests, _)| digests)
+    }
+    /// # Read From Reader Cancellable
+    ///
+    /// Same as `read_from_reader()`, but returns `FileError::Cancelled` as soon as `cancel` is set to `true`.
+    pub fn read_from_reader_cancellable<R: Read>(&self, reader: R, cancel: &AtomicBool) -> Result<(String,String),FileError> {
+        let mut hasher = self.pair_hasher(None)?;
+
+        let reader = RetryReader::new(reader, self.retry_policy);
+        let length = hasher.update_reader_checked(BufReader::with_capacity(CHUNK_SIZE, reader), |total| {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(FileError::Cancelled)
+            }
+            return self.check_size(total)
+        })?;
+        self.check_empty(length)?;
+
+        return Ok(self.encode_pair(hasher.finalize_pair()?.0))
+    }
+}
@@ -0,0 +1,45 @@
+// Chained Hashing
+//
+// The Operating System Hash Function hashes the BLAKE2B digest instead of the data, so one result depends on both implementations.
+
+use std::path::Path;
+
+use crate::hashers::{Digester, DigestPair};
+use crate::{FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Chained
+    ///
+    /// Returns `(BLAKE2B(file), OS(BLAKE2B(file)))`:
+    ///
+    /// 1. The file is hashed with unkeyed BLAKE2B at the digest size of this context. This is the first digest and is the same as the first digest of `read()`.
+    /// 2. The **raw bytes** of that digest (not the encoded string) are hashed with the Operating System Hash Function. This is the second digest.
+    ///
+    /// This construction is stable and will not change. Both digests are returned in the Output Encoding.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    ///
+    /// let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    /// let (blake2b,chained) = context.read_chained("examples/example_file.txt").unwrap();
+    /// assert_eq!(chained,context.read_bytes(&ParanoidHash::decode_from_hex(&blake2b)).1);
+    /// ```
+    pub fn read_chained<T: AsRef<Path>>(&self, path: T) -> Result<(String,String),FileError> {
+        let ((library, _), _) = self.hash_path_raw(path.as_ref(), None)?;
+        return Ok(self.encode_pair(self.chain(library)?))
+    }
+    /// # Read Bytes Chained
+    ///
+    /// Same as `read_chained()` for bytes.
+    pub fn read_bytes_chained(&self, bytes: &[u8]) -> Result<(String,String),FileError> {
+        let ((library, _), _) = self.hash_bytes_raw(bytes)?;
+        return Ok(self.encode_pair(self.chain(library)?))
+    }
+    // Hashes the BLAKE2B digest with the Operating System Hash Function
+    fn chain(&self, library: Vec<u8>) -> Result<DigestPair,FileError> {
+        let mut digester = Digester::os(&self.os_hash_function, self.os_options)?;
+        digester.update(&library)?;
+        let os = digester.finalize()?.0;
+        return Ok((library, os))
+    }
+}
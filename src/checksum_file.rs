@@ -0,0 +1,225 @@
+// Checksum Files
+//
+// Checks one file against its entry in a checksum file written by `sha256sum`, `b2sum` and friends (GNU format), without verifying the rest of the list, and writes checksum files in the same format.
+//
+// Names containing a backslash, newline or carriage return are escaped the way GNU coreutils does it: the line starts with `\` and those characters are written as `\\`, `\n` and `\r`.
+
+use std::borrow::Cow;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use crate::digest::constant_time_eq;
+use crate::{FileError, ParanoidHash};
+
+/// # Verify Outcome
+///
+/// The result of checking a file against a checksum file.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum VerifyOutcome {
+    /// The listed digest matches the file
+    Match,
+    /// The listed digest does not match the file
+    Mismatch,
+    /// The checksum file has no entry for the file
+    NotListed,
+}
+
+impl ParanoidHash {
+    /// # Write Checksum File
+    ///
+    /// Hashes every file and writes a GNU style checksum file listing their Operating System digests in lower case hexadecimal, in the order given, the same as `sha256sum` (or `sha1sum`, `sha512sum`, ...) would. The file can be checked with `verify_against_checksum_file()` or `sha256sum -c` run from its directory.
+    ///
+    /// Files inside the directory of the checksum file are named relative to it, with `/` separators; other files are named by the path as given. Names containing a backslash, newline or carriage return are escaped as coreutils does. A name that is not valid UTF-8 returns `FileError::AtPath` holding `FileError::Io(ErrorKind::InvalidData)`, and a file that fails to hash returns `FileError::AtPath` with its error. Nothing is written if any file fails.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm,VerifyOutcome};
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// std::fs::write(dir.path().join("data.txt"),"data").unwrap();
+    ///
+    /// let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    /// context.write_checksum_file(&[dir.path().join("data.txt")],dir.path().join("SHA256SUMS")).unwrap();
+    ///
+    /// let outcome = context.verify_against_checksum_file(dir.path().join("data.txt"),dir.path().join("SHA256SUMS")).unwrap();
+    /// assert_eq!(outcome,VerifyOutcome::Match);
+    /// ```
+    pub fn write_checksum_file<P: AsRef<Path>, C: AsRef<Path>>(&self, files: &[P], checksum_file: C) -> Result<(),FileError> {
+        let base = checksum_file.as_ref().parent().unwrap_or_else(|| Path::new(""));
+        let mut window = Vec::new();
+
+        let mut text = String::new();
+        for file in files.iter() {
+            let file = file.as_ref();
+            let name = checksum_name(base, file)?;
+            let (_, os) = self.hash_path_buffered(file, None, &mut window).map_err(|e| FileError::AtPath(file.to_path_buf(), Box::new(e)))?.0;
+            text.push_str(&format_line(&hex::encode(os), &name));
+        }
+        return fs::write(checksum_file, text).map_err(|e| FileError::Io(e.kind()))
+    }
+    /// # Verify Against Checksum File
+    ///
+    /// Finds the entry for `file` in a GNU style checksum file (`<hex digest>  <name>` for text mode or `<hex digest> *<name>` for binary mode), hashes the file and compares it against the listed digest.
+    ///
+    /// * Names are relative to the directory of the checksum file, as written by `sha256sum`. A leading `./` is ignored.
+    /// * The listed digest is compared against the Operating System digest and, if it has the same length, the BLAKE2B digest. This lets the same context check `SHA512SUMS` and `B2SUMS` files.
+    /// * Escaped names (lines starting with `\`, as written by coreutils for names containing a backslash or newline) are unescaped. Lines with an unknown escape are skipped.
+    /// * Blank lines, comments (`#`) and lines that are not in GNU format are skipped. Whitespace before the digest and Windows line endings are ignored, but trailing whitespace is kept as part of the name.
+    ///
+    /// Returns `FileError::ConflictingChecksums` if the file is listed more than once with different digests and `FileError::InvalidDigest` if the listed digest is not hexadecimal or has the wrong length for this context.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm,VerifyOutcome};
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// std::fs::write(dir.path().join("data.txt"),"data").unwrap();
+    ///
+    /// let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    /// let sha256 = context.read(dir.path().join("data.txt")).unwrap().1;
+    /// std::fs::write(dir.path().join("SHA256SUMS"),format!("{} *data.txt\n",sha256.to_lowercase())).unwrap();
+    ///
+    /// let outcome = context.verify_against_checksum_file(dir.path().join("data.txt"),dir.path().join("SHA256SUMS")).unwrap();
+    /// assert_eq!(outcome,VerifyOutcome::Match);
+    /// ```
+    pub fn verify_against_checksum_file<F: AsRef<Path>, C: AsRef<Path>>(&self, file: F, checksum_file: C) -> Result<VerifyOutcome,FileError> {
+        let file = file.as_ref();
+        let checksum_file = checksum_file.as_ref();
+
+        // Checks whether both files exist. If a file does not exist, returns error as FileError.
+        if !file.exists() || !checksum_file.exists() {
+            return Err(FileError::FileNotFound)
+        }
+
+        let contents = fs::read_to_string(checksum_file).map_err(|e| FileError::Io(e.kind()))?;
+        let base = checksum_file.parent().unwrap_or_else(|| Path::new(""));
+        let target = file.canonicalize().map_err(|e| FileError::Io(e.kind()))?;
+
+        let mut expected: Option<Vec<u8>> = None;
+        for (digest, name) in contents.lines().filter_map(parse_line) {
+            if !refers_to(base, &name, file, &target) {
+                continue
+            }
+            let digest = hex::decode(digest).map_err(|_| FileError::InvalidDigest)?;
+            match &expected {
+                Some(previous) if *previous != digest => return Err(FileError::ConflictingChecksums),
+                Some(_) => {}
+                None => expected = Some(digest),
+            }
+        }
+        let expected = match expected {
+            Some(expected) => expected,
+            None => return Ok(VerifyOutcome::NotListed),
+        };
+
+        if expected.len() != self.os_hash_function.digest_len() && expected.len() != self.digest_size {
+            return Err(FileError::InvalidDigest)
+        }
+
+        let ((library, os), _) = self.hash_path_raw(file, None)?;
+        if constant_time_eq(&expected, &os) || constant_time_eq(&expected, &library) {
+            return Ok(VerifyOutcome::Match)
+        }
+        return Ok(VerifyOutcome::Mismatch)
+    }
+}
+
+// Splits a GNU line into the digest and the (unescaped) name, dropping the binary (`*`) or text (` `) marker
+pub(crate) fn parse_line(line: &str) -> Option<(&str, Cow<'_, str>)> {
+    let line = line.trim_start();
+    if line.is_empty() || line.starts_with('#') {
+        return None
+    }
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    let (digest, rest) = line.split_once(' ')?;
+    let name = rest.strip_prefix('*').or_else(|| rest.strip_prefix(' '))?;
+    if digest.is_empty() || name.is_empty() {
+        return None
+    }
+    match escaped {
+        true => return Some((digest, Cow::Owned(unescape_name(name)?))),
+        false => return Some((digest, Cow::Borrowed(name))),
+    }
+}
+
+// Writes a GNU line, escaping the name if needed
+pub(crate) fn format_line(digest: &str, name: &str) -> String {
+    match escape_name(name) {
+        Some(escaped) => return format!("\\{}  {}\n", digest, escaped),
+        None => return format!("{}  {}\n", digest, name),
+    }
+}
+
+// The name with backslashes, newlines and carriage returns escaped, or `None` if it has none of them. Lines holding an escaped name start with `\`.
+pub(crate) fn escape_name(name: &str) -> Option<String> {
+    if !name.contains(['\\', '\n', '\r']) {
+        return None
+    }
+    let mut escaped = String::with_capacity(name.len() + 1);
+    for character in name.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            character => escaped.push(character),
+        }
+    }
+    return Some(escaped)
+}
+
+// Reverses the escaping of `format_line()`. Returns `None` for an unknown or unfinished escape.
+fn unescape_name(name: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut characters = name.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue
+        }
+        match characters.next()? {
+            '\\' => unescaped.push('\\'),
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            _ => return None,
+        }
+    }
+    return Some(unescaped)
+}
+
+// The name of `file` in a checksum file in `base`: relative to `base` with `/` separators, or the path as given if it is not inside `base`
+fn checksum_name(base: &Path, file: &Path) -> Result<String,FileError> {
+    let relative = file.strip_prefix(base).unwrap_or(file);
+    let invalid = || FileError::AtPath(file.to_path_buf(), Box::new(FileError::Io(ErrorKind::InvalidData)));
+    if relative.is_absolute() {
+        return relative.to_str().map(str::to_string).ok_or_else(invalid)
+    }
+    let mut components: Vec<&str> = Vec::new();
+    for component in relative.components() {
+        match component {
+            Component::CurDir => continue,
+            component => components.push(component.as_os_str().to_str().ok_or_else(invalid)?),
+        }
+    }
+    return Ok(components.join("/"))
+}
+
+// Whether `name` (relative to `base`) is `file`, whose canonical path is `target`
+fn refers_to(base: &Path, name: &str, file: &Path, target: &Path) -> bool {
+    let mut name = name;
+    while let Some(stripped) = name.strip_prefix("./") {
+        name = stripped;
+    }
+    let candidate: PathBuf = base.join(name);
+
+    // Only entries with the same file name are worth resolving
+    if candidate.file_name() != file.file_name() && candidate.file_name() != target.file_name() {
+        return false
+    }
+    match candidate.canonicalize() {
+        Ok(candidate) => return candidate == target,
+        Err(_) => return false,
+    }
+}
@@ -0,0 +1,75 @@
+// File Comparison
+//
+// Answers "are these two files the same?" using both hash functions.
+
+use std::fs;
+use std::path::Path;
+
+use crate::{FileError, ParanoidHash};
+
+/// # File Comparison
+///
+/// The result of `compare_files_detailed()`.
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FileComparison {
+    /// Whether the files have the same contents
+    pub equal: bool,
+    pub size_a: u64,
+    pub size_b: u64,
+    /// `true` if the sizes differ and the files were not hashed (see `size_precheck()`)
+    pub size_shortcut: bool,
+    /// The digests of the first file, or `None` if it was not hashed
+    pub digests_a: Option<(String,String)>,
+    /// The digests of the second file, or `None` if it was not hashed
+    pub digests_b: Option<(String,String)>,
+}
+
+impl ParanoidHash {
+    /// # Compare Files
+    ///
+    /// Hashes both files and returns `true` if both the Blake2b and Operating System digests match, or `false` if neither do. Files of different sizes are reported as different without being hashed, unless `size_precheck(false)` is set.
+    ///
+    /// If one digest matches and the other does not, `FileError::DigestDisagreement` is returned. That should never happen with working hash functions and is a sign that one of them is broken or has been tampered with.
+    pub fn compare_files<A: AsRef<Path>, B: AsRef<Path>>(&self, a: A, b: B) -> Result<bool,FileError> {
+        return Ok(self.compare_files_detailed(a, b)?.equal)
+    }
+    /// # Compare Files Detailed
+    ///
+    /// Same as `compare_files()`, but also returns the sizes, the digests and whether hashing was skipped because the sizes differ.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let comparison = ParanoidHash::default().compare_files_detailed("examples/example_file.txt","Cargo.toml").unwrap();
+    /// assert!(!comparison.equal);
+    /// assert!(comparison.size_shortcut);
+    /// assert_eq!(comparison.digests_a,None);
+    /// ```
+    pub fn compare_files_detailed<A: AsRef<Path>, B: AsRef<Path>>(&self, a: A, b: B) -> Result<FileComparison,FileError> {
+        let (a, b) = (a.as_ref(), b.as_ref());
+
+        // Checks whether files exist. If a file does not exist, returns error as FileError.
+        if !a.exists() || !b.exists() {
+            return Err(FileError::FileNotFound)
+        }
+
+        // Files of different lengths cannot be the same, so they are only hashed without the precheck
+        let size_a = fs::metadata(a).map_err(|e| FileError::Io(e.kind()))?.len();
+        let size_b = fs::metadata(b).map_err(|e| FileError::Io(e.kind()))?.len();
+        if size_a != size_b && self.size_precheck {
+            return Ok(FileComparison { equal: false, size_a, size_b, size_shortcut: true, digests_a: None, digests_b: None })
+        }
+
+        let digests_a = self.read(a)?;
+        let digests_b = self.read(b)?;
+
+        let blake2b_matches = ParanoidHash::compare_hash(&digests_a.0, &digests_b.0);
+        let os_matches = ParanoidHash::compare_hash(&digests_a.1, &digests_b.1);
+
+        if blake2b_matches != os_matches {
+            return Err(FileError::DigestDisagreement)
+        }
+        return Ok(FileComparison { equal: blake2b_matches, size_a, size_b, size_shortcut: false, digests_a: Some(digests_a), digests_b: Some(digests_b) })
+    }
+}
@@ -0,0 +1,70 @@
+// Comparison Against Several Digests
+//
+// Checks a digest against a list of acceptable ones (such as MACs under rotating keys) in constant time. Every candidate is compared in full and the results are combined without branching, so the time taken does not reveal which candidate matched or whether any did. Only the number and lengths of the candidates can be told from the timing.
+
+use std::hint::black_box;
+
+use crate::ParanoidHash;
+
+impl ParanoidHash {
+    /// # Compare Hash Any
+    ///
+    /// Returns `true` if `hash` matches at least one of `candidates`, ignoring case like `compare_hash()`. Every candidate is compared in constant time, with no early exit.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// assert!(ParanoidHash::compare_hash_any("abcd",&["0000","ABCD","1111"]));
+    /// assert!(!ParanoidHash::compare_hash_any("abcd",&[]));
+    /// ```
+    pub fn compare_hash_any(hash: &str, candidates: &[&str]) -> bool {
+        return ParanoidHash::compare_hash_any_index(hash, candidates).is_some()
+    }
+    /// # Compare Hash Any Index
+    ///
+    /// Same as `compare_hash_any()`, but returns the index of the first matching candidate. The index is selected without branching, so finding it early does not shorten the remaining comparisons.
+    pub fn compare_hash_any_index(hash: &str, candidates: &[&str]) -> Option<usize> {
+        let hash = hash.to_lowercase();
+        let candidates: Vec<String> = candidates.iter().map(|candidate| candidate.to_lowercase()).collect();
+        return first_match(hash.as_bytes(), candidates.iter().map(|candidate| candidate.as_bytes()))
+    }
+    /// # Compare Bytes Any
+    ///
+    /// Same as `compare_hash_any()` for digests as bytes.
+    pub fn compare_bytes_any(digest: &[u8], candidates: &[&[u8]]) -> bool {
+        return ParanoidHash::compare_bytes_any_index(digest, candidates).is_some()
+    }
+    /// # Compare Bytes Any Index
+    ///
+    /// Same as `compare_hash_any_index()` for digests as bytes.
+    pub fn compare_bytes_any_index(digest: &[u8], candidates: &[&[u8]]) -> Option<usize> {
+        return first_match(digest, candidates.iter().copied())
+    }
+}
+
+fn first_match<'a, I: Iterator<Item = &'a [u8]>>(digest: &[u8], candidates: I) -> Option<usize> {
+    let mut matched: usize = 0;
+    let mut index: usize = 0;
+
+    for (i, candidate) in candidates.enumerate() {
+        let equal = black_box(equal(digest, candidate));
+
+        // All ones for the first match only
+        let mask = (equal & !matched).wrapping_neg();
+        index = (i & mask) | (index & !mask);
+        matched |= equal;
+    }
+    match matched {
+        0 => return None,
+        _ => return Some(index),
+    }
+}
+
+// 1 if the slices are equal and 0 otherwise, comparing every byte
+fn equal(a: &[u8], b: &[u8]) -> usize {
+    if a.len() != b.len() {
+        return 0
+    }
+    let difference = a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    return ((difference as usize).wrapping_sub(1) >> (usize::BITS - 1)) & 1
+}
@@ -0,0 +1,111 @@
+// Directory Comparison
+//
+// Answers "does this tree have the same files as that one?", such as after copying a directory with rsync. Files are matched by their path relative to each directory.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{walk, FileError, ParanoidHash};
+
+/// # Directory Comparison Report
+///
+/// The result of `compare_dirs()`. Every list is sorted by path.
+#[derive(Debug,Clone,PartialEq,Eq,Hash,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DirCompareReport {
+    /// Files only in the first directory
+    pub only_in_a: Vec<PathBuf>,
+    /// Files only in the second directory
+    pub only_in_b: Vec<PathBuf>,
+    /// Files in both directories whose contents differ
+    pub mismatched: Vec<DirMismatch>,
+    /// The number of files in both directories with the same contents
+    pub matched: usize,
+}
+
+/// # Directory Mismatch
+///
+/// A file whose contents differ between the two directories of a [`DirCompareReport`].
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DirMismatch {
+    /// Path relative to both directories
+    pub path: PathBuf,
+    pub size_a: u64,
+    pub size_b: u64,
+    /// `true` if the sizes differ and the files were not hashed (see `size_precheck()`)
+    pub size_shortcut: bool,
+    /// The digests of the file in the first directory, or `None` if it was not hashed
+    pub digests_a: Option<(String,String)>,
+    /// The digests of the file in the second directory, or `None` if it was not hashed
+    pub digests_b: Option<(String,String)>,
+}
+
+impl DirCompareReport {
+    /// ## Is Identical
+    ///
+    /// Returns true if both directories have the same files with the same contents.
+    pub fn is_identical(&self) -> bool {
+        return self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+impl ParanoidHash {
+    /// # Compare Directories
+    ///
+    /// Walks both directories with this context's `walk_options()` (see [`WalkOptions`](crate::WalkOptions)), matches files by their path relative to each directory and hashes the files found in both.
+    ///
+    /// Files of different sizes are reported as mismatched without being hashed, unless `size_precheck(false)` is set. If one digest of a file matches and the other does not, `FileError::DigestDisagreement` is returned (see `compare_files()`). Errors with a single file are returned as `FileError::AtPath` with the path that failed.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let report = context.compare_dirs("tests/fixtures/dir_digest","tests/fixtures/dir_digest").unwrap();
+    /// assert!(report.is_identical());
+    /// ```
+    pub fn compare_dirs<A: AsRef<Path>, B: AsRef<Path>>(&self, a: A, b: B) -> Result<DirCompareReport,FileError> {
+        let files_a: BTreeMap<String, PathBuf> = walk::walk_entries(a.as_ref(), &self.walk_options)?.into_iter().collect();
+        let files_b: BTreeMap<String, PathBuf> = walk::walk_entries(b.as_ref(), &self.walk_options)?.into_iter().collect();
+        let mut window = Vec::new();
+
+        let mut report = DirCompareReport::default();
+        for (name, path_a) in files_a.iter() {
+            let path_b = match files_b.get(name) {
+                Some(path_b) => path_b,
+                None => {
+                    report.only_in_a.push(PathBuf::from(name));
+                    continue
+                }
+            };
+
+            let size_a = fs::metadata(path_a).map_err(|e| FileError::AtPath(path_a.clone(), Box::new(FileError::Io(e.kind()))))?.len();
+            let size_b = fs::metadata(path_b).map_err(|e| FileError::AtPath(path_b.clone(), Box::new(FileError::Io(e.kind()))))?.len();
+            if size_a != size_b && self.size_precheck {
+                report.mismatched.push(DirMismatch { path: PathBuf::from(name), size_a, size_b, size_shortcut: true, digests_a: None, digests_b: None });
+                continue
+            }
+
+            let digests_a = self.hash_path_buffered(path_a, None, &mut window).map_err(|e| FileError::AtPath(path_a.clone(), Box::new(e)))?.0;
+            let digests_b = self.hash_path_buffered(path_b, None, &mut window).map_err(|e| FileError::AtPath(path_b.clone(), Box::new(e)))?.0;
+
+            // The digests are bytes here, so they are compared directly rather than as encoded strings
+            match (digests_a.0 == digests_b.0, digests_a.1 == digests_b.1) {
+                (true, true) => report.matched += 1,
+                (false, false) => report.mismatched.push(DirMismatch {
+                    path: PathBuf::from(name),
+                    size_a,
+                    size_b,
+                    size_shortcut: false,
+                    digests_a: Some(self.encode_pair(digests_a)),
+                    digests_b: Some(self.encode_pair(digests_b)),
+                }),
+                _ => return Err(FileError::AtPath(path_a.clone(), Box::new(FileError::DigestDisagreement))),
+            }
+        }
+        report.only_in_b = files_b.keys().filter(|name| !files_a.contains_key(*name)).map(PathBuf::from).collect();
+
+        return Ok(report)
+    }
+}
@@ -0,0 +1,88 @@
+// Concatenated Hashing
+//
+// One pair of digests for an ordered set of files (such as the parts of a split archive), as if they had been joined into a single file.
+//
+// `read_chunks()` does the same for chunks of bytes in memory, such as those received from a channel.
+
+use std::path::Path;
+
+use crate::retry::RetryReader;
+use crate::{long_path, FileError, ParanoidHash, WINDOW_SIZE};
+
+impl ParanoidHash {
+    /// # Read Concatenated
+    ///
+    /// Streams every file, in the given order, into the same BLAKE2B and Operating System hashers. The digests are the same as the digests of a single file holding the contents of `paths` one after another, but no combined buffer is ever built.
+    ///
+    /// Every path is checked before anything is hashed (including against `max_file_size()`, which applies to each file). If a file is missing (or fails to be read), the error is returned as `FileError::AtPath` with the path that failed. `reject_empty_input()` applies to the total length, so empty parts are allowed as long as one part is not.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,FileError};
+    ///
+    /// let context = ParanoidHash::default();
+    /// let result = context.read_concat(&["examples/example_file.txt","missing.part"]);
+    /// assert_eq!(result,Err(FileError::AtPath("missing.part".into(),Box::new(FileError::FileNotFound))));
+    /// ```
+    pub fn read_concat<P: AsRef<Path>>(&self, paths: &[P]) -> Result<(String,String),FileError> {
+        // Checks whether every file exists before hashing so a missing part fails fast
+        for path in paths.iter() {
+            if !long_path::extended(path.as_ref()).exists() {
+                return Err(FileError::AtPath(path.as_ref().to_path_buf(), Box::new(FileError::FileNotFound)))
+            }
+            self.check_file_size(path.as_ref()).map_err(|e| FileError::AtPath(path.as_ref().to_path_buf(), Box::new(e)))?;
+        }
+
+        let mut hasher = self.pair_hasher(None)?;
+        let mut window = vec![0u8; self.window_size.unwrap_or(WINDOW_SIZE)];
+        let mut length: u64 = 0;
+
+        for path in paths.iter() {
+            let at_path = |e: FileError| FileError::AtPath(path.as_ref().to_path_buf(), Box::new(e));
+            let file = self.open_file(path.as_ref()).map_err(at_path)?;
+            length += hasher.update_read(RetryReader::new(file, self.retry_policy), &mut window).map_err(at_path)?;
+        }
+        self.check_empty(length)?;
+
+        return Ok(self.encode_pair(hasher.finalize_pair()?.0))
+    }
+    /// # Read Chunks
+    ///
+    /// Feeds every chunk, in order, into the same BLAKE2B and Operating System hashers without collecting them. The digests are the same as `read_bytes()` of the chunks concatenated, so an empty iterator or empty chunks give the digests of the empty input.
+    ///
+    /// Like `read_bytes()`, empty input is always hashed and this panics if the Operating System Hash Function fails. Use `try_read_chunks()` to get a `Result` instead.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let chunks = vec![b"Hello, ".to_vec(),Vec::new(),b"World".to_vec()];
+    /// assert_eq!(context.read_chunks(chunks),context.read_bytes(b"Hello, World"));
+    /// ```
+    pub fn read_chunks<I, B>(&self, chunks: I) -> (String,String) where I: IntoIterator<Item = B>, B: AsRef<[u8]> {
+        return self.hash_chunks(chunks, false).expect("[Error] Failed To Hash Using Operating System Hash Function")
+    }
+    /// # Try Read Chunks
+    ///
+    /// Same as `read_chunks()` but returns a `Result`, honoring `reject_empty_input()` and `max_file_size()` (which applies to the total length). Chunks after the limit is exceeded are not consumed.
+    pub fn try_read_chunks<I, B>(&self, chunks: I) -> Result<(String,String),FileError> where I: IntoIterator<Item = B>, B: AsRef<[u8]> {
+        return self.hash_chunks(chunks, true)
+    }
+    fn hash_chunks<I, B>(&self, chunks: I, checked: bool) -> Result<(String,String),FileError> where I: IntoIterator<Item = B>, B: AsRef<[u8]> {
+        let mut hasher = self.pair_hasher(None)?;
+        let mut length: u64 = 0;
+
+        for chunk in chunks {
+            let chunk = chunk.as_ref();
+            length += chunk.len() as u64;
+            if checked {
+                self.check_size(length)?;
+            }
+            hasher.update(chunk)?;
+        }
+        if checked {
+            self.check_empty(length)?;
+        }
+
+        return Ok(self.encode_pair(hasher.finalize_pair()?.0))
+    }
+}
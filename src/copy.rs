@@ -0,0 +1,138 @@
+// Copy And Hash
+//
+// Copies a file while hashing it, so the data is only read once. The destination is never left half written: it is removed if anything fails.
+//
+// `copy_and_verify()` also reads the copy back from disk after syncing it, to catch data that was corrupted on its way to storage.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::hashers::CHUNK_SIZE;
+use crate::retry::{io_error, RetryReader};
+use crate::{long_path, FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Copy And Hash
+    ///
+    /// Copies `src` to `dst` and returns the digests of the data, reading the source only once. The digests are the same as `read(src)` and `read(dst)`.
+    ///
+    /// Returns `FileError::Io(ErrorKind::AlreadyExists)` if `dst` exists; use `copy_and_hash_overwrite()` to replace it. Permissions and timestamps are not copied. If hashing or writing fails the partial destination is removed.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let dst = dir.path().join("copy.txt");
+    ///
+    /// let context = ParanoidHash::default();
+    /// let digests = context.copy_and_hash("examples/example_file.txt",&dst).unwrap();
+    /// assert_eq!(digests,context.read(&dst).unwrap());
+    /// ```
+    pub fn copy_and_hash<S: AsRef<Path>, D: AsRef<Path>>(&self, src: S, dst: D) -> Result<(String,String),FileError> {
+        return self.copy_hashed(src.as_ref(), dst.as_ref(), false).map(|(digests, _)| digests)
+    }
+    /// # Copy And Hash Overwrite
+    ///
+    /// Same as `copy_and_hash()`, but replaces `dst` if it exists. Returns `FileError::Io(ErrorKind::InvalidInput)` if `src` and `dst` are the same file.
+    pub fn copy_and_hash_overwrite<S: AsRef<Path>, D: AsRef<Path>>(&self, src: S, dst: D) -> Result<(String,String),FileError> {
+        return self.copy_hashed(src.as_ref(), dst.as_ref(), true).map(|(digests, _)| digests)
+    }
+    /// # Copy And Verify
+    ///
+    /// Same as `copy_and_hash()`, but then syncs the destination to disk with `File::sync_all()`, hashes it again with `read()` and returns `FileError::VerificationFailed` if its digests differ from the source's. A destination that fails verification is removed.
+    ///
+    /// The source is read once and the copy is read back once.
+    pub fn copy_and_verify<S: AsRef<Path>, D: AsRef<Path>>(&self, src: S, dst: D) -> Result<(String,String),FileError> {
+        return self.copy_and_verify_with_hook(src, dst, |_| {})
+    }
+    /// Same as `copy_and_verify()`, calling `hook` with the destination after it is synced and before it is read back. Only intended for testing the failure path.
+    #[doc(hidden)]
+    pub fn copy_and_verify_with_hook<S: AsRef<Path>, D: AsRef<Path>, F: FnOnce(&Path)>(&self, src: S, dst: D, hook: F) -> Result<(String,String),FileError> {
+        let dst = dst.as_ref();
+        let (expected, destination) = self.copy_hashed(src.as_ref(), dst, false)?;
+
+        let synced = destination.sync_all().map_err(|e| FileError::Io(e.kind()));
+        drop(destination);
+        let verified = synced.and_then(|_| {
+            hook(dst);
+            return self.read(dst)
+        });
+
+        match verified {
+            Ok(digests) if digests == expected => return Ok(digests),
+            Ok(_) => {
+                let _ = fs::remove_file(dst);
+                return Err(FileError::VerificationFailed)
+            }
+            Err(error) => {
+                let _ = fs::remove_file(dst);
+                return Err(error)
+            }
+        }
+    }
+    // Copies `src` to `dst` while hashing it, returning the digests and the destination (still open for writing)
+    pub(crate) fn copy_hashed(&self, src: &Path, dst: &Path, overwrite: bool) -> Result<((String,String),File),FileError> {
+        let src: &Path = &long_path::extended(src);
+        let dst: &Path = &long_path::extended(dst);
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !src.exists() {
+            return Err(FileError::FileNotFound)
+        }
+        self.check_file_size(src)?;
+
+        // Truncating the destination would destroy the source
+        if overwrite && same_file(src, dst) {
+            return Err(FileError::Io(io::ErrorKind::InvalidInput))
+        }
+
+        let source = self.open_file(src)?;
+        let mut options = OpenOptions::new();
+        if overwrite {
+            options.write(true).create(true).truncate(true);
+        }
+        else {
+            options.write(true).create_new(true);
+        }
+        let mut destination = options.open(dst).map_err(|e| FileError::Io(e.kind()))?;
+
+        match self.copy_into(source, &mut destination) {
+            Ok(digests) => return Ok((digests, destination)),
+            Err(error) => {
+                drop(destination);
+                let _ = fs::remove_file(dst);
+                return Err(error)
+            }
+        }
+    }
+    fn copy_into(&self, source: File, destination: &mut File) -> Result<(String,String),FileError> {
+        let mut source = RetryReader::new(source, self.retry_policy);
+        let mut hasher = self.pair_hasher(None)?;
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut total: u64 = 0;
+
+        loop {
+            let length = match source.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(length) => length,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(io_error(e)),
+            };
+            total += length as u64;
+            self.check_size(total)?;
+            hasher.update(&buffer[..length])?;
+            destination.write_all(&buffer[..length]).map_err(|e| FileError::Io(e.kind()))?;
+        }
+        self.check_empty(total)?;
+
+        return Ok(self.encode_pair(hasher.finalize_pair()?.0))
+    }
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => return a == b,
+        _ => return false,
+    }
+}
@@ -0,0 +1,33 @@
+// Subtle Integration
+//
+// Digest comparisons as `subtle::Choice`, so they can be combined with other constant-time checks without branching on any of them.
+
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::{HashDigest, ParanoidHash};
+
+impl ConstantTimeEq for HashDigest {
+    /// The algorithms are compared normally since they are not secret, and the bytes in constant time. Digests from different hash functions are never equal, as with `==`.
+    fn ct_eq(&self, other: &Self) -> Choice {
+        return Choice::from((self.algorithm() == other.algorithm()) as u8) & self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+impl ParanoidHash {
+    /// # Compare Hash CT
+    ///
+    /// Same as `compare_hash()`, but returns a `subtle::Choice` instead of a `bool`. Digests are compared case-insensitively and digests of different lengths are never equal.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let (blake2b,sha512) = ParanoidHash::default().read_str("Hello");
+    /// let both = ParanoidHash::compare_hash_ct(&blake2b,blake2b.to_lowercase()) & ParanoidHash::compare_hash_ct(&sha512,&sha512);
+    /// assert!(bool::from(both));
+    /// ```
+    pub fn compare_hash_ct<A: AsRef<str>, B: AsRef<str>>(hash1: A, hash2: B) -> Choice {
+        let hash1_lowercase = hash1.as_ref().to_lowercase();
+        let hash2_lowercase = hash2.as_ref().to_lowercase();
+        return hash1_lowercase.as_bytes().ct_eq(hash2_lowercase.as_bytes())
+    }
+}
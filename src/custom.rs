@@ -0,0 +1,211 @@
+// Custom Library Hash Functions
+//
+// The library half of a `ParanoidHash` can be replaced by any implementation of `LibraryHasher` (such as one backed by a hardware security module), while the Operating System Hash Function, the file handling, the checks and the verification stay the same. The built-in BLAKE2B is itself a `LibraryHasher`.
+
+use std::io::{self, Read};
+use std::path::Path;
+
+#[cfg(not(feature = "blake2-rustcrypto"))]
+use blake2_rfc::blake2b::Blake2b;
+
+#[cfg(feature = "blake2-rustcrypto")]
+use crate::blake2_rustcrypto::Blake2b;
+
+use crate::hashers::{DigestPair, Digester, CHUNK_SIZE};
+use crate::retry::{io_error, RetryReader};
+use crate::verify::check_expected;
+use crate::{long_path, trace, FileError, ParanoidHash, Verification, VerifyPolicy};
+
+/// # Library Hasher
+///
+/// A running hash function for the library half of the digests. A new hasher is used for every input.
+pub trait LibraryHasher {
+    /// Feeds the next bytes of the input.
+    fn update(&mut self, bytes: &[u8]);
+    /// Consumes the hasher and returns the digest, which must be `output_len()` bytes long.
+    fn finalize(self) -> Vec<u8> where Self: Sized;
+    /// The length of the digest in bytes.
+    fn output_len(&self) -> usize;
+}
+
+/// # Library Hasher Factory
+///
+/// Creates a fresh `LibraryHasher` for every input hashed by a [`ParanoidHashCustom`]. Implemented for every closure returning a `LibraryHasher`.
+pub trait LibraryHasherFactory {
+    type Hasher: LibraryHasher;
+
+    fn new_hasher(&self) -> Self::Hasher;
+}
+
+impl<H: LibraryHasher, F: Fn() -> H> LibraryHasherFactory for F {
+    type Hasher = H;
+
+    fn new_hasher(&self) -> H {
+        return self()
+    }
+}
+
+/// # BLAKE2B Hasher
+///
+/// The built-in BLAKE2B as a `LibraryHasher`. It is implemented by `blake2-rfc`, or by the RustCrypto `blake2` crate with the `blake2-rustcrypto` feature; both produce the same digests.
+#[derive(Clone)]
+pub struct Blake2bHasher {
+    state: Blake2b,
+    digest_size: usize,
+}
+
+impl Blake2bHasher {
+    /// ## New
+    ///
+    /// Panics if `digest_size` is not between 1 and 64 bytes.
+    pub fn new(digest_size: usize) -> Self {
+        return Blake2bHasher { state: Blake2b::new(digest_size), digest_size }
+    }
+    /// ## With Key
+    ///
+    /// Panics if `digest_size` is not between 1 and 64 bytes or `key` is longer than 64 bytes.
+    pub fn with_key(digest_size: usize, key: &[u8]) -> Self {
+        return Blake2bHasher { state: Blake2b::with_key(digest_size, key), digest_size }
+    }
+}
+
+impl LibraryHasher for Blake2bHasher {
+    fn update(&mut self, bytes: &[u8]) {
+        self.state.update(bytes);
+    }
+    fn finalize(self) -> Vec<u8> {
+        return self.state.finalize().as_bytes().to_vec()
+    }
+    fn output_len(&self) -> usize {
+        return self.digest_size
+    }
+}
+
+/// # Custom Library Hashing Constructor
+///
+/// A `ParanoidHash` whose library hash function is created by `factory`. The digest size and library hash function of `config` are ignored; everything else (the Operating System Hash Function, Output Encoding, size limits and empty input handling) applies as usual.
+///
+/// ```rust
+/// use paranoid_hash::{Blake2bHasher,ParanoidHash,ParanoidHashCustom};
+///
+/// let config = ParanoidHash::default();
+/// let custom = ParanoidHashCustom::new(config.clone(),|| Blake2bHasher::new(64));
+/// assert_eq!(custom.read("examples/example_file.txt").unwrap(),config.read("examples/example_file.txt").unwrap());
+/// ```
+#[derive(Debug,Clone)]
+pub struct ParanoidHashCustom<F: LibraryHasherFactory> {
+    config: ParanoidHash,
+    factory: F,
+}
+
+impl<F: LibraryHasherFactory> ParanoidHashCustom<F> {
+    /// ## New
+    pub fn new(config: ParanoidHash, factory: F) -> Self {
+        return ParanoidHashCustom { config, factory }
+    }
+    /// ## Return Config
+    pub fn return_config(&self) -> &ParanoidHash {
+        return &self.config
+    }
+    /// # Read
+    ///
+    /// Same as `ParanoidHash::read()` with the custom library hash function.
+    pub fn read<T: AsRef<Path>>(&self, path: T) -> Result<(String,String),FileError> {
+        return self.config.read_with_hasher(path, self.factory.new_hasher())
+    }
+    /// # Read Bytes
+    ///
+    /// Same as `ParanoidHash::try_read_bytes()` with the custom library hash function.
+    pub fn read_bytes(&self, bytes: &[u8]) -> Result<(String,String),FileError> {
+        return self.config.read_bytes_with_hasher(bytes, self.factory.new_hasher())
+    }
+    /// # Read From Reader
+    ///
+    /// Same as `ParanoidHash::read_from_reader()` with the custom library hash function.
+    pub fn read_from_reader<R: Read>(&self, reader: R) -> Result<(String,String),FileError> {
+        return self.config.read_from_reader_with_hasher(reader, self.factory.new_hasher())
+    }
+    /// # Verify File
+    ///
+    /// Same as `ParanoidHash::verify_file()` with the custom library hash function. `expected_library` takes the place of the BLAKE2B digest.
+    pub fn verify_file<T: AsRef<Path>>(&self, path: T, expected_library: Option<&str>, expected_os: Option<&str>, policy: VerifyPolicy) -> Result<Verification,FileError> {
+        let hasher = self.factory.new_hasher();
+        check_expected(expected_library, hasher.output_len())?;
+        check_expected(expected_os, self.config.os_hash_function.digest_len())?;
+
+        let span = trace::Span::verify(&self.config, path.as_ref());
+        let result = span.in_scope(|| self.config.hash_path_with_hasher(path.as_ref(), hasher));
+        span.finish(result.as_ref().map(|(_, length)| *length));
+
+        let (digests, _) = result?;
+        let verification = Verification::new(policy, &digests, expected_library, expected_os);
+        span.verified(verification.passed);
+        return Ok(verification)
+    }
+}
+
+impl ParanoidHash {
+    /// # Read With Hasher
+    ///
+    /// Same as `read()`, but the library digest is computed by `hasher` instead of BLAKE2B. The digest size and library hash function of this context are ignored.
+    ///
+    /// Every byte of the file is fed to `hasher` exactly once, in order.
+    pub fn read_with_hasher<T: AsRef<Path>, H: LibraryHasher>(&self, path: T, hasher: H) -> Result<(String,String),FileError> {
+        let span = trace::Span::read(self, path.as_ref());
+        span.backend("stream");
+        let result = span.in_scope(|| self.hash_path_with_hasher(path.as_ref(), hasher));
+        span.finish(result.as_ref().map(|(_, length)| *length));
+        return Ok(self.encode_pair(result?.0))
+    }
+    /// # Read Bytes With Hasher
+    ///
+    /// Same as `try_read_bytes()`, but the library digest is computed by `hasher` instead of BLAKE2B.
+    pub fn read_bytes_with_hasher<H: LibraryHasher>(&self, bytes: &[u8], hasher: H) -> Result<(String,String),FileError> {
+        return Ok(self.encode_pair(self.hash_reader_with_hasher(bytes, hasher)?.0))
+    }
+    /// # Read From Reader With Hasher
+    ///
+    /// Same as `read_from_reader()`, but the library digest is computed by `hasher` instead of BLAKE2B.
+    pub fn read_from_reader_with_hasher<R: Read, H: LibraryHasher>(&self, reader: R, hasher: H) -> Result<(String,String),FileError> {
+        let span = trace::Span::reader(self);
+        let result = span.in_scope(|| self.hash_reader_with_hasher(reader, hasher));
+        span.finish(result.as_ref().map(|(_, length)| *length));
+        return Ok(self.encode_pair(result?.0))
+    }
+    fn hash_path_with_hasher<H: LibraryHasher>(&self, path: &Path, hasher: H) -> Result<(DigestPair,u64),FileError> {
+        let path: &Path = &long_path::extended(path);
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+        self.check_file_size(path)?;
+
+        let file = self.open_file(path)?;
+        return self.hash_reader_with_hasher(file, hasher)
+    }
+    // Streams the reader into `hasher` and the Operating System Hash Function. Also returns the number of bytes read.
+    fn hash_reader_with_hasher<R: Read, H: LibraryHasher>(&self, reader: R, mut hasher: H) -> Result<(DigestPair,u64),FileError> {
+        let mut reader = RetryReader::new(reader, self.retry_policy);
+        let mut os = Digester::os(&self.os_hash_function, self.os_options)?;
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut total: u64 = 0;
+
+        loop {
+            let length = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(length) => length,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(io_error(e)),
+            };
+            total += length as u64;
+            self.check_size(total)?;
+            hasher.update(&buffer[..length]);
+            os.update(&buffer[..length])?;
+        }
+        self.check_empty(total)?;
+
+        let (os, _) = os.finalize()?;
+        return Ok(((hasher.finalize(), os), total))
+    }
+}
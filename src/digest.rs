@@ -0,0 +1,228 @@
+// Hash Digest
+//
+// Digests as bytes rather than hexadecimal strings, so they cannot be compared by accident with a case-sensitive, variable-time `==`.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use crate::hashers::DigestPair;
+use crate::hex_input::prefix_array;
+use crate::{Algo, FileError, ParanoidHash};
+
+// The number of bytes shown by `Debug` before the digest is truncated
+const DEBUG_BYTES: usize = 8;
+
+/// # Hash Digest
+///
+/// A digest and the hash function that produced it.
+///
+/// Equality is constant-time over the digest bytes. Two digests from different hash functions are never equal. With the `subtle` feature it also implements `subtle::ConstantTimeEq`.
+///
+/// ```rust
+/// use paranoid_hash::{Algo,HashDigest,OsAlgorithm,ParanoidHash};
+///
+/// let (_,sha256) = ParanoidHash::new(64,OsAlgorithm::SHA256).read_bytes_digests(b"abc").unwrap();
+/// let expected = HashDigest::from_hex(Algo::Os(OsAlgorithm::SHA256),"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad").unwrap();
+/// assert_eq!(sha256,expected);
+/// ```
+#[derive(Clone)]
+pub struct HashDigest {
+    algorithm: Algo,
+    bytes: Vec<u8>,
+}
+
+impl HashDigest {
+    /// ## New Hash Digest
+    ///
+    /// Returns `FileError::InvalidDigest` if the number of bytes does not match the digest size of the hash function.
+    pub fn new(algorithm: Algo, bytes: Vec<u8>) -> Result<Self,FileError> {
+        // Library digests are 1 byte up to the largest digest size of the hash function
+        let max = algorithm.library().map_or(usize::MAX, |(library, _)| library.max_digest_size());
+        if bytes.is_empty() || bytes.len() > max || bytes.len() != digest_size(&algorithm) {
+            return Err(FileError::InvalidDigest)
+        }
+        return Ok(HashDigest { algorithm, bytes })
+    }
+    /// ## From Hex
+    ///
+    /// Decodes a hexadecimal digest (either case). Returns `FileError::InvalidDigest` if it is not hexadecimal or has the wrong length for the hash function.
+    pub fn from_hex<T: AsRef<str>>(algorithm: Algo, s: T) -> Result<Self,FileError> {
+        let bytes = hex::decode(s.as_ref()).map_err(|_| FileError::InvalidDigest)?;
+        return HashDigest::new(algorithm, bytes)
+    }
+    /// ## Algorithm
+    ///
+    /// The hash function that produced the digest.
+    pub fn algorithm(&self) -> &Algo {
+        return &self.algorithm
+    }
+    /// ## As Bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        return &self.bytes
+    }
+    /// ## To Upper Hexadecimal
+    ///
+    /// The same format as the `String` returned by `read()`.
+    pub fn to_hex_upper(&self) -> String {
+        return hex::encode_upper(&self.bytes)
+    }
+    /// ## To Lower Hexadecimal
+    pub fn to_hex_lower(&self) -> String {
+        return hex::encode(&self.bytes)
+    }
+    /// ## Short
+    ///
+    /// The first `n` upper hexadecimal characters of the digest, for log lines and display. Returns `None` if `n` is 0 or longer than the full hexadecimal digest.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{OsAlgorithm,ParanoidHash};
+    ///
+    /// let (_,sha256) = ParanoidHash::new(64,OsAlgorithm::SHA256).read_bytes_digests(b"abc").unwrap();
+    /// assert_eq!(sha256.short(8).unwrap(),"BA7816BF");
+    /// ```
+    pub fn short(&self, n: usize) -> Option<String> {
+        if n == 0 || n > self.bytes.len() * 2 {
+            return None
+        }
+        let mut hex = self.to_hex_upper();
+        hex.truncate(n);
+        return Some(hex)
+    }
+    /// ## Prefix Bytes
+    ///
+    /// The first `n` bytes of the digest, such as for content-addressed file names. Returns `None` if `n` is 0 or longer than the digest.
+    pub fn prefix_bytes(&self, n: usize) -> Option<&[u8]> {
+        if n == 0 || n > self.bytes.len() {
+            return None
+        }
+        return Some(&self.bytes[..n])
+    }
+    /// ## Prefix U64
+    ///
+    /// The first 8 bytes of the digest as a big-endian integer, the same as `ParanoidHash::digest_prefix_u64()`. Returns `None` if the digest is shorter than 8 bytes.
+    pub fn prefix_u64(&self) -> Option<u64> {
+        return prefix_array(&self.bytes).ok().map(u64::from_be_bytes)
+    }
+    /// ## Prefix U128
+    ///
+    /// The first 16 bytes of the digest as a big-endian integer. Returns `None` if the digest is shorter than 16 bytes.
+    pub fn prefix_u128(&self) -> Option<u128> {
+        return prefix_array(&self.bytes).ok().map(u128::from_be_bytes)
+    }
+    /// ## Matches Prefix
+    ///
+    /// Same as `ParanoidHash::matches_prefix()` using this digest as the full digest.
+    pub fn matches_prefix<T: AsRef<str>>(&self, prefix: T) -> bool {
+        return ParanoidHash::matches_prefix(self.to_hex_upper(), prefix)
+    }
+}
+
+// The digest size in bytes of a hash function
+fn digest_size(algorithm: &Algo) -> usize {
+    match algorithm {
+        Algo::Os(os_hash) => return os_hash.digest_len(),
+        library => return library.library().map_or(0, |(_, size)| size),
+    }
+}
+
+/// Compares two byte slices without exiting early on the first difference.
+#[cfg(not(feature = "subtle"))]
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false
+    }
+    return a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Compares two byte slices with `subtle`. Slices of different lengths are unequal, as above.
+#[cfg(feature = "subtle")]
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    use subtle::ConstantTimeEq;
+    return a.ct_eq(b).into()
+}
+
+impl PartialEq for HashDigest {
+    fn eq(&self, other: &Self) -> bool {
+        // The algorithm is not secret, only the bytes need a constant-time comparison
+        return self.algorithm == other.algorithm && constant_time_eq(&self.bytes, &other.bytes)
+    }
+}
+
+impl Eq for HashDigest {}
+
+impl Hash for HashDigest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.algorithm.hash(state);
+        self.bytes.hash(state);
+    }
+}
+
+impl AsRef<[u8]> for HashDigest {
+    fn as_ref(&self) -> &[u8] {
+        return &self.bytes
+    }
+}
+
+/// Upper hexadecimal, matching the rest of the crate.
+impl fmt::Display for HashDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex_upper())
+    }
+}
+
+/// Only the first 8 bytes are shown.
+impl fmt::Debug for HashDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digest = hex::encode_upper(&self.bytes[..self.bytes.len().min(DEBUG_BYTES)]);
+        if self.bytes.len() > DEBUG_BYTES {
+            digest.push_str("...");
+        }
+        f.debug_struct("HashDigest")
+            .field("algorithm", &self.algorithm)
+            .field("digest", &digest)
+            .finish()
+    }
+}
+
+impl ParanoidHash {
+    /// ## Matches Prefix
+    ///
+    /// Returns true if the hexadecimal `prefix` is the start of the hexadecimal digest `full` (case-insensitive). The comparison is constant-time over the length of the prefix.
+    ///
+    /// Returns false if `prefix` is empty, longer than `full`, or either is not hexadecimal.
+    ///
+    /// **Warning:** An abbreviated digest is much weaker than the full digest. A prefix of `n` hexadecimal characters only has `4n` bits, so a collision can be found with about `2^(2n)` attempts (`2^32` for 16 characters). Only use prefixes to find a file, never to prove it has not been tampered with.
+    pub fn matches_prefix<A: AsRef<str>, B: AsRef<str>>(full: A, prefix: B) -> bool {
+        let full = full.as_ref().as_bytes();
+        let prefix = prefix.as_ref().as_bytes();
+
+        if prefix.is_empty() || prefix.len() > full.len() {
+            return false
+        }
+        if !full.iter().chain(prefix.iter()).all(|b| b.is_ascii_hexdigit()) {
+            return false
+        }
+        return constant_time_eq(&full[..prefix.len()].to_ascii_lowercase(), &prefix.to_ascii_lowercase())
+    }
+    /// # Read Digests
+    ///
+    /// Same as `read()`, but returns the BLAKE2B and Operating System digests as `HashDigest` instead of hexadecimal strings.
+    pub fn read_digests<T: AsRef<Path>>(&self, path: T) -> Result<(HashDigest,HashDigest),FileError> {
+        let (digests, _) = self.hash_path_raw(path.as_ref(), None)?;
+        return Ok(self.to_digests(digests))
+    }
+    /// # Read Bytes Digests
+    ///
+    /// Same as `try_read_bytes()`, but returns the BLAKE2B and Operating System digests as `HashDigest` instead of hexadecimal strings.
+    pub fn read_bytes_digests(&self, bytes: &[u8]) -> Result<(HashDigest,HashDigest),FileError> {
+        self.check_empty(bytes.len() as u64)?;
+        let (digests, _) = self.hash_bytes_raw(bytes)?;
+        return Ok(self.to_digests(digests))
+    }
+    fn to_digests(&self, (library, os): DigestPair) -> (HashDigest,HashDigest) {
+        let library = HashDigest { algorithm: self.library_algo(), bytes: library };
+        let os = HashDigest { algorithm: Algo::Os(self.os_hash_function.clone()), bytes: os };
+        return (library, os)
+    }
+}
@@ -0,0 +1,148 @@
+// Digest Key
+//
+// Digests as compact map keys for deduplication and indexing: the raw bytes are held inline with their length, instead of in a 128 character hexadecimal `String`.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{HashDigest, HashOutput, HexError, ParanoidHash};
+
+/// The largest digest a `DigestKey` can hold, in bytes (the full size of BLAKE2B and SHA-512)
+pub const MAX_KEY_BYTES: usize = 64;
+
+/// # Digest Key
+///
+/// A digest of 1 to 64 bytes stored inline, for use as a key in a `HashMap`, `HashSet` or `BTreeMap`. It is `Copy` and takes 65 bytes without any allocation.
+///
+/// Digests longer than 64 bytes (such as SHAKE256 with the `xof` feature) cannot be held, so conversions from `HashDigest` and `HashOutput` are `TryFrom` and return `HexError::KeyLength` for them.
+///
+/// `Eq`, `Ord` and `Hash` are the same as for the digest bytes as a `[u8]`, so a map keyed by `DigestKey` can be looked up with a `&[u8]` through `Borrow`. The hash function that produced the digest is not stored, so keep digests of different hash functions in separate maps.
+///
+/// **Note:** Equality here is **not** constant-time. It is meant for data structures, where the digests are not secret. Compare digests that verify something with `HashDigest` or `compare_hash()` instead.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use std::convert::TryFrom;
+/// use paranoid_hash::{DigestKey,ParanoidHash};
+///
+/// let (blake2b,_) = ParanoidHash::default().read_bytes_digests(b"abc").unwrap();
+/// let mut seen = HashMap::new();
+/// seen.insert(DigestKey::try_from(&blake2b).unwrap(),"abc");
+///
+/// assert_eq!(seen.get(blake2b.as_bytes()),Some(&"abc"));
+/// ```
+#[derive(Clone,Copy)]
+pub struct DigestKey {
+    len: u8,
+    bytes: [u8; MAX_KEY_BYTES],
+}
+
+impl DigestKey {
+    /// ## From Bytes
+    ///
+    /// Returns `None` if `bytes` is empty or longer than `MAX_KEY_BYTES`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() || bytes.len() > MAX_KEY_BYTES {
+            return None
+        }
+        let mut key = DigestKey { len: bytes.len() as u8, bytes: [0u8; MAX_KEY_BYTES] };
+        key.bytes[..bytes.len()].copy_from_slice(bytes);
+        return Some(key)
+    }
+    /// ## From Hex
+    ///
+    /// Decodes a hexadecimal digest, such as one returned by `read()`. An optional `0x` prefix and mixed case are accepted. Returns `HexError::KeyLength` if it decodes to no bytes or more than `MAX_KEY_BYTES`.
+    pub fn from_hex<T: AsRef<str>>(s: T) -> Result<Self,HexError> {
+        let bytes = ParanoidHash::try_decode_from_hex(s)?;
+        return DigestKey::from_bytes(&bytes).ok_or(HexError::KeyLength { actual: bytes.len() })
+    }
+    /// ## As Bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        return &self.bytes[..self.len as usize]
+    }
+    /// ## To Hex Upper
+    pub fn to_hex_upper(&self) -> String {
+        return hex::encode_upper(self.as_bytes())
+    }
+}
+
+/// Returns `HexError::KeyLength` if the digest is longer than `MAX_KEY_BYTES`.
+impl TryFrom<&HashDigest> for DigestKey {
+    type Error = HexError;
+
+    fn try_from(digest: &HashDigest) -> Result<Self,HexError> {
+        return DigestKey::from_bytes(digest.as_bytes()).ok_or(HexError::KeyLength { actual: digest.as_bytes().len() })
+    }
+}
+
+impl TryFrom<HashDigest> for DigestKey {
+    type Error = HexError;
+
+    fn try_from(digest: HashDigest) -> Result<Self,HexError> {
+        return DigestKey::try_from(&digest)
+    }
+}
+
+/// The key of the library (BLAKE2B) digest.
+impl TryFrom<&HashOutput> for DigestKey {
+    type Error = HexError;
+
+    fn try_from(output: &HashOutput) -> Result<Self,HexError> {
+        return DigestKey::try_from(&output.blake2b)
+    }
+}
+
+impl PartialEq for DigestKey {
+    fn eq(&self, other: &Self) -> bool {
+        return self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for DigestKey {}
+
+impl PartialOrd for DigestKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DigestKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+// Hashes the same as the `[u8]` it borrows as
+impl Hash for DigestKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl Borrow<[u8]> for DigestKey {
+    fn borrow(&self) -> &[u8] {
+        return self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for DigestKey {
+    fn as_ref(&self) -> &[u8] {
+        return self.as_bytes()
+    }
+}
+
+/// Upper hexadecimal, matching the rest of the crate.
+impl fmt::Display for DigestKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex_upper())
+    }
+}
+
+impl fmt::Debug for DigestKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DigestKey").field(&self.to_hex_upper()).finish()
+    }
+}
@@ -0,0 +1,77 @@
+// Directory Digest
+//
+// A single pair of digests covering every file in a directory, with a framing that is fully specified so the same tree gives the same digests on every platform and filesystem.
+
+use std::io::{ErrorKind, Read};
+use std::path::Path;
+
+use crate::modified::PausingReader;
+use crate::retry::RetryReader;
+use crate::{trace, walk, FileError, ParanoidHash, WINDOW_SIZE};
+
+impl ParanoidHash {
+    /// # Read Directory
+    ///
+    /// Hashes the names and contents of every regular file below `dir` into one BLAKE2B digest and one Operating System digest.
+    ///
+    /// ## Ordering
+    ///
+    /// Each file is named by its path relative to `dir` with components joined by `/` (on every platform). Files are sorted by the UTF-8 bytes of that name, so the order never depends on the filesystem. Symbolic links are only followed with `WalkOptions::follow_symlinks` and empty directories are not included.
+    ///
+    /// File names that are not valid UTF-8 are rejected with `FileError::AtPath` holding `FileError::Io(ErrorKind::InvalidData)`.
+    ///
+    /// ## Framing
+    ///
+    /// For each file, in order, the following is fed to both hash functions:
+    ///
+    /// ```text
+    /// u64 big-endian length of the name | name (UTF-8) | u64 big-endian length of the contents | contents
+    /// ```
+    ///
+    /// Nothing else is hashed, so a directory without files has the digests of empty input (or `FileError::EmptyFile` with `reject_empty_input()`). A file that changes size while it is being hashed fails with `FileError::AtPath` holding `FileError::Io(ErrorKind::UnexpectedEof)`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let digests = context.read_dir("tests/fixtures/dir_digest").unwrap();
+    /// assert_eq!(digests,context.read_dir("tests/fixtures/dir_digest").unwrap());
+    /// ```
+    pub fn read_dir<T: AsRef<Path>>(&self, dir: T) -> Result<(String,String),FileError> {
+        let span = trace::Span::read_dir(self, dir.as_ref());
+        let result = span.in_scope(|| self.hash_dir(dir.as_ref()));
+        span.finish(result.as_ref().map(|(_, bytes)| *bytes));
+        return Ok(result?.0)
+    }
+    // Also returns the number of bytes of file contents hashed
+    fn hash_dir(&self, dir: &Path) -> Result<((String,String),u64),FileError> {
+        let mut hasher = self.pair_hasher(None)?;
+        let mut window = vec![0u8; self.window_size.unwrap_or(WINDOW_SIZE)];
+        let mut count = 0;
+        let mut bytes = 0;
+
+        for (name, path) in walk::walk_entries(dir, &self.walk_options)? {
+            let at_path = |e: FileError| FileError::AtPath(path.clone(), Box::new(e));
+            let file = self.open_file(&path).map_err(at_path)?;
+            let before = file.metadata().map_err(|e| at_path(FileError::Io(e.kind())))?;
+            let length = before.len();
+            self.check_size(length).map_err(at_path)?;
+
+            hasher.update(&(name.len() as u64).to_be_bytes())?;
+            hasher.update(name.as_bytes())?;
+            hasher.update(&length.to_be_bytes())?;
+
+            // Reads one byte past the expected length so a file that grew is caught too
+            let read = hasher.update_read(PausingReader::new(RetryReader::new((&file).take(length + 1), self.retry_policy), self.read_pause), &mut window).map_err(at_path)?;
+            self.check_modified(&before, || file.metadata()).map_err(at_path)?;
+            if read != length {
+                return Err(at_path(FileError::Io(ErrorKind::UnexpectedEof)))
+            }
+            count += 1;
+            bytes += length;
+        }
+        self.check_empty(count)?;
+
+        return Ok((self.encode_pair(hasher.finalize_pair()?.0), bytes))
+    }
+}
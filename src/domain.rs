@@ -0,0 +1,46 @@
+// Domain Separation
+//
+// Hashes bytes under a context string, so a digest computed for one protocol role cannot be replayed as a digest for another.
+
+use crate::hashers::DigestPair;
+use crate::{FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Bytes With Context
+    ///
+    /// Hashes `data` in the domain named by `context`. Both hash functions are given the same framed input:
+    ///
+    /// ```text
+    /// u64_be(len(context)) || context || data
+    /// ```
+    ///
+    /// where `len(context)` is the length of the UTF-8 encoded context in bytes, written as an 8 byte big-endian integer. The length prefix makes the framing unambiguous: the context `"ab"` with the data `"c"` and the context `"a"` with the data `"bc"` are different inputs. BLAKE2B is never keyed or personalized here, so the construction does not depend on the length of the context.
+    ///
+    /// The framing is part of the stable output of this crate and will not change between releases. The same digests can be computed without this function by hashing the framed bytes with `read_bytes()`.
+    ///
+    /// Panics if the Operating System Hash Function fails.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let signing = context.read_bytes_with_context("signing",b"payload");
+    /// let encryption = context.read_bytes_with_context("encryption",b"payload");
+    /// assert_ne!(signing,encryption);
+    ///
+    /// let mut framed = (7 as u64).to_be_bytes().to_vec();
+    /// framed.extend_from_slice(b"signingpayload");
+    /// assert_eq!(signing,context.read_bytes(&framed));
+    /// ```
+    pub fn read_bytes_with_context(&self, context: &str, data: &[u8]) -> (String,String) {
+        let digests = self.hash_with_context(context, data).expect("[Error] Failed To Hash Using Operating System Hash Function");
+        return self.encode_pair(digests)
+    }
+    fn hash_with_context(&self, context: &str, data: &[u8]) -> Result<DigestPair,FileError> {
+        let mut hasher = self.pair_hasher(None)?;
+        hasher.update(&(context.len() as u64).to_be_bytes())?;
+        hasher.update(context.as_bytes())?;
+        hasher.update(data)?;
+        return Ok(hasher.finalize_pair()?.0)
+    }
+}
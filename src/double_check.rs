@@ -0,0 +1,76 @@
+// Double-Checked Reads
+//
+// Hashes a file twice through independent paths (a memory map and buffered reads with their own buffer) and only returns digests both agree on, to catch bit flips in the page cache or a faulty memory mapped read. This reads the file twice, so it is never used by default.
+
+use std::io::BufReader;
+use std::path::Path;
+
+use filebuffer::FileBuffer;
+
+use crate::hashers::{DigestPair, CHUNK_SIZE};
+use crate::retry::RetryReader;
+use crate::{long_path, trace, FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Double Checked
+    ///
+    /// Hashes the file once through a memory map and once with buffered `std::fs` reads, and returns the digests only if both passes produce the same digests. Otherwise returns `FileError::ReadInconsistency`, which means the data read back differed between the passes (the file changed while it was being hashed, or something between the disk and this process is corrupting data).
+    ///
+    /// The file must be a regular file that can be memory mapped. `max_file_size()` and `reject_empty_input()` apply as usual.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// assert_eq!(context.read_double_checked("examples/example_file.txt").unwrap(),context.read("examples/example_file.txt").unwrap());
+    /// ```
+    pub fn read_double_checked<T: AsRef<Path>>(&self, path: T) -> Result<(String,String),FileError> {
+        return self.read_double_checked_with_hook(path, |_| {})
+    }
+    /// Same as `read_double_checked()`, calling `hook` with the path between the two passes. Only intended for testing the failure path.
+    #[doc(hidden)]
+    pub fn read_double_checked_with_hook<T: AsRef<Path>, F: FnOnce(&Path)>(&self, path: T, hook: F) -> Result<(String,String),FileError> {
+        let path = path.as_ref();
+        let span = trace::Span::read(self, path);
+        span.backend("double-checked");
+        let result = span.in_scope(|| self.hash_double_checked(path, hook));
+        span.finish(result.as_ref().map(|(_, length)| *length));
+
+        return Ok(self.encode_pair(result?.0))
+    }
+    fn hash_double_checked<F: FnOnce(&Path)>(&self, path: &Path, hook: F) -> Result<(DigestPair,u64),FileError> {
+        let extended = long_path::extended(path);
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !extended.exists() {
+            return Err(FileError::FileNotFound)
+        }
+        self.check_file_size(&extended)?;
+
+        let metadata = extended.metadata().map_err(|e| FileError::Io(e.kind()))?;
+        if !metadata.is_file() {
+            return Err(FileError::Io(std::io::ErrorKind::InvalidInput))
+        }
+
+        // First pass through the memory map, which is released before the second pass
+        let mapped = {
+            let fbuffer = FileBuffer::open(&extended).map_err(|e| FileError::Io(e.kind()))?;
+            let mut hasher = self.pair_hasher(None)?;
+            hasher.update(&fbuffer)?;
+            (hasher.finalize_pair()?.0, fbuffer.len() as u64)
+        };
+        hook(path);
+
+        // Second pass through buffered reads into a separate buffer
+        let file = self.open_file(&extended)?;
+        let mut hasher = self.pair_hasher(None)?;
+        let length = hasher.update_reader_checked(BufReader::with_capacity(CHUNK_SIZE, RetryReader::new(file, self.retry_policy)), |total| self.check_size(total))?;
+        let buffered = (hasher.finalize_pair()?.0, length);
+
+        if mapped != buffered {
+            return Err(FileError::ReadInconsistency)
+        }
+        self.check_empty(length)?;
+        return Ok(buffered)
+    }
+}
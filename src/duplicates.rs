@@ -0,0 +1,103 @@
+// Duplicate Files
+//
+// Files are first grouped by size and only files that share a size with another file are hashed.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{walk, FileError, ParanoidHash};
+
+/// # Duplicates
+///
+/// The result of `find_duplicates_with_errors()`.
+#[derive(Debug,Clone,PartialEq,Default)]
+pub struct Duplicates {
+    /// Groups of two or more identical files
+    pub groups: Vec<Vec<PathBuf>>,
+    /// Files that could not be read and were left out
+    pub skipped: Vec<(PathBuf,FileError)>,
+    /// Files that were not hashed because no other file has their size (always empty with `size_precheck(false)`)
+    pub unique_size: Vec<PathBuf>,
+}
+
+impl ParanoidHash {
+    /// # Find Duplicates
+    ///
+    /// Walks `dir` recursively and returns groups of two or more files whose Blake2b and Operating System digests both match.
+    ///
+    /// * Only files that share their size with another file are hashed, unless `size_precheck(false)` is set
+    /// * Files that cannot be read are skipped (see `find_duplicates_with_errors()` to find out which)
+    /// * Symbolic links are only followed with `WalkOptions::follow_symlinks` and hard links to the same file are only reported once (on Unix)
+    ///
+    /// Paths in each group and the groups themselves are sorted.
+    pub fn find_duplicates<T: AsRef<Path>>(&self, dir: T) -> Result<Vec<Vec<PathBuf>>,FileError> {
+        return Ok(self.find_duplicates_with_errors(dir)?.groups)
+    }
+    /// # Find Duplicates With Errors
+    ///
+    /// Same as `find_duplicates()`, but also returns the files that were skipped because they could not be read and the files that were not hashed because of their size.
+    pub fn find_duplicates_with_errors<T: AsRef<Path>>(&self, dir: T) -> Result<Duplicates,FileError> {
+        let files = walk::walk_files(dir.as_ref(), &self.walk_options)?;
+
+        let mut skipped: Vec<(PathBuf,FileError)> = Vec::new();
+        let mut seen_inodes: HashSet<(u64,u64)> = HashSet::new();
+
+        // Groups files by size
+        let mut by_size: BTreeMap<u64,Vec<PathBuf>> = BTreeMap::new();
+        for path in files {
+            let metadata = match fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    skipped.push((path, FileError::Io(e.kind())));
+                    continue
+                }
+            };
+            if let Some(inode) = inode(&metadata) {
+                // Another hard link to a file that has already been seen
+                if !seen_inodes.insert(inode) {
+                    continue
+                }
+            }
+            // Without the precheck every file is a candidate of every other
+            let size = if self.size_precheck { metadata.len() } else { 0 };
+            by_size.entry(size).or_default().push(path);
+        }
+
+        // Hashes only the files that share a size
+        let mut groups: Vec<Vec<PathBuf>> = Vec::new();
+        let mut unique_size: Vec<PathBuf> = Vec::new();
+        for (_, mut candidates) in by_size {
+            if candidates.len() < 2 && self.size_precheck {
+                unique_size.append(&mut candidates);
+                continue
+            }
+            let mut by_digest: HashMap<(String,String),Vec<PathBuf>> = HashMap::new();
+            for path in candidates {
+                match self.read(&path) {
+                    Ok(digests) => by_digest.entry(digests).or_default().push(path),
+                    Err(e) => skipped.push((path, e)),
+                }
+            }
+            groups.extend(by_digest.into_values().filter(|group| group.len() >= 2));
+        }
+
+        for group in groups.iter_mut() {
+            group.sort();
+        }
+        groups.sort();
+        unique_size.sort();
+        return Ok(Duplicates { groups, skipped, unique_size })
+    }
+}
+
+#[cfg(unix)]
+fn inode(metadata: &fs::Metadata) -> Option<(u64,u64)> {
+    use std::os::unix::fs::MetadataExt;
+    return Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn inode(_metadata: &fs::Metadata) -> Option<(u64,u64)> {
+    return None
+}
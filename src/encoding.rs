@@ -0,0 +1,232 @@
+// Output Encoding
+//
+// How digests are turned into strings. Hexadecimal decoding is handled by the `hex` crate, everything else (including Base32 from RFC 4648 and Base58) is implemented here.
+
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32_PADDING: u8 = b'=';
+#[cfg(feature = "base58")]
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// # Output Encoding
+///
+/// The encoding of the digests returned by a `ParanoidHash`.
+///
+/// * `HexUpper`: Upper hexadecimal
+/// * `HexLower`: Lower hexadecimal
+/// * `Base32`: RFC 4648 Base32 with padding. It only uses upper case letters and digits, which suits case-insensitive filesystems.
+/// * `Base58`: Base58 with the Bitcoin alphabet and no checksum, which leaves out `0`, `O`, `I` and `l` so it is easy to copy by hand. Requires the `base58` feature.
+///
+/// **Default** uses **HexUpper**
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum OutputEncoding {
+    #[default]
+    HexUpper,
+    HexLower,
+    Base32,
+    #[cfg(feature = "base58")]
+    Base58,
+}
+
+#[cfg(not(feature = "base58"))]
+const DECODE_ORDER: [OutputEncoding; 2] = [OutputEncoding::HexUpper, OutputEncoding::Base32];
+#[cfg(feature = "base58")]
+const DECODE_ORDER: [OutputEncoding; 3] = [OutputEncoding::HexUpper, OutputEncoding::Base32, OutputEncoding::Base58];
+
+impl OutputEncoding {
+    /// ## Encode
+    ///
+    /// Encodes bytes as a string in this encoding.
+    pub fn encode<T: AsRef<[u8]>>(&self, bytes: T) -> String {
+        let mut output = String::with_capacity(self.encoded_len(bytes.as_ref().len()));
+        self.encode_into(bytes.as_ref(), &mut output);
+        return output
+    }
+    // Appends the encoded bytes to `output` without allocating if it has enough capacity
+    pub(crate) fn encode_into(&self, bytes: &[u8], output: &mut String) {
+        match self {
+            OutputEncoding::HexUpper => hex_encode_into(bytes, HEX_UPPER, output),
+            OutputEncoding::HexLower => hex_encode_into(bytes, HEX_LOWER, output),
+            OutputEncoding::Base32 => base32_encode_into(bytes, output),
+            #[cfg(feature = "base58")]
+            OutputEncoding::Base58 => base58_encode_into(bytes, output),
+        }
+    }
+    // The length of `length` bytes once encoded
+    pub(crate) fn encoded_len(&self, length: usize) -> usize {
+        match self {
+            OutputEncoding::HexUpper | OutputEncoding::HexLower => return length * 2,
+            OutputEncoding::Base32 => return length.div_ceil(5) * 8,
+            // An upper bound, as each byte needs log(256)/log(58) (about 1.37) characters
+            #[cfg(feature = "base58")]
+            OutputEncoding::Base58 => return length * 138 / 100 + 1,
+        }
+    }
+    /// ## Decode
+    ///
+    /// Decodes a string in this encoding. Hexadecimal and Base32 are both accepted in either case, while Base58 is case-sensitive. Returns `None` if the string is not valid.
+    pub fn decode<T: AsRef<str>>(&self, s: T) -> Option<Vec<u8>> {
+        match self {
+            OutputEncoding::HexUpper | OutputEncoding::HexLower => return hex::decode(s.as_ref()).ok(),
+            OutputEncoding::Base32 => return base32_decode(s.as_ref()),
+            #[cfg(feature = "base58")]
+            OutputEncoding::Base58 => return decode_base58(s.as_ref()),
+        }
+    }
+    /// ## Decode Any
+    ///
+    /// Decodes a string in any supported encoding. Hexadecimal is tried first, then Base32 and then Base58, so a string that is valid in more than one is read as the first of those.
+    ///
+    /// ```rust
+    /// use paranoid_hash::OutputEncoding;
+    ///
+    /// let bytes = b"paranoid";
+    /// for encoding in [OutputEncoding::HexUpper,OutputEncoding::HexLower,OutputEncoding::Base32] {
+    ///     assert_eq!(OutputEncoding::decode_any(encoding.encode(bytes)).unwrap(),bytes);
+    /// }
+    /// ```
+    pub fn decode_any<T: AsRef<str>>(s: T) -> Option<Vec<u8>> {
+        let decoded = OutputEncoding::HexUpper.decode(s.as_ref()).or_else(|| OutputEncoding::Base32.decode(s.as_ref()));
+        #[cfg(feature = "base58")]
+        let decoded = decoded.or_else(|| decode_base58(s.as_ref()));
+        return decoded
+    }
+}
+
+// Decodes an expected digest of `length` bytes, trying hexadecimal, Base32 and then Base58. Leading and trailing ASCII whitespace is ignored.
+pub(crate) fn decode_expected(s: &str, length: usize) -> Option<Vec<u8>> {
+    let s = s.trim_ascii();
+    for encoding in DECODE_ORDER {
+        if let Some(bytes) = encoding.decode(s) {
+            if bytes.len() == length {
+                return Some(bytes)
+            }
+        }
+    }
+    return None
+}
+
+fn hex_encode_into(bytes: &[u8], alphabet: &[u8; 16], output: &mut String) {
+    for byte in bytes {
+        output.push(alphabet[(byte >> 4) as usize] as char);
+        output.push(alphabet[(byte & 0x0F) as usize] as char);
+    }
+}
+
+fn base32_encode_into(bytes: &[u8], output: &mut String) {
+    for block in bytes.chunks(5) {
+        let mut buffer = [0u8; 5];
+        buffer[..block.len()].copy_from_slice(block);
+        let bits = buffer.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+
+        // Each 5 byte block becomes 8 characters, padded when the block is short
+        let characters = (block.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < characters {
+                let index = (bits >> (35 - i * 5)) & 0x1F;
+                output.push(BASE32_ALPHABET[index as usize] as char);
+            }
+            else {
+                output.push(BASE32_PADDING as char);
+            }
+        }
+    }
+}
+
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim_end_matches(BASE32_PADDING as char);
+    let mut output = Vec::with_capacity(s.len() * 5 / 8);
+    let mut bits: u64 = 0;
+    let mut bit_count = 0;
+
+    for c in s.bytes() {
+        let value = match c.to_ascii_uppercase() {
+            c @ b'A'..=b'Z' => c - b'A',
+            c @ b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        bits = (bits << 5) | value as u64;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            output.push((bits >> bit_count) as u8);
+        }
+    }
+    // Leftover bits must be padding zeros and there can never be 5 or more of them
+    if bit_count >= 5 || bits & ((1 << bit_count) - 1) != 0 {
+        return None
+    }
+    return Some(output)
+}
+
+/// # Encode Base58
+///
+/// Encodes bytes as Base58 with the Bitcoin alphabet (no checksum). Each leading zero byte becomes a leading `1`, so the exact bytes come back from `decode_base58()`.
+///
+/// ```rust
+/// use paranoid_hash::{encode_base58,decode_base58};
+///
+/// assert_eq!(encode_base58([0u8,0,1]),"112");
+/// assert_eq!(decode_base58("112").unwrap(),vec![0,0,1]);
+/// ```
+#[cfg(feature = "base58")]
+pub fn encode_base58<T: AsRef<[u8]>>(bytes: T) -> String {
+    return OutputEncoding::Base58.encode(bytes)
+}
+
+/// # Decode Base58
+///
+/// Decodes Base58 with the Bitcoin alphabet. Returns `None` if a character is not in the alphabet (such as `0`, `O`, `I` or `l`).
+#[cfg(feature = "base58")]
+pub fn decode_base58<T: AsRef<str>>(s: T) -> Option<Vec<u8>> {
+    let s = s.as_ref();
+    let zeros = s.bytes().take_while(|c| *c == BASE58_ALPHABET[0]).count();
+
+    // Little-endian base 256 digits of the number
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len());
+    for c in s.bytes().skip(zeros) {
+        let mut carry = BASE58_ALPHABET.iter().position(|a| *a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut output = vec![0u8; zeros];
+    output.extend(bytes.iter().rev());
+    return Some(output)
+}
+
+#[cfg(feature = "base58")]
+fn base58_encode_into(bytes: &[u8], output: &mut String) {
+    let zeros = bytes.iter().take_while(|b| **b == 0).count();
+
+    // Little-endian base 58 digits of the number
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for byte in bytes.iter().skip(zeros) {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    for _ in 0..zeros {
+        output.push(BASE58_ALPHABET[0] as char);
+    }
+    for digit in digits.iter().rev() {
+        output.push(BASE58_ALPHABET[*digit as usize] as char);
+    }
+}
@@ -0,0 +1,285 @@
+// C Interface
+//
+// `extern "C"` functions for calling the crate from C and C++. The declarations are in `include/paranoid_hash.h`, which is generated from this file with `cbindgen --config cbindgen.toml --output include/paranoid_hash.h`.
+//
+// Every function catches panics and reports them as `PARANOID_HASH_STATUS_PANIC`, so no panic ever unwinds into the caller. Digests are always upper hexadecimal.
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::ptr;
+use std::slice;
+
+use crate::{FileError, OsAlgorithm, ParanoidHash};
+
+/// SHA1 as the Operating System Hash Function
+pub const PARANOID_HASH_SHA1: u32 = 1;
+/// SHA256 as the Operating System Hash Function
+pub const PARANOID_HASH_SHA256: u32 = 2;
+/// SHA512 as the Operating System Hash Function
+pub const PARANOID_HASH_SHA512: u32 = 3;
+/// MD5 as the Operating System Hash Function, only accepted when the crate is built with the `insecure-md5` feature
+pub const PARANOID_HASH_MD5: u32 = 4;
+/// SHA512_256 as the Operating System Hash Function
+pub const PARANOID_HASH_SHA512_256: u32 = 5;
+/// SHA224 as the Operating System Hash Function
+pub const PARANOID_HASH_SHA224: u32 = 6;
+
+/// # Status
+///
+/// Returned by every function that can fail. The values up to 99 mirror `FileError`, with `FileError::AtPath` reported as the error it wraps.
+#[repr(C)]
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum ParanoidHashStatus {
+    Ok = 0,
+    FileNotFound = 1,
+    OsHashingError = 2,
+    Io = 3,
+    EmptyFile = 4,
+    Cancelled = 5,
+    DigestDisagreement = 6,
+    InvalidDigest = 7,
+    KeyFile = 8,
+    InvalidKey = 9,
+    InvalidManifest = 10,
+    UnsupportedManifestVersion = 11,
+    ConflictingChecksums = 12,
+    FileTooLarge = 13,
+    VerificationFailed = 14,
+    ImplementationMismatch = 15,
+    InvalidGzip = 16,
+    InvalidRange = 17,
+    ReadInconsistency = 18,
+    RetriesExhausted = 19,
+    SymlinkLoop = 20,
+    InvalidDigestSize = 21,
+    OsAlgorithmUnavailable = 22,
+    XattrUnsupported = 23,
+    NoExpectedDigest = 24,
+    WeakAlgorithm = 25,
+    NotResumable = 26,
+    InvalidHasherState = 27,
+    UnsupportedStateVersion = 28,
+    TimedOut = 29,
+    ModifiedDuringHash = 30,
+    /// A required pointer was null
+    NullPointer = 100,
+    /// A path is not valid for this platform (it must be UTF-8 on Windows)
+    InvalidArgument = 101,
+    /// The buffer cannot hold the digest and its terminating NUL
+    BufferTooSmall = 102,
+    /// Nothing has been hashed successfully with this handle yet
+    NoDigest = 103,
+    /// The crate panicked. This is a bug.
+    Panic = 104,
+}
+
+impl From<&FileError> for ParanoidHashStatus {
+    fn from(error: &FileError) -> Self {
+        match error {
+            FileError::FileNotFound => return ParanoidHashStatus::FileNotFound,
+            FileError::OsHashingError => return ParanoidHashStatus::OsHashingError,
+            FileError::Io(_) => return ParanoidHashStatus::Io,
+            FileError::EmptyFile => return ParanoidHashStatus::EmptyFile,
+            FileError::Cancelled => return ParanoidHashStatus::Cancelled,
+            FileError::DigestDisagreement => return ParanoidHashStatus::DigestDisagreement,
+            FileError::InvalidDigest => return ParanoidHashStatus::InvalidDigest,
+            FileError::KeyFile(_) => return ParanoidHashStatus::KeyFile,
+            FileError::InvalidKey => return ParanoidHashStatus::InvalidKey,
+            FileError::InvalidManifest(_) => return ParanoidHashStatus::InvalidManifest,
+            FileError::UnsupportedManifestVersion(_) => return ParanoidHashStatus::UnsupportedManifestVersion,
+            FileError::ConflictingChecksums => return ParanoidHashStatus::ConflictingChecksums,
+            FileError::AtPath(_, error) => return ParanoidHashStatus::from(error.as_ref()),
+            FileError::FileTooLarge { .. } => return ParanoidHashStatus::FileTooLarge,
+            FileError::VerificationFailed => return ParanoidHashStatus::VerificationFailed,
+            FileError::ImplementationMismatch => return ParanoidHashStatus::ImplementationMismatch,
+            FileError::InvalidGzip => return ParanoidHashStatus::InvalidGzip,
+            FileError::InvalidRange(_) => return ParanoidHashStatus::InvalidRange,
+            FileError::ReadInconsistency => return ParanoidHashStatus::ReadInconsistency,
+            FileError::RetriesExhausted { .. } => return ParanoidHashStatus::RetriesExhausted,
+            FileError::SymlinkLoop => return ParanoidHashStatus::SymlinkLoop,
+            FileError::InvalidDigestSize(_) => return ParanoidHashStatus::InvalidDigestSize,
+            FileError::OsAlgorithmUnavailable(_) => return ParanoidHashStatus::OsAlgorithmUnavailable,
+            FileError::XattrUnsupported => return ParanoidHashStatus::XattrUnsupported,
+            FileError::NoExpectedDigest => return ParanoidHashStatus::NoExpectedDigest,
+            FileError::WeakAlgorithm(_) => return ParanoidHashStatus::WeakAlgorithm,
+            FileError::NotResumable => return ParanoidHashStatus::NotResumable,
+            FileError::InvalidHasherState => return ParanoidHashStatus::InvalidHasherState,
+            FileError::UnsupportedStateVersion(_) => return ParanoidHashStatus::UnsupportedStateVersion,
+            FileError::TimedOut { .. } => return ParanoidHashStatus::TimedOut,
+            FileError::ModifiedDuringHash => return ParanoidHashStatus::ModifiedDuringHash,
+        }
+    }
+}
+
+/// # Handle
+///
+/// A configuration along with the digests of the last successful call. Created with `paranoid_hash_new()` and released with `paranoid_hash_free()`.
+///
+/// A handle must not be used from two threads at the same time.
+pub struct ParanoidHashHandle {
+    context: ParanoidHash,
+    digests: Option<(String,String)>,
+}
+
+// Runs `f`, turning a panic into `ParanoidHashStatus::Panic`
+fn guard<F: FnOnce() -> ParanoidHashStatus>(f: F) -> ParanoidHashStatus {
+    return panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(ParanoidHashStatus::Panic)
+}
+
+/// Creates a handle hashing with BLAKE2B at `digest_size` bytes (1-64) and the Operating System Hash Function `os_algorithm` (one of the `PARANOID_HASH_SHA256`, `PARANOID_HASH_SHA512`, `PARANOID_HASH_SHA512_256`, `PARANOID_HASH_SHA224` or `PARANOID_HASH_MD5` constants). `PARANOID_HASH_MD5` needs the `insecure-md5` feature and returns null without it.
+///
+/// Returns null if either argument is out of range. `PARANOID_HASH_SHA1` is broken and also returns null; use `paranoid_hash_new_allowing_weak()` for it.
+#[no_mangle]
+pub extern "C" fn paranoid_hash_new(digest_size: usize, os_algorithm: u32) -> *mut ParanoidHashHandle {
+    return new_handle(digest_size, os_algorithm, false)
+}
+
+/// Same as `paranoid_hash_new()`, but also accepts `PARANOID_HASH_SHA1`, for verifying digests that were recorded with it.
+#[no_mangle]
+pub extern "C" fn paranoid_hash_new_allowing_weak(digest_size: usize, os_algorithm: u32) -> *mut ParanoidHashHandle {
+    return new_handle(digest_size, os_algorithm, true)
+}
+
+fn new_handle(digest_size: usize, os_algorithm: u32, allow_weak: bool) -> *mut ParanoidHashHandle {
+    let os_algorithm = match os_algorithm {
+        PARANOID_HASH_SHA1 => OsAlgorithm::SHA1,
+        PARANOID_HASH_SHA256 => OsAlgorithm::SHA256,
+        PARANOID_HASH_SHA512 => OsAlgorithm::SHA512,
+        #[cfg(feature = "insecure-md5")]
+        PARANOID_HASH_MD5 => OsAlgorithm::MD5,
+        PARANOID_HASH_SHA512_256 => OsAlgorithm::SHA512_256,
+        PARANOID_HASH_SHA224 => OsAlgorithm::SHA224,
+        _ => return ptr::null_mut(),
+    };
+    if digest_size == 0 || digest_size > 64 || (os_algorithm.is_weak() && !allow_weak) {
+        return ptr::null_mut()
+    }
+    let handle = panic::catch_unwind(|| Box::new(ParanoidHashHandle { context: ParanoidHash::new_allowing_weak(digest_size, os_algorithm), digests: None }));
+    match handle {
+        Ok(handle) => return Box::into_raw(handle),
+        Err(_) => return ptr::null_mut(),
+    }
+}
+
+/// Releases a handle created with `paranoid_hash_new()`. Passing null does nothing.
+///
+/// # Safety
+///
+/// `handle` must be null or a handle from `paranoid_hash_new()` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn paranoid_hash_free(handle: *mut ParanoidHashHandle) {
+    if handle.is_null() {
+        return
+    }
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(handle))));
+}
+
+/// Hashes the file at `path` (a NUL-terminated string) and keeps the digests in the handle. On failure the previous digests are cleared.
+///
+/// # Safety
+///
+/// `handle` must be a valid handle and `path` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn paranoid_hash_file(handle: *mut ParanoidHashHandle, path: *const c_char) -> ParanoidHashStatus {
+    if handle.is_null() || path.is_null() {
+        return ParanoidHashStatus::NullPointer
+    }
+    let handle = &mut *handle;
+    let path = CStr::from_ptr(path);
+    return guard(|| {
+        handle.digests = None;
+        let path = match c_path(path) {
+            Some(path) => path,
+            None => return ParanoidHashStatus::InvalidArgument,
+        };
+        return store(handle, handle.context.read(path))
+    })
+}
+
+/// Hashes `length` bytes at `data` and keeps the digests in the handle. `data` may be null when `length` is 0.
+///
+/// # Safety
+///
+/// `handle` must be a valid handle and `data` must point to `length` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn paranoid_hash_bytes(handle: *mut ParanoidHashHandle, data: *const u8, length: usize) -> ParanoidHashStatus {
+    if handle.is_null() || (data.is_null() && length > 0) {
+        return ParanoidHashStatus::NullPointer
+    }
+    let handle = &mut *handle;
+    let data = match length {
+        0 => &[][..],
+        _ => slice::from_raw_parts(data, length),
+    };
+    return guard(|| {
+        handle.digests = None;
+        return store(handle, handle.context.try_read_bytes(data))
+    })
+}
+
+/// Copies the BLAKE2B digest of the last successful call into `buffer` as a NUL-terminated upper hexadecimal string. `buffer_length` must be at least twice the digest size plus one.
+///
+/// # Safety
+///
+/// `handle` must be a valid handle and `buffer` must point to `buffer_length` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn paranoid_hash_blake2b_hex(handle: *const ParanoidHashHandle, buffer: *mut c_char, buffer_length: usize) -> ParanoidHashStatus {
+    if handle.is_null() {
+        return ParanoidHashStatus::NullPointer
+    }
+    return copy_digest((*handle).digests.as_ref().map(|(blake2b, _)| blake2b.as_str()), buffer, buffer_length)
+}
+
+/// Copies the Operating System digest of the last successful call into `buffer` as a NUL-terminated upper hexadecimal string. `buffer_length` must be at least twice the digest size plus one (129 always suffices).
+///
+/// # Safety
+///
+/// `handle` must be a valid handle and `buffer` must point to `buffer_length` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn paranoid_hash_os_hex(handle: *const ParanoidHashHandle, buffer: *mut c_char, buffer_length: usize) -> ParanoidHashStatus {
+    if handle.is_null() {
+        return ParanoidHashStatus::NullPointer
+    }
+    return copy_digest((*handle).digests.as_ref().map(|(_, os)| os.as_str()), buffer, buffer_length)
+}
+
+fn store(handle: &mut ParanoidHashHandle, result: Result<(String,String),FileError>) -> ParanoidHashStatus {
+    match result {
+        Ok(digests) => {
+            handle.digests = Some(digests);
+            return ParanoidHashStatus::Ok
+        }
+        Err(error) => return ParanoidHashStatus::from(&error),
+    }
+}
+
+unsafe fn copy_digest(digest: Option<&str>, buffer: *mut c_char, buffer_length: usize) -> ParanoidHashStatus {
+    if buffer.is_null() {
+        return ParanoidHashStatus::NullPointer
+    }
+    let digest = match digest {
+        Some(digest) => digest,
+        None => return ParanoidHashStatus::NoDigest,
+    };
+    if buffer_length < digest.len() + 1 {
+        return ParanoidHashStatus::BufferTooSmall
+    }
+    ptr::copy_nonoverlapping(digest.as_ptr() as *const c_char, buffer, digest.len());
+    *buffer.add(digest.len()) = 0;
+    return ParanoidHashStatus::Ok
+}
+
+// Paths are raw bytes on Unix and must be UTF-8 elsewhere
+#[cfg(unix)]
+fn c_path(path: &CStr) -> Option<PathBuf> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    return Some(PathBuf::from(OsStr::from_bytes(path.to_bytes())))
+}
+
+#[cfg(not(unix))]
+fn c_path(path: &CStr) -> Option<PathBuf> {
+    return path.to_str().ok().map(PathBuf::from)
+}
@@ -0,0 +1,107 @@
+// Metadata Fingerprints
+//
+// A cheap fingerprint of a file's size, modification time and permissions, hashed with both hash functions, for deciding which files are worth hashing again. It never reads the contents, so it is not a content hash: a file rewritten with the same size and timestamp keeps its fingerprint.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::{walk, FileError, ParanoidHash};
+
+// Identifies the encoding, so a fingerprint can never equal the digest of some file contents
+const FINGERPRINT_LABEL: &[u8] = b"paranoid-hash metadata fingerprint v1";
+
+impl ParanoidHash {
+    /// # Fingerprint Metadata
+    ///
+    /// Hashes the metadata of a file (**not its contents**) with the library hash function and the Operating System Hash Function, in the Output Encoding. If the fingerprint of a file has not changed since the last run, its size, modification time and permissions have not changed either, which is usually enough to skip hashing it again with `read()`.
+    ///
+    /// Symbolic links are followed. Returns `FileError::Io` if the metadata (or the modification time, on platforms without one) cannot be read.
+    ///
+    /// ## Encoding
+    ///
+    /// The following fields are fed to both hash functions, each preceded by its length in bytes as a u64 big-endian:
+    ///
+    /// ```text
+    /// "paranoid-hash metadata fingerprint v1" (ASCII)
+    /// size in bytes (u64 big-endian)
+    /// modification time: whole seconds since the Unix epoch (i64 big-endian, negative before 1970) | nanoseconds (u32 big-endian)
+    /// permissions (u32 big-endian): the mode bits (`mode & 0o7777`) on Unix, 1 for read-only and 0 otherwise elsewhere
+    /// ```
+    ///
+    /// The path is not part of the fingerprint, so a renamed file keeps it.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let fingerprint = context.fingerprint_metadata("examples/example_file.txt").unwrap();
+    /// assert_eq!(fingerprint,context.fingerprint_metadata("examples/example_file.txt").unwrap());
+    /// assert_ne!(fingerprint,context.read("examples/example_file.txt").unwrap());
+    /// ```
+    pub fn fingerprint_metadata<T: AsRef<Path>>(&self, path: T) -> Result<(String,String),FileError> {
+        let path = path.as_ref();
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+        let metadata = fs::metadata(path).map_err(|e| FileError::Io(e.kind()))?;
+
+        let mut hasher = self.pair_hasher(None)?;
+        for field in metadata_fields(&metadata)? {
+            hasher.update(&(field.len() as u64).to_be_bytes())?;
+            hasher.update(&field)?;
+        }
+        return Ok(self.encode_pair(hasher.finalize_pair()?.0))
+    }
+    /// # Fingerprint Metadata Directory
+    ///
+    /// The `fingerprint_metadata()` of every regular file below `dir`, named by its path relative to `dir` and sorted like `read_dir()`. `walk_options()` applies.
+    ///
+    /// Fails on the first file whose metadata cannot be read with `FileError::AtPath`.
+    #[allow(clippy::type_complexity)]
+    pub fn fingerprint_metadata_dir<T: AsRef<Path>>(&self, dir: T) -> Result<Vec<(PathBuf,(String,String))>,FileError> {
+        let mut fingerprints = Vec::new();
+        for (name, path) in walk::walk_entries(dir.as_ref(), &self.walk_options)? {
+            let fingerprint = self.fingerprint_metadata(&path).map_err(|e| FileError::AtPath(path.clone(), Box::new(e)))?;
+            fingerprints.push((PathBuf::from(name), fingerprint));
+        }
+        return Ok(fingerprints)
+    }
+}
+
+// The fields of the encoding, without their length prefixes
+fn metadata_fields(metadata: &fs::Metadata) -> Result<[Vec<u8>; 4],FileError> {
+    let modified = metadata.modified().map_err(|e| FileError::Io(e.kind()))?;
+    let (seconds, nanoseconds) = match modified.duration_since(UNIX_EPOCH) {
+        Ok(since) => (since.as_secs() as i64, since.subsec_nanos()),
+        // Before the epoch, the nanoseconds still count forwards from the (earlier) whole second
+        Err(before) => match before.duration().subsec_nanos() {
+            0 => (-(before.duration().as_secs() as i64), 0),
+            nanoseconds => (-(before.duration().as_secs() as i64) - 1, 1_000_000_000 - nanoseconds),
+        },
+    };
+
+    let mut time = seconds.to_be_bytes().to_vec();
+    time.extend_from_slice(&nanoseconds.to_be_bytes());
+
+    return Ok([
+        FINGERPRINT_LABEL.to_vec(),
+        metadata.len().to_be_bytes().to_vec(),
+        time,
+        permissions(metadata).to_be_bytes().to_vec(),
+    ])
+}
+
+#[cfg(unix)]
+fn permissions(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+
+    return metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn permissions(metadata: &fs::Metadata) -> u32 {
+    return metadata.permissions().readonly() as u32
+}
@@ -0,0 +1,226 @@
+// Fixed-Size Digests
+//
+// Digests as arrays such as `[u8; 32]` for packed structs and on-disk formats, or written into buffers provided by the caller, checked against the configured digest size instead of silently truncating or padding.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::{HashDigest, OsAlgorithm, ParanoidHash};
+
+/// # Digest Size Mismatch
+///
+/// Returned when a digest is requested as an array whose length is not the size of the digest.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+pub struct DigestSizeMismatch {
+    /// The size of the digest in bytes
+    pub expected: usize,
+    /// The length of the requested array
+    pub requested: usize,
+}
+
+impl fmt::Display for DigestSizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "digest is {} bytes but an array of {} bytes was requested", self.expected, self.requested)
+    }
+}
+
+impl std::error::Error for DigestSizeMismatch {}
+
+/// # Output Size Error
+///
+/// Returned by `hash_bytes_into()` and `hash_bytes_into_hex()` when an output buffer does not have the length of its digest. The BLAKE2B buffer is checked first.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+pub enum OutputSizeError {
+    /// The buffer for the BLAKE2B digest has the wrong length
+    Blake2b(DigestSizeMismatch),
+    /// The buffer for the Operating System digest has the wrong length
+    Os(DigestSizeMismatch),
+}
+
+impl fmt::Display for OutputSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputSizeError::Blake2b(mismatch) => write!(f, "blake2b output: {}", mismatch),
+            OutputSizeError::Os(mismatch) => write!(f, "operating system output: {}", mismatch),
+        }
+    }
+}
+
+impl std::error::Error for OutputSizeError {}
+
+/// # OS Digest Array
+///
+/// A digest of the Operating System Hash Function as an array of its output length.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+pub enum OsDigestArray {
+    SHA1([u8; 20]),
+    SHA224([u8; 28]),
+    SHA256([u8; 32]),
+    SHA512([u8; 64]),
+    SHA512_256([u8; 32]),
+    #[cfg(feature = "insecure-md5")]
+    MD5([u8; 16]),
+}
+
+impl OsDigestArray {
+    /// ## Algorithm
+    pub fn algorithm(&self) -> OsAlgorithm {
+        match self {
+            OsDigestArray::SHA1(_) => return OsAlgorithm::SHA1,
+            OsDigestArray::SHA224(_) => return OsAlgorithm::SHA224,
+            OsDigestArray::SHA256(_) => return OsAlgorithm::SHA256,
+            OsDigestArray::SHA512(_) => return OsAlgorithm::SHA512,
+            OsDigestArray::SHA512_256(_) => return OsAlgorithm::SHA512_256,
+            #[cfg(feature = "insecure-md5")]
+            OsDigestArray::MD5(_) => return OsAlgorithm::MD5,
+        }
+    }
+    /// ## As Bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            OsDigestArray::SHA1(bytes) => return bytes,
+            OsDigestArray::SHA224(bytes) => return bytes,
+            OsDigestArray::SHA256(bytes) => return bytes,
+            OsDigestArray::SHA512(bytes) => return bytes,
+            OsDigestArray::SHA512_256(bytes) => return bytes,
+            #[cfg(feature = "insecure-md5")]
+            OsDigestArray::MD5(bytes) => return bytes,
+        }
+    }
+    // `bytes` must be the output length of `algorithm`
+    fn new(algorithm: &OsAlgorithm, bytes: &[u8]) -> Self {
+        match algorithm {
+            OsAlgorithm::SHA1 => return OsDigestArray::SHA1(array(bytes).unwrap()),
+            OsAlgorithm::SHA224 => return OsDigestArray::SHA224(array(bytes).unwrap()),
+            OsAlgorithm::SHA256 => return OsDigestArray::SHA256(array(bytes).unwrap()),
+            OsAlgorithm::SHA512 => return OsDigestArray::SHA512(array(bytes).unwrap()),
+            OsAlgorithm::SHA512_256 => return OsDigestArray::SHA512_256(array(bytes).unwrap()),
+            #[cfg(feature = "insecure-md5")]
+            OsAlgorithm::MD5 => return OsDigestArray::MD5(array(bytes).unwrap()),
+        }
+    }
+}
+
+impl AsRef<[u8]> for OsDigestArray {
+    fn as_ref(&self) -> &[u8] {
+        return self.as_bytes()
+    }
+}
+
+impl HashDigest {
+    /// ## To Array
+    ///
+    /// Copies the digest into an array of `N` bytes. Returns `DigestSizeMismatch` if `N` is not the size of the digest.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{OsAlgorithm,ParanoidHash};
+    ///
+    /// let (blake2b,sha256) = ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes_digests(b"abc").unwrap();
+    /// let blake2b: [u8; 32] = blake2b.to_array().unwrap();
+    /// assert!(sha256.to_array::<64>().is_err());
+    /// ```
+    pub fn to_array<const N: usize>(&self) -> Result<[u8; N],DigestSizeMismatch> {
+        return array(self.as_bytes())
+    }
+}
+
+impl<const N: usize> TryFrom<&HashDigest> for [u8; N] {
+    type Error = DigestSizeMismatch;
+
+    fn try_from(digest: &HashDigest) -> Result<Self,Self::Error> {
+        return digest.to_array()
+    }
+}
+
+impl ParanoidHash {
+    /// # Read Bytes Fixed
+    ///
+    /// Same as `read_bytes()`, but returns the BLAKE2B digest as an array of `N` bytes and the Operating System digest as an `OsDigestArray`.
+    ///
+    /// Returns `DigestSizeMismatch` without hashing if `N` is not the configured digest size. Like `read_bytes()`, panics if the Operating System Hash Function fails; use `read_bytes_digests()` and `HashDigest::to_array()` to get an error instead.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{OsAlgorithm,OsDigestArray,ParanoidHash};
+    ///
+    /// let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    /// let (blake2b,sha256): ([u8; 32],OsDigestArray) = context.read_bytes_fixed(b"abc").unwrap();
+    /// assert!(matches!(sha256,OsDigestArray::SHA256(_)));
+    /// assert!(context.read_bytes_fixed::<64>(b"abc").is_err());
+    /// ```
+    pub fn read_bytes_fixed<const N: usize>(&self, bytes: &[u8]) -> Result<([u8; N],OsDigestArray),DigestSizeMismatch> {
+        if N != self.digest_size {
+            return Err(DigestSizeMismatch { expected: self.digest_size, requested: N })
+        }
+        let ((library, os), _) = self.hash_bytes_raw(bytes).expect("[Error] Failed To Hash Using Operating System Hash Function");
+        return Ok((array(&library)?, OsDigestArray::new(&self.os_hash_function, &os)))
+    }
+    /// # Hash Bytes Into
+    ///
+    /// Hashes the bytes and writes the raw digests into `blake2b_out` and `os_out`, which must be exactly the configured digest size and the output length of the Operating System Hash Function. The output buffers can be reused across calls and no hexadecimal strings are built.
+    ///
+    /// **This does allocate on every call.** The hash functions are set up on the heap and each digest is returned in its own `Vec` (the crypto library of the operating system only returns its digest that way), which is then copied into the buffer. It saves the string allocations of `read_bytes()`, not all of them.
+    ///
+    /// Returns `OutputSizeError` without hashing if a buffer has the wrong length. Like `read_bytes()`, panics if the Operating System Hash Function fails.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{OsAlgorithm,ParanoidHash};
+    ///
+    /// let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    /// let (mut blake2b, mut sha256) = ([0u8; 32],[0u8; 32]);
+    /// for record in [b"first",b"other"] {
+    ///     context.hash_bytes_into(record,&mut blake2b,&mut sha256).unwrap();
+    /// }
+    /// assert_eq!(hex::encode_upper(sha256),context.read_bytes(b"other").1);
+    /// ```
+    pub fn hash_bytes_into(&self, data: &[u8], blake2b_out: &mut [u8], os_out: &mut [u8]) -> Result<(),OutputSizeError> {
+        self.check_outputs(blake2b_out.len(), os_out.len(), 1)?;
+        let ((library, os), _) = self.hash_bytes_raw(data).expect("[Error] Failed To Hash Using Operating System Hash Function");
+        blake2b_out.copy_from_slice(&library);
+        os_out.copy_from_slice(&os);
+        return Ok(())
+    }
+    /// # Hash Bytes Into Hex
+    ///
+    /// Same as `hash_bytes_into()`, but writes the digests as upper case hexadecimal ASCII, so each buffer must be twice the length of its digest. The output is the same as `read_bytes()` with the default `OutputEncoding::HexUpper`. The digest vectors are allocated just as in `hash_bytes_into()`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{OsAlgorithm,ParanoidHash};
+    ///
+    /// let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    /// let (mut blake2b, mut sha256) = ([0u8; 64],[0u8; 64]);
+    /// context.hash_bytes_into_hex(b"abc",&mut blake2b,&mut sha256).unwrap();
+    /// assert_eq!(&sha256[..],b"BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+    /// ```
+    pub fn hash_bytes_into_hex(&self, data: &[u8], blake2b_out: &mut [u8], os_out: &mut [u8]) -> Result<(),OutputSizeError> {
+        self.check_outputs(blake2b_out.len(), os_out.len(), 2)?;
+        let ((library, os), _) = self.hash_bytes_raw(data).expect("[Error] Failed To Hash Using Operating System Hash Function");
+        write_hex(&library, blake2b_out);
+        write_hex(&os, os_out);
+        return Ok(())
+    }
+    // Each output must be `scale` times the length of its digest
+    fn check_outputs(&self, blake2b_len: usize, os_len: usize, scale: usize) -> Result<(),OutputSizeError> {
+        let expected = self.digest_size * scale;
+        if blake2b_len != expected {
+            return Err(OutputSizeError::Blake2b(DigestSizeMismatch { expected, requested: blake2b_len }))
+        }
+        let expected = self.os_hash_function.digest_len() * scale;
+        if os_len != expected {
+            return Err(OutputSizeError::Os(DigestSizeMismatch { expected, requested: os_len }))
+        }
+        return Ok(())
+    }
+}
+
+// `out` must be twice the length of `bytes`
+fn write_hex(bytes: &[u8], out: &mut [u8]) {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    for (byte, pair) in bytes.iter().zip(out.chunks_exact_mut(2)) {
+        pair[0] = DIGITS[(byte >> 4) as usize];
+        pair[1] = DIGITS[(byte & 0x0f) as usize];
+    }
+}
+
+fn array<const N: usize>(bytes: &[u8]) -> Result<[u8; N],DigestSizeMismatch> {
+    return <[u8; N]>::try_from(bytes).map_err(|_| DigestSizeMismatch { expected: bytes.len(), requested: N })
+}
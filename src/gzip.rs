@@ -0,0 +1,47 @@
+// Gzip
+//
+// Hashes the decompressed contents of gzip files, for checksums recorded over the uncompressed data. The decompressed data is streamed into the hash functions and never stored.
+
+use std::io::{ErrorKind, Read};
+use std::path::Path;
+
+use flate2::read::MultiGzDecoder;
+
+use crate::retry::RetryReader;
+use crate::{long_path, FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Gzip Decompressed
+    ///
+    /// Requires the `gzip` feature.
+    ///
+    /// Decompresses the gzip file at `path` while hashing it and returns the digests of the uncompressed data, the same as `read()` of the file before it was compressed.
+    ///
+    /// A file with several gzip members (such as `cat a.gz b.gz`) is decompressed as the concatenation of the members, like `gzip -d` does. A stream that is malformed, truncated or fails its CRC returns `FileError::InvalidGzip`.
+    ///
+    /// `max_file_size()` and `reject_empty_input()` apply to the decompressed size, which makes a limit a guard against decompression bombs.
+    pub fn read_gzip_decompressed<T: AsRef<Path>>(&self, path: T) -> Result<(String,String),FileError> {
+        let path = long_path::extended(path.as_ref());
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+
+        let file = self.open_file(&path)?;
+        return self.hash_reader(gzip_decoder(RetryReader::new(file, self.retry_policy))).map(|(digests, _)| digests).map_err(gzip_error)
+    }
+}
+
+pub(crate) fn gzip_decoder<R: Read>(reader: R) -> MultiGzDecoder<R> {
+    return MultiGzDecoder::new(reader)
+}
+
+// The decoder reports corrupt data as these kinds of I/O error
+pub(crate) fn gzip_error(error: FileError) -> FileError {
+    match error {
+        FileError::Io(ErrorKind::InvalidInput) | FileError::Io(ErrorKind::InvalidData) | FileError::Io(ErrorKind::UnexpectedEof) => return FileError::InvalidGzip,
+        FileError::AtPath(path, error) => return FileError::AtPath(path, Box::new(gzip_error(*error))),
+        error => return error,
+    }
+}
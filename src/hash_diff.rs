@@ -0,0 +1,119 @@
+// Hash Diff
+//
+// Explains why two hexadecimal digests do not match, for debugging a failed verification. Unlike `compare_hash()` it stops at the first difference and reports where it is, so it must never decide whether a digest is accepted.
+
+use std::fmt;
+
+use crate::{HexError, ParanoidHash};
+
+/// # Hash Diff
+///
+/// What `diff_hashes()` found when comparing two hexadecimal digests, `left` and `right`.
+///
+/// **Warning:** This is for debugging only. It is **not** constant-time and reports where the digests differ, which would help an attacker forge a digest one byte at a time. Use `compare_hash()` (or `HashDigest`) to decide whether a digest is accepted, and `diff_hashes()` only to explain a failure afterwards.
+#[derive(Debug,Clone,PartialEq)]
+pub struct HashDiff {
+    /// The length of `left` in bytes (the number of hexadecimal digits when it is valid)
+    pub left_len: usize,
+    /// The length of `right` in bytes
+    pub right_len: usize,
+    /// Why `left` is not valid hexadecimal: the first character that is not a hexadecimal digit (with its byte index), or an odd number of digits
+    pub left_error: Option<HexError>,
+    /// Why `right` is not valid hexadecimal
+    pub right_error: Option<HexError>,
+    /// The index of the first decoded byte that differs, when both sides are valid. If one digest is the start of the other, this is the length of the shorter one in bytes.
+    pub first_difference: Option<usize>,
+}
+
+impl HashDiff {
+    /// ## Is Match
+    ///
+    /// Returns true if both sides are valid hexadecimal and decode to the same bytes, in which case `compare_hash()` also returns true.
+    pub fn is_match(&self) -> bool {
+        return self.left_error.is_none() && self.right_error.is_none() && self.first_difference.is_none()
+    }
+    /// ## Lengths Differ
+    pub fn lengths_differ(&self) -> bool {
+        return self.left_len != self.right_len
+    }
+}
+
+/// One line per problem found, or `digests match`.
+impl fmt::Display for HashDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_match() {
+            return write!(f, "digests match")
+        }
+        let mut lines = Vec::new();
+        if self.lengths_differ() {
+            lines.push(format!("lengths differ: left has {} characters and right has {}", self.left_len, self.right_len));
+        }
+        if let Some(error) = &self.left_error {
+            lines.push(format!("left is not valid hexadecimal: {}", error));
+        }
+        if let Some(error) = &self.right_error {
+            lines.push(format!("right is not valid hexadecimal: {}", error));
+        }
+        if let Some(index) = self.first_difference {
+            lines.push(format!("first difference at byte {}", index));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl ParanoidHash {
+    /// # Diff Hashes
+    ///
+    /// **For debugging only. Never use the result to decide whether a digest is accepted; use `compare_hash()` for that.** This function is not constant-time and returns where the digests differ.
+    ///
+    /// Compares two hexadecimal digests (case-insensitive, like `compare_hash()`) and reports whether their lengths differ, whether either is not valid hexadecimal and, when both are valid, the index of the first byte that differs. No `0x` prefix or whitespace is accepted, since `compare_hash()` does not accept them either.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{HexError,ParanoidHash};
+    ///
+    /// let diff = ParanoidHash::diff_hashes("ABCD1234","abcd1299");
+    /// assert_eq!(diff.first_difference,Some(3));
+    ///
+    /// let diff = ParanoidHash::diff_hashes("ABCD1234","ABCD 1234");
+    /// assert_eq!(diff.right_error,Some(HexError::InvalidCharacter { character: ' ', index: 4 }));
+    /// assert!(diff.lengths_differ());
+    /// ```
+    pub fn diff_hashes<A: AsRef<str>, B: AsRef<str>>(left: A, right: B) -> HashDiff {
+        let left = left.as_ref();
+        let right = right.as_ref();
+        let left_decoded = decode_strict(left);
+        let right_decoded = decode_strict(right);
+
+        let first_difference = match (&left_decoded, &right_decoded) {
+            (Ok(left), Ok(right)) => first_difference(left, right),
+            _ => None,
+        };
+
+        return HashDiff {
+            left_len: left.len(),
+            right_len: right.len(),
+            left_error: left_decoded.err(),
+            right_error: right_decoded.err(),
+            first_difference,
+        }
+    }
+}
+
+// Decodes hexadecimal digits only, with no prefix, reporting the first invalid character before an odd length
+fn decode_strict(s: &str) -> Result<Vec<u8>,HexError> {
+    if let Some((index, character)) = s.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(HexError::InvalidCharacter { character, index })
+    }
+    if !s.len().is_multiple_of(2) {
+        return Err(HexError::OddLength)
+    }
+    return Ok(hex::decode(s).expect("[Error] Hexadecimal digits were already checked"))
+}
+
+fn first_difference(left: &[u8], right: &[u8]) -> Option<usize> {
+    match left.iter().zip(right.iter()).position(|(a, b)| a != b) {
+        Some(index) => return Some(index),
+        None if left.len() != right.len() => return Some(left.len().min(right.len())),
+        None => return None,
+    }
+}
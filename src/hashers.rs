@@ -0,0 +1,493 @@
+// Hashing Engine
+//
+// Every public hashing function ends up here so that the library and operating system hash functions are always set up and fed the same way.
+
+use crypto_hash::{Algorithm, Hasher};
+use std::io::{self, BufRead, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+#[cfg(feature = "blake2-parallel")]
+use crate::blake2p::ParallelBlake2;
+use crate::custom::{Blake2bHasher, LibraryHasher};
+use crate::multi::Algo;
+use crate::retry::io_error;
+#[cfg(feature = "xof")]
+use crate::xof::Shake256Hasher;
+use crate::{trace, FileError, LibAlgorithm, OsAlgorithm};
+
+/// How the operating system hash function is set up.
+#[derive(Debug,Clone,Copy,PartialEq,PartialOrd,Hash,Default)]
+pub(crate) struct OsOptions {
+    /// Computes the same algorithm in pure Rust alongside the operating system and uses it if the operating system fails
+    pub(crate) fallback: bool,
+    /// Also computes the algorithm in pure Rust and fails with `FileError::ImplementationMismatch` if the digests differ
+    pub(crate) cross_check: bool,
+    /// Replaces the operating system backend with one that always fails
+    pub(crate) simulate_failure: bool,
+    /// Replaces the operating system backend with one whose digests are wrong
+    pub(crate) simulate_mismatch: bool,
+}
+
+/// A backend for an operating system hash function. Every failure is reported as an error rather than a panic.
+pub(crate) trait OsBackend: Send {
+    fn update(&mut self, bytes: &[u8]) -> Result<(), FileError>;
+    fn finish(self: Box<Self>) -> Result<Vec<u8>, FileError>;
+}
+
+// `crypto-hash` panics when the platform crypto library reports an error, so those panics are caught here.
+impl OsBackend for Hasher {
+    fn update(&mut self, bytes: &[u8]) -> Result<(), FileError> {
+        return write_guarded(self, bytes)
+    }
+    fn finish(mut self: Box<Self>) -> Result<Vec<u8>, FileError> {
+        return panic::catch_unwind(AssertUnwindSafe(move || Hasher::finish(&mut self))).map_err(|_| FileError::OsHashingError)
+    }
+}
+
+// Writes to a hasher that may panic (such as `crypto-hash` when `CryptHashData` fails on Windows), reporting both an error and a panic as `FileError::OsHashingError`
+fn write_guarded<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<(), FileError> {
+    match panic::catch_unwind(AssertUnwindSafe(|| writer.write_all(bytes))) {
+        Ok(Ok(())) => return Ok(()),
+        Ok(Err(_)) | Err(_) => return Err(FileError::OsHashingError),
+    }
+}
+
+// Used to test the failure path
+struct FailingBackend;
+
+impl OsBackend for FailingBackend {
+    fn update(&mut self, _bytes: &[u8]) -> Result<(), FileError> {
+        return Err(FileError::OsHashingError)
+    }
+    fn finish(self: Box<Self>) -> Result<Vec<u8>, FileError> {
+        return Err(FileError::OsHashingError)
+    }
+}
+
+// Used to test cross checking: the digest of the wrapped backend with its first bit flipped
+struct TamperedBackend(Box<dyn OsBackend>);
+
+impl OsBackend for TamperedBackend {
+    fn update(&mut self, bytes: &[u8]) -> Result<(), FileError> {
+        return self.0.update(bytes)
+    }
+    fn finish(self: Box<Self>) -> Result<Vec<u8>, FileError> {
+        let mut digest = self.0.finish()?;
+        digest[0] ^= 0x80;
+        return Ok(digest)
+    }
+}
+
+// Pure Rust implementation, used for SHA224 and SHA512_256 and as a fallback for the operating system
+struct RustBackend<D>(D);
+
+impl<D: sha2::Digest + Send> OsBackend for RustBackend<D> {
+    fn update(&mut self, bytes: &[u8]) -> Result<(), FileError> {
+        self.0.update(bytes);
+        return Ok(())
+    }
+    fn finish(self: Box<Self>) -> Result<Vec<u8>, FileError> {
+        return Ok(self.0.finalize().to_vec())
+    }
+}
+
+#[cfg(feature = "fallback")]
+fn rust_backend(os_hash: &OsAlgorithm) -> Option<Box<dyn OsBackend>> {
+    match os_hash {
+        OsAlgorithm::SHA1 => Some(Box::new(RustBackend(sha1::Sha1::default()))),
+        OsAlgorithm::SHA256 => Some(Box::new(RustBackend(sha2::Sha256::default()))),
+        OsAlgorithm::SHA512 => Some(Box::new(RustBackend(sha2::Sha512::default()))),
+        // Already computed in pure Rust
+        OsAlgorithm::SHA224 | OsAlgorithm::SHA512_256 => None,
+        #[cfg(feature = "insecure-md5")]
+        OsAlgorithm::MD5 => Some(Box::new(RustBackend(md5::Md5::default()))),
+    }
+}
+
+#[cfg(not(feature = "fallback"))]
+fn rust_backend(_os_hash: &OsAlgorithm) -> Option<Box<dyn OsBackend>> {
+    return None
+}
+
+/// An operating system hash function, optionally shadowed by a pure Rust implementation of the same algorithm.
+pub(crate) struct OsDigester {
+    backend: Option<Box<dyn OsBackend>>,
+    // The pure Rust implementation, present when falling back or cross checking
+    shadow: Option<Box<dyn OsBackend>>,
+    fallback: bool,
+    cross_check: bool,
+}
+
+impl OsDigester {
+    pub(crate) fn new(os_hash: &OsAlgorithm, options: OsOptions) -> Result<Self, FileError> {
+        let mut backend: Option<Box<dyn OsBackend>> = if options.simulate_failure {
+            Some(Box::new(FailingBackend))
+        }
+        else {
+            os_hasher(os_hash).ok()
+        };
+        if options.simulate_mismatch {
+            backend = backend.map(|backend| Box::new(TamperedBackend(backend)) as Box<dyn OsBackend>);
+        }
+        let shadow = if options.fallback || options.cross_check { rust_backend(os_hash) } else { None };
+        let fallback = options.fallback && shadow.is_some();
+
+        if backend.is_none() && !fallback {
+            return Err(FileError::OsHashingError)
+        }
+        return Ok(OsDigester { backend, shadow, fallback, cross_check: options.cross_check })
+    }
+    fn update(&mut self, bytes: &[u8]) -> Result<(), FileError> {
+        // Once the operating system fails it is dropped and only the fallback (if any) is fed
+        if let Some(backend) = self.backend.as_mut() {
+            if backend.update(bytes).is_err() {
+                trace::os_failure(self.fallback);
+                self.backend = None;
+            }
+        }
+        if self.backend.is_none() && !self.fallback {
+            return Err(FileError::OsHashingError)
+        }
+        match self.shadow.as_mut() {
+            Some(shadow) => return shadow.update(bytes),
+            None => return Ok(()),
+        }
+    }
+    // Returns the digest and whether it came from the fallback
+    fn finalize(self) -> Result<(Vec<u8>, bool), FileError> {
+        if let Some(backend) = self.backend {
+            match backend.finish() {
+                Ok(digest) => {
+                    if let (true, Some(shadow)) = (self.cross_check, self.shadow) {
+                        if shadow.finish()? != digest {
+                            return Err(FileError::ImplementationMismatch)
+                        }
+                    }
+                    return Ok((digest, false))
+                }
+                Err(_) => trace::os_failure(self.fallback),
+            }
+        }
+        match self.shadow {
+            Some(shadow) if self.fallback => return Ok((shadow.finish()?, true)),
+            _ => return Err(FileError::OsHashingError),
+        }
+    }
+}
+
+/// A single running hash function.
+pub(crate) enum Digester {
+    Blake2b(Blake2bHasher),
+    #[cfg(feature = "blake2-parallel")]
+    Parallel(Box<ParallelBlake2>),
+    #[cfg(feature = "xof")]
+    Shake256(Shake256Hasher),
+    Os(OsDigester),
+}
+
+impl Digester {
+    /// `threads` is the most threads a parallel hash function may use, as in `library()`.
+    pub(crate) fn new(algo: &Algo, threads: usize) -> Result<Self, FileError> {
+        return Digester::with_options(algo, OsOptions::default(), threads)
+    }
+    pub(crate) fn with_options(algo: &Algo, options: OsOptions, threads: usize) -> Result<Self, FileError> {
+        match algo {
+            Algo::Blake2b(digest_size) => return Digester::library(LibAlgorithm::BLAKE2B, *digest_size, None, threads),
+            #[cfg(feature = "blake2-parallel")]
+            Algo::Blake2bp(digest_size) => return Digester::library(LibAlgorithm::BLAKE2BP, *digest_size, None, threads),
+            #[cfg(feature = "blake2-parallel")]
+            Algo::Blake2sp(digest_size) => return Digester::library(LibAlgorithm::BLAKE2SP, *digest_size, None, threads),
+            #[cfg(feature = "xof")]
+            Algo::Shake256(digest_size) => return Digester::library(LibAlgorithm::SHAKE256, *digest_size, None, threads),
+            Algo::Os(os_hash) => return Digester::os(os_hash, options),
+        }
+    }
+    /// An Operating System Hash Function, which never uses more than the calling thread.
+    pub(crate) fn os(os_hash: &OsAlgorithm, options: OsOptions) -> Result<Self, FileError> {
+        return Ok(Digester::Os(OsDigester::new(os_hash, options)?))
+    }
+    /// A library hash function, keyed if `key` is given. Returns `FileError::InvalidKey` if the key is longer than the hash function allows (any key for SHAKE256).
+    ///
+    /// `threads` is the most threads a parallel hash function may use.
+    #[cfg_attr(not(feature = "blake2-parallel"), allow(unused_variables))]
+    pub(crate) fn library(library: LibAlgorithm, digest_size: usize, key: Option<&[u8]>, threads: usize) -> Result<Self, FileError> {
+        if key.is_some_and(|key| key.len() > library.max_key_size()) {
+            return Err(FileError::InvalidKey)
+        }
+        match (library, key) {
+            (LibAlgorithm::BLAKE2B, Some(key)) => return Ok(Digester::Blake2b(Blake2bHasher::with_key(digest_size, key))),
+            (LibAlgorithm::BLAKE2B, None) => return Ok(Digester::Blake2b(Blake2bHasher::new(digest_size))),
+            #[cfg(feature = "xof")]
+            (LibAlgorithm::SHAKE256, _) => return Ok(Digester::Shake256(Shake256Hasher::new(digest_size))),
+            #[cfg(feature = "blake2-parallel")]
+            (library, key) => return Ok(Digester::Parallel(Box::new(ParallelBlake2::new(library, digest_size, key, threads)))),
+        }
+    }
+    pub(crate) fn update(&mut self, bytes: &[u8]) -> Result<(), FileError> {
+        match self {
+            Digester::Blake2b(context) => {
+                context.update(bytes);
+                return Ok(())
+            }
+            #[cfg(feature = "blake2-parallel")]
+            Digester::Parallel(context) => {
+                context.update(bytes);
+                return Ok(())
+            }
+            #[cfg(feature = "xof")]
+            Digester::Shake256(context) => {
+                context.update(bytes);
+                return Ok(())
+            }
+            Digester::Os(os_digester) => return os_digester.update(bytes),
+        }
+    }
+    /// Returns the digest and whether the pure Rust fallback produced it.
+    pub(crate) fn finalize(self) -> Result<(Vec<u8>, bool), FileError> {
+        match self {
+            Digester::Blake2b(context) => return Ok((context.finalize(), false)),
+            #[cfg(feature = "blake2-parallel")]
+            Digester::Parallel(context) => return Ok((context.finalize(), false)),
+            #[cfg(feature = "xof")]
+            Digester::Shake256(context) => return Ok((context.finalize(), false)),
+            Digester::Os(os_digester) => return os_digester.finalize(),
+        }
+    }
+}
+
+/// The size of the buffer used when streaming data into the hash functions.
+pub(crate) const CHUNK_SIZE: usize = 64 * 1024;
+
+// Each hash function with its digest, in order
+pub(crate) type Digests = Vec<(Algo, Vec<u8>)>;
+
+// The library and operating system digests from a hasher created with `pair()`
+pub(crate) type DigestPair = (Vec<u8>, Vec<u8>);
+
+/// Runs several hash functions over the same data in a single pass.
+pub(crate) struct MultiHasher {
+    digesters: Vec<(Algo, Digester)>,
+}
+
+impl MultiHasher {
+    /// `threads` is the most threads each parallel hash function may use.
+    pub(crate) fn new(algorithms: &[Algo], threads: usize) -> Result<Self, FileError> {
+        let mut digesters = Vec::with_capacity(algorithms.len());
+        for algo in algorithms {
+            digesters.push((algo.clone(), Digester::new(algo, threads)?));
+        }
+        return Ok(MultiHasher { digesters })
+    }
+    /// The library and operating system hash functions used by `ParanoidHash`, in that order.
+    pub(crate) fn pair(library_hash: &LibAlgorithm, digest_size: usize, key: Option<&[u8]>, os_hash: &OsAlgorithm, options: OsOptions, threads: usize) -> Result<Self, FileError> {
+        let library = Digester::library(library_hash.clone(), digest_size, key, threads)?;
+        let os = Digester::os(os_hash, options)?;
+        return Ok(MultiHasher {
+            digesters: vec![
+                (library_hash.algo(digest_size), library),
+                (Algo::Os(os_hash.clone()), os),
+            ],
+        })
+    }
+    pub(crate) fn update(&mut self, bytes: &[u8]) -> Result<(), FileError> {
+        for (_, digester) in self.digesters.iter_mut() {
+            digester.update(bytes)?;
+        }
+        return Ok(())
+    }
+    /// Feeds everything from `reader` into the hash functions, returning the number of bytes hashed. Memory usage is bounded by the reader's buffer.
+    ///
+    /// `check` is called with the number of bytes hashed so far before every chunk (and once at the end). An error from `check` stops hashing.
+    pub(crate) fn update_reader_checked<R: BufRead, F: FnMut(u64) -> Result<(), FileError>>(&mut self, mut reader: R, mut check: F) -> Result<u64, FileError> {
+        let mut total: u64 = 0;
+        loop {
+            check(total)?;
+            let length = {
+                let chunk = match reader.fill_buf() {
+                    Ok(chunk) => chunk,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(io_error(e)),
+                };
+                if chunk.is_empty() {
+                    return Ok(total)
+                }
+                self.update(chunk)?;
+                chunk.len()
+            };
+            reader.consume(length);
+            total += length as u64;
+        }
+    }
+    /// Same as `update_reader_checked()` without a check for an unbuffered reader, using `buffer` to hold each chunk.
+    pub(crate) fn update_read<R: Read>(&mut self, mut reader: R, buffer: &mut [u8]) -> Result<u64, FileError> {
+        let mut total: u64 = 0;
+        loop {
+            let length = match reader.read(buffer) {
+                Ok(0) => return Ok(total),
+                Ok(length) => length,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(io_error(e)),
+            };
+            self.update(&buffer[..length])?;
+            total += length as u64;
+        }
+    }
+    pub(crate) fn finalize(self) -> Result<Digests, FileError> {
+        return Ok(self.finalize_flagged()?.0)
+    }
+    /// Same as `finalize()`, but also returns whether any digest came from the pure Rust fallback.
+    pub(crate) fn finalize_flagged(self) -> Result<(Digests, bool), FileError> {
+        let mut digests = Vec::with_capacity(self.digesters.len());
+        let mut fallback_used = false;
+        for (algo, digester) in self.digesters {
+            let (digest, fallback) = digester.finalize()?;
+            fallback_used |= fallback;
+            digests.push((algo, digest));
+        }
+        return Ok((digests, fallback_used))
+    }
+    /// Finalizes a hasher created with `pair()`, along with whether the pure Rust fallback replaced the operating system.
+    pub(crate) fn finalize_pair(self) -> Result<(DigestPair, bool), FileError> {
+        let (digests, fallback_used) = self.finalize_flagged()?;
+        let mut digests = digests.into_iter().map(|(_, digest)| digest);
+        let library = digests.next().unwrap();
+        let os = digests.next().unwrap();
+        return Ok(((library, os), fallback_used))
+    }
+}
+
+// `crypto-hash` panics if the platform crypto library cannot create a context
+pub(crate) fn os_hasher(os_hash: &OsAlgorithm) -> Result<Box<dyn OsBackend>, FileError> {
+    let algorithm = match os_hash {
+        OsAlgorithm::SHA1 => Algorithm::SHA1,
+        OsAlgorithm::SHA256 => Algorithm::SHA256,
+        OsAlgorithm::SHA512 => Algorithm::SHA512,
+        // Not provided by `crypto-hash`
+        OsAlgorithm::SHA224 => return Ok(Box::new(RustBackend(sha2::Sha224::default()))),
+        OsAlgorithm::SHA512_256 => return Ok(Box::new(RustBackend(sha2::Sha512_256::default()))),
+        #[cfg(feature = "insecure-md5")]
+        OsAlgorithm::MD5 => Algorithm::MD5,
+    };
+    return panic::catch_unwind(|| Hasher::new(algorithm)).map(|hasher| Box::new(hasher) as Box<dyn OsBackend>).map_err(|_| FileError::OsHashingError)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+
+    use super::{write_guarded, OsBackend, OsDigester, RustBackend};
+    use crate::{FileError, OsAlgorithm, ParanoidHash};
+
+    const FILE: &str = "tests/fixtures/report/alpha.txt";
+
+    // Panics on every write, like `crypto-hash` when the platform crypto library fails
+    struct PanickingWriter;
+
+    impl Write for PanickingWriter {
+        fn write(&mut self, _bytes: &[u8]) -> io::Result<usize> {
+            panic!("CryptHashData failed")
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            return Ok(())
+        }
+    }
+
+    // Written to through the same guard as `crypto-hash`
+    struct PanickingBackend(PanickingWriter);
+
+    impl OsBackend for PanickingBackend {
+        fn update(&mut self, bytes: &[u8]) -> Result<(), FileError> {
+            return write_guarded(&mut self.0, bytes)
+        }
+        fn finish(self: Box<Self>) -> Result<Vec<u8>, FileError> {
+            return Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn panicking_backend_is_an_error(){
+        let mut digester = OsDigester { backend: Some(Box::new(PanickingBackend(PanickingWriter))), shadow: None, fallback: false, cross_check: false };
+        assert_eq!(digester.update(b"alpha\n"),Err(FileError::OsHashingError));
+        assert!(digester.backend.is_none());
+        assert_eq!(digester.finalize(),Err(FileError::OsHashingError));
+
+        // The pure Rust fallback takes over as it would for any other failure
+        let mut digester = OsDigester { backend: Some(Box::new(PanickingBackend(PanickingWriter))), shadow: Some(Box::new(RustBackend(sha2::Sha256::default()))), fallback: true, cross_check: false };
+        assert_eq!(digester.update(b"abc"),Ok(()));
+        let (digest,fallback) = digester.finalize().unwrap();
+        assert_eq!(hex::encode_upper(digest),"BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+        assert!(fallback);
+    }
+
+    #[test]
+    fn os_failure_is_an_error(){
+        let context = ParanoidHash::new(64,OsAlgorithm::SHA256).simulate_os_failure(true);
+
+        assert_eq!(context.read(FILE),Err(FileError::OsHashingError));
+        assert_eq!(context.read_with_key(FILE,b"key"),Err(FileError::OsHashingError));
+        assert_eq!(context.read_using_std(FILE),Err(FileError::OsHashingError));
+        assert_eq!(context.read_from_reader(&b"alpha\n"[..]),Err(FileError::OsHashingError));
+        assert_eq!(context.read_parallel(FILE),Err(FileError::OsHashingError));
+        assert_eq!(context.try_read_bytes(b"alpha\n"),Err(FileError::OsHashingError));
+        assert_eq!(context.try_read_str("alpha\n"),Err(FileError::OsHashingError));
+        assert_eq!(context.try_read_bytes_parallel(b"alpha\n"),Err(FileError::OsHashingError));
+        assert_eq!(context.session().hash_bytes_into(b"x"),Err(FileError::OsHashingError));
+    }
+
+    #[test]
+    fn report_records_os_failure(){
+        let report = ParanoidHash::default().simulate_os_failure(true).report_files(&[FILE]);
+        assert_eq!(report.entries[0].error,Some(FileError::OsHashingError));
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_bytes_panics_on_os_failure(){
+        ParanoidHash::default().simulate_os_failure(true).read_bytes(b"alpha\n");
+    }
+
+    #[cfg(feature = "fallback")]
+    #[test]
+    fn fallback_matches_os(){
+        for os_hash in [OsAlgorithm::SHA1,OsAlgorithm::SHA256,OsAlgorithm::SHA512,#[cfg(feature = "insecure-md5")] OsAlgorithm::MD5] {
+            let expected = ParanoidHash::new_allowing_weak(48,os_hash.clone()).read(FILE).unwrap();
+            let context = ParanoidHash::new_allowing_weak(48,os_hash).os_fallback(true).simulate_os_failure(true);
+
+            let (digests,stats) = context.read_with_stats(FILE).unwrap();
+            assert_eq!(digests,expected);
+            assert!(stats.os_fallback);
+
+            let (digests,stats) = context.read_parallel_with_stats(FILE).unwrap();
+            assert_eq!(digests,expected);
+            assert!(stats.os_fallback);
+
+            assert_eq!(context.read_bytes(b"alpha\n"),expected);
+            assert_eq!(context.read_from_reader(&b"alpha\n"[..]).unwrap(),expected);
+        }
+    }
+
+    #[cfg(feature = "fallback")]
+    #[test]
+    fn os_failure_is_not_hidden_by_cross_check(){
+        let context = ParanoidHash::new(64,OsAlgorithm::SHA256).cross_check(true).simulate_os_failure(true);
+        assert_eq!(context.read(FILE),Err(FileError::OsHashingError));
+        assert!(context.os_fallback(true).read(FILE).is_ok());
+    }
+
+    #[cfg(feature = "fallback")]
+    #[test]
+    fn disagreeing_implementations_fail(){
+        let context = ParanoidHash::new(64,OsAlgorithm::SHA256).cross_check(true).simulate_os_mismatch(true);
+        assert_eq!(context.read(FILE),Err(FileError::ImplementationMismatch));
+        assert_eq!(context.read_using_std(FILE),Err(FileError::ImplementationMismatch));
+        assert_eq!(context.try_read_bytes(b"alpha\n"),Err(FileError::ImplementationMismatch));
+        assert_eq!(context.session().hash_bytes_into(b"alpha\n"),Err(FileError::ImplementationMismatch));
+    }
+
+    #[cfg(feature = "fallback")]
+    #[test]
+    fn mismatch_is_not_detected_without_cross_check(){
+        let tampered = ParanoidHash::new(64,OsAlgorithm::SHA256).simulate_os_mismatch(true).read(FILE).unwrap();
+        assert_ne!(tampered,ParanoidHash::new(64,OsAlgorithm::SHA256).read(FILE).unwrap());
+    }
+}
@@ -0,0 +1,233 @@
+// Hexadecimal Input
+//
+// Hashes the bytes a hexadecimal string stands for, rather than the text of the string.
+
+use std::fmt;
+
+use crate::{FileError, ParanoidHash, MAX_KEY_BYTES};
+
+/// # Hex Error
+///
+/// Returned by `read_hex()` and the hexadecimal decoders when the input is not valid hexadecimal or cannot be hashed.
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
+pub enum HexError {
+    /// The input has an odd number of hexadecimal digits
+    OddLength,
+    /// A character that is not a hexadecimal digit was found at byte `index` (counting from the start of the input, including any `0x` prefix)
+    InvalidCharacter { character: char, index: usize },
+    /// The digest has `actual` bytes, fewer than the `required` bytes of the prefix
+    TooShort { required: usize, actual: usize },
+    /// The digest has `actual` bytes, which cannot be held by a `DigestKey` (1 to `MAX_KEY_BYTES` bytes)
+    KeyLength { actual: usize },
+    /// The decoded bytes could not be hashed
+    Hashing(FileError),
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexError::OddLength => write!(f, "hexadecimal input has an odd number of digits"),
+            HexError::InvalidCharacter { character, index } => write!(f, "invalid hexadecimal character {:?} at index {}", character, index),
+            HexError::TooShort { required, actual } => write!(f, "digest has {} bytes but a prefix of {} was requested", actual, required),
+            HexError::KeyLength { actual } => write!(f, "digest key must be 1 to {} bytes but has {}", MAX_KEY_BYTES, actual),
+            HexError::Hashing(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+/// # Digest Kind
+///
+/// Which of the two digests of a `ParanoidHash` a string is checked against by `is_valid_hex_digest()`.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+pub enum DigestKind {
+    /// The library hash function at the configured digest size
+    Library,
+    /// The Operating System Hash Function
+    Os,
+}
+
+/// # Digest Format Error
+///
+/// Returned by `is_valid_hex_digest()` when a string cannot be a hexadecimal digest of the configured hash function.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+pub enum DigestFormatError {
+    /// The string has `actual` characters instead of the `expected` two per byte of the digest
+    Length { expected: usize, actual: usize },
+    /// A character that is not a hexadecimal digit was found at byte `index`
+    InvalidCharacter { character: char, index: usize },
+}
+
+impl fmt::Display for DigestFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DigestFormatError::Length { expected, actual } => write!(f, "digest has {} characters but {} were expected", actual, expected),
+            DigestFormatError::InvalidCharacter { character, index } => write!(f, "invalid hexadecimal character {:?} at index {}", character, index),
+        }
+    }
+}
+
+impl std::error::Error for DigestFormatError {}
+
+impl From<FileError> for HexError {
+    fn from(error: FileError) -> Self {
+        return HexError::Hashing(error)
+    }
+}
+
+impl ParanoidHash {
+    /// # Is Valid Hex Digest
+    ///
+    /// Checks that `s` could be a hexadecimal digest (in either case) of the library hash function at the configured digest size or of the Operating System Hash Function, without hashing anything. Invalid characters are reported before a wrong length.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{DigestFormatError,DigestKind,OsAlgorithm,ParanoidHash};
+    ///
+    /// let context = ParanoidHash::new_allowing_weak(32,OsAlgorithm::SHA1);
+    /// assert!(context.is_valid_hex_digest("A9993E364706816ABA3E25717850C26C9CD0D89D",DigestKind::Os).is_ok());
+    /// assert_eq!(context.is_valid_hex_digest("A9993E",DigestKind::Library),Err(DigestFormatError::Length { expected: 64, actual: 6 }));
+    /// ```
+    pub fn is_valid_hex_digest(&self, s: &str, which: DigestKind) -> Result<(),DigestFormatError> {
+        let expected = match which {
+            DigestKind::Library => self.digest_size * 2,
+            DigestKind::Os => self.os_hash_function.digest_len() * 2,
+        };
+        if let Some((index, character)) = s.char_indices().find(|(_, character)| !character.is_ascii_hexdigit()) {
+            return Err(DigestFormatError::InvalidCharacter { character, index })
+        }
+        if s.len() != expected {
+            return Err(DigestFormatError::Length { expected, actual: s.len() })
+        }
+        return Ok(())
+    }
+    /// # Read Hex
+    ///
+    /// Decodes a hexadecimal string and hashes the resulting bytes. An optional `0x` prefix and mixed case are accepted.
+    ///
+    /// This is the same as `read_bytes()` on the decoded bytes and **not** the same as `read_str()` on the hexadecimal text.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// assert_eq!(context.read_hex("0x48656C6c6f").unwrap(),context.read_bytes(b"Hello"));
+    /// ```
+    pub fn read_hex<T: AsRef<str>>(&self, hex_input: T) -> Result<(String,String),HexError> {
+        let bytes = decode_hex_input(hex_input.as_ref())?;
+        return Ok(self.try_read_bytes(&bytes)?)
+    }
+    /// # Try Decode From Hex
+    ///
+    /// Same as `decode_from_hex()` but returns a `HexError` instead of panicking. An optional `0x` prefix and mixed case are accepted.
+    pub fn try_decode_from_hex<T: AsRef<str>>(s: T) -> Result<Vec<u8>,HexError> {
+        return decode_hex_input(s.as_ref())
+    }
+    /// # Decode From Hex Lenient
+    ///
+    /// Decodes hexadecimal copied from certificates, hardware tools or terminals. Colons, dashes and whitespace (including a trailing newline) are ignored anywhere in the input, as is an optional `0x` prefix. Both cases are accepted.
+    ///
+    /// Any other character is still rejected with `HexError::InvalidCharacter` (its index is in the original input) and an odd number of digits left after stripping gives `HexError::OddLength`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// assert_eq!(ParanoidHash::decode_from_hex_lenient("AB:cd:EF\n").unwrap(),vec![0xAB,0xCD,0xEF]);
+    /// assert_eq!(ParanoidHash::decode_from_hex_lenient("ab cd-ef").unwrap(),vec![0xAB,0xCD,0xEF]);
+    /// ```
+    pub fn decode_from_hex_lenient<T: AsRef<str>>(s: T) -> Result<Vec<u8>,HexError> {
+        return decode_hex_lenient(s.as_ref())
+    }
+    /// # Compare Hash Lenient
+    ///
+    /// Same as `compare_hash()`, but ignores colons, dashes and whitespace in both digests first, so an OpenSSL-style fingerprint (`AB:CD:...`) compares equal to the output of this crate.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// assert!(ParanoidHash::compare_hash_lenient("ab:cd:ef","ABCDEF\n"));
+    /// ```
+    pub fn compare_hash_lenient<A: AsRef<str>, B: AsRef<str>>(hash1: A, hash2: B) -> bool {
+        return ParanoidHash::compare_hash(strip_separators(hash1.as_ref()), strip_separators(hash2.as_ref()))
+    }
+    /// # Digest Prefix U64
+    ///
+    /// Decodes a hexadecimal digest and returns its first 8 bytes as a big-endian integer, such as for picking a shard or as a deduplication key. An optional `0x` prefix and mixed case are accepted.
+    ///
+    /// A digest shorter than 8 bytes (possible with small BLAKE2B digest sizes) returns `HexError::TooShort` rather than being padded.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let sha256 = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+    /// assert_eq!(ParanoidHash::digest_prefix_u64(sha256).unwrap(),0xBA7816BF8F01CFEA);
+    /// ```
+    pub fn digest_prefix_u64<T: AsRef<str>>(hex: T) -> Result<u64,HexError> {
+        let bytes = decode_hex_input(hex.as_ref())?;
+        return Ok(u64::from_be_bytes(prefix_array(&bytes)?))
+    }
+    /// # Digest Prefix U128
+    ///
+    /// Same as `digest_prefix_u64()` with the first 16 bytes.
+    pub fn digest_prefix_u128<T: AsRef<str>>(hex: T) -> Result<u128,HexError> {
+        let bytes = decode_hex_input(hex.as_ref())?;
+        return Ok(u128::from_be_bytes(prefix_array(&bytes)?))
+    }
+}
+
+// The first `N` bytes of a digest
+pub(crate) fn prefix_array<const N: usize>(bytes: &[u8]) -> Result<[u8; N],HexError> {
+    if bytes.len() < N {
+        return Err(HexError::TooShort { required: N, actual: bytes.len() })
+    }
+    let mut prefix = [0u8; N];
+    prefix.copy_from_slice(&bytes[..N]);
+    return Ok(prefix)
+}
+
+pub(crate) fn decode_hex_input(input: &str) -> Result<Vec<u8>,HexError> {
+    let (offset, digits) = match input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+        Some(digits) => (2, digits),
+        None => (0, input),
+    };
+
+    if let Some((index, character)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(HexError::InvalidCharacter { character, index: index + offset })
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err(HexError::OddLength)
+    }
+    return Ok(hex::decode(digits).expect("[Error] Hexadecimal digits were already checked"))
+}
+
+pub(crate) fn decode_hex_lenient(input: &str) -> Result<Vec<u8>,HexError> {
+    let trimmed = input.trim_start();
+    let offset = input.len() - trimmed.len();
+    let (offset, digits) = match trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")) {
+        Some(digits) => (offset + 2, digits),
+        None => (offset, trimmed),
+    };
+
+    let mut kept = String::with_capacity(digits.len());
+    for (index, character) in digits.char_indices() {
+        if is_separator(character) {
+            continue
+        }
+        if !character.is_ascii_hexdigit() {
+            return Err(HexError::InvalidCharacter { character, index: index + offset })
+        }
+        kept.push(character);
+    }
+    if !kept.len().is_multiple_of(2) {
+        return Err(HexError::OddLength)
+    }
+    return Ok(hex::decode(kept).expect("[Error] Hexadecimal digits were already checked"))
+}
+
+fn is_separator(c: char) -> bool {
+    return c == ':' || c == '-' || c.is_whitespace()
+}
+
+fn strip_separators(s: &str) -> String {
+    return s.chars().filter(|c| !is_separator(*c)).collect()
+}
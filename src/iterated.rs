@@ -0,0 +1,65 @@
+// Iterated Hashing
+//
+// Runs each hash function over its own previous digest, for audit chains that need a digest of a digest.
+
+use std::path::Path;
+
+use crate::hashers::{Digester, DigestPair};
+use crate::{FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Bytes Iterated
+    ///
+    /// Hashes the bytes `rounds` times. The chaining is done separately for each hash function:
+    ///
+    /// * Round 1 is the same as `read_bytes()`: `B1 = BLAKE2B(bytes)` and `S1 = OS(bytes)`
+    /// * Every following round hashes the **raw digest bytes** (not the encoded string) of the round before it: `B(n+1) = BLAKE2B(Bn)` and `S(n+1) = OS(Sn)`
+    ///
+    /// BLAKE2B keeps the digest size of this context and is never keyed. Only the digests of the last round are returned, in the Output Encoding.
+    ///
+    /// Panics if `rounds` is 0 or the Operating System Hash Function fails.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// assert_eq!(context.read_bytes_iterated(b"Hello",1),context.read_bytes(b"Hello"));
+    /// ```
+    pub fn read_bytes_iterated(&self, bytes: &[u8], rounds: u32) -> (String,String) {
+        check_rounds(rounds);
+        let (digests, _) = self.hash_bytes_raw(bytes).expect("[Error] Failed To Hash Using Operating System Hash Function");
+        let digests = self.iterate(digests, rounds).expect("[Error] Failed To Hash Using Operating System Hash Function");
+        return self.encode_pair(digests)
+    }
+    /// # Read Iterated
+    ///
+    /// Same as `read_bytes_iterated()` for a file. Round 1 is the same as `read()`.
+    ///
+    /// Panics if `rounds` is 0.
+    pub fn read_iterated<T: AsRef<Path>>(&self, path: T, rounds: u32) -> Result<(String,String),FileError> {
+        check_rounds(rounds);
+        let (digests, _) = self.hash_path_raw(path.as_ref(), None)?;
+        return Ok(self.encode_pair(self.iterate(digests, rounds)?))
+    }
+    // Applies rounds 2 to `rounds`
+    fn iterate(&self, (mut library, mut os): DigestPair, rounds: u32) -> Result<DigestPair,FileError> {
+        let library_algo = self.library_algo();
+
+        for _ in 1..rounds {
+            let mut digester = Digester::new(&library_algo, self.thread_count())?;
+            digester.update(&library)?;
+            library = digester.finalize()?.0;
+
+            let mut digester = Digester::os(&self.os_hash_function, self.os_options)?;
+            digester.update(&os)?;
+            os = digester.finalize()?.0;
+        }
+        return Ok((library, os))
+    }
+}
+
+fn check_rounds(rounds: u32) {
+    if rounds == 0 {
+        panic!("[Error] At least one round is required.")
+    }
+}
@@ -0,0 +1,174 @@
+// Secret Keys
+//
+// Key material that is wiped from memory when it is no longer needed.
+
+use std::fmt;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::digest::constant_time_eq;
+use crate::hex_input::decode_hex_input;
+use crate::HexError;
+
+/// # Secret Key
+///
+/// Holds a key for BLAKE2B keyed hashing and overwrites it with zeroes when dropped. It can be passed anywhere a key is accepted, such as `read_with_key()`.
+///
+/// Only the buffer owned by this type is wiped. Copies made by the BLAKE2B implementation while hashing cannot be scrubbed, see `read_with_key()`.
+///
+/// ```rust
+/// use paranoid_hash::{ParanoidHash,SecretKey};
+///
+/// let key = SecretKey::new(vec![7u8; 32]);
+/// let context = ParanoidHash::default();
+/// let (blake2b,sha512) = context.read_with_key("examples/example_file.txt",&key).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct SecretKey {
+    bytes: Vec<u8>,
+}
+
+impl SecretKey {
+    /// ## New Secret Key
+    ///
+    /// Takes ownership of the key bytes.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        return SecretKey { bytes }
+    }
+    /// ## From Slice
+    ///
+    /// Copies the key bytes. The caller remains responsible for wiping the original.
+    pub fn from_slice(bytes: &[u8]) -> Self {
+        return SecretKey { bytes: bytes.to_vec() }
+    }
+    /// ## From Hex
+    ///
+    /// Decodes a hexadecimal key. Surrounding whitespace (such as the trailing newline of an environment variable or file), an optional `0x` prefix and mixed case are accepted.
+    ///
+    /// ```rust
+    /// use paranoid_hash::SecretKey;
+    ///
+    /// let key = SecretKey::from_hex("0x00010203\n").unwrap();
+    /// assert_eq!(key.as_ref(),&[0,1,2,3]);
+    /// ```
+    pub fn from_hex<T: AsRef<str>>(s: T) -> Result<Self,HexError> {
+        return Ok(SecretKey::new(decode_hex_input(s.as_ref().trim())?))
+    }
+    /// ## Length
+    ///
+    /// Returns the length of the key in bytes.
+    pub fn len(&self) -> usize {
+        return self.bytes.len()
+    }
+    /// ## Is Empty
+    pub fn is_empty(&self) -> bool {
+        return self.bytes.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for SecretKey {
+    fn from(bytes: Vec<u8>) -> Self {
+        return SecretKey::new(bytes)
+    }
+}
+
+impl AsRef<[u8]> for SecretKey {
+    fn as_ref(&self) -> &[u8] {
+        return &self.bytes
+    }
+}
+
+// Comparing keys must not reveal how many leading bytes match
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        return constant_time_eq(&self.bytes, &other.bytes)
+    }
+}
+
+impl Eq for SecretKey {}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretKey {}
+
+// Never print key material
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKey([REDACTED; {}])", self.bytes.len())
+    }
+}
+
+#[cfg(test)]
+#[allow(unsafe_code)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::slice;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use zeroize::Zeroize;
+
+    use super::SecretKey;
+
+    // Records whether the one watched buffer was all zeroes when it was freed, before handing it back to the system
+    struct WatchingAllocator;
+
+    static WATCHED: AtomicUsize = AtomicUsize::new(0);
+    static FREED_ZEROED: AtomicBool = AtomicBool::new(false);
+
+    unsafe impl GlobalAlloc for WatchingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            return System.alloc(layout)
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            if ptr as usize == WATCHED.load(Ordering::SeqCst) {
+                let zeroed = slice::from_raw_parts(ptr, layout.size()).iter().all(|byte| *byte == 0);
+                FREED_ZEROED.store(zeroed, Ordering::SeqCst);
+                WATCHED.store(0, Ordering::SeqCst);
+            }
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: WatchingAllocator = WatchingAllocator;
+
+    #[test]
+    fn zeroize_overwrites_the_buffer(){
+        let mut key = SecretKey::new(vec![0xAA; 64]);
+        let (ptr, capacity) = (key.bytes.as_ptr(), key.bytes.capacity());
+        key.zeroize();
+
+        // The buffer is kept (only its length is reset), so the old contents can still be read through it
+        assert_eq!(key.bytes.as_ptr(),ptr);
+        let buffer = unsafe { slice::from_raw_parts(ptr, capacity) };
+        assert_eq!(buffer,&[0u8; 64][..]);
+        assert!(key.is_empty());
+    }
+
+    #[test]
+    fn drop_overwrites_the_buffer(){
+        let key = SecretKey::new(vec![0xAA; 64]);
+        FREED_ZEROED.store(false, Ordering::SeqCst);
+        WATCHED.store(key.bytes.as_ptr() as usize, Ordering::SeqCst);
+        drop(key);
+        assert_eq!(WATCHED.load(Ordering::SeqCst),0);
+        assert!(FREED_ZEROED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn equality_compares_the_bytes(){
+        assert_eq!(SecretKey::from_slice(&[1,2,3]),SecretKey::new(vec![1,2,3]));
+        assert_ne!(SecretKey::from_slice(&[1,2,3]),SecretKey::from_slice(&[1,2,4]));
+        assert_ne!(SecretKey::from_slice(&[1,2,3]),SecretKey::from_slice(&[1,2]));
+    }
+}
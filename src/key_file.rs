@@ -0,0 +1,74 @@
+// Key Files
+//
+// Loads the key for `read_with_key()` from a file. With the `zeroize` feature the key (and the raw contents of a hex key file) are wiped once hashing is done.
+
+use std::fs;
+use std::path::Path;
+
+use crate::hex_input::decode_hex_input;
+use crate::{FileError, ParanoidHash};
+
+#[cfg(feature = "zeroize")]
+type KeyBytes = crate::SecretKey;
+#[cfg(not(feature = "zeroize"))]
+type KeyBytes = Vec<u8>;
+
+// The longest key accepted by BLAKE2B
+const MAX_KEY_SIZE: usize = 64;
+
+/// # Key Format
+///
+/// How the contents of a key file are interpreted.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash,Default)]
+pub enum KeyFormat {
+    /// The raw bytes of the file are the key
+    #[default]
+    Raw,
+    /// The file holds the key in hexadecimal. Surrounding whitespace (such as a trailing newline) and an optional `0x` prefix are ignored.
+    Hex,
+}
+
+impl ParanoidHash {
+    /// # Read With Key File
+    ///
+    /// Same as `read_with_key()`, but loads the key from `key_path`.
+    ///
+    /// The key must be 1-64 bytes long once decoded. Errors with the key are kept apart from errors with the data file:
+    ///
+    /// * `FileError::KeyFile` if the key file is missing or cannot be read
+    /// * `FileError::InvalidKey` if the key is not valid hexadecimal (with `KeyFormat::Hex`) or has the wrong length
+    /// * `FileError::FileNotFound` if the data file is missing
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,KeyFormat};
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let key_path = dir.path().join("key.hex");
+    /// std::fs::write(&key_path,"000102030405060708090a0b0c0d0e0f\n").unwrap();
+    ///
+    /// let context = ParanoidHash::default();
+    /// let digests = context.read_with_key_file("examples/example_file.txt",&key_path,KeyFormat::Hex).unwrap();
+    /// assert_eq!(digests,context.read_with_key("examples/example_file.txt",(0u8..16).collect::<Vec<u8>>()).unwrap());
+    /// ```
+    pub fn read_with_key_file<P: AsRef<Path>, K: AsRef<Path>>(&self, path: P, key_path: K, format: KeyFormat) -> Result<(String,String),FileError> {
+        let key = load_key(key_path.as_ref(), format)?;
+        return self.read_with_key(path, &key)
+    }
+}
+
+fn load_key(key_path: &Path, format: KeyFormat) -> Result<KeyBytes,FileError> {
+    let contents = KeyBytes::from(fs::read(key_path).map_err(|e| FileError::KeyFile(e.kind()))?);
+
+    let key = match format {
+        KeyFormat::Raw => contents,
+        KeyFormat::Hex => {
+            let text = std::str::from_utf8(contents.as_ref()).map_err(|_| FileError::InvalidKey)?;
+            KeyBytes::from(decode_hex_input(text.trim()).map_err(|_| FileError::InvalidKey)?)
+        }
+    };
+
+    if key.is_empty() || key.len() > MAX_KEY_SIZE {
+        return Err(FileError::InvalidKey)
+    }
+    return Ok(key)
+}
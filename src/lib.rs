@@ -9,7 +9,9 @@
 //! 
 //! It supports the following hash functions
 //! * [Library] BLAKE2B
-//! * [OS] SHA1
+//! * [Library] BLAKE2BP and BLAKE2SP (with the `blake2-parallel` feature)
+//! * [Library] SHAKE256, with digests of up to 1024 bytes (with the `xof` feature)
+//! * [OS] SHA1 (only through `ParanoidHash::new_allowing_weak()`)
 //! * [OS] SHA256
 //! * [OS] SHA512
 //! 
@@ -31,6 +33,15 @@
 //! 
 //! After hashing, two variables are returned. The first one is the Blake2B hash digest. The second one is the chosen operating system digest.
 //! 
+//! ## Empty Input
+//! 
+//! Hashing empty input (a zero-byte file, an empty reader, `""` or `b""`) succeeds by default and returns the digests of the empty message, for example with the default configuration:
+//! 
+//! * BLAKE2B (64 bytes): `786A02F742015903C6C6FD852552D272912F4740E15847618A86E217F71F5419D25E1031AFEE585313896444934EB04B903A685B1448B755D56F701AFE9BE2CE`
+//! * SHA512: `CF83E1357EEFB8BDF1542850D66D8007D620E4050B5715DC83F4A921D36CE9CE47D0D13C5D85F2B0FF8318D2877EEC2F63B931BD47417A81A538327AF927DA3E`
+//! 
+//! Empty input is often a sign of a truncated upload or some other upstream bug. Use `reject_empty_input(true)` to make files, readers and `try_read_bytes()` return `FileError::EmptyFile` instead.
+//! 
 //! ## How To Use
 //! 
 //! This is an example using Blake2B (64 byte digest) and SHA256 (OS) to hash a file
@@ -47,12 +58,137 @@
 //! }
 //! ```
 
-#![forbid(unsafe_code)]
+// The C interface needs `unsafe` to read the pointers it is given and is the only module allowed to use it, apart from tests that inspect freed key memory
+#![cfg_attr(not(any(feature = "ffi", test)), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "ffi", test), deny(unsafe_code))]
+
+mod accel;
+#[cfg(feature = "tar")]
+mod archive;
+mod batch;
+mod cache;
+#[cfg(feature = "blake2-parallel")]
+mod blake2p;
+#[cfg(feature = "blake2-rustcrypto")]
+mod blake2_rustcrypto;
+mod cancel;
+mod chained;
+mod checksum_file;
+mod compare;
+mod compare_any;
+mod compare_dirs;
+mod concat;
+#[cfg(feature = "subtle")]
+mod ct;
+mod copy;
+mod custom;
+mod digest;
+mod digest_key;
+mod domain;
+mod double_check;
+mod dir_digest;
+mod duplicates;
+mod encoding;
+mod fingerprint;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
+mod fixed;
+#[cfg(feature = "gzip")]
+mod gzip;
+mod hash_diff;
+mod hashers;
+mod hex_input;
+mod iterated;
+#[cfg(feature = "zeroize")]
+mod key;
+mod key_file;
+mod limit;
+mod lines;
+mod long_path;
+pub mod manifest;
+mod manifest_diff;
+mod manifest_update;
+mod merkle;
+mod modified;
+pub mod multi;
+#[cfg(feature = "multihash")]
+mod multihash;
+mod oneshot;
+mod output;
+mod parallel;
+mod pattern;
+mod probe;
+mod ranges;
+#[cfg(feature = "glob")]
+mod read_glob;
+pub mod report;
+mod resumable;
+mod retry;
+mod selftest;
+mod session;
+mod sidecar;
+mod stats;
+mod tagged;
+mod timeout;
+mod trace;
+mod tuning;
+mod uuid;
+mod verify;
+mod walk;
+#[cfg(feature = "xattr")]
+mod xattr_stamp;
+#[cfg(feature = "xof")]
+mod xof;
 
+pub use accel::{AccelBackend, AccelInfo, AlgorithmAccel};
+pub use cache::{CacheStats, HashCache, CACHE_FORMAT_VERSION};
+pub use checksum_file::VerifyOutcome;
+pub use compare::FileComparison;
+pub use compare_dirs::{DirCompareReport, DirMismatch};
+pub use custom::{Blake2bHasher, LibraryHasher, LibraryHasherFactory, ParanoidHashCustom};
+pub use digest::HashDigest;
+pub use digest_key::{DigestKey, MAX_KEY_BYTES};
+pub use duplicates::Duplicates;
+pub use encoding::OutputEncoding;
+#[cfg(feature = "base58")]
+pub use encoding::{decode_base58, encode_base58};
+pub use fixed::{DigestSizeMismatch, OsDigestArray, OutputSizeError};
+pub use hash_diff::HashDiff;
+pub use hex_input::{DigestFormatError, DigestKind, HexError};
+#[cfg(feature = "zeroize")]
+pub use key::SecretKey;
+pub use key_file::KeyFormat;
+pub use manifest::{Manifest, ManifestEntry, ManifestStatus};
+pub use manifest_diff::{ManifestChange, ManifestDiff};
+pub use manifest_update::{UpdateOptions, UpdateSummary};
+pub use merkle::MerkleProof;
+pub use modified::ModificationCheck;
+pub use multi::{detect_algorithm, Algo, ParanoidHashMulti};
+pub use oneshot::{hash_bytes, hash_file, hash_file_default};
+#[cfg(feature = "multihash")]
+pub use multihash::MultihashError;
+pub use output::HashOutput;
+#[cfg(feature = "glob")]
+pub use read_glob::GlobError;
+pub use report::{HashReport, ReportEntry};
+pub use resumable::{ParanoidHasher, STATE_FORMAT_VERSION};
+pub use retry::RetryPolicy;
+pub use selftest::SelfTestError;
+pub use session::ParanoidHashSession;
+pub use sidecar::SidecarOutcome;
+pub use stats::HashStats;
+pub use tagged::TaggedParseError;
+pub use verify::{DigestStatus, Verification, VerificationReport, VerifyPolicy};
+pub use tuning::ReadTuning;
+pub use uuid::uuid_from_digest;
+pub use walk::WalkOptions;
+#[cfg(feature = "xattr")]
+pub use xattr_stamp::{XattrOutcome, XATTR_NAME};
 
-use blake2_rfc::blake2b::Blake2b;
-use crypto_hash::{Algorithm, Hasher};
-use std::io::Write;
+use hashers::{DigestPair, MultiHasher, OsOptions, CHUNK_SIZE};
+use modified::PausingReader;
+use retry::RetryReader;
 
 use filebuffer::FileBuffer;
 use std::path::Path;
@@ -60,9 +196,22 @@ use std::path::Path;
 
 // For Reading Files without use FileBuffer
 use std::fs;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// Files larger than this are read in windows rather than memory mapped. On 32-bit targets a large mapping can exhaust the address space.
+#[cfg(target_pointer_width = "64")]
+const MMAP_LIMIT: u64 = u64::MAX;
+#[cfg(not(target_pointer_width = "64"))]
+const MMAP_LIMIT: u64 = 512 * 1024 * 1024;
+
+// The size of each window when a file is not memory mapped
+const WINDOW_SIZE: usize = 8 * 1024 * 1024;
 
 // For Developer:
-// * All outputs are in upper hexadecimal
+// * All outputs are in upper hexadecimal unless another `OutputEncoding` is chosen
 // * You can use `as_bytes()` to convert from hexadecimal string to bytes
 // * Blake2b digest size is between 1 and 64 bytes and will always be returned in hexadecimal format as a `String`
 // * One function `read_using_fs()` uses the standard library as opposed to filebuffer to read files.
@@ -73,42 +222,379 @@ use std::fs;
 #[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
 pub struct ParanoidHash {
     digest_size: usize,
+    library_hash_function: LibAlgorithm,
     os_hash_function: OsAlgorithm,
+    reject_empty: bool,
+    window_size: Option<usize>,
+    os_options: OsOptions,
+    encoding: OutputEncoding,
+    walk_options: WalkOptions,
+    max_file_size: Option<u64>,
+    threads: Option<usize>,
+    retry_policy: Option<RetryPolicy>,
+    canonical_paths: bool,
+    #[cfg_attr(not(feature = "glob"), allow(dead_code))]
+    require_glob_match: bool,
+    size_precheck: bool,
+    read_tuning: ReadTuning,
+    modification_check: ModificationCheck,
+    read_pause: Option<Duration>,
+}
+
+/// # Library Hashing Function
+/// 
+/// This enum contains the hash functions computed in pure rust alongside the operating system hash function.
+/// 
+/// It contains the following hash functions:
+/// * BLAKE2B, with a digest size of 1-64 bytes
+/// * BLAKE2BP, with a digest size of 1-64 bytes (requires the `blake2-parallel` feature)
+/// * BLAKE2SP, with a digest size of 1-32 bytes (requires the `blake2-parallel` feature)
+/// * SHAKE256, with a digest size of 1-1024 bytes (requires the `xof` feature)
+/// 
+/// BLAKE2BP and BLAKE2SP are the parallel modes from the BLAKE2 specification: the input is dealt out block by block to 4 (BLAKE2BP) or 8 (BLAKE2SP) leaves, each of which is hashed on its own thread once the input is large enough. Their digests are the standardized tree hashes and differ from BLAKE2B of the same input. Threads are only used at the full digest size (64 bytes for BLAKE2BP, 32 bytes for BLAKE2SP) without a key; other configurations produce the same digests on a single thread.
+/// 
+/// SHAKE256 (FIPS 202) is an extendable-output function, so it can produce digests longer than the 64 bytes of BLAKE2B. The first bytes of a longer digest are the same as a shorter digest of the same input. It cannot be keyed, so the keyed functions return `FileError::InvalidKey` with it.
+/// 
+/// **Default** uses **BLAKE2B**
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum LibAlgorithm {
+    #[default]
+    BLAKE2B,
+    #[cfg(feature = "blake2-parallel")]
+    BLAKE2BP,
+    #[cfg(feature = "blake2-parallel")]
+    BLAKE2SP,
+    #[cfg(feature = "xof")]
+    SHAKE256,
 }
 
 /// # OS Hashing Function
 /// 
-/// This enum contains three hash functions that is performed by the operating system. It does not use MD5 which is deprecated and insecure.
+/// This enum contains the hash functions that are performed by the operating system.
 /// 
 /// It contains the following hash functions:
 /// * SHA1
+/// * SHA224
 /// * SHA256
 /// * SHA512
-/// * MD5
+/// * SHA512_256
+/// * MD5 (requires the `insecure-md5` feature)
+/// 
+/// **SHA512_256** (SHA-512/256 from FIPS 180-4) has the 32 byte output of SHA256 with the speed of SHA512 on 64-bit processors and is not vulnerable to length extension. `crypto-hash` does not provide it, so it is always computed by the pure Rust `sha2` crate rather than the operating system.
+/// 
+/// **SHA224** (FIPS 180-4) is SHA256 truncated to 28 bytes with different initial values, for systems that only understand it. `crypto-hash` does not provide it either, so it is also computed by the `sha2` crate.
+/// 
+/// **SHA1** is broken for collision resistance and can only be used through `ParanoidHash::new_allowing_weak()` (see `is_weak()`).
+/// 
+/// **MD5** only exists with the `insecure-md5` feature, for verifying digests recorded by legacy systems.
 /// 
 /// **Default** uses **SHA512**
-#[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum OsAlgorithm {
     SHA1,
+    SHA224,
     SHA256,
+    #[default]
     SHA512,
+    SHA512_256,
+    /// MD5 (RFC 1321) is broken for collision resistance. It exists purely to verify digests recorded by legacy systems and must never be relied on for new integrity guarantees.
+    #[cfg(feature = "insecure-md5")]
     MD5,
 }
+/// # File Error
+/// 
+/// The error returned when hashing a file (or a set of files) fails.
 #[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
 pub enum FileError {
     FileNotFound,
+    /// The Operating System Hash Function failed
     OsHashingError,
+    /// An I/O error occured, such as while walking a directory
+    Io(std::io::ErrorKind),
+    /// The input was empty and `reject_empty_input()` is enabled
+    EmptyFile,
+    /// The operation was cancelled by the caller
+    Cancelled,
+    /// One hash function reported a match while the other reported a mismatch
+    DigestDisagreement,
+    /// An expected digest is not valid hexadecimal or its length does not match any supported hash function
+    InvalidDigest,
+    /// The key file could not be read (it is missing or unreadable)
+    KeyFile(std::io::ErrorKind),
+    /// The key is not valid hexadecimal or is not 1-64 bytes long
+    InvalidKey,
+    /// A manifest is malformed at the given line (starting from 1)
+    InvalidManifest(usize),
+    /// A manifest was written in a format version this crate does not support
+    UnsupportedManifestVersion(u32),
+    /// A checksum file lists the same file more than once with different digests
+    ConflictingChecksums,
+    /// An error with one file of a set of files, along with the path of that file
+    AtPath(std::path::PathBuf, Box<FileError>),
+    /// The input is larger than `max_file_size()`. For readers without a known length, `size` is the number of bytes read before hashing stopped.
+    FileTooLarge { size: u64, limit: u64 },
+    /// A copy read back from disk does not have the digests of its source
+    VerificationFailed,
+    /// The operating system and the bundled pure Rust implementation of the Operating System Hash Function produced different digests (see `cross_check()`)
+    ImplementationMismatch,
+    /// A gzip stream is malformed, truncated or fails its checksum
+    InvalidGzip,
+    /// The range at the given index (starting from 0) is reversed, extends past the end of the file or overlaps another range
+    InvalidRange(usize),
+    /// Two reads of the same file produced different digests (see `read_double_checked()`)
+    ReadInconsistency,
+    /// Opening or reading a file still failed with an I/O error after `attempts` tries under the `RetryPolicy`
+    RetriesExhausted { kind: std::io::ErrorKind, attempts: u32 },
+    /// A symbolic link points to a directory that contains it, so following it would walk the same directories forever (see `WalkOptions::follow_symlinks`)
+    SymlinkLoop,
+    /// The digest size requested for a single call is not supported by the library hash function
+    InvalidDigestSize(usize),
+    /// The operating system cannot compute the Operating System Hash Function (see `OsAlgorithm::is_available()`)
+    OsAlgorithmUnavailable(OsAlgorithm),
+    /// The filesystem or platform does not support extended attributes (see `store_xattr()`)
+    XattrUnsupported,
+    /// Neither an expected BLAKE2B digest nor an expected Operating System digest was given (see `verify_bytes()`)
+    NoExpectedDigest,
+    /// The Operating System Hash Function is broken and was not explicitly allowed (see `OsAlgorithm::is_weak()` and `new_allowing_weak()`)
+    WeakAlgorithm(OsAlgorithm),
+    /// The library hash function or Operating System Hash Function cannot be resumed (see `hasher()`)
+    NotResumable,
+    /// A saved hasher state is malformed or was saved with a different digest size or different algorithms (see `ParanoidHasher::restore_state()`)
+    InvalidHasherState,
+    /// A hasher state was saved in a format version this crate does not support
+    UnsupportedStateVersion(u8),
+    /// Hashing did not finish within the timeout given to `read_with_timeout()`. `bytes` is the number of bytes hashed before it stopped.
+    TimedOut { bytes: u64 },
+    /// The size or modification time of the file changed while it was hashed (see `modification_check()`)
+    ModifiedDuringHash,
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileError::FileNotFound => write!(f, "file not found"),
+            FileError::OsHashingError => write!(f, "operating system hash function failed"),
+            FileError::Io(kind) => write!(f, "i/o error: {:?}", kind),
+            FileError::EmptyFile => write!(f, "input is empty"),
+            FileError::Cancelled => write!(f, "hashing was cancelled"),
+            FileError::DigestDisagreement => write!(f, "the library and operating system digests disagree"),
+            FileError::InvalidDigest => write!(f, "the expected digest is not recognized"),
+            FileError::KeyFile(kind) => write!(f, "key file error: {:?}", kind),
+            FileError::InvalidKey => write!(f, "the key is invalid"),
+            FileError::InvalidManifest(line) => write!(f, "invalid manifest at line {}", line),
+            FileError::UnsupportedManifestVersion(version) => write!(f, "unsupported manifest version {}", version),
+            FileError::ConflictingChecksums => write!(f, "the checksum file lists conflicting digests"),
+            FileError::AtPath(path, error) => write!(f, "{}: {}", path.display(), error),
+            FileError::FileTooLarge { size, limit } => write!(f, "input of {} bytes is larger than the limit of {} bytes", size, limit),
+            FileError::VerificationFailed => write!(f, "the copy does not match its source"),
+            FileError::ImplementationMismatch => write!(f, "the operating system and pure rust implementations disagree"),
+            FileError::InvalidGzip => write!(f, "invalid gzip stream"),
+            FileError::InvalidRange(index) => write!(f, "invalid range at index {}", index),
+            FileError::ReadInconsistency => write!(f, "two reads of the file produced different digests"),
+            FileError::RetriesExhausted { kind, attempts } => write!(f, "i/o error: {:?} after {} attempts", kind, attempts),
+            FileError::SymlinkLoop => write!(f, "symbolic link loop"),
+            FileError::InvalidDigestSize(size) => write!(f, "unsupported digest size of {} bytes", size),
+            FileError::OsAlgorithmUnavailable(os_hash) => write!(f, "{:?} is not available from the operating system", os_hash),
+            FileError::XattrUnsupported => write!(f, "extended attributes are not supported"),
+            FileError::NoExpectedDigest => write!(f, "no expected digest was given"),
+            FileError::WeakAlgorithm(os_hash) => write!(f, "{:?} is broken for collision resistance and must be explicitly allowed", os_hash),
+            FileError::NotResumable => write!(f, "the hash functions cannot be resumed"),
+            FileError::InvalidHasherState => write!(f, "the saved hasher state is invalid"),
+            FileError::UnsupportedStateVersion(version) => write!(f, "unsupported hasher state version {}", version),
+            FileError::TimedOut { bytes } => write!(f, "timed out after hashing {} bytes", bytes),
+            FileError::ModifiedDuringHash => write!(f, "the file was modified while it was hashed"),
+        }
+    }
+}
+
+impl std::error::Error for FileError {}
+
+impl LibAlgorithm {
+    // The largest digest size in bytes
+    pub(crate) fn max_digest_size(&self) -> usize {
+        match self {
+            LibAlgorithm::BLAKE2B => return 64,
+            #[cfg(feature = "blake2-parallel")]
+            LibAlgorithm::BLAKE2BP => return 64,
+            #[cfg(feature = "blake2-parallel")]
+            LibAlgorithm::BLAKE2SP => return 32,
+            #[cfg(feature = "xof")]
+            LibAlgorithm::SHAKE256 => return 1024,
+        }
+    }
+    // The largest key size in bytes (0 if the hash function cannot be keyed)
+    pub(crate) fn max_key_size(&self) -> usize {
+        #[cfg(feature = "xof")]
+        if *self == LibAlgorithm::SHAKE256 {
+            return 0
+        }
+        return self.max_digest_size()
+    }
+    // The lower case name used in tagged digests and manifests
+    pub(crate) fn tag(&self) -> &'static str {
+        match self {
+            LibAlgorithm::BLAKE2B => return "blake2b",
+            #[cfg(feature = "blake2-parallel")]
+            LibAlgorithm::BLAKE2BP => return "blake2bp",
+            #[cfg(feature = "blake2-parallel")]
+            LibAlgorithm::BLAKE2SP => return "blake2sp",
+            #[cfg(feature = "xof")]
+            LibAlgorithm::SHAKE256 => return "shake256",
+        }
+    }
+    pub(crate) fn from_tag(tag: &str) -> Option<LibAlgorithm> {
+        match tag {
+            "blake2b" => return Some(LibAlgorithm::BLAKE2B),
+            #[cfg(feature = "blake2-parallel")]
+            "blake2bp" => return Some(LibAlgorithm::BLAKE2BP),
+            #[cfg(feature = "blake2-parallel")]
+            "blake2sp" => return Some(LibAlgorithm::BLAKE2SP),
+            #[cfg(feature = "xof")]
+            "shake256" => return Some(LibAlgorithm::SHAKE256),
+            _ => return None,
+        }
+    }
+    // As an `Algo` with the given digest size
+    pub(crate) fn algo(&self, digest_size: usize) -> Algo {
+        match self {
+            LibAlgorithm::BLAKE2B => return Algo::Blake2b(digest_size),
+            #[cfg(feature = "blake2-parallel")]
+            LibAlgorithm::BLAKE2BP => return Algo::Blake2bp(digest_size),
+            #[cfg(feature = "blake2-parallel")]
+            LibAlgorithm::BLAKE2SP => return Algo::Blake2sp(digest_size),
+            #[cfg(feature = "xof")]
+            LibAlgorithm::SHAKE256 => return Algo::Shake256(digest_size),
+        }
+    }
+    // The number of leaves
+    #[cfg(feature = "blake2-parallel")]
+    pub(crate) fn lanes(&self) -> usize {
+        match self {
+            LibAlgorithm::BLAKE2B => return 1,
+            LibAlgorithm::BLAKE2BP => return 4,
+            LibAlgorithm::BLAKE2SP => return 8,
+            #[cfg(feature = "xof")]
+            LibAlgorithm::SHAKE256 => return 1,
+        }
+    }
+    // The block size in bytes
+    #[cfg(feature = "blake2-parallel")]
+    pub(crate) fn block_len(&self) -> usize {
+        match self {
+            LibAlgorithm::BLAKE2SP => return 64,
+            _ => return 128,
+        }
+    }
+}
+
+impl OsAlgorithm {
+    /// ## From Hex Digest
+    /// 
+    /// Guesses the Operating System Hash Function from the length of a hexadecimal digest:
+    /// 
+    /// * 32 characters: MD5 (with the `insecure-md5` feature)
+    /// * 40 characters: SHA1
+    /// * 56 characters: SHA224
+    /// * 64 characters: SHA256
+    /// * 128 characters: SHA512
+    /// 
+    /// SHA512_256 digests are also 64 characters long and are reported as SHA256; `detect_algorithm()` returns both.
+    /// 
+    /// Returns `None` if the digest is not valid hexadecimal or has any other length. BLAKE2B can produce digests of the same lengths; use `detect_algorithm()` to get every candidate.
+    pub fn from_hex_digest(s: &str) -> Option<OsAlgorithm> {
+        if !is_hex(s) {
+            return None
+        }
+        match s.len() {
+            #[cfg(feature = "insecure-md5")]
+            32 => return Some(OsAlgorithm::MD5),
+            40 => return Some(OsAlgorithm::SHA1),
+            56 => return Some(OsAlgorithm::SHA224),
+            64 => return Some(OsAlgorithm::SHA256),
+            128 => return Some(OsAlgorithm::SHA512),
+            _ => return None,
+        }
+    }
+    /// ## Is Weak
+    /// 
+    /// Returns `true` for hash functions with practical collision attacks (SHA1, broken by SHAttered in 2017). `ParanoidHash::new()` and `ParanoidHash::try_new()` reject them; `ParanoidHash::new_allowing_weak()` has to be used instead, for example to verify digests that were recorded with SHA1.
+    pub fn is_weak(&self) -> bool {
+        return *self == OsAlgorithm::SHA1
+    }
+    // The digest size in bytes
+    pub(crate) fn digest_len(&self) -> usize {
+        match self {
+            #[cfg(feature = "insecure-md5")]
+            OsAlgorithm::MD5 => return 16,
+            OsAlgorithm::SHA1 => return 20,
+            OsAlgorithm::SHA224 => return 28,
+            OsAlgorithm::SHA256 => return 32,
+            OsAlgorithm::SHA512 => return 64,
+            OsAlgorithm::SHA512_256 => return 32,
+        }
+    }
+    // The lower case name used in tagged digests and traces
+    pub(crate) fn tag(&self) -> &'static str {
+        match self {
+            OsAlgorithm::SHA1 => return "sha1",
+            OsAlgorithm::SHA224 => return "sha224",
+            OsAlgorithm::SHA256 => return "sha256",
+            OsAlgorithm::SHA512 => return "sha512",
+            OsAlgorithm::SHA512_256 => return "sha512_256",
+            #[cfg(feature = "insecure-md5")]
+            OsAlgorithm::MD5 => return "md5",
+        }
+    }
+    pub(crate) fn from_tag(tag: &str) -> Option<OsAlgorithm> {
+        match tag {
+            "sha1" => return Some(OsAlgorithm::SHA1),
+            "sha224" => return Some(OsAlgorithm::SHA224),
+            "sha256" => return Some(OsAlgorithm::SHA256),
+            "sha512" => return Some(OsAlgorithm::SHA512),
+            "sha512_256" => return Some(OsAlgorithm::SHA512_256),
+            #[cfg(feature = "insecure-md5")]
+            "md5" => return Some(OsAlgorithm::MD5),
+            _ => return None,
+        }
+    }
 }
 
-impl Default for OsAlgorithm {
-    fn default() -> Self { OsAlgorithm::SHA512 }
+impl std::str::FromStr for OsAlgorithm {
+    type Err = TaggedParseError;
+
+    /// Parses the name of an Operating System Hash Function in either case, such as `sha256`, `SHA512_256` or (with the `insecure-md5` feature) `md5`. Any other name is `TaggedParseError::UnknownAlgorithm`.
+    fn from_str(s: &str) -> Result<Self,Self::Err> {
+        return OsAlgorithm::from_tag(&s.to_ascii_lowercase()).ok_or_else(|| TaggedParseError::UnknownAlgorithm(s.to_string()))
+    }
+}
+
+// Whether `s` is a non-empty string of hexadecimal digit pairs (either case)
+pub(crate) fn is_hex(s: &str) -> bool {
+    return !s.is_empty() && s.len().is_multiple_of(2) && s.bytes().all(|b| b.is_ascii_hexdigit())
 }
 
 impl Default for ParanoidHash {
     fn default() -> Self {
         return Self {
             digest_size: 64usize,
-            os_hash_function: OsAlgorithm::SHA512
+            library_hash_function: LibAlgorithm::BLAKE2B,
+            os_hash_function: OsAlgorithm::SHA512,
+            reject_empty: false,
+            window_size: None,
+            os_options: OsOptions::default(),
+            encoding: OutputEncoding::default(),
+            walk_options: WalkOptions::default(),
+            max_file_size: None,
+            threads: None,
+            retry_policy: None,
+            canonical_paths: true,
+            size_precheck: true,
+            require_glob_match: false,
+            read_tuning: ReadTuning::default(),
+            modification_check: ModificationCheck::default(),
+            read_pause: None,
         }
     }
 }
@@ -121,10 +607,12 @@ impl ParanoidHash {
     /// It accepts the following:
     /// 
     /// * BLAKE2B Digest Size In Bytes `[1-64]`
-    /// * Operating System Hash Function `{SHA1,SHA256,SHA512}`
+    /// * Operating System Hash Function `{SHA224,SHA256,SHA512,SHA512_256}`, or `MD5` with the `insecure-md5` feature
     /// 
     /// You can choose to use the default if you want optimal security.
     /// 
+    /// Panics if the digest size is out of range or the Operating System Hash Function is weak (SHA1, see `OsAlgorithm::is_weak()`). Use `try_new()` to get an error instead, or `new_allowing_weak()` to use SHA1 anyway.
+    /// 
     /// ## Example Code
     /// ```rust
     /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
@@ -134,169 +622,684 @@ impl ParanoidHash {
     /// }
     /// ```
     pub fn new(digest: usize,os_hash: OsAlgorithm) -> Self {
-        if digest > 0 && digest <= 64 {
+        return ParanoidHash::new_with_library(LibAlgorithm::BLAKE2B, digest, os_hash)
+    }
+    /// # Try New Hasher
+    /// 
+    /// Same as `new()` but returns `FileError::InvalidDigestSize` for a digest size outside 1-64 and `FileError::WeakAlgorithm` for a weak Operating System Hash Function instead of panicking.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm,FileError};
+    /// 
+    /// assert!(ParanoidHash::try_new(64,OsAlgorithm::SHA256).is_ok());
+    /// assert_eq!(ParanoidHash::try_new(20,OsAlgorithm::SHA1).unwrap_err(),FileError::WeakAlgorithm(OsAlgorithm::SHA1));
+    /// ```
+    pub fn try_new(digest: usize, os_hash: OsAlgorithm) -> Result<Self,FileError> {
+        if digest == 0 || digest > LibAlgorithm::BLAKE2B.max_digest_size() {
+            return Err(FileError::InvalidDigestSize(digest))
+        }
+        if os_hash.is_weak() {
+            return Err(FileError::WeakAlgorithm(os_hash))
+        }
+        return Ok(ParanoidHash::new(digest, os_hash))
+    }
+    /// # New Hasher Allowing Weak Algorithms
+    /// 
+    /// Same as `new()`, but also accepts a weak Operating System Hash Function such as SHA1. This is the explicit opt-in for SHA1, meant for verifying digests that were already recorded with it rather than for new ones.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    /// 
+    /// let context = ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1);
+    /// assert_eq!(context.read_bytes(b"abc").1,"A9993E364706816ABA3E25717850C26C9CD0D89D");
+    /// ```
+    pub fn new_allowing_weak(digest: usize, os_hash: OsAlgorithm) -> Self {
+        return ParanoidHash::build(LibAlgorithm::BLAKE2B, digest, os_hash)
+    }
+    /// # New Hasher With Library Hash Function
+    /// 
+    /// Same as `new()` with a library hash function other than BLAKE2B. The digest size must be supported by that hash function (1-32 bytes for BLAKE2SP, 1-1024 bytes for SHAKE256), otherwise this panics. Weak Operating System Hash Functions panic as in `new()`.
+    /// 
+    /// ```rust
+    /// # #[cfg(feature = "xof")]
+    /// # {
+    /// use paranoid_hash::{ParanoidHash,LibAlgorithm,OsAlgorithm};
+    /// 
+    /// let context = ParanoidHash::new_with_library(LibAlgorithm::SHAKE256,128,OsAlgorithm::SHA512);
+    /// let (shake256,_) = context.read_str("Hello World");
+    /// assert_eq!(shake256.len(),256);
+    /// # }
+    /// ```
+    pub fn new_with_library(library: LibAlgorithm, digest: usize, os_hash: OsAlgorithm) -> Self {
+        if os_hash.is_weak() {
+            panic!("[Error] {:?} is broken for collision resistance. Use new_allowing_weak() if it is needed to verify existing digests.", os_hash)
+        }
+        return ParanoidHash::build(library, digest, os_hash)
+    }
+    // Constructs any configuration, including weak ones. Also used for configurations recorded in manifests and tagged digests.
+    pub(crate) fn build(library: LibAlgorithm, digest: usize, os_hash: OsAlgorithm) -> Self {
+        if digest > 0 && digest <= library.max_digest_size() {
             return ParanoidHash {
                 digest_size: digest,
+                library_hash_function: library,
                 os_hash_function: os_hash,
+                reject_empty: false,
+                window_size: None,
+                os_options: OsOptions::default(),
+                encoding: OutputEncoding::default(),
+                walk_options: WalkOptions::default(),
+                max_file_size: None,
+                threads: None,
+                retry_policy: None,
+                canonical_paths: true,
+                size_precheck: true,
+                require_glob_match: false,
+                read_tuning: ReadTuning::default(),
+                modification_check: ModificationCheck::default(),
+                read_pause: None,
             }
         }
         else {
-            panic!("[Error] Digest Size is either too large or too small. It should be 1-64.")
+            panic!("[Error] Digest Size is either too large or too small. It should be 1-{}.", library.max_digest_size())
         }
     }
+    /// # Read
+    /// 
+    /// Reads the file using `FileBuffer` (a memory map) and hashes it with Blake2b and the Operating System Hash Function.
+    /// 
+    /// Files that are too large to map into the address space (which can happen on 32-bit targets) or that fail to map are read in bounded windows instead. The digests are identical either way.
+    /// 
+    /// Returns `FileError::FileNotFound` if the file does not exist and `FileError::Io` if it cannot be opened or read (such as when permission is denied or it is removed while being hashed). It never panics on an I/O error.
+    /// 
+    /// ## Special Files
+    /// 
+    /// Named pipes (FIFOs), sockets and devices are never mapped. They are streamed until EOF, so hashing a FIFO waits for its writer to close it. A device that never ends (such as `/dev/zero`) is only safe to pass together with `max_file_size()`, which stops hashing with `FileError::FileTooLarge`, or through `read_from_reader_cancellable()`.
     pub fn read<T: AsRef<Path>>(&self, path: T) -> Result<(String,String),FileError> {
-        
-        // Checks whether file exists. If file does not exist, returns error as FileError.
-        let does_file_exist = path.as_ref().exists();
-        if does_file_exist == false {
-            return Err(FileError::FileNotFound)
-        }
-
-        // Opens File Using File Buffer
-        let fbuffer = FileBuffer::open(path).expect("Failed To Read File");
-        
-        // Sets Blake2b Context at the given digest size
-        let mut context = Blake2b::new(self.digest_size);
-        context.update(&fbuffer);
-        let hash = context.finalize();
-
-        // Operating System Hashing
-        let mut os_hasher: Hasher = match self.os_hash_function {
-            OsAlgorithm::SHA1 => Hasher::new(Algorithm::SHA1),
-            OsAlgorithm::SHA256 => Hasher::new(Algorithm::SHA256),
-            OsAlgorithm::SHA512 => Hasher::new(Algorithm::SHA512),
-            OsAlgorithm::MD5 => Hasher::new(Algorithm::MD5),
-        };
-
-        // Finish Operating System Hashing
-        os_hasher.write_all(&fbuffer).expect("[Error] Failed To Hash File Using Operating System Hash Function");
-        let os_hash = os_hasher.finish();
-        
-        // Return as Upper Hexadecimal Encoded String
-        return Ok((hex::encode_upper(hash.as_bytes()),hex::encode_upper(os_hash)))
+        return Ok(self.hash_path(path.as_ref(), None)?.0)
     }
     /// # Read With Key
     /// 
     /// This method reads the file and uses a key with the Blake2b hash function. It does not and cannot use the key with the operating system hash function.
-    pub fn read_with_key<T: AsRef<Path>>(&self, path: T, key: &[u8]) -> Result<(String,String),FileError> {
+    /// 
+    /// Returns `FileError::InvalidKey` if the key is longer than the largest digest size of the library hash function (64 bytes, or 32 bytes for BLAKE2SP) or the library hash function cannot be keyed (SHAKE256).
+    /// 
+    /// The key can be anything that can be viewed as bytes, including a `SecretKey` (with the `zeroize` feature) which wipes itself when dropped.
+    /// 
+    /// **Note:** The BLAKE2B implementation keeps its own copy of the key block until the first block of data is compressed and does not wipe its state when dropped. This internal state cannot be scrubbed by this crate.
+    pub fn read_with_key<T: AsRef<Path>, K: AsRef<[u8]>>(&self, path: T, key: K) -> Result<(String,String),FileError> {
+        return Ok(self.hash_path(path.as_ref(), Some(key.as_ref()))?.0)
+    }
+    /// # Read With Digest Size
+    /// 
+    /// Same as `read()` but with the library hash function producing a digest of `digest_size` bytes for this call only. The configuration of this context is left unchanged and the Operating System Hash Function stays as configured.
+    /// 
+    /// Returns `FileError::InvalidDigestSize` if `digest_size` is not between 1 and the largest digest size of the library hash function (64 bytes for BLAKE2B).
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    /// 
+    /// let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    /// let digests = context.read_with_digest_size("examples/example_file.txt",32).unwrap();
+    /// assert_eq!(digests,ParanoidHash::new(32,OsAlgorithm::SHA256).read("examples/example_file.txt").unwrap());
+    /// assert_eq!(context.return_digest_size(),64);
+    /// ```
+    pub fn read_with_digest_size<T: AsRef<Path>>(&self, path: T, digest_size: usize) -> Result<(String,String),FileError> {
+        return self.with_digest_size(digest_size)?.read(path)
+    }
+    /// # Read Bytes With Digest Size
+    /// 
+    /// Same as `read_bytes()` but with the library hash function producing a digest of `digest_size` bytes for this call only, like `read_with_digest_size()`.
+    pub fn read_bytes_with_digest_size(&self, bytes: &[u8], digest_size: usize) -> Result<(String,String),FileError> {
+        return Ok(self.with_digest_size(digest_size)?.hash_bytes(bytes)?.0)
+    }
+    // A copy of this context with another digest size
+    fn with_digest_size(&self, digest_size: usize) -> Result<ParanoidHash,FileError> {
+        if digest_size == 0 || digest_size > self.library_hash_function.max_digest_size() {
+            return Err(FileError::InvalidDigestSize(digest_size))
+        }
+        let mut context = self.clone();
+        context.digest_size = digest_size;
+        return Ok(context)
+    }
+    // Shared by `read()`, `read_with_key()` and `read_with_stats()`
+    pub(crate) fn hash_path(&self, path: &Path, key: Option<&[u8]>) -> Result<((String,String),HashStats),FileError> {
+        let (digests, stats) = self.hash_path_raw(path, key)?;
+        return Ok((self.encode_pair(digests), stats))
+    }
+    // Same as `hash_path()` but returns the digests as bytes
+    pub(crate) fn hash_path_raw(&self, path: &Path, key: Option<&[u8]>) -> Result<(DigestPair,HashStats),FileError> {
+        return self.hash_path_buffered(path, key, &mut Vec::new())
+    }
+    // `window` is the buffer used for files that are not memory mapped, so it can be kept between files
+    pub(crate) fn hash_path_buffered(&self, path: &Path, key: Option<&[u8]>, window: &mut Vec<u8>) -> Result<(DigestPair,HashStats),FileError> {
+        let span = trace::Span::read(self, path);
+        let result = span.in_scope(|| self.hash_path_traced(path, key, window, &span));
+        span.finish(result.as_ref().map(|(_, stats)| stats.bytes));
+        return result
+    }
+    fn hash_path_traced(&self, path: &Path, key: Option<&[u8]>, window: &mut Vec<u8>, span: &trace::Span) -> Result<(DigestPair,HashStats),FileError> {
+        let path: &Path = &long_path::extended(path);
         
-        // Checks if file exists. If file does not exist, returns error.
-        let does_file_exist = path.as_ref().exists();
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        let does_file_exist = path.exists();
         if does_file_exist == false {
             return Err(FileError::FileNotFound)
         }
+        self.check_file_size(path)?;
 
-        // Opens File Using File Buffer
-        let fbuffer = FileBuffer::open(path).expect("failed to open file");
-        
-        // Sets Blake2b Context at the given digest size and hashes with the provided key
-        let mut context = Blake2b::with_key(self.digest_size, key);
-        context.update(&fbuffer);
-        let hash = context.finalize();
-        
-        // Operating System Hashing
-        let mut os_hasher: Hasher = match self.os_hash_function {
-            OsAlgorithm::SHA1 => Hasher::new(Algorithm::SHA1),
-            OsAlgorithm::SHA256 => Hasher::new(Algorithm::SHA256),
-            OsAlgorithm::SHA512 => Hasher::new(Algorithm::SHA512),
-            OsAlgorithm::MD5 => Hasher::new(Algorithm::MD5),
+        // Opens File Using File Buffer unless it is too large to map. Special files (such as FIFOs and devices) are never mapped.
+        let open_start = Instant::now();
+        let metadata = fs::metadata(path).map_err(|e| FileError::Io(e.kind()))?;
+        let special = !metadata.is_file() && !metadata.is_dir();
+        let fbuffer = match metadata.len() <= self.mmap_limit() && metadata.is_file() {
+            true => FileBuffer::open(path).ok(),
+            false => None,
         };
+        // Otherwise opens the file to be read in bounded windows
+        let file = match fbuffer {
+            Some(_) => None,
+            None => Some(self.open_file(path)?),
+        };
+        let open_elapsed = open_start.elapsed();
+
+        // Sets Blake2b Context at the given digest size (with the key if provided) and the Operating System Hash Function
+        let hash_start = Instant::now();
+        let mut hasher = self.pair_hasher(key)?;
 
-        // Finish Operating System Hashing
-        os_hasher.write_all(&fbuffer).expect("[Error] Failed To Hash File Using Operating System Hash Function");
-        let os_hash = os_hasher.finish();
+        let length = match (fbuffer, file) {
+            (Some(fbuffer), _) => {
+                span.backend("mmap");
+                hasher.update(&fbuffer)?;
+                fbuffer.len() as u64
+            }
+            // Special files have no known length, so they are streamed until EOF and checked against the size limit as they go
+            (None, Some(file)) if special => {
+                span.backend("stream");
+                self.read_tuning.before_read(&file);
+                let reader = PausingReader::new(RetryReader::new(&file, self.retry_policy), self.read_pause);
+                let length = hasher.update_reader_checked(BufReader::with_capacity(CHUNK_SIZE, reader), |total| self.check_size(total));
+                self.read_tuning.after_read(&file);
+                length?
+            }
+            (None, Some(file)) => {
+                span.backend("window");
+                window.resize(self.window_size.unwrap_or(WINDOW_SIZE), 0);
+                self.read_tuning.before_read(&file);
+                let length = hasher.update_read(PausingReader::new(RetryReader::new(&file, self.retry_policy), self.read_pause), window);
+                self.read_tuning.after_read(&file);
+                length?
+            }
+            (None, None) => unreachable!(),
+        };
+        self.check_empty(length)?;
+        let modified = self.check_modified(&metadata, || fs::metadata(path))?;
         
-        // Return as Upper Hexadecimal Encoded String
-        return Ok((hex::encode_upper(hash.as_bytes()),hex::encode_upper(os_hash)))
+        let (digests, os_fallback) = hasher.finalize_pair()?;
+        let mut stats = HashStats::new(length, open_elapsed, hash_start.elapsed());
+        stats.os_fallback = os_fallback;
+        stats.modified = modified;
+        return Ok((digests, stats))
+    }
+    // Encodes the BLAKE2B and Operating System digests in the Output Encoding
+    pub(crate) fn encode_pair(&self, (library, os): DigestPair) -> (String,String) {
+        return (self.encoding.encode(library), self.encoding.encode(os))
+    }
+    // The BLAKE2B and Operating System hashers for this configuration
+    pub(crate) fn pair_hasher(&self, key: Option<&[u8]>) -> Result<MultiHasher,FileError> {
+        return MultiHasher::pair(&self.library_hash_function, self.digest_size, key, &self.os_hash_function, self.os_options, self.thread_count())
+    }
+    // The library hash function at the configured digest size
+    pub(crate) fn library_algo(&self) -> Algo {
+        return self.library_hash_function.algo(self.digest_size)
+    }
+    // The library hash function, digest size and Operating System Hash Function, such as `blake2b-64+sha512`
+    pub(crate) fn algorithms_tag(&self) -> String {
+        return format!("{}-{}+{}", self.library_hash_function.tag(), self.digest_size, self.os_hash_function.tag())
+    }
+    // The largest file that will be memory mapped
+    pub(crate) fn mmap_limit(&self) -> u64 {
+        match self.window_size {
+            Some(window) => return window as u64,
+            None => return MMAP_LIMIT,
+        }
+    }
+    /// Forces files larger than `size` bytes to be read in windows of `size` bytes instead of being memory mapped. Only intended for testing the windowed path.
+    #[doc(hidden)]
+    pub fn window_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "[Error] Window Size must be at least 1 byte");
+        self.window_size = Some(size);
+        return self
     }
     /// # Read useing std::fs
     /// 
     /// This function allows you to read files using `std::fs`. This is rust's default way of reading files.
+    /// 
+    /// The file is streamed through a fixed-size buffer so memory usage stays the same no matter how large the file is. The output is identical to `read()`.
     pub fn read_using_std<T: AsRef<Path>>(&self, path: T) -> Result<(String,String),FileError> {
+        let span = trace::Span::read(self, path.as_ref());
+        span.backend("std");
+        let result = span.in_scope(|| {
+            let ((digests, mut stats), modified) = self.read_file_with(path.as_ref(), |reader| self.hash_reader(reader))?;
+            stats.modified = modified;
+            return Ok((digests, stats))
+        });
+        span.finish(result.as_ref().map(|(_, stats)| stats.bytes));
+        return Ok(result?.0)
+    }
+    /// # Read From Reader
+    /// 
+    /// Streams anything implementing `std::io::Read` (such as a socket, a pipe or a decompressor) through both hash functions until it reaches the end.
+    /// 
+    /// Memory usage is constant regardless of how much data is read.
+    pub fn read_from_reader<R: Read>(&self, reader: R) -> Result<(String,String),FileError> {
+        return self.read_from_reader_with_stats(reader).map(|(digests, _)| digests)
+    }
+    // Shared by `read_from_reader()`, `read_from_reader_with_stats()` and `read_using_std()`
+    fn hash_reader<R: Read>(&self, reader: R) -> Result<((String,String),HashStats),FileError> {
+        let (digests, stats) = self.hash_reader_raw(reader)?;
 
-        // Checks whether file exists and if it doesn't, returns error. For Error-Handling.
-        let does_file_exist = path.as_ref().exists();
-        if does_file_exist == false {
-            return Err(FileError::FileNotFound)
-        }
-
-        // Opens File Using Standard Library (fs) and read file to string
-        let fbuffer = fs::read(path).expect("failed to open file");
+        // Return in the Output Encoding
+        return Ok((self.encode_pair(digests), stats))
+    }
+    // Same as `hash_reader()` but returns the digests as bytes
+    pub(crate) fn hash_reader_raw<R: Read>(&self, reader: R) -> Result<(DigestPair,HashStats),FileError> {
+        let start = Instant::now();
 
-        
-        // Sets Blake2b Context at the given digest size
-        let mut context = Blake2b::new(self.digest_size);
-        // Convert str to bytes and updated context
-        context.update(&fbuffer);
-        let hash = context.finalize();
-        
-        // Operating System Hashing
-        let mut os_hasher = match self.os_hash_function {
-            OsAlgorithm::SHA1 => Hasher::new(Algorithm::SHA1),
-            OsAlgorithm::SHA256 => Hasher::new(Algorithm::SHA256),
-            OsAlgorithm::SHA512 => Hasher::new(Algorithm::SHA512),
-            OsAlgorithm::MD5 => Hasher::new(Algorithm::MD5),
-        };
+        // Streams the reader into Blake2b and the Operating System Hash Function
+        let mut hasher = self.pair_hasher(None)?;
+        let reader = RetryReader::new(reader, self.retry_policy);
+        let length = hasher.update_reader_checked(BufReader::with_capacity(CHUNK_SIZE, reader), |total| self.check_size(total))?;
+        self.check_empty(length)?;
+        let (digests, os_fallback) = hasher.finalize_pair()?;
 
-        // Finish Operating System Hashing
-        os_hasher.write_all(&fbuffer).expect("[Error] Failed To Hash File Using Operating System Hash Function");
-        let os_hash = os_hasher.finish();
-        
-        // Return as Upper Hexadecimal Encoded String
-        return Ok((hex::encode_upper(hash.as_bytes()),hex::encode_upper(os_hash)))
+        let mut stats = HashStats::new(length, Duration::from_secs(0), start.elapsed());
+        stats.os_fallback = os_fallback;
+        return Ok((digests, stats))
+    }
+    /// # Read File
+    /// 
+    /// Hashes an already open file from its current position to the end, leaving the cursor at the end. The file is never opened again, so the sharing flags it was opened with are kept and a file that has since been unlinked (on Unix) can still be hashed.
+    /// 
+    /// Use `read_file_from_start()` to hash the whole file regardless of the cursor.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    /// 
+    /// let context = ParanoidHash::default();
+    /// let mut file = std::fs::File::open("examples/example_file.txt").unwrap();
+    /// assert_eq!(context.read_file(&mut file).unwrap(),context.read("examples/example_file.txt").unwrap());
+    /// ```
+    pub fn read_file(&self, file: &mut fs::File) -> Result<(String,String),FileError> {
+        return self.read_from_reader(file)
+    }
+    /// # Read File From Start
+    /// 
+    /// Same as `read_file()` but seeks to the start of the file first.
+    pub fn read_file_from_start(&self, file: &mut fs::File) -> Result<(String,String),FileError> {
+        file.seek(SeekFrom::Start(0)).map_err(|e| FileError::Io(e.kind()))?;
+        return self.read_file(file)
     }
     /// # Read String
     /// This function will allow you to take a `String` or `str`, convert it to bytes, then hash it.
+    /// 
+    /// Like `read_bytes()`, an empty string is always hashed, even when `reject_empty_input()` is enabled. Use `try_read_str()` if you want the check.
+    /// 
+    /// Panics if the Operating System Hash Function fails. Use `try_read_str()` to get `FileError::OsHashingError` instead.
     pub fn read_str<T: AsRef<str>>(&self, string: T) -> (String,String) {
         
-        // Sets Blake2b Context at the given digest size
-        let mut context = Blake2b::new(self.digest_size);
         // Convert str to bytes
-        context.update(string.as_ref().as_bytes());
-        let hash = context.finalize();
-        
-        // Operating System Hashing
-        let mut os_hasher = match self.os_hash_function {
-            OsAlgorithm::SHA1 => Hasher::new(Algorithm::SHA1),
-            OsAlgorithm::SHA256 => Hasher::new(Algorithm::SHA256),
-            OsAlgorithm::SHA512 => Hasher::new(Algorithm::SHA512),
-            OsAlgorithm::MD5 => Hasher::new(Algorithm::MD5),
-        };
-
-        // Finish Operating System Hashing
-        os_hasher.write_all(string.as_ref().as_bytes()).expect("[Error] Failed To Hash File Using Operating System Hash Function");
-        let os_hash = os_hasher.finish();
-        
-        // Return as Upper Hexadecimal Encoded String
-        return (hex::encode_upper(hash.as_bytes()),hex::encode_upper(os_hash))
+        return self.read_bytes(string.as_ref().as_bytes())
+    }
+    /// # Try Read String
+    /// 
+    /// Same as `read_str()` but returns a `Result`, honoring `reject_empty_input()`.
+    pub fn try_read_str<T: AsRef<str>>(&self, string: T) -> Result<(String,String),FileError> {
+        return self.try_read_bytes(string.as_ref().as_bytes())
     }
     /// # Read Bytes
     /// 
     /// This function will hash bytes and return the output as two seperate strings.
+    /// 
+    /// Empty input is always hashed, even when `reject_empty_input()` is enabled. Use `try_read_bytes()` if you want the check.
+    /// 
+    /// Panics if the Operating System Hash Function fails. Use `try_read_bytes()` to get `FileError::OsHashingError` instead.
     pub fn read_bytes(&self, bytes: &[u8]) -> (String,String) {
+        return self.hash_bytes(bytes).expect("[Error] Failed To Hash Using Operating System Hash Function").0
+    }
+    // Hashes using Blake2b at the given digest size and the Operating System Hash Function. Also returns whether the fallback was used.
+    pub(crate) fn hash_bytes(&self, bytes: &[u8]) -> Result<((String,String),bool),FileError> {
+        let (digests, os_fallback) = self.hash_bytes_raw(bytes)?;
         
-        // Sets Blake2b Context at the given digest size
-        let mut context = Blake2b::new(self.digest_size);
-        context.update(bytes);
-        let hash = context.finalize();
-
-        // Operating System Hashing
-        let mut os_hasher = match self.os_hash_function {
-            OsAlgorithm::SHA1 => Hasher::new(Algorithm::SHA1),
-            OsAlgorithm::SHA256 => Hasher::new(Algorithm::SHA256),
-            OsAlgorithm::SHA512 => Hasher::new(Algorithm::SHA512),
-            OsAlgorithm::MD5 => Hasher::new(Algorithm::MD5),
-        };
+        // Return in the Output Encoding
+        return Ok((self.encode_pair(digests), os_fallback))
+    }
+    // Same as `hash_bytes()` but returns the digests as bytes
+    pub(crate) fn hash_bytes_raw(&self, bytes: &[u8]) -> Result<(DigestPair,bool),FileError> {
+        let mut hasher = self.pair_hasher(None)?;
+        hasher.update(bytes)?;
+        return hasher.finalize_pair()
+    }
+    /// # Try Read Bytes
+    /// 
+    /// Same as `read_bytes()` but returns a `Result`, honoring `reject_empty_input()` and returning `FileError::OsHashingError` if the Operating System Hash Function fails.
+    pub fn try_read_bytes(&self, bytes: &[u8]) -> Result<(String,String),FileError> {
+        self.check_empty(bytes.len() as u64)?;
+        return Ok(self.hash_bytes(bytes)?.0)
+    }
+    /// ## Reject Empty Input
+    /// 
+    /// When set to `true`, files, readers, `try_read_bytes()` and `try_read_str()` return `FileError::EmptyFile` if there is nothing to hash instead of returning the digests of the empty message.
+    /// 
+    /// `read_bytes()` and `read_str()` cannot return an error, so they always hash empty input. Use `try_read_bytes()` or `try_read_str()` when the check matters.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,FileError};
+    /// 
+    /// let context = ParanoidHash::default().reject_empty_input(true);
+    /// assert_eq!(context.try_read_bytes(b""),Err(FileError::EmptyFile));
+    /// assert_eq!(context.read_bytes(b""),ParanoidHash::default().read_bytes(b""));
+    /// ```
+    pub fn reject_empty_input(mut self, reject: bool) -> Self {
+        self.reject_empty = reject;
+        return self
+    }
+    /// ## Operating System Fallback
+    /// 
+    /// Requires the `fallback` feature.
+    /// 
+    /// When set to `true`, the chosen Operating System Hash Function is also computed by a bundled pure Rust implementation. If the operating system backend fails, the pure Rust digest is returned instead of `FileError::OsHashingError`.
+    /// 
+    /// The second digest is then not from the operating system. `HashStats::os_fallback` (from the `*_with_stats()` functions) is `true` when this happens.
+    /// 
+    /// **Note:** The algorithm is computed twice while this is enabled.
+    #[cfg(feature = "fallback")]
+    pub fn os_fallback(mut self, fallback: bool) -> Self {
+        self.os_options.fallback = fallback;
+        return self
+    }
+    /// ## Cross Check
+    /// 
+    /// Requires the `fallback` feature.
+    /// 
+    /// When set to `true`, the Operating System Hash Function is also computed by a bundled pure Rust implementation of the same algorithm and every function returns `FileError::ImplementationMismatch` if the two digests differ. That should never happen and would mean the platform crypto library is broken or has been tampered with.
+    /// 
+    /// SHA224 and SHA512_256 are already computed in pure Rust and have nothing to be checked against.
+    /// 
+    /// **Note:** The algorithm is computed twice while this is enabled.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    /// 
+    /// let context = ParanoidHash::new(64,OsAlgorithm::SHA256).cross_check(true);
+    /// assert_eq!(context.try_read_str("abc"),Ok(ParanoidHash::new(64,OsAlgorithm::SHA256).read_str("abc")));
+    /// ```
+    #[cfg(feature = "fallback")]
+    pub fn cross_check(mut self, cross_check: bool) -> Self {
+        self.os_options.cross_check = cross_check;
+        return self
+    }
+    // Replaces the Operating System Hash Function with a backend whose digests are wrong, so `cross_check()` fails. Only compiled into the crate's own tests.
+    #[cfg(all(test, feature = "fallback"))]
+    pub(crate) fn simulate_os_mismatch(mut self, mismatch: bool) -> Self {
+        self.os_options.simulate_mismatch = mismatch;
+        return self
+    }
+    // Replaces the Operating System Hash Function with a backend that always fails, to test the failure path. Only compiled into the crate's own tests.
+    #[cfg(test)]
+    pub(crate) fn simulate_os_failure(mut self, fail: bool) -> Self {
+        self.os_options.simulate_failure = fail;
+        return self
+    }
+    /// ## Output Encoding
+    /// 
+    /// Sets the encoding of the digests returned by `read()`, `read_bytes()`, `read_str()` and every other function returning digests as strings. The default is upper hexadecimal.
+    /// 
+    /// `compare_hash()` works the same with every encoding as long as both digests use the same one. Verification functions accept an expected digest in any supported encoding.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OutputEncoding};
+    /// 
+    /// let context = ParanoidHash::default().output_encoding(OutputEncoding::Base32);
+    /// let (blake2b,sha512) = context.read_str("Hello World");
+    /// assert_eq!(OutputEncoding::decode_any(&sha512).unwrap().len(),64);
+    /// ```
+    pub fn output_encoding(mut self, encoding: OutputEncoding) -> Self {
+        self.encoding = encoding;
+        return self
+    }
+    /// ## Return Output Encoding
+    pub fn return_output_encoding(&self) -> OutputEncoding {
+        return self.encoding
+    }
+    /// ## Canonicalize Paths
+    /// 
+    /// Whether every entry of a [`HashReport`] (from `report_files()` and `report_dir()`) records the canonical absolute path of the file that was read in `resolved_path`, as returned by `std::fs::canonicalize()`. Enabled by default.
+    /// 
+    /// Disable it to save the extra system calls or when canonicalization fails (as it can on some network mounts). The path as given is then recorded in `resolved_path` with `canonical` set to `false`, which also happens when canonicalization fails for a single entry.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    /// 
+    /// let report = ParanoidHash::default().report_files(&["examples/../examples/example_file.txt"]);
+    /// assert!(report.entries[0].canonical);
+    /// assert_eq!(report.entries[0].resolved_path,std::fs::canonicalize("examples/example_file.txt").unwrap());
+    /// 
+    /// let report = ParanoidHash::default().canonicalize_paths(false).report_files(&["examples/example_file.txt"]);
+    /// assert!(!report.entries[0].canonical);
+    /// ```
+    pub fn canonicalize_paths(mut self, canonicalize: bool) -> Self {
+        self.canonical_paths = canonicalize;
+        return self
+    }
+    /// ## Return Canonicalize Paths
+    pub fn return_canonicalize_paths(&self) -> bool {
+        return self.canonical_paths
+    }
+    /// ## Size Precheck
+    /// 
+    /// Whether `compare_files()`, `compare_dirs()` and `find_duplicates()` compare file sizes from the metadata first and skip hashing files that cannot be equal (because their sizes differ, or no other file has the same size). Enabled by default.
+    /// 
+    /// Disable it if the metadata cannot be trusted, such as with a writer still appending to the files. Every file is then hashed and only the digests decide. The detailed results (`compare_files_detailed()`, `DirMismatch::size_shortcut` and `Duplicates::unique_size`) show when the shortcut was taken.
+    pub fn size_precheck(mut self, precheck: bool) -> Self {
+        self.size_precheck = precheck;
+        return self
+    }
+    /// ## Return Size Precheck
+    pub fn return_size_precheck(&self) -> bool {
+        return self.size_precheck
+    }
+    /// ## Retry Policy
+    /// 
+    /// Retries transient I/O errors (such as a timeout on a network filesystem) when opening and reading files and readers, see [`RetryPolicy`]. `None` (the default) returns the first error. `ErrorKind::Interrupted` is always retried.
+    /// 
+    /// Applies to every function of `ParanoidHash` that opens a file by path or streams a reader, including the `_cancellable`, `_with_timeout`, `_with_limit` and `_with_hasher` variants, `read_lines()`, `read_concat()`, `read_ranges()`, `copy_and_hash()`, `read_gzip_decompressed()`, `read_tar_members()` and the directory functions. A read that keeps failing returns `FileError::RetriesExhausted` with the number of attempts.
+    /// 
+    /// Files that are memory mapped are only retried when opening them, since a failed read of a mapping cannot be caught. `ParanoidHashMulti` does not retry.
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use paranoid_hash::{ParanoidHash,RetryPolicy};
+    /// 
+    /// let policy = RetryPolicy { max_attempts: 5, backoff: Duration::from_millis(50) };
+    /// let context = ParanoidHash::default().retry_policy(Some(policy));
+    /// assert!(context.read("examples/example_file.txt").is_ok());
+    /// ```
+    /// 
+    /// Panics if `max_attempts` is 0.
+    pub fn retry_policy(mut self, policy: Option<RetryPolicy>) -> Self {
+        if policy.is_some_and(|policy| policy.max_attempts == 0) {
+            panic!("[Error] A retry policy needs at least one attempt.")
+        }
+        self.retry_policy = policy;
+        return self
+    }
+    /// ## Return Retry Policy
+    pub fn return_retry_policy(&self) -> Option<RetryPolicy> {
+        return self.retry_policy
+    }
+    /// ## Read Tuning
+    /// 
+    /// Gives the operating system access pattern hints for files that are streamed rather than memory mapped, see [`ReadTuning`]. This covers `read_using_std()`, special files and files too large to map. The hints need the `fadvise` feature and only take effect on Linux and Android. The default gives no hints.
+    /// 
+    /// The digests are identical with or without hints.
+    pub fn read_tuning(mut self, tuning: ReadTuning) -> Self {
+        self.read_tuning = tuning;
+        return self
+    }
+    /// ## Return Read Tuning
+    pub fn return_read_tuning(&self) -> ReadTuning {
+        return self.read_tuning
+    }
+    /// ## Modification Check
+    /// 
+    /// Records the size and modification time of each file before hashing it and compares them afterwards, see [`ModificationCheck`]. With `ModificationCheck::Error` a file that changed returns `FileError::ModifiedDuringHash`; with `ModificationCheck::Flag` the digests are kept and `HashStats::modified` is set. The default, `ModificationCheck::Off`, does not look.
+    /// 
+    /// Applies to `read()`, `read_using_std()`, `read_cancellable()`, `read_with_timeout()` and the functions built on them, such as `read_with_stats()`, `read_files()` and `read_dir()` (where each file is checked on its own).
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,ModificationCheck};
+    /// 
+    /// let context = ParanoidHash::default().modification_check(ModificationCheck::Flag);
+    /// let (_,stats) = context.read_with_stats("examples/example_file.txt").unwrap();
+    /// assert!(!stats.modified);
+    /// ```
+    pub fn modification_check(mut self, check: ModificationCheck) -> Self {
+        self.modification_check = check;
+        return self
+    }
+    /// ## Return Modification Check
+    pub fn return_modification_check(&self) -> ModificationCheck {
+        return self.modification_check
+    }
+    // Sleeps for `pause` before every read of a file that is not memory mapped, to test changes to a file while it is hashed. Only compiled into the crate's own tests.
+    #[cfg(test)]
+    pub(crate) fn simulate_slow_read(mut self, pause: Option<Duration>) -> Self {
+        self.read_pause = pause;
+        return self
+    }
+    /// ## Threads
+    /// 
+    /// Sets the largest number of threads used by a single call. The default is the number of logical CPUs. It bounds:
+    /// 
+    /// * `read_files()`, `report_files()` and `report_dir()`, which hash several files at once
+    /// * `read_parallel()` and the other `*_parallel()` functions, which hash sequentially with 1 thread
+    /// * The leaves of BLAKE2BP and BLAKE2SP (with the `blake2-parallel` feature)
+    /// 
+    /// The results never depend on the number of threads and with 1 thread everything runs on the calling thread. Panics if `threads` is 0.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    /// 
+    /// let context = ParanoidHash::default().threads(2);
+    /// let results = context.read_files(&["examples/example_file.txt","Cargo.toml"]);
+    /// assert_eq!(results,ParanoidHash::default().threads(1).read_files(&["examples/example_file.txt","Cargo.toml"]));
+    /// ```
+    pub fn threads(mut self, threads: usize) -> Self {
+        if threads == 0 {
+            panic!("[Error] At least one thread is required.")
+        }
+        self.threads = Some(threads);
+        return self
+    }
+    /// ## Return Threads
+    /// 
+    /// The number of threads set with `threads()`, or the number of logical CPUs if it was not set.
+    pub fn return_threads(&self) -> usize {
+        return self.thread_count()
+    }
+    pub(crate) fn thread_count(&self) -> usize {
+        return self.threads.unwrap_or_else(parallel::available_threads)
+    }
+    /// # Walk Options
+    /// 
+    /// Sets which files are visited by the directory-level functions using include and exclude glob patterns and whether hidden files are included. See [`WalkOptions`] for the pattern syntax.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,WalkOptions};
+    /// 
+    /// let context = ParanoidHash::default().walk_options(WalkOptions {
+    ///     exclude: vec!["target/".to_string(),".git/".to_string(),"*.swp".to_string()],
+    ///     ..WalkOptions::default()
+    /// });
+    /// ```
+    pub fn walk_options(mut self, options: WalkOptions) -> Self {
+        self.walk_options = options;
+        return self
+    }
+    /// ## Return Walk Options
+    pub fn return_walk_options(&self) -> &WalkOptions {
+        return &self.walk_options
+    }
+    fn check_empty(&self, length: u64) -> Result<(),FileError> {
+        if self.reject_empty && length == 0 {
+            return Err(FileError::EmptyFile)
+        }
+        return Ok(())
+    }
+    /// # Max File Size
+    /// 
+    /// Refuses inputs larger than `limit` bytes with `FileError::FileTooLarge` instead of hashing them. `None` (the default) removes the limit.
+    /// 
+    /// Files are checked against their metadata before anything is hashed. Readers (and open files passed to `read_file()`) are checked while streaming and hashing stops as soon as the limit is passed. Byte slices and strings are not limited.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,FileError};
+    /// 
+    /// let context = ParanoidHash::default().max_file_size(Some(4));
+    /// assert_eq!(context.read_from_reader(&b"Hello"[..]),Err(FileError::FileTooLarge { size: 5, limit: 4 }));
+    /// ```
+    pub fn max_file_size(mut self, limit: Option<u64>) -> Self {
+        self.max_file_size = limit;
+        return self
+    }
+    /// ## Return Max File Size
+    pub fn return_max_file_size(&self) -> Option<u64> {
+        return self.max_file_size
+    }
+    // Checks a size against `max_file_size()`
+    pub(crate) fn check_size(&self, size: u64) -> Result<(),FileError> {
+        match self.max_file_size {
+            Some(limit) if size > limit => return Err(FileError::FileTooLarge { size, limit }),
+            _ => return Ok(()),
+        }
+    }
+    // Streams a file the way `read_using_std()` does, for every function that reads a file through a `Read`: checks that it exists and its size, opens it with `open_file()`, applies the Read Tuning around `read` and checks whether the file was modified while it was read. Returns what `read` returned and whether the file was modified.
+    pub(crate) fn read_file_with<O, F: FnOnce(PausingReader<&fs::File>) -> Result<O,FileError>>(&self, path: &Path, read: F) -> Result<(O,bool),FileError> {
+        let path = long_path::extended(path);
 
-        // Finish Operating System Hashing
-        os_hasher.write_all(&bytes).expect("[Error] Failed To Hash File Using Operating System Hash Function");
-        let os_hash = os_hasher.finish();
-        
-        // Return as Upper Hexadecimal Encoded String
-        return (hex::encode_upper(hash.as_bytes()),hex::encode_upper(os_hash))
+        // Checks whether file exists and if it doesn't, returns error. For Error-Handling.
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+        self.check_file_size(&path)?;
+
+        // Opens File Using Standard Library (fs). If it cannot be opened (such as when permission is denied), returns error as FileError.
+        let file = self.open_file(&path)?;
+        let before = file.metadata().map_err(|e| FileError::Io(e.kind()))?;
+
+        self.read_tuning.before_read(&file);
+        let result = read(PausingReader::new(&file, self.read_pause));
+        self.read_tuning.after_read(&file);
+        let output = result?;
+        let modified = self.check_modified(&before, || file.metadata())?;
+        return Ok((output, modified))
+    }
+    // Opens a file to be read. Every function that opens a file by path goes through here, so a path over MAX_PATH gets the extended-length prefix on Windows and transient errors are retried under the Retry Policy.
+    pub(crate) fn open_file(&self, path: &Path) -> Result<fs::File,FileError> {
+        return retry::open(&long_path::extended(path), self.retry_policy.as_ref())
+    }
+    // Checks the size of a file against `max_file_size()`. The metadata is only read when there is a limit.
+    pub(crate) fn check_file_size(&self, path: &Path) -> Result<(),FileError> {
+        if self.max_file_size.is_none() {
+            return Ok(())
+        }
+        let size = fs::metadata(path).map_err(|e| FileError::Io(e.kind()))?.len();
+        return self.check_size(size)
     }
     /// ## decode_from_hex()
     /// `decode_from_hex()` (which was `as_bytes()`) converts from a **Hexadecimal String** to a **Vector of Bytes**
+    /// 
+    /// Panics if the string is not valid hexadecimal. Use `try_decode_from_hex()` to get a `HexError` instead, or `decode_from_hex_lenient()` for input with separators such as `AB:CD:EF`.
     pub fn decode_from_hex<T: AsRef<str>>(s: T) -> Vec<u8> {
         return hex::decode(s.as_ref()).unwrap()
     }
@@ -305,6 +1308,35 @@ impl ParanoidHash {
     pub fn return_digest_size(&self) -> usize {
         return self.digest_size
     }
+    /// ## Library Algorithm
+    /// 
+    /// Sets the library hash function. The default is BLAKE2B.
+    /// 
+    /// Panics if the digest size is larger than the hash function supports (32 bytes for BLAKE2SP).
+    /// 
+    /// ```rust
+    /// # #[cfg(feature = "blake2-parallel")]
+    /// # {
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm,LibAlgorithm};
+    /// 
+    /// let context = ParanoidHash::new(64,OsAlgorithm::SHA512).library_algorithm(LibAlgorithm::BLAKE2BP);
+    /// let (blake2bp,sha512) = context.read_str("Hello World");
+    /// assert_ne!(blake2bp,ParanoidHash::default().read_str("Hello World").0);
+    /// # }
+    /// ```
+    pub fn library_algorithm(mut self, algorithm: LibAlgorithm) -> Self {
+        if self.digest_size > algorithm.max_digest_size() {
+            panic!("[Error] Digest Size is too large for {:?}. It should be 1-{}.", algorithm, algorithm.max_digest_size())
+        }
+        self.library_hash_function = algorithm;
+        return self
+    }
+    /// ## Return Library Hash Function
+    /// 
+    /// This method will return the library hash function that was chosen
+    pub fn return_library_algorithm(&self) -> LibAlgorithm {
+        return self.library_hash_function.clone()
+    }
     /// ## Return Operating System Hash Function
     /// 
     /// This method will return the hash function used by the operating system that was chosen
@@ -316,15 +1348,53 @@ impl ParanoidHash {
     /// **Notice:** This function attempts to use constant-time operations in comparing strings based on [this](https://stackoverflow.com/questions/44691363/how-to-compare-strings-in-constant-time).
     /// 
     /// **Description:** Compares two hash functions (case-insensitive) and if they are the same, returns true. If they are different, returns false.
-    pub fn compare_hash<T: AsRef<str>>(hash1: T,hash2: T) -> bool {
+    /// 
+    /// With the `subtle` feature the bytes are compared by `subtle::ConstantTimeEq`; see `compare_hash_ct()` for the `Choice` itself.
+    /// 
+    /// To find out why two digests do not match while debugging, see `diff_hashes()`.
+    /// 
+    /// The two digests can be of different types, such as a `&str` literal and the `String` returned by `read()`:
+    /// 
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    /// use std::borrow::Cow;
+    /// 
+    /// let (blake2b,_) = ParanoidHash::default().read_str("Hello");
+    /// assert!(ParanoidHash::compare_hash(blake2b.as_str(),&blake2b));
+    /// assert!(ParanoidHash::compare_hash(Cow::Borrowed(blake2b.as_str()),blake2b.clone()));
+    /// assert!(!ParanoidHash::compare_hash("00",blake2b));
+    /// ```
+    pub fn compare_hash<A: AsRef<str>, B: AsRef<str>>(hash1: A,hash2: B) -> bool {
         let hash1_lowercase = hash1.as_ref().to_lowercase();
         let hash2_lowercase: String = hash2.as_ref().to_lowercase();
         
-        if hash1_lowercase.len() != hash2_lowercase.len() {
-            return false;
-        }
-        hash1_lowercase.bytes().zip(hash2_lowercase.bytes())
-            .fold(0, |acc, (a, b)| acc | (a ^ b) ) == 0
+        return digest::constant_time_eq(hash1_lowercase.as_bytes(), hash2_lowercase.as_bytes())
+    }
+    /// # Compare Hash Bytes
+    /// 
+    /// Same as `compare_hash()` for digests as bytes. Digests of different lengths are never equal.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    /// 
+    /// assert!(ParanoidHash::compare_hash_bytes(&[0xAB,0xCD],&[0xAB,0xCD]));
+    /// assert!(!ParanoidHash::compare_hash_bytes(&[0xAB,0xCD],&[0xAB]));
+    /// ```
+    pub fn compare_hash_bytes<A: AsRef<[u8]>, B: AsRef<[u8]>>(hash1: A, hash2: B) -> bool {
+        return digest::constant_time_eq(hash1.as_ref(), hash2.as_ref())
+    }
+    /// # Compare Hash Trimmed
+    /// 
+    /// Same as `compare_hash()`, but first removes leading and trailing ASCII whitespace (spaces, tabs, `\r` and `\n`) from both digests, such as the newline at the end of a digest read from a file. Whitespace inside a digest is still a mismatch.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    /// 
+    /// assert!(ParanoidHash::compare_hash_trimmed("ABCDEF\r\n","abcdef"));
+    /// assert!(!ParanoidHash::compare_hash_trimmed("ABC DEF","abcdef"));
+    /// ```
+    pub fn compare_hash_trimmed<A: AsRef<str>, B: AsRef<str>>(hash1: A, hash2: B) -> bool {
+        return ParanoidHash::compare_hash(hash1.as_ref().trim_ascii(), hash2.as_ref().trim_ascii())
     }
     
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+// Limited Hashing
+//
+// Hashes only the start of a file or reader, such as a quick fingerprint of a huge file to decide whether a full hash is worth it.
+
+use std::io::Read;
+use std::path::Path;
+
+use crate::{long_path, trace, FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read With Limit
+    ///
+    /// Hashes at most the first `limit` bytes of the file (all of it if the file is shorter) and returns the digests with the number of bytes hashed, so a short file can be told apart from a truncated read. The digests are the same as `read_bytes()` of those bytes.
+    ///
+    /// `max_file_size()` and `reject_empty_input()` apply to the bytes hashed rather than the whole file.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let contents = std::fs::read("examples/example_file.txt").unwrap();
+    ///
+    /// let (digests,hashed) = context.read_with_limit("examples/example_file.txt",64).unwrap();
+    /// assert_eq!(hashed,64);
+    /// assert_eq!(digests,context.read_bytes(&contents[..64]));
+    /// ```
+    pub fn read_with_limit<T: AsRef<Path>>(&self, path: T, limit: u64) -> Result<((String,String),u64),FileError> {
+        let path = path.as_ref();
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !long_path::extended(path).exists() {
+            return Err(FileError::FileNotFound)
+        }
+
+        let span = trace::Span::read(self, path);
+        span.backend("stream");
+        let result = span.in_scope(|| {
+            let file = self.open_file(path)?;
+            return self.hash_reader(file.take(limit))
+        });
+        span.finish(result.as_ref().map(|(_, stats)| stats.bytes));
+
+        let (digests, stats) = result?;
+        return Ok((digests, stats.bytes))
+    }
+    /// # Read Reader With Limit
+    ///
+    /// Same as `read_with_limit()` for a reader. At most `limit` bytes are read from it, so the rest can still be read afterwards.
+    pub fn read_reader_with_limit<R: Read>(&self, reader: R, limit: u64) -> Result<((String,String),u64),FileError> {
+        let span = trace::Span::reader(self);
+        let result = span.in_scope(|| self.hash_reader(reader.take(limit)));
+        span.finish(result.as_ref().map(|(_, stats)| stats.bytes));
+
+        let (digests, stats) = result?;
+        return Ok((digests, stats.bytes))
+    }
+}
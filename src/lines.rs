@@ -0,0 +1,82 @@
+// Line-by-line Hashing
+//
+// One pair of digests per line, so tampering with an append-only file can be traced to the line that changed.
+
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::hashers::CHUNK_SIZE;
+use crate::retry::{io_error, RetryReader};
+use crate::{long_path, FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Lines
+    ///
+    /// Streams the file and returns the BLAKE2B and Operating System digests of every line, in order.
+    ///
+    /// * Lines are split on `\n` and the terminator is **not** hashed. A `\r` before the `\n` is removed too, so `\r\n` and `\n` files give the same digests.
+    /// * A last line without a trailing newline is still hashed. A trailing newline does not produce an extra empty line.
+    /// * An empty file has no lines and returns an empty list (even with `reject_empty_input()`). Empty lines in the middle of a file are hashed as empty input.
+    ///
+    /// Only one line is held in memory at a time, so memory usage is bounded by the longest line.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let lines = context.read_lines("tests/fixtures/report/alpha.txt").unwrap();
+    /// assert_eq!(lines,vec![context.read_str("alpha")]);
+    /// ```
+    pub fn read_lines<T: AsRef<Path>>(&self, path: T) -> Result<Vec<(String,String)>,FileError> {
+        let path = long_path::extended(path.as_ref());
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+        self.check_file_size(&path)?;
+
+        let file = self.open_file(&path)?;
+        let mut reader = BufReader::with_capacity(CHUNK_SIZE, RetryReader::new(file, self.retry_policy));
+
+        // The line buffer is reused and only grows to the longest line
+        let mut line: Vec<u8> = Vec::new();
+        let mut digests = Vec::new();
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => return Ok(digests),
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(io_error(e)),
+            }
+            digests.push(self.hash_bytes(strip_terminator(&line))?.0);
+        }
+    }
+    /// # Read String Lines
+    ///
+    /// Same as `read_lines()` for lines that are already in memory. Each item is hashed as it is, so lines should not include their terminator.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let log = "first\nsecond\n";
+    /// assert_eq!(context.read_str_lines(log.lines()).unwrap().len(),2);
+    /// ```
+    pub fn read_str_lines<I: IntoIterator<Item = S>, S: AsRef<str>>(&self, lines: I) -> Result<Vec<(String,String)>,FileError> {
+        let mut digests = Vec::new();
+        for line in lines {
+            digests.push(self.hash_bytes(line.as_ref().as_bytes())?.0);
+        }
+        return Ok(digests)
+    }
+}
+
+// Removes a trailing `\n` or `\r\n`
+fn strip_terminator(line: &[u8]) -> &[u8] {
+    match line.strip_suffix(b"\n") {
+        Some(line) => return line.strip_suffix(b"\r").unwrap_or(line),
+        None => return line,
+    }
+}
@@ -0,0 +1,46 @@
+// Windows Long Paths
+//
+// Windows refuses paths of MAX_PATH (260 characters) or more unless they carry the `\\?\` extended-length prefix, which also turns off its normalisation of `/`, `.` and `..`. Long paths are therefore made absolute (which normalises them) before the prefix is added. Everywhere else paths are used as they are.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Returns `path` in a form that can be opened even when it is longer than MAX_PATH. Paths that are short enough, already prefixed or cannot be made absolute are returned unchanged.
+#[cfg(windows)]
+pub(crate) fn extended(path: &Path) -> Cow<'_, Path> {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+    use std::path::{Component, Prefix, PathBuf};
+
+    // Directories are limited to MAX_PATH minus room for an 8.3 file name
+    const LONG_PATH: usize = 248;
+
+    let is_long = |path: &Path| path.as_os_str().encode_wide().count() >= LONG_PATH;
+
+    // A relative path is resolved against the current directory, so its absolute form is what has to fit
+    if path.is_absolute() && !is_long(path) {
+        return Cow::Borrowed(path)
+    }
+    let absolute = match std::path::absolute(path) {
+        Ok(absolute) if is_long(absolute.as_path()) => absolute,
+        _ => return Cow::Borrowed(path),
+    };
+    let wide: Vec<u16> = absolute.as_os_str().encode_wide().collect();
+    let (mut extended, rest) = match absolute.components().next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(_) => (OsString::from(r"\\?\"), &wide[..]),
+            // `\\server\share\...` becomes `\\?\UNC\server\share\...`
+            Prefix::UNC(_, _) => (OsString::from(r"\\?\UNC"), &wide[1..]),
+            // Already extended or a device path
+            _ => return Cow::Borrowed(path),
+        },
+        _ => return Cow::Borrowed(path),
+    };
+    extended.push(OsString::from_wide(rest));
+    return Cow::Owned(PathBuf::from(extended))
+}
+
+#[cfg(not(windows))]
+pub(crate) fn extended(path: &Path) -> Cow<'_, Path> {
+    return Cow::Borrowed(path)
+}
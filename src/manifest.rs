@@ -0,0 +1,330 @@
+//! # Manifests
+//!
+//! A [`Manifest`] is a snapshot of the digests of every file in a directory that can be saved and verified later, even by a program using a different configuration.
+//!
+//! ## File Format
+//!
+//! Manifests are UTF-8 text, one record per line. The format is **stable** for a given format version.
+//!
+//! ```text
+//! paranoid-hash manifest 3
+//! created-by 0.5.0
+//! blake2b 64
+//! os SHA512
+//! hidden true
+//! <size> <modified> <blake2b> <os digest> <path>
+//! ...
+//! ```
+//!
+//! * The first line holds the format version. Manifests with a version this crate does not know are rejected with `FileError::UnsupportedManifestVersion`.
+//! * `created-by` is the version of the crate that wrote the manifest. It is informational only.
+//! * `blake2b` is the BLAKE2B digest size in bytes and `os` is the Operating System Hash Function (`SHA1`, `SHA224`, `SHA256`, `SHA512`, `SHA512_256` or `MD5` with the `insecure-md5` feature). `blake2b` is replaced by `blake2bp` or `blake2sp` (with the `blake2-parallel` feature) or `shake256` (with the `xof` feature) for manifests created with those library hash functions.
+//! * `hidden` is `true` if hidden files were included (see `WalkOptions::include_hidden`). It was added in version 2; version 1 manifests have no `hidden` line and are loaded as `true`.
+//! * Each remaining line is a file: its size in bytes, its modification time in nanoseconds since the Unix epoch (or `-` if it is not known), both digests in upper hexadecimal and its path relative to the directory, separated by single spaces. The modification time was added in version 3 and is only used by `Manifest::update()`; entries of older versions are loaded without one. Paths always use `/` as the separator. A backslash is written as `\\` and a newline as `\n`.
+//! * Entries are sorted by the UTF-8 bytes of their path, the same order as `ParanoidHash::read_dir()`.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use crate::cache;
+use crate::hashers::DigestPair;
+use crate::{walk, FileError, HashCache, LibAlgorithm, OsAlgorithm, ParanoidHash, VerifyPolicy, Verification, WalkOptions};
+
+/// The version of the format written by [`Manifest::save()`].
+pub const MANIFEST_FORMAT_VERSION: u32 = 3;
+
+const MAGIC: &str = "paranoid-hash manifest";
+
+/// # Manifest
+///
+/// The digests of every file in a directory, created with `ParanoidHash::manifest_dir()`.
+#[derive(Debug,Clone,PartialEq,Hash)]
+pub struct Manifest {
+    pub format_version: u32,
+    /// The version of the crate that created the manifest
+    pub created_by: String,
+    pub library_algorithm: LibAlgorithm,
+    /// The digest size of the library hash function
+    pub blake2b_digest_size: usize,
+    pub os_algorithm: OsAlgorithm,
+    /// Whether hidden files were included when the manifest was created
+    pub include_hidden: bool,
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// # Manifest Entry
+///
+/// A single file in a [`Manifest`].
+#[derive(Debug,Clone,PartialEq,Eq,PartialOrd,Ord,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ManifestEntry {
+    /// Path relative to the directory
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::report::json::lossy_path"))]
+    pub path: PathBuf,
+    pub size: u64,
+    /// Modification time when the file was hashed, in nanoseconds since the Unix epoch. `None` if it was not available or the manifest was loaded from format version 1 or 2.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub modified: Option<u64>,
+    /// BLAKE2B digest in upper hexadecimal
+    pub blake2b: String,
+    /// Operating System digest in upper hexadecimal
+    pub os_digest: String,
+}
+
+/// # Manifest Status
+///
+/// The result of checking one entry of a [`Manifest`] against a directory.
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
+pub enum ManifestStatus {
+    /// Both digests match
+    Match,
+    /// At least one digest does not match
+    Mismatch,
+    /// The file no longer exists
+    Missing,
+    /// The file could not be hashed
+    Error(FileError),
+}
+
+impl ParanoidHash {
+    /// # Manifest Directory
+    ///
+    /// Recursively hashes every regular file inside `dir` (sorted by the UTF-8 bytes of their relative path, symbolic links are not followed) into a [`Manifest`]. Fails on the first file that cannot be hashed so a manifest is never incomplete.
+    ///
+    /// Digests in a manifest are always upper hexadecimal, regardless of the Output Encoding.
+    pub fn manifest_dir<T: AsRef<Path>>(&self, dir: T) -> Result<Manifest,FileError> {
+        return self.manifest_dir_with_cache(dir.as_ref(), None)
+    }
+    /// # Manifest Directory Cached
+    ///
+    /// Same as `manifest_dir()`, but reuses the digests in `cache` for files that have not changed and stores the digests of the rest. See [`HashCache`].
+    pub fn manifest_dir_cached<T: AsRef<Path>>(&self, dir: T, cache: &mut HashCache) -> Result<Manifest,FileError> {
+        return self.manifest_dir_with_cache(dir.as_ref(), Some(cache))
+    }
+    fn manifest_dir_with_cache(&self, dir: &Path, mut cache: Option<&mut HashCache>) -> Result<Manifest,FileError> {
+        let mut entries = Vec::new();
+        let mut window = Vec::new();
+
+        for file in walk::walk_files(dir, &self.walk_options)? {
+            let relative = file.strip_prefix(dir).expect("[Error] Walked file is outside of the directory").to_path_buf();
+            entries.push(self.manifest_entry(&file, relative, cache.as_deref_mut(), &mut window)?);
+        }
+
+        return Ok(Manifest {
+            format_version: MANIFEST_FORMAT_VERSION,
+            created_by: env!("CARGO_PKG_VERSION").to_string(),
+            library_algorithm: self.library_hash_function.clone(),
+            blake2b_digest_size: self.digest_size,
+            os_algorithm: self.os_hash_function.clone(),
+            include_hidden: self.walk_options.include_hidden,
+            entries,
+        })
+    }
+    // The modification time is read before hashing, so a file written while it is hashed is hashed again by the next `Manifest::update()`
+    pub(crate) fn manifest_entry(&self, file: &Path, relative: PathBuf, cache: Option<&mut HashCache>, window: &mut Vec<u8>) -> Result<ManifestEntry,FileError> {
+        let modified = fs::metadata(file).ok().and_then(|metadata| cache::modified(&metadata));
+        let ((library, os), size) = self.hash_path_with_cache(file, cache, window)?;
+        return Ok(ManifestEntry {
+            path: relative,
+            size,
+            modified,
+            blake2b: hex::encode_upper(library),
+            os_digest: hex::encode_upper(os),
+        })
+    }
+}
+
+impl Manifest {
+    /// ## Config
+    ///
+    /// A `ParanoidHash` with the configuration the manifest was created with, including whether hidden files are visited. A manifest recorded with SHA1 gives a configuration with SHA1, as if it was created with `ParanoidHash::new_allowing_weak()`.
+    pub fn config(&self) -> ParanoidHash {
+        let options = WalkOptions { include_hidden: self.include_hidden, ..WalkOptions::default() };
+        return ParanoidHash::build(self.library_algorithm.clone(), self.blake2b_digest_size, self.os_algorithm.clone()).walk_options(options)
+    }
+    /// ## Save
+    ///
+    /// Writes the manifest to `path` in the [documented format](crate::manifest). The current format version is always written, even for a manifest loaded from an older version.
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<(),FileError> {
+        let text = self.to_text()?;
+        return fs::write(path, text).map_err(|e| FileError::Io(e.kind()))
+    }
+    /// ## Load
+    ///
+    /// Reads a manifest written by `save()`. The configuration is taken from the file, so it does not need to match the caller's.
+    ///
+    /// Returns `FileError::UnsupportedManifestVersion` for a format version this crate does not understand and `FileError::InvalidManifest` (with the 1-based line number) for a malformed file.
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Manifest,FileError> {
+        if !path.as_ref().exists() {
+            return Err(FileError::FileNotFound)
+        }
+        let text = fs::read_to_string(path).map_err(|e| FileError::Io(e.kind()))?;
+        return Manifest::parse(&text)
+    }
+    /// ## Verify
+    ///
+    /// Hashes every file listed in the manifest inside `dir` with the manifest's configuration and returns the status of each, in the order of the manifest. Files in `dir` that are not listed are ignored.
+    pub fn verify<T: AsRef<Path>>(&self, dir: T) -> Vec<(PathBuf,ManifestStatus)> {
+        return self.verify_with_cache(dir.as_ref(), None)
+    }
+    /// ## Verify Cached
+    ///
+    /// Same as `verify()`, but reuses the digests in `cache` for files that have not changed since they were last hashed with the manifest's configuration. See [`HashCache`].
+    ///
+    /// A file that was modified is always hashed again, but a file altered without changing its size or modification time is reported as `ManifestStatus::Match` if it matched before. Use `verify()` when that matters.
+    pub fn verify_cached<T: AsRef<Path>>(&self, dir: T, cache: &mut HashCache) -> Vec<(PathBuf,ManifestStatus)> {
+        return self.verify_with_cache(dir.as_ref(), Some(cache))
+    }
+    fn verify_with_cache(&self, dir: &Path, mut cache: Option<&mut HashCache>) -> Vec<(PathBuf,ManifestStatus)> {
+        let context = self.config();
+        let mut window = Vec::new();
+
+        return self.entries.iter().map(|entry| {
+            let status = match context.hash_path_with_cache(&dir.join(&entry.path), cache.as_deref_mut(), &mut window) {
+                Ok((digests, _)) if entry.matches(&digests) => ManifestStatus::Match,
+                Ok(_) => ManifestStatus::Mismatch,
+                Err(FileError::FileNotFound) => ManifestStatus::Missing,
+                Err(e) => ManifestStatus::Error(e),
+            };
+            (entry.path.clone(), status)
+        }).collect()
+    }
+    fn to_text(&self) -> Result<String,FileError> {
+        let mut text = format!("{} {}\ncreated-by {}\n{} {}\nos {:?}\n", MAGIC, MANIFEST_FORMAT_VERSION, self.created_by, self.library_algorithm.tag(), self.blake2b_digest_size, self.os_algorithm);
+        text.push_str(&format!("hidden {}\n", self.include_hidden));
+        for entry in self.entries.iter() {
+            let modified = entry.modified.map_or(String::from("-"), |modified| modified.to_string());
+            text.push_str(&format!("{} {} {} {} {}\n", entry.size, modified, entry.blake2b, entry.os_digest, escape_path(&entry.path)?));
+        }
+        return Ok(text)
+    }
+    fn parse(text: &str) -> Result<Manifest,FileError> {
+        let mut lines = text.lines().enumerate().map(|(index, line)| (index + 1, line));
+
+        let format_version = match lines.next() {
+            Some((_, line)) => match line.strip_prefix(MAGIC).map(str::trim).map(str::parse::<u32>) {
+                Some(Ok(version)) => version,
+                _ => return Err(FileError::InvalidManifest(1)),
+            },
+            None => return Err(FileError::InvalidManifest(1)),
+        };
+        if format_version == 0 || format_version > MANIFEST_FORMAT_VERSION {
+            return Err(FileError::UnsupportedManifestVersion(format_version))
+        }
+
+        let created_by = header(lines.next(), "created-by ", 2)?.to_string();
+        let (library_algorithm, blake2b_digest_size) = match lines.next().and_then(|(_, line)| line.split_once(' ')) {
+            Some((name, size)) => match (LibAlgorithm::from_tag(name), size.parse::<usize>()) {
+                (Some(library), Ok(size)) if size > 0 && size <= library.max_digest_size() => (library, size),
+                _ => return Err(FileError::InvalidManifest(3)),
+            },
+            None => return Err(FileError::InvalidManifest(3)),
+        };
+        let os_algorithm = match header(lines.next(), "os ", 4)? {
+            "SHA1" => OsAlgorithm::SHA1,
+            "SHA224" => OsAlgorithm::SHA224,
+            "SHA256" => OsAlgorithm::SHA256,
+            "SHA512" => OsAlgorithm::SHA512,
+            "SHA512_256" => OsAlgorithm::SHA512_256,
+            #[cfg(feature = "insecure-md5")]
+            "MD5" => OsAlgorithm::MD5,
+            _ => return Err(FileError::InvalidManifest(4)),
+        };
+        let include_hidden = match format_version {
+            1 => true,
+            _ => match header(lines.next(), "hidden ", 5)? {
+                "true" => true,
+                "false" => false,
+                _ => return Err(FileError::InvalidManifest(5)),
+            },
+        };
+
+        let mut entries = Vec::new();
+        for (number, line) in lines {
+            let (size, rest) = line.split_once(' ').ok_or(FileError::InvalidManifest(number))?;
+            let (modified, rest) = match format_version {
+                1 | 2 => (None, rest),
+                _ => match rest.split_once(' ') {
+                    Some(("-", rest)) => (None, rest),
+                    Some((modified, rest)) => (Some(modified.parse::<u64>().map_err(|_| FileError::InvalidManifest(number))?), rest),
+                    None => return Err(FileError::InvalidManifest(number)),
+                },
+            };
+            let mut fields = rest.splitn(3, ' ');
+            let entry = match (fields.next(), fields.next(), fields.next()) {
+                (Some(blake2b), Some(os_digest), Some(path)) => {
+                    let size = size.parse::<u64>().map_err(|_| FileError::InvalidManifest(number))?;
+                    let path = unescape_path(path).ok_or(FileError::InvalidManifest(number))?;
+                    ManifestEntry { path, size, modified, blake2b: blake2b.to_string(), os_digest: os_digest.to_string() }
+                }
+                _ => return Err(FileError::InvalidManifest(number)),
+            };
+            entries.push(entry);
+        }
+
+        return Ok(Manifest {
+            format_version,
+            created_by,
+            library_algorithm,
+            blake2b_digest_size,
+            os_algorithm,
+            include_hidden,
+            entries,
+        })
+    }
+}
+
+impl ManifestEntry {
+    fn matches(&self, digests: &DigestPair) -> bool {
+        return Verification::new(VerifyPolicy::Both, digests, Some(&self.blake2b), Some(&self.os_digest)).passed
+    }
+}
+
+// Reads the `name value` header on line `number`
+fn header<'a>(line: Option<(usize, &'a str)>, name: &str, number: usize) -> Result<&'a str,FileError> {
+    match line {
+        Some((_, line)) => return line.strip_prefix(name).ok_or(FileError::InvalidManifest(number)),
+        None => return Err(FileError::InvalidManifest(number)),
+    }
+}
+
+// Joins the components with `/` and escapes backslashes and newlines. Paths that are not UTF-8 cannot be written.
+fn escape_path(path: &Path) -> Result<String,FileError> {
+    let mut escaped = String::new();
+    for component in path.components() {
+        let component = match component {
+            Component::Normal(name) => name.to_str().ok_or(FileError::Io(ErrorKind::InvalidData))?,
+            _ => return Err(FileError::Io(ErrorKind::InvalidData)),
+        };
+        if !escaped.is_empty() {
+            escaped.push('/');
+        }
+        escaped.push_str(&component.replace('\\', "\\\\").replace('\n', "\\n"));
+    }
+    return Ok(escaped)
+}
+
+fn unescape_path(escaped: &str) -> Option<PathBuf> {
+    let mut path = PathBuf::new();
+    for component in escaped.split('/') {
+        let mut name = String::new();
+        let mut characters = component.chars();
+        while let Some(c) = characters.next() {
+            match c {
+                '\\' => match characters.next() {
+                    Some('\\') => name.push('\\'),
+                    Some('n') => name.push('\n'),
+                    _ => return None,
+                },
+                c => name.push(c),
+            }
+        }
+        // Never let an entry point outside of the directory
+        if name.is_empty() || name == "." || name == ".." {
+            return None
+        }
+        path.push(name);
+    }
+    return Some(path)
+}
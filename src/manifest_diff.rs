@@ -0,0 +1,123 @@
+// Manifest Diff
+//
+// The files added, removed and modified between two manifests, such as yesterday's and today's snapshot of the same directory.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::{Manifest, ManifestEntry};
+
+/// # Manifest Diff
+///
+/// The differences between two manifests, created with `Manifest::diff()`. Every list is sorted by path.
+///
+/// Iterating over a `&ManifestDiff` yields a [`ManifestChange`] for every difference, sorted by path.
+#[derive(Debug,Clone,PartialEq,Eq,Hash,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ManifestDiff {
+    /// Entries only in the newer manifest
+    pub added: Vec<ManifestEntry>,
+    /// Entries only in the older manifest
+    pub removed: Vec<ManifestEntry>,
+    /// Entries in both manifests whose size or digests differ, as `(older, newer)`
+    pub modified: Vec<(ManifestEntry,ManifestEntry)>,
+    /// `true` if the manifests were created with a different library hash function, digest size or Operating System Hash Function. Their digests cannot be compared, so entries are only reported as modified if their size differs.
+    pub incompatible: bool,
+}
+
+/// # Manifest Change
+///
+/// A single difference in a [`ManifestDiff`].
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+pub enum ManifestChange<'a> {
+    Added(&'a ManifestEntry),
+    Removed(&'a ManifestEntry),
+    Modified(&'a ManifestEntry, &'a ManifestEntry),
+}
+
+impl<'a> ManifestChange<'a> {
+    /// ## Path
+    pub fn path(&self) -> &'a PathBuf {
+        match self {
+            ManifestChange::Added(entry) => return &entry.path,
+            ManifestChange::Removed(entry) => return &entry.path,
+            ManifestChange::Modified(_, newer) => return &newer.path,
+        }
+    }
+}
+
+impl ManifestDiff {
+    /// ## Is Empty
+    ///
+    /// Returns true if no file was added, removed or modified.
+    pub fn is_empty(&self) -> bool {
+        return self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+    /// ## Iter
+    ///
+    /// Every change, sorted by path.
+    pub fn iter(&self) -> std::vec::IntoIter<ManifestChange<'_>> {
+        let mut changes: Vec<ManifestChange<'_>> = self.added.iter().map(ManifestChange::Added)
+            .chain(self.removed.iter().map(ManifestChange::Removed))
+            .chain(self.modified.iter().map(|(older, newer)| ManifestChange::Modified(older, newer)))
+            .collect();
+        changes.sort_by(|a, b| a.path().cmp(b.path()));
+        return changes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ManifestDiff {
+    type Item = ManifestChange<'a>;
+    type IntoIter = std::vec::IntoIter<ManifestChange<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter()
+    }
+}
+
+impl Manifest {
+    /// ## Diff
+    ///
+    /// Compares this (older) manifest with `other` (newer) by path. An entry is modified if its size or either digest differs (digests are compared case-insensitively).
+    ///
+    /// If the manifests were created with different configurations, `incompatible` is set and only sizes are compared.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let yesterday = context.manifest_dir("tests/fixtures/dir_digest").unwrap();
+    /// let today = context.manifest_dir("tests/fixtures/dir_digest").unwrap();
+    /// assert!(yesterday.diff(&today).is_empty());
+    /// ```
+    pub fn diff(&self, other: &Manifest) -> ManifestDiff {
+        let incompatible = self.library_algorithm != other.library_algorithm || self.blake2b_digest_size != other.blake2b_digest_size || self.os_algorithm != other.os_algorithm;
+        let older: BTreeMap<&PathBuf, &ManifestEntry> = self.entries.iter().map(|entry| (&entry.path, entry)).collect();
+        let newer: BTreeMap<&PathBuf, &ManifestEntry> = other.entries.iter().map(|entry| (&entry.path, entry)).collect();
+
+        let mut diff = ManifestDiff { incompatible, ..ManifestDiff::default() };
+        for (path, entry) in older.iter() {
+            match newer.get(path) {
+                None => diff.removed.push((*entry).clone()),
+                Some(newer) if modified(entry, newer, incompatible) => diff.modified.push(((*entry).clone(), (*newer).clone())),
+                Some(_) => {}
+            }
+        }
+        for (path, entry) in newer.iter() {
+            if !older.contains_key(path) {
+                diff.added.push((*entry).clone());
+            }
+        }
+        return diff
+    }
+}
+
+fn modified(older: &ManifestEntry, newer: &ManifestEntry, sizes_only: bool) -> bool {
+    if older.size != newer.size {
+        return true
+    }
+    if sizes_only {
+        return false
+    }
+    return !older.blake2b.eq_ignore_ascii_case(&newer.blake2b) || !older.os_digest.eq_ignore_ascii_case(&newer.os_digest)
+}
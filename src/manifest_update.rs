@@ -0,0 +1,99 @@
+// Manifest Update
+//
+// Brings a manifest up to date with its directory without rehashing every file. Files whose size and modification time match the manifest keep their digests.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cache::modified;
+use crate::manifest::MANIFEST_FORMAT_VERSION;
+use crate::{walk, FileError, Manifest, ManifestEntry};
+
+/// # Update Options
+///
+/// How `Manifest::update()` decides which files to hash again.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Default)]
+pub struct UpdateOptions {
+    /// Hash every file again, even if its size and modification time match the manifest
+    pub paranoid: bool,
+}
+
+/// # Update Summary
+///
+/// The number of entries `Manifest::update()` kept, hashed again, added and removed.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UpdateSummary {
+    /// Files whose size and modification time matched, so their digests were kept
+    pub reused: usize,
+    /// Files already in the manifest that were hashed again
+    pub rehashed: usize,
+    /// Files that were not in the manifest
+    pub added: usize,
+    /// Entries whose file no longer exists
+    pub removed: usize,
+}
+
+impl Manifest {
+    /// ## Update
+    ///
+    /// Walks `dir` with the manifest's configuration and updates the entries to match it:
+    ///
+    /// * Files whose size and modification time (to the nanosecond) match their entry keep it without being read
+    /// * Files that look changed, or whose entry has no modification time (such as from a format version 1 or 2 manifest), are hashed again
+    /// * New files are hashed and added, and entries of files that no longer exist are removed
+    ///
+    /// With `UpdateOptions::paranoid` every file is hashed again. Fails on the first file that cannot be hashed and leaves the manifest unchanged, so it is never incomplete. On success the manifest is marked as written by this version of the crate.
+    ///
+    /// **Warning:** A file changed without changing its size or modification time (such as by a tool that restores the modification time, or on a filesystem with coarse timestamps) keeps its old digests. Use `UpdateOptions::paranoid` or `verify()` when that matters.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,UpdateOptions};
+    ///
+    /// let mut manifest = ParanoidHash::default().manifest_dir("tests/fixtures/dir_digest").unwrap();
+    /// let summary = manifest.update("tests/fixtures/dir_digest",UpdateOptions::default()).unwrap();
+    /// assert_eq!(summary.reused,manifest.entries.len());
+    /// ```
+    pub fn update<T: AsRef<Path>>(&mut self, dir: T, options: UpdateOptions) -> Result<UpdateSummary,FileError> {
+        let dir = dir.as_ref();
+        let context = self.config();
+        let mut previous: BTreeMap<&PathBuf, &ManifestEntry> = self.entries.iter().map(|entry| (&entry.path, entry)).collect();
+        let mut summary = UpdateSummary::default();
+        let mut entries = Vec::new();
+        let mut window = Vec::new();
+
+        for file in walk::walk_files(dir, &context.walk_options)? {
+            let relative = file.strip_prefix(dir).expect("[Error] Walked file is outside of the directory").to_path_buf();
+            match previous.remove(&relative) {
+                Some(entry) if !options.paranoid && unchanged(&file, entry) => {
+                    summary.reused += 1;
+                    entries.push(entry.clone());
+                }
+                Some(_) => {
+                    summary.rehashed += 1;
+                    entries.push(context.manifest_entry(&file, relative, None, &mut window)?);
+                }
+                None => {
+                    summary.added += 1;
+                    entries.push(context.manifest_entry(&file, relative, None, &mut window)?);
+                }
+            }
+        }
+        summary.removed = previous.len();
+
+        self.format_version = MANIFEST_FORMAT_VERSION;
+        self.created_by = env!("CARGO_PKG_VERSION").to_string();
+        self.entries = entries;
+        return Ok(summary)
+    }
+}
+
+// Whether the size and modification time of `file` match its entry. An entry without a modification time never matches.
+fn unchanged(file: &Path, entry: &ManifestEntry) -> bool {
+    let metadata = match fs::metadata(file) {
+        Ok(metadata) => metadata,
+        Err(_) => return false,
+    };
+    return entry.modified.is_some() && metadata.len() == entry.size && modified(&metadata) == entry.modified
+}
@@ -0,0 +1,238 @@
+// Merkle Tree
+//
+// A Merkle tree over the files of a directory, so a single root can be published and any one file later proven to be part of it with a short list of sibling digests instead of the whole tree.
+
+use std::path::{Component, Path};
+
+use crate::encoding::decode_expected;
+use crate::hashers::{Digester, DigestPair};
+use crate::{walk, Algo, FileError, ParanoidHash};
+
+// Prefixes that keep leaves and internal nodes from ever hashing the same input
+const LEAF: u8 = 0x00;
+const NODE: u8 = 0x01;
+
+// The leaf digests of one tree, in order
+type Leaves = Vec<Vec<u8>>;
+
+/// # Merkle Proof
+///
+/// Everything needed to check that one file is part of a Merkle root, returned by `merkle_proof()` and checked with `verify_merkle_proof()`. All digests are in the Output Encoding of the context that created the proof.
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MerkleProof {
+    /// The path of the file relative to the directory, with components joined by `/`
+    pub name: String,
+    /// The position of the file among the leaves, starting from 0
+    pub index: usize,
+    /// The number of files in the tree
+    pub leaf_count: usize,
+    /// The BLAKE2B and Operating System digests of the contents of the file
+    pub digests: (String,String),
+    /// The sibling digests of the BLAKE2B tree, from the leaf up to the root
+    pub blake2b_path: Vec<String>,
+    /// The sibling digests of the Operating System tree, from the leaf up to the root
+    pub os_path: Vec<String>,
+}
+
+impl ParanoidHash {
+    /// # Merkle Root
+    ///
+    /// Hashes every regular file below `dir` and returns the roots of two Merkle trees over them, one built with BLAKE2B and one with the Operating System Hash Function. The trees are computed independently and never mix the two hash functions.
+    ///
+    /// ## Leaves
+    ///
+    /// The files are named and ordered as in `read_dir()`: the path relative to `dir` with components joined by `/`, sorted by its UTF-8 bytes. The leaf of a file is
+    ///
+    /// ```text
+    /// H(0x00 | u64 big-endian length of the name | name (UTF-8) | digest of the contents)
+    /// ```
+    ///
+    /// where `H` is the hash function of the tree and the digest of the contents is computed with the same hash function, as by `read()`.
+    ///
+    /// ## Internal Nodes
+    ///
+    /// The tree has the shape of RFC 6962 (Certificate Transparency): the root of `n > 1` leaves is
+    ///
+    /// ```text
+    /// H(0x01 | root of the first k leaves | root of the remaining n - k leaves)
+    /// ```
+    ///
+    /// where `k` is the largest power of two smaller than `n`. The root of a single leaf is the leaf itself, and a directory without files has the digests of empty input (or `FileError::EmptyFile` with `reject_empty_input()`). The `0x00` and `0x01` prefixes prevent a leaf from being passed off as an internal node, or the other way around.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let (blake2b_root,sha512_root) = context.merkle_root("tests/fixtures/dir_digest").unwrap();
+    ///
+    /// let proof = context.merkle_proof("tests/fixtures/dir_digest","a/b.txt").unwrap();
+    /// assert!(context.verify_merkle_proof(&proof,&blake2b_root,&sha512_root).unwrap());
+    /// ```
+    pub fn merkle_root<T: AsRef<Path>>(&self, dir: T) -> Result<(String,String),FileError> {
+        let (library, os) = self.leaves_of(&self.merkle_entries(dir.as_ref())?)?;
+        self.check_empty(library.len() as u64)?;
+
+        let library_root = Tree::library(self).root(&library)?;
+        let os_root = Tree::os(self).root(&os)?;
+        return Ok(self.encode_pair((library_root, os_root)))
+    }
+    /// # Merkle Proof
+    ///
+    /// Returns the proof that the file at `rel_path` (relative to `dir`) is part of the Merkle root of `dir`: its digests, its position and the sibling digests of both trees (the audit path of RFC 6962).
+    ///
+    /// Returns `FileError::FileNotFound` if `rel_path` is not one of the files that `merkle_root()` covers.
+    pub fn merkle_proof<T: AsRef<Path>, P: AsRef<Path>>(&self, dir: T, rel_path: P) -> Result<MerkleProof,FileError> {
+        let name = relative_name(rel_path.as_ref()).ok_or(FileError::FileNotFound)?;
+        let entries = self.merkle_entries(dir.as_ref())?;
+        let index = entries.iter().position(|(entry, _)| *entry == name).ok_or(FileError::FileNotFound)?;
+
+        let (library, os) = self.leaves_of(&entries)?;
+        let blake2b_path = Tree::library(self).path(index, &library)?;
+        let os_path = Tree::os(self).path(index, &os)?;
+
+        return Ok(MerkleProof {
+            name,
+            index,
+            leaf_count: entries.len(),
+            digests: self.encode_pair(entries[index].1.clone()),
+            blake2b_path: blake2b_path.into_iter().map(|digest| self.encoding.encode(digest)).collect(),
+            os_path: os_path.into_iter().map(|digest| self.encoding.encode(digest)).collect(),
+        })
+    }
+    /// # Verify Merkle Proof
+    ///
+    /// Checks a proof against the roots from `merkle_root()` without access to the directory. Returns `true` if both trees lead to their root, `false` if neither does and `FileError::DigestDisagreement` if only one does, which means one of the hash functions is broken or the proof has been tampered with.
+    ///
+    /// The proof only shows that a file with `proof.digests` is in the tree; compare those with the digests of the file itself (such as from `read()`) to check the contents. Digests in the proof and the roots are accepted in any `OutputEncoding`. Returns `FileError::InvalidDigest` if one of them cannot be decoded to the length of its hash function.
+    pub fn verify_merkle_proof<A: AsRef<str>, B: AsRef<str>>(&self, proof: &MerkleProof, blake2b_root: A, os_root: B) -> Result<bool,FileError> {
+        let (library, os) = (Tree::library(self), Tree::os(self));
+        let library_matches = library.verify(proof, &proof.digests.0, &proof.blake2b_path, blake2b_root.as_ref())?;
+        let os_matches = os.verify(proof, &proof.digests.1, &proof.os_path, os_root.as_ref())?;
+
+        if library_matches != os_matches {
+            return Err(FileError::DigestDisagreement)
+        }
+        return Ok(library_matches)
+    }
+    // The name and digests of every file, in the order of the leaves
+    fn merkle_entries(&self, dir: &Path) -> Result<Vec<(String,DigestPair)>,FileError> {
+        let mut entries = Vec::new();
+        for (name, path) in walk::walk_entries(dir, &self.walk_options)? {
+            let (digests, _) = self.hash_path_raw(&path, None).map_err(|e| FileError::AtPath(path.clone(), Box::new(e)))?;
+            entries.push((name, digests));
+        }
+        return Ok(entries)
+    }
+    // The leaves of the BLAKE2B and Operating System trees
+    fn leaves_of(&self, entries: &[(String,DigestPair)]) -> Result<(Leaves,Leaves),FileError> {
+        let (library, os) = (Tree::library(self), Tree::os(self));
+        let mut library_leaves = Vec::with_capacity(entries.len());
+        let mut os_leaves = Vec::with_capacity(entries.len());
+        for (name, (library_digest, os_digest)) in entries {
+            library_leaves.push(library.leaf(name, library_digest)?);
+            os_leaves.push(os.leaf(name, os_digest)?);
+        }
+        return Ok((library_leaves, os_leaves))
+    }
+}
+
+// One of the two trees
+struct Tree<'a> {
+    context: &'a ParanoidHash,
+    algo: Algo,
+    digest_len: usize,
+}
+
+impl<'a> Tree<'a> {
+    fn library(context: &'a ParanoidHash) -> Self {
+        return Tree { context, algo: context.library_algo(), digest_len: context.digest_size }
+    }
+    fn os(context: &'a ParanoidHash) -> Self {
+        return Tree { context, algo: Algo::Os(context.os_hash_function.clone()), digest_len: context.os_hash_function.digest_len() }
+    }
+    fn hash(&self, parts: &[&[u8]]) -> Result<Vec<u8>,FileError> {
+        let mut digester = Digester::with_options(&self.algo, self.context.os_options, self.context.thread_count())?;
+        for part in parts {
+            digester.update(part)?;
+        }
+        return Ok(digester.finalize()?.0)
+    }
+    fn leaf(&self, name: &str, digest: &[u8]) -> Result<Vec<u8>,FileError> {
+        return self.hash(&[&[LEAF], &(name.len() as u64).to_be_bytes(), name.as_bytes(), digest])
+    }
+    fn node(&self, left: &[u8], right: &[u8]) -> Result<Vec<u8>,FileError> {
+        return self.hash(&[&[NODE], left, right])
+    }
+    fn root(&self, leaves: &[Vec<u8>]) -> Result<Vec<u8>,FileError> {
+        match leaves.len() {
+            0 => return self.hash(&[]),
+            1 => return Ok(leaves[0].clone()),
+            n => {
+                let k = split(n);
+                return self.node(&self.root(&leaves[..k])?, &self.root(&leaves[k..])?)
+            }
+        }
+    }
+    // The audit path of the leaf at `index`, from the leaf up
+    fn path(&self, index: usize, leaves: &[Vec<u8>]) -> Result<Vec<Vec<u8>>,FileError> {
+        if leaves.len() <= 1 {
+            return Ok(Vec::new())
+        }
+        let k = split(leaves.len());
+        let (mut path, sibling) = match index < k {
+            true => (self.path(index, &leaves[..k])?, self.root(&leaves[k..])?),
+            false => (self.path(index - k, &leaves[k..])?, self.root(&leaves[..k])?),
+        };
+        path.push(sibling);
+        return Ok(path)
+    }
+    // Follows the audit path from the leaf to a root as in RFC 9162 (section 2.1.3.2) and compares it with `expected_root`
+    fn verify(&self, proof: &MerkleProof, digest: &str, path: &[String], expected_root: &str) -> Result<bool,FileError> {
+        let decode = |digest: &str| decode_expected(digest, self.digest_len).ok_or(FileError::InvalidDigest);
+        let expected_root = decode(expected_root)?;
+        let path = path.iter().map(|sibling| decode(sibling)).collect::<Result<Vec<_>,_>>()?;
+        let mut root = self.leaf(&proof.name, &decode(digest)?)?;
+        if proof.index >= proof.leaf_count {
+            return Ok(false)
+        }
+
+        let (mut index, mut last) = (proof.index, proof.leaf_count - 1);
+        for sibling in path.iter() {
+            if last == 0 {
+                return Ok(false)
+            }
+            if index & 1 == 1 || index == last {
+                root = self.node(sibling, &root)?;
+                while index & 1 == 0 && index != 0 {
+                    index >>= 1;
+                    last >>= 1;
+                }
+            }
+            else {
+                root = self.node(&root, sibling)?;
+            }
+            index >>= 1;
+            last >>= 1;
+        }
+        return Ok(last == 0 && crate::digest::constant_time_eq(&root, &expected_root))
+    }
+}
+
+// The largest power of two smaller than `n` (for `n > 1`)
+fn split(n: usize) -> usize {
+    return 1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+// The `/` separated name of a relative path, or `None` for a path that cannot name a file below the directory
+fn relative_name(path: &Path) -> Option<String> {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_str()?),
+            Component::CurDir => continue,
+            _ => return None,
+        }
+    }
+    return Some(components.join("/"))
+}
@@ -0,0 +1,164 @@
+// Modification Check
+//
+// A file that is written while it is hashed produces digests of no version of the file that ever existed. The size and modification time are recorded before hashing and compared afterwards.
+
+use std::fs::Metadata;
+use std::io::{self, Read};
+use std::thread;
+use std::time::Duration;
+
+use crate::{FileError, ParanoidHash};
+
+/// # Modification Check
+///
+/// What happens when a file's size or modification time changes while it is being hashed, set with `ParanoidHash::modification_check()`.
+///
+/// * `Off` does not look (the default)
+/// * `Error` returns `FileError::ModifiedDuringHash` and discards the digests
+/// * `Flag` keeps the digests and sets `HashStats::modified`, for the functions that return `HashStats` (such as `read_with_stats()`). Functions without stats return the digests as usual.
+///
+/// Only regular files are checked. A change that keeps both the size and the modification time (such as an overwrite within the timestamp resolution of the filesystem) is not detected.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash,Default)]
+pub enum ModificationCheck {
+    #[default]
+    Off,
+    Error,
+    Flag,
+}
+
+impl ParanoidHash {
+    // Compares the metadata recorded before hashing with `after`, which is only read if the check is enabled. Returns whether the file changed in `Flag` mode.
+    pub(crate) fn check_modified<F: FnOnce() -> io::Result<Metadata>>(&self, before: &Metadata, after: F) -> Result<bool,FileError> {
+        if self.modification_check == ModificationCheck::Off || !before.is_file() {
+            return Ok(false)
+        }
+        let after = after().map_err(|e| FileError::Io(e.kind()))?;
+        let modified = after.len() != before.len() || after.modified().ok() != before.modified().ok();
+        if modified && self.modification_check == ModificationCheck::Error {
+            return Err(FileError::ModifiedDuringHash)
+        }
+        return Ok(modified)
+    }
+}
+
+// Sleeps before every read when `pause` is set, so a test can change a file in the middle of hashing it
+pub(crate) struct PausingReader<R> {
+    inner: R,
+    pause: Option<Duration>,
+}
+
+impl<R: Read> PausingReader<R> {
+    pub(crate) fn new(inner: R, pause: Option<Duration>) -> Self {
+        return PausingReader { inner, pause }
+    }
+}
+
+impl<R: Read> Read for PausingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(pause) = self.pause {
+            thread::sleep(pause);
+        }
+        return self.inner.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::ModificationCheck;
+    use crate::{FileError, ParanoidHash};
+
+    // 16 windows of 64 KiB, each read after a pause of 20 milliseconds
+    const SIZE: usize = 1024 * 1024;
+    const WINDOW: usize = 64 * 1024;
+    const PAUSE: Option<Duration> = Some(Duration::from_millis(20));
+
+    fn write_file(path: &Path) {
+        fs::write(path, vec![0x5A; SIZE]).unwrap();
+    }
+
+    // Appends to the file from another thread while it is being hashed, about a third of the way through
+    fn hash_while_appending<T, F: FnOnce(&Path) -> T>(path: &Path, hash: F) -> T {
+        let writer_path = path.to_path_buf();
+        let writer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            let mut file = fs::OpenOptions::new().append(true).open(writer_path).unwrap();
+            file.write_all(b"appended").unwrap();
+        });
+        let result = hash(path);
+        writer.join().unwrap();
+        return result
+    }
+
+    fn context(check: ModificationCheck) -> ParanoidHash {
+        return ParanoidHash::default().window_size(WINDOW).simulate_slow_read(PAUSE).modification_check(check)
+    }
+
+    fn temp_file(dir: &tempfile::TempDir) -> PathBuf {
+        let path = dir.path().join("growing.bin");
+        write_file(&path);
+        return path
+    }
+
+    #[test]
+    fn error_when_appended(){
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(&dir);
+        let result = hash_while_appending(&path, |path| context(ModificationCheck::Error).read(path));
+        assert_eq!(result,Err(FileError::ModifiedDuringHash));
+    }
+
+    #[test]
+    fn flag_keeps_the_digests(){
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(&dir);
+        let (digests,stats) = hash_while_appending(&path, |path| context(ModificationCheck::Flag).read_with_stats(path)).unwrap();
+        assert!(stats.modified);
+        // The digests are of the bytes that were read, including the ones appended before the end was reached
+        let mut contents = vec![0x5A; SIZE];
+        contents.extend_from_slice(b"appended");
+        assert_eq!(stats.bytes,contents.len() as u64);
+        assert_eq!(digests,ParanoidHash::default().read_bytes(&contents));
+    }
+
+    #[test]
+    fn off_by_default(){
+        assert_eq!(ParanoidHash::default().return_modification_check(),ModificationCheck::Off);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(&dir);
+        let (_,stats) = hash_while_appending(&path, |path| ParanoidHash::default().window_size(WINDOW).simulate_slow_read(PAUSE).read_with_stats(path)).unwrap();
+        assert!(!stats.modified);
+    }
+
+    #[test]
+    fn streamed_with_std(){
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(&dir);
+        let result = hash_while_appending(&path, |path| context(ModificationCheck::Error).read_using_std(path));
+        assert_eq!(result,Err(FileError::ModifiedDuringHash));
+    }
+
+    #[test]
+    fn each_file_of_a_directory(){
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("steady.txt"),"steady").unwrap();
+        let path = temp_file(&dir);
+        let result = hash_while_appending(&path, |_| context(ModificationCheck::Error).read_dir(dir.path()));
+        assert_eq!(result,Err(FileError::AtPath(path,Box::new(FileError::ModifiedDuringHash))));
+    }
+
+    #[test]
+    fn unmodified_file_passes(){
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(&dir);
+        let expected = ParanoidHash::default().read(&path).unwrap();
+        assert_eq!(context(ModificationCheck::Error).read(&path).unwrap(),expected);
+        assert_eq!(context(ModificationCheck::Error).read_using_std(&path).unwrap(),expected);
+    }
+}
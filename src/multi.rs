@@ -0,0 +1,185 @@
+//! # Multi-Algorithm Hashing
+//!
+//! [`ParanoidHashMulti`] computes any set of digests in a **single pass** over the data, so a file is only read once no matter how many hash functions are requested.
+//!
+//! ```rust
+//! use paranoid_hash::{Algo,OsAlgorithm,ParanoidHashMulti};
+//!
+//! let context = ParanoidHashMulti::new(&[Algo::Blake2b(64),Algo::Os(OsAlgorithm::SHA256),Algo::Os(OsAlgorithm::SHA512)]);
+//!
+//! let digests = context.read_bytes(b"Hello World");
+//! assert_eq!(digests.len(),3);
+//! ```
+
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+
+use filebuffer::FileBuffer;
+
+use crate::hashers::{Digests, MultiHasher, CHUNK_SIZE};
+use crate::parallel::available_threads;
+use crate::{long_path, FileError, LibAlgorithm, OsAlgorithm};
+
+/// # Hash Algorithm
+///
+/// Any hash function supported by the crate. `Blake2b` and `Blake2bp` carry their digest size in bytes `[1-64]`, `Blake2sp` carries its digest size in bytes `[1-32]` and `Shake256` carries its digest size in bytes `[1-1024]`.
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
+pub enum Algo {
+    Blake2b(usize),
+    #[cfg(feature = "blake2-parallel")]
+    Blake2bp(usize),
+    #[cfg(feature = "blake2-parallel")]
+    Blake2sp(usize),
+    #[cfg(feature = "xof")]
+    Shake256(usize),
+    Os(OsAlgorithm),
+}
+
+impl Algo {
+    // The library hash function and its digest size, or `None` for an Operating System Hash Function
+    pub(crate) fn library(&self) -> Option<(LibAlgorithm, usize)> {
+        match self {
+            Algo::Blake2b(size) => return Some((LibAlgorithm::BLAKE2B, *size)),
+            #[cfg(feature = "blake2-parallel")]
+            Algo::Blake2bp(size) => return Some((LibAlgorithm::BLAKE2BP, *size)),
+            #[cfg(feature = "blake2-parallel")]
+            Algo::Blake2sp(size) => return Some((LibAlgorithm::BLAKE2SP, *size)),
+            #[cfg(feature = "xof")]
+            Algo::Shake256(size) => return Some((LibAlgorithm::SHAKE256, *size)),
+            Algo::Os(_) => return None,
+        }
+    }
+}
+
+impl From<OsAlgorithm> for Algo {
+    fn from(os_hash: OsAlgorithm) -> Self {
+        return Algo::Os(os_hash)
+    }
+}
+
+// Common BLAKE2B digest sizes in bytes
+const COMMON_BLAKE2B_SIZES: [usize; 4] = [20, 32, 48, 64];
+
+/// # Detect Algorithm
+///
+/// Returns every hash function that produces hexadecimal digests of the same length as `digest`, Operating System Hash Functions first. A length can be ambiguous (64 characters is SHA256, SHA512_256 and BLAKE2B with a 32 byte digest), so there may be more than one candidate.
+///
+/// BLAKE2B is only considered at its common digest sizes: 20, 32, 48 and 64 bytes.
+///
+/// Returns an empty list if `digest` is not valid hexadecimal or no hash function matches its length.
+///
+/// ```rust
+/// use paranoid_hash::{detect_algorithm,Algo,OsAlgorithm};
+///
+/// assert_eq!(detect_algorithm("A9993E364706816ABA3E25717850C26C9CD0D89D"),vec![Algo::Os(OsAlgorithm::SHA1),Algo::Blake2b(20)]);
+/// assert!(detect_algorithm("not hex").is_empty());
+/// ```
+pub fn detect_algorithm(digest: &str) -> Vec<Algo> {
+    let mut candidates = Vec::new();
+    if !crate::is_hex(digest) {
+        return candidates
+    }
+    if let Some(os_hash) = OsAlgorithm::from_hex_digest(digest) {
+        // SHA512_256 has the same length as SHA256
+        let truncated = os_hash == OsAlgorithm::SHA256;
+        candidates.push(Algo::Os(os_hash));
+        if truncated {
+            candidates.push(Algo::Os(OsAlgorithm::SHA512_256));
+        }
+    }
+    let size = digest.len() / 2;
+    if COMMON_BLAKE2B_SIZES.contains(&size) {
+        candidates.push(Algo::Blake2b(size));
+    }
+    return candidates
+}
+
+/// # Multi-Algorithm Hashing Constructor
+///
+/// Holds an ordered list of hash functions. Results are always returned in the same order as the list.
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
+pub struct ParanoidHashMulti {
+    algorithms: Vec<Algo>,
+}
+
+impl ParanoidHashMulti {
+    /// # New Multi-Algorithm Hasher
+    ///
+    /// Duplicate entries are removed, keeping the first occurence. Panics if the list is empty or a library digest size is not between 1 and 64 (1 and 32 for `Blake2sp`, 1 and 1024 for `Shake256`).
+    pub fn new(algorithms: &[Algo]) -> Self {
+        let mut deduplicated: Vec<Algo> = Vec::with_capacity(algorithms.len());
+
+        for algo in algorithms {
+            if let Some((library, digest)) = algo.library() {
+                if digest == 0 || digest > library.max_digest_size() {
+                    panic!("[Error] Digest Size is either too large or too small. It should be 1-{}.", library.max_digest_size())
+                }
+            }
+            if !deduplicated.contains(algo) {
+                deduplicated.push(algo.clone());
+            }
+        }
+        if deduplicated.is_empty() {
+            panic!("[Error] At least one hash function is required.")
+        }
+
+        return ParanoidHashMulti {
+            algorithms: deduplicated,
+        }
+    }
+    /// ## Return Algorithms
+    ///
+    /// Returns the (deduplicated) hash functions in the order their digests are returned.
+    pub fn return_algorithms(&self) -> &[Algo] {
+        return &self.algorithms
+    }
+    /// # Read
+    ///
+    /// Reads the file once and returns every digest as an upper hexadecimal string. Returns `FileError::Io` if the file cannot be opened or read.
+    pub fn read<T: AsRef<Path>>(&self, path: T) -> Result<Vec<(Algo,String)>,FileError> {
+        return Ok(encode_upper(self.read_raw(path.as_ref())?))
+    }
+    // Same as `read()` but returns the digests as bytes
+    pub(crate) fn read_raw(&self, path: &Path) -> Result<Digests,FileError> {
+        let path: &Path = &long_path::extended(path);
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+
+        // Only regular files are memory mapped. Special files (such as FIFOs and devices) are streamed instead, so they are opened only once. An error opening the file is returned as `FileError::Io`.
+        let metadata = fs::metadata(path).map_err(|e| FileError::Io(e.kind()))?;
+        if metadata.is_file() {
+            if let Ok(fbuffer) = FileBuffer::open(path) {
+                return self.hash_raw(&fbuffer)
+            }
+        }
+        let file = File::open(path).map_err(|e| FileError::Io(e.kind()))?;
+        let mut hasher = MultiHasher::new(&self.algorithms, available_threads())?;
+        hasher.update_reader_checked(BufReader::with_capacity(CHUNK_SIZE, file), |_| Ok(()))?;
+        return hasher.finalize()
+    }
+    /// # Read Bytes
+    ///
+    /// Hashes the bytes with every hash function and returns the digests as upper hexadecimal strings.
+    ///
+    /// Panics if an Operating System Hash Function fails. Use `try_read_bytes()` to get `FileError::OsHashingError` instead.
+    pub fn read_bytes(&self, bytes: &[u8]) -> Vec<(Algo,String)> {
+        return self.try_read_bytes(bytes).expect("[Error] Failed To Hash Using Operating System Hash Function")
+    }
+    /// # Try Read Bytes
+    ///
+    /// Same as `read_bytes()`, but returns `FileError::OsHashingError` if an Operating System Hash Function fails.
+    pub fn try_read_bytes(&self, bytes: &[u8]) -> Result<Vec<(Algo,String)>,FileError> {
+        return Ok(encode_upper(self.hash_raw(bytes)?))
+    }
+    fn hash_raw(&self, bytes: &[u8]) -> Result<Digests,FileError> {
+        let mut hasher = MultiHasher::new(&self.algorithms, available_threads())?;
+        hasher.update(bytes)?;
+        return hasher.finalize()
+    }
+}
+
+fn encode_upper(digests: Digests) -> Vec<(Algo,String)> {
+    return digests.into_iter().map(|(algo, digest)| (algo, hex::encode_upper(digest))).collect()
+}
@@ -0,0 +1,187 @@
+// Multihash
+//
+// Self-describing digests from the multiformats project (https://github.com/multiformats/multihash): an unsigned varint code for the hash function, a varint digest length and the digest bytes.
+
+use std::fmt;
+
+use crate::{Algo, HashDigest, OsAlgorithm, OutputEncoding};
+
+// Registered codes from the multicodec table
+const CODE_SHA1: u64 = 0x11;
+const CODE_SHA2_256: u64 = 0x12;
+const CODE_SHA2_512: u64 = 0x13;
+const CODE_SHA2_512_256: u64 = 0x1015;
+const CODE_SHA2_224: u64 = 0x1013;
+#[cfg(feature = "insecure-md5")]
+const CODE_MD5: u64 = 0xd5;
+// `blake2b-8` to `blake2b-512` are `0xb201` to `0xb240`, one code for each digest size in bytes
+const CODE_BLAKE2B: u64 = 0xb200;
+#[cfg(feature = "xof")]
+const CODE_SHAKE256: u64 = 0x19;
+
+// Multibase prefix for lower case Base32 without padding
+const MULTIBASE_BASE32: char = 'b';
+
+/// # Multihash Error
+///
+/// Returned when multihash bytes or a multibase string cannot be decoded.
+#[derive(Debug,Clone,PartialEq,Eq,PartialOrd,Hash)]
+pub enum MultihashError {
+    /// The code is not a hash function supported by this crate
+    UnknownCode(u64),
+    /// A varint is truncated or longer than 9 bytes
+    InvalidVarint,
+    /// The length does not match the hash function or the number of digest bytes
+    InvalidLength,
+    /// The hash function has no registered code (BLAKE2BP and BLAKE2SP)
+    UnsupportedAlgorithm,
+    /// The multibase prefix is not Base32 (`b` or `B`)
+    UnsupportedMultibase(char),
+    /// The multibase string is not valid Base32
+    InvalidEncoding,
+}
+
+impl fmt::Display for MultihashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultihashError::UnknownCode(code) => write!(f, "unknown multihash code 0x{:x}", code),
+            MultihashError::InvalidVarint => write!(f, "invalid multihash varint"),
+            MultihashError::InvalidLength => write!(f, "multihash length does not match the digest"),
+            MultihashError::UnsupportedAlgorithm => write!(f, "hash function has no multihash code"),
+            MultihashError::UnsupportedMultibase(prefix) => write!(f, "unsupported multibase prefix {:?}", prefix),
+            MultihashError::InvalidEncoding => write!(f, "invalid multibase encoding"),
+        }
+    }
+}
+
+impl std::error::Error for MultihashError {}
+
+impl HashDigest {
+    /// ## To Multihash
+    ///
+    /// Encodes the digest as multihash bytes: the varint code of the hash function, the varint digest length and the digest.
+    ///
+    /// | Hash Function | Code |
+    /// | ------------- | ---- |
+    /// | BLAKE2B (`n` bytes) | `0xb200 + n` (`blake2b-8` to `blake2b-512`) |
+    /// | SHA224 | `0x1013` (`sha2-224`) |
+    /// | SHA256 | `0x12` (`sha2-256`) |
+    /// | SHA512 | `0x13` (`sha2-512`) |
+    /// | SHA512_256 | `0x1015` (`sha2-512-256`) |
+    /// | SHA1 | `0x11` (`sha1`) |
+    /// | MD5 (with the `insecure-md5` feature) | `0xd5` (`md5`) |
+    ///
+    /// **Deprecated algorithms:** SHA1 and MD5 have registered codes so they can be exchanged, but both are broken for collision resistance and should not be used for new content addresses.
+    ///
+    /// Returns `MultihashError::UnsupportedAlgorithm` for BLAKE2BP and BLAKE2SP, which have no registered code.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{OsAlgorithm,ParanoidHash};
+    ///
+    /// let (_,sha256) = ParanoidHash::new(64,OsAlgorithm::SHA256).read_bytes_digests(b"abc").unwrap();
+    /// assert_eq!(&sha256.to_multihash().unwrap()[..2],&[0x12,0x20]);
+    /// ```
+    pub fn to_multihash(&self) -> Result<Vec<u8>,MultihashError> {
+        let code = code(self.algorithm()).ok_or(MultihashError::UnsupportedAlgorithm)?;
+        let mut multihash = Vec::with_capacity(self.as_bytes().len() + 4);
+        write_varint(code, &mut multihash);
+        write_varint(self.as_bytes().len() as u64, &mut multihash);
+        multihash.extend_from_slice(self.as_bytes());
+        return Ok(multihash)
+    }
+    /// ## To Multibase
+    ///
+    /// The multihash bytes as a multibase string, using lower case Base32 without padding (prefix `b`).
+    pub fn to_multibase(&self) -> Result<String,MultihashError> {
+        let encoded = OutputEncoding::Base32.encode(self.to_multihash()?);
+        let mut multibase = String::with_capacity(encoded.len() + 1);
+        multibase.push(MULTIBASE_BASE32);
+        multibase.push_str(&encoded.trim_end_matches('=').to_ascii_lowercase());
+        return Ok(multibase)
+    }
+    /// ## From Multihash
+    ///
+    /// Decodes multihash bytes into the digest and the hash function it came from, so it can be compared against the output of a `ParanoidHash` with the same function.
+    ///
+    /// Returns `MultihashError::UnknownCode` for hash functions this crate cannot compute.
+    pub fn from_multihash(bytes: &[u8]) -> Result<HashDigest,MultihashError> {
+        let (code, rest) = read_varint(bytes)?;
+        let (length, digest) = read_varint(rest)?;
+        let algorithm = algorithm(code, length)?;
+
+        if length != digest.len() as u64 {
+            return Err(MultihashError::InvalidLength)
+        }
+        return HashDigest::new(algorithm, digest.to_vec()).map_err(|_| MultihashError::InvalidLength)
+    }
+    /// ## From Multibase
+    ///
+    /// Decodes a multibase string in Base32 (prefix `b` for lower case or `B` for upper case) holding multihash bytes.
+    pub fn from_multibase<T: AsRef<str>>(s: T) -> Result<HashDigest,MultihashError> {
+        let s = s.as_ref();
+        let mut characters = s.chars();
+        match characters.next() {
+            Some('b') | Some('B') => {}
+            Some(prefix) => return Err(MultihashError::UnsupportedMultibase(prefix)),
+            None => return Err(MultihashError::InvalidEncoding),
+        }
+        let bytes = OutputEncoding::Base32.decode(characters.as_str()).ok_or(MultihashError::InvalidEncoding)?;
+        return HashDigest::from_multihash(&bytes)
+    }
+}
+
+fn code(algorithm: &Algo) -> Option<u64> {
+    match algorithm {
+        Algo::Blake2b(size) => return Some(CODE_BLAKE2B + *size as u64),
+        #[cfg(feature = "blake2-parallel")]
+        Algo::Blake2bp(_) | Algo::Blake2sp(_) => return None,
+        #[cfg(feature = "xof")]
+        Algo::Shake256(_) => return Some(CODE_SHAKE256),
+        Algo::Os(OsAlgorithm::SHA1) => return Some(CODE_SHA1),
+        Algo::Os(OsAlgorithm::SHA224) => return Some(CODE_SHA2_224),
+        Algo::Os(OsAlgorithm::SHA256) => return Some(CODE_SHA2_256),
+        Algo::Os(OsAlgorithm::SHA512) => return Some(CODE_SHA2_512),
+        Algo::Os(OsAlgorithm::SHA512_256) => return Some(CODE_SHA2_512_256),
+        #[cfg(feature = "insecure-md5")]
+        Algo::Os(OsAlgorithm::MD5) => return Some(CODE_MD5),
+    }
+}
+
+// SHAKE256 has a single code for every digest length
+#[cfg_attr(not(feature = "xof"), allow(unused_variables))]
+fn algorithm(code: u64, length: u64) -> Result<Algo,MultihashError> {
+    match code {
+        CODE_SHA1 => return Ok(Algo::Os(OsAlgorithm::SHA1)),
+        CODE_SHA2_224 => return Ok(Algo::Os(OsAlgorithm::SHA224)),
+        CODE_SHA2_256 => return Ok(Algo::Os(OsAlgorithm::SHA256)),
+        CODE_SHA2_512 => return Ok(Algo::Os(OsAlgorithm::SHA512)),
+        CODE_SHA2_512_256 => return Ok(Algo::Os(OsAlgorithm::SHA512_256)),
+        #[cfg(feature = "insecure-md5")]
+        CODE_MD5 => return Ok(Algo::Os(OsAlgorithm::MD5)),
+        code if code > CODE_BLAKE2B && code <= CODE_BLAKE2B + 64 => return Ok(Algo::Blake2b((code - CODE_BLAKE2B) as usize)),
+        #[cfg(feature = "xof")]
+        CODE_SHAKE256 => return Ok(Algo::Shake256(length as usize)),
+        code => return Err(MultihashError::UnknownCode(code)),
+    }
+}
+
+// Unsigned LEB128, 7 bits per byte with the high bit set on every byte but the last
+fn write_varint(mut value: u64, output: &mut Vec<u8>) {
+    while value >= 0x80 {
+        output.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+// Multiformats limit varints to 9 bytes (63 bits)
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]),MultihashError> {
+    let mut value: u64 = 0;
+    for (index, byte) in bytes.iter().enumerate().take(9) {
+        value |= ((byte & 0x7f) as u64) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[index + 1..]))
+        }
+    }
+    return Err(MultihashError::InvalidVarint)
+}
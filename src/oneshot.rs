@@ -0,0 +1,51 @@
+// One-Off Hashing
+//
+// Free functions for hashing a single file or byte slice without keeping a `ParanoidHash` around. Each one builds the configuration on the stack and calls the same method as the struct-based API.
+
+use std::path::Path;
+
+use crate::{FileError, OsAlgorithm, ParanoidHash};
+
+/// # Hash File
+///
+/// Hashes a file with BLAKE2B at `digest_size` bytes and the Operating System Hash Function. The result is the same as `ParanoidHash::new(digest_size,os_hash).read(path)`.
+///
+/// Returns `FileError::InvalidDigestSize` or `FileError::WeakAlgorithm` for a configuration `ParanoidHash::try_new()` rejects.
+///
+/// ```rust
+/// use paranoid_hash::OsAlgorithm;
+///
+/// let (blake2b,sha256) = paranoid_hash::hash_file("examples/example_file.txt",32,OsAlgorithm::SHA256).unwrap();
+/// assert_eq!(sha256,"D33C098BE35262CBD8DFAF6CD89F642E4460AFCDDDD58DD8F598594526AE8452");
+/// ```
+pub fn hash_file<T: AsRef<Path>>(path: T, digest_size: usize, os_hash: OsAlgorithm) -> Result<(String,String),FileError> {
+    return ParanoidHash::try_new(digest_size, os_hash)?.read(path)
+}
+
+/// # Hash Bytes
+///
+/// Hashes bytes with BLAKE2B at `digest_size` bytes and the Operating System Hash Function. The result is the same as `ParanoidHash::new(digest_size,os_hash).read_bytes(data)`, but a failing Operating System Hash Function returns `FileError::OsHashingError` instead of panicking.
+///
+/// Returns `FileError::InvalidDigestSize` or `FileError::WeakAlgorithm` for a configuration `ParanoidHash::try_new()` rejects.
+///
+/// ```rust
+/// use paranoid_hash::OsAlgorithm;
+///
+/// let (blake2b,sha256) = paranoid_hash::hash_bytes(b"abc",32,OsAlgorithm::SHA256).unwrap();
+/// assert_eq!(sha256,"BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+/// ```
+pub fn hash_bytes<T: AsRef<[u8]>>(data: T, digest_size: usize, os_hash: OsAlgorithm) -> Result<(String,String),FileError> {
+    return ParanoidHash::try_new(digest_size, os_hash)?.try_read_bytes(data.as_ref())
+}
+
+/// # Hash File Default
+///
+/// Hashes a file with the default configuration (BLAKE2B at 64 bytes and SHA512). The result is the same as `ParanoidHash::default().read(path)`.
+///
+/// ```rust
+/// let (blake2b,sha512) = paranoid_hash::hash_file_default("examples/example_file.txt").unwrap();
+/// assert_eq!((blake2b.len(),sha512.len()),(128,128));
+/// ```
+pub fn hash_file_default<T: AsRef<Path>>(path: T) -> Result<(String,String),FileError> {
+    return ParanoidHash::default().read(path)
+}
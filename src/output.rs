@@ -0,0 +1,166 @@
+// Hash Output
+//
+// Both digests of one input along with the Output Encoding of the context that produced them, so they can be printed consistently: labelled for people, as checksum file lines for `sha256sum -c` and `b2sum -c`, or as a single line.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::checksum_file::{escape_name, format_line};
+use crate::{Algo, FileError, HashDigest, LibAlgorithm, OsAlgorithm, OutputEncoding, ParanoidHash};
+
+/// # Hash Output
+///
+/// The BLAKE2B (library) and Operating System digests of one input, returned by `read_output()` and `read_bytes_output()`.
+///
+/// `Display` writes one labelled line per digest in the Output Encoding of the context:
+///
+/// ```rust
+/// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+///
+/// let output = ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes_output(b"abc").unwrap();
+/// assert_eq!(output.to_string(),"BLAKE2b-256: BDDD813C634239723171EF3FEE98579B94964E3BB1CB3E427262C8C068D52319\nSHA-256: BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+/// ```
+#[derive(Debug,Clone,PartialEq)]
+pub struct HashOutput {
+    pub blake2b: HashDigest,
+    pub os: HashDigest,
+    encoding: OutputEncoding,
+}
+
+impl HashOutput {
+    /// ## Format GNU
+    ///
+    /// Two GNU checksum file lines (`<digest>  <name>`), the library digest first, each ending with a newline. The digests are hexadecimal in the case of the Output Encoding (lower case for `HexLower`, upper case otherwise). Names containing a backslash, newline or carriage return are escaped as coreutils does.
+    ///
+    /// The lines belong in separate files (such as `B2SUMS` and `SHA512SUMS`), since `verify_against_checksum_file()` rejects a file listed twice with different digests.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm,OutputEncoding};
+    ///
+    /// let context = ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1).output_encoding(OutputEncoding::HexLower);
+    /// let output = context.read_bytes_output(b"abc").unwrap();
+    /// assert_eq!(output.format_gnu("abc.txt"),"384264f676f39536840523f284921cdc68b6846b  abc.txt\na9993e364706816aba3e25717850c26c9cd0d89d  abc.txt\n");
+    /// ```
+    pub fn format_gnu<T: AsRef<Path>>(&self, filename: T) -> String {
+        let name = filename.as_ref().to_string_lossy();
+        return format!("{}{}", format_line(&self.hex(&self.blake2b), &name), format_line(&self.hex(&self.os), &name))
+    }
+    /// ## Format Tagged
+    ///
+    /// Two BSD style lines (`<ALGORITHM> (<name>) = <digest>`, as written by `sha256sum --tag` and `b2sum --tag`), the library digest first, each ending with a newline. Digests and escaping are the same as `format_gnu()`.
+    ///
+    /// The tags are those of coreutils: `BLAKE2b` for a 64 byte BLAKE2B digest and `BLAKE2b-<bits>` otherwise, then `MD5` (with the `insecure-md5` feature), `SHA1`, `SHA224`, `SHA256` or `SHA512`. SHA512_256 is tagged `SHA512/256`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    ///
+    /// let output = ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1).read_bytes_output(b"abc").unwrap();
+    /// assert_eq!(output.format_tagged("abc.txt"),"BLAKE2b-160 (abc.txt) = 384264F676F39536840523F284921CDC68B6846B\nSHA1 (abc.txt) = A9993E364706816ABA3E25717850C26C9CD0D89D\n");
+    /// ```
+    pub fn format_tagged<T: AsRef<Path>>(&self, filename: T) -> String {
+        let name = filename.as_ref().to_string_lossy();
+        return format!("{}{}", tagged_line(&bsd_name(&self.blake2b), &self.hex(&self.blake2b), &name), tagged_line(&bsd_name(&self.os), &self.hex(&self.os), &name))
+    }
+    /// ## Format Compact
+    ///
+    /// Both digests in the Output Encoding on a single line, separated by a space, without a newline.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    ///
+    /// let output = ParanoidHash::new(1,OsAlgorithm::SHA224).read_bytes_output(b"abc").unwrap();
+    /// assert_eq!(output.format_compact(),"6B 23097D223405D8228642A477BDA255B32AADBCE4BDA0B3F7E36C9DA7");
+    /// ```
+    pub fn format_compact(&self) -> String {
+        return format!("{} {}", self.encoding.encode(self.blake2b.as_bytes()), self.encoding.encode(self.os.as_bytes()))
+    }
+    // Checksum files only hold hexadecimal digests
+    fn hex(&self, digest: &HashDigest) -> String {
+        match self.encoding {
+            OutputEncoding::HexLower => return digest.to_hex_lower(),
+            _ => return digest.to_hex_upper(),
+        }
+    }
+}
+
+impl fmt::Display for HashOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}\n{}: {}", display_name(&self.blake2b), self.encoding.encode(self.blake2b.as_bytes()), display_name(&self.os), self.encoding.encode(self.os.as_bytes()))
+    }
+}
+
+impl ParanoidHash {
+    /// # Read Output
+    ///
+    /// Same as `read()`, but returns the digests as a `HashOutput` for printing.
+    pub fn read_output<T: AsRef<Path>>(&self, path: T) -> Result<HashOutput,FileError> {
+        let (blake2b, os) = self.read_digests(path)?;
+        return Ok(HashOutput { blake2b, os, encoding: self.encoding })
+    }
+    /// # Read Bytes Output
+    ///
+    /// Same as `try_read_bytes()`, but returns the digests as a `HashOutput` for printing.
+    pub fn read_bytes_output(&self, bytes: &[u8]) -> Result<HashOutput,FileError> {
+        let (blake2b, os) = self.read_bytes_digests(bytes)?;
+        return Ok(HashOutput { blake2b, os, encoding: self.encoding })
+    }
+}
+
+// A BSD style line, escaping the name if needed
+fn tagged_line(tag: &str, digest: &str, name: &str) -> String {
+    match escape_name(name) {
+        Some(escaped) => return format!("\\{} ({}) = {}\n", tag, escaped, digest),
+        None => return format!("{} ({}) = {}\n", tag, name, digest),
+    }
+}
+
+// The name of the hash function for people, such as `BLAKE2b-512` or `SHA-256`
+fn display_name(digest: &HashDigest) -> String {
+    let bits = digest.as_bytes().len() * 8;
+    match digest.algorithm() {
+        Algo::Os(os_hash) => return os_display_name(os_hash).to_string(),
+        library => match library.library() {
+            Some((library, _)) => return format!("{}-{}", library_name(&library), bits),
+            None => unreachable!(),
+        },
+    }
+}
+
+// The tag of the hash function in BSD style lines
+fn bsd_name(digest: &HashDigest) -> String {
+    let bits = digest.as_bytes().len() * 8;
+    match digest.algorithm() {
+        Algo::Os(OsAlgorithm::SHA512_256) => return "SHA512/256".to_string(),
+        Algo::Os(os_hash) => return os_hash.tag().to_ascii_uppercase(),
+        // `b2sum --tag` leaves out the size of a full digest
+        Algo::Blake2b(64) => return "BLAKE2b".to_string(),
+        library => match library.library() {
+            Some((library, _)) => return format!("{}-{}", library_name(&library), bits),
+            None => unreachable!(),
+        },
+    }
+}
+
+fn os_display_name(os_hash: &OsAlgorithm) -> &'static str {
+    match os_hash {
+        OsAlgorithm::SHA1 => return "SHA-1",
+        OsAlgorithm::SHA224 => return "SHA-224",
+        OsAlgorithm::SHA256 => return "SHA-256",
+        OsAlgorithm::SHA512 => return "SHA-512",
+        OsAlgorithm::SHA512_256 => return "SHA-512/256",
+        #[cfg(feature = "insecure-md5")]
+        OsAlgorithm::MD5 => return "MD5",
+    }
+}
+
+fn library_name(library: &LibAlgorithm) -> &'static str {
+    match library {
+        LibAlgorithm::BLAKE2B => return "BLAKE2b",
+        #[cfg(feature = "blake2-parallel")]
+        LibAlgorithm::BLAKE2BP => return "BLAKE2bp",
+        #[cfg(feature = "blake2-parallel")]
+        LibAlgorithm::BLAKE2SP => return "BLAKE2sp",
+        #[cfg(feature = "xof")]
+        LibAlgorithm::SHAKE256 => return "SHAKE256",
+    }
+}
@@ -0,0 +1,101 @@
+// Parallel Hashing
+//
+// Runs the library hash function and the operating system hash function on two threads over the same buffer. The digests are identical to the sequential functions.
+
+use std::fs;
+use std::path::Path;
+use std::thread;
+
+use filebuffer::FileBuffer;
+
+use crate::hashers::Digester;
+use crate::{long_path, FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read In Parallel
+    ///
+    /// Same as `read()`, but hashes BLAKE2B and the Operating System Hash Function on separate threads. This is faster on large files when the CPU, not the disk, is the bottleneck.
+    ///
+    /// On a single-core machine, with `threads(1)` or if a thread cannot be spawned it falls back to hashing sequentially.
+    pub fn read_parallel<T: AsRef<Path>>(&self, path: T) -> Result<(String,String),FileError> {
+        let path = long_path::extended(path.as_ref());
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.as_ref().exists() {
+            return Err(FileError::FileNotFound)
+        }
+        self.check_file_size(path.as_ref())?;
+
+        // Opens File Using File Buffer. Files that cannot be mapped (including special files) are hashed sequentially by `read()`.
+        let fbuffer = match fs::metadata(path.as_ref()) {
+            Ok(metadata) if metadata.is_file() && metadata.len() <= self.mmap_limit() => FileBuffer::open(path.as_ref()).ok(),
+            _ => None,
+        };
+        let fbuffer = match fbuffer {
+            Some(fbuffer) => fbuffer,
+            None => return self.read(path),
+        };
+        self.check_empty(fbuffer.len() as u64)?;
+
+        return Ok(self.hash_bytes_parallel(&fbuffer)?.0)
+    }
+    /// # Read Bytes In Parallel
+    ///
+    /// Same as `read_bytes()`, but hashes BLAKE2B and the Operating System Hash Function on separate threads.
+    ///
+    /// Panics if the Operating System Hash Function fails. Use `try_read_bytes_parallel()` to get `FileError::OsHashingError` instead.
+    pub fn read_bytes_parallel(&self, bytes: &[u8]) -> (String,String) {
+        return self.hash_bytes_parallel(bytes).expect("[Error] Failed To Hash Using Operating System Hash Function").0
+    }
+    /// # Try Read Bytes In Parallel
+    ///
+    /// Same as `read_bytes_parallel()` but returns a `Result`, honoring `reject_empty_input()`.
+    pub fn try_read_bytes_parallel(&self, bytes: &[u8]) -> Result<(String,String),FileError> {
+        self.check_empty(bytes.len() as u64)?;
+        return Ok(self.hash_bytes_parallel(bytes)?.0)
+    }
+    // Also returns whether the pure Rust fallback replaced the Operating System Hash Function
+    pub(crate) fn hash_bytes_parallel(&self, bytes: &[u8]) -> Result<((String,String),bool),FileError> {
+        if self.thread_count() < 2 {
+            return self.hash_bytes(bytes)
+        }
+
+        let library_algo = self.library_algo();
+
+        let digests = thread::scope(|scope| {
+            // Operating System Hashing on the second thread
+            let os_thread = thread::Builder::new().spawn_scoped(scope, || {
+                let mut digester = Digester::os(&self.os_hash_function, self.os_options)?;
+                digester.update(bytes)?;
+                digester.finalize()
+            });
+            let os_thread = match os_thread {
+                Ok(handle) => handle,
+                Err(_) => return None,
+            };
+
+            // Library Hashing on this thread
+            let library = Digester::new(&library_algo, self.thread_count()).and_then(|mut digester| {
+                digester.update(bytes)?;
+                digester.finalize()
+            });
+
+            let os = os_thread.join().unwrap_or(Err(FileError::OsHashingError));
+            Some((library, os))
+        });
+
+        match digests {
+            Some((library, os)) => {
+                let (library, _) = library?;
+                let (os, os_fallback) = os?;
+                return Ok((self.encode_pair((library, os)), os_fallback))
+            }
+            None => return self.hash_bytes(bytes),
+        }
+    }
+}
+
+// The number of logical CPUs, or 1 if it cannot be determined
+pub(crate) fn available_threads() -> usize {
+    return thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
@@ -0,0 +1,112 @@
+// Glob Patterns
+//
+// A small gitignore-style matcher for `WalkOptions`, applied to `/` separated paths relative to the directory being walked.
+//
+// * `*` matches anything within one path component, `?` matches one character and `[a-z]` / `[!a-z]` match a character class
+// * `**` as a whole component matches any number of components (including none)
+// * A pattern with no `/` (apart from a trailing one) matches a file or directory name at any depth. Any other pattern is anchored to the root and a leading `/` is ignored.
+// * A trailing `/` only matches directories
+
+#[derive(Debug,Clone)]
+pub(crate) struct Pattern {
+    segments: Vec<String>,
+    anchored: bool,
+    directory_only: bool,
+}
+
+impl Pattern {
+    pub(crate) fn new(pattern: &str) -> Self {
+        let directory_only = pattern.ends_with('/');
+        let body = pattern.trim_end_matches('/');
+        let anchored = body.contains('/');
+        let segments = body.trim_start_matches('/').split('/').filter(|s| !s.is_empty()).map(str::to_string).collect();
+
+        return Pattern {
+            segments,
+            anchored,
+            directory_only,
+        }
+    }
+    // Whether the relative path `components` matches. `is_dir` tells whether it names a directory.
+    pub(crate) fn matches(&self, components: &[&str], is_dir: bool) -> bool {
+        if self.directory_only && !is_dir {
+            return false
+        }
+        if self.segments.is_empty() {
+            return false
+        }
+        if !self.anchored {
+            return match components.last() {
+                Some(name) => self.segments.len() == 1 && wildcard(&self.segments[0], name),
+                None => false,
+            }
+        }
+        return match_segments(&self.segments, components)
+    }
+}
+
+fn match_segments(segments: &[String], components: &[&str]) -> bool {
+    match segments.split_first() {
+        None => return components.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            // `**` takes zero or more components
+            return (0..=components.len()).any(|taken| match_segments(rest, &components[taken..]))
+        }
+        Some((first, rest)) => match components.split_first() {
+            Some((component, remaining)) => return wildcard(first, component) && match_segments(rest, remaining),
+            None => return false,
+        },
+    }
+}
+
+// Matches a single component against `*`, `?` and `[...]`
+fn wildcard(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    return wildcard_chars(&pattern, &name)
+}
+
+fn wildcard_chars(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => return name.is_empty(),
+        Some('*') => return (0..=name.len()).any(|skipped| wildcard_chars(&pattern[1..], &name[skipped..])),
+        Some('?') => return !name.is_empty() && wildcard_chars(&pattern[1..], &name[1..]),
+        Some('[') => {
+            let c = match name.first() {
+                Some(c) => *c,
+                None => return false,
+            };
+            match class(&pattern[1..], c) {
+                Some((matched, length)) => return matched && wildcard_chars(&pattern[1 + length..], &name[1..]),
+                // An unterminated class is a literal `[`
+                None => return c == '[' && wildcard_chars(&pattern[1..], &name[1..]),
+            }
+        }
+        Some(c) => return name.first() == Some(c) && wildcard_chars(&pattern[1..], &name[1..]),
+    }
+}
+
+// Matches `c` against the class after its `[`, returning whether it matched and the length of the class including its `]`
+fn class(pattern: &[char], c: char) -> Option<(bool, usize)> {
+    let negated = matches!(pattern.first(), Some('!') | Some('^'));
+    let mut index = if negated { 1 } else { 0 };
+    let mut matched = false;
+    let mut first = true;
+
+    while index < pattern.len() {
+        // A `]` right after the `[` is part of the class
+        if pattern[index] == ']' && !first {
+            return Some((matched != negated, index + 1))
+        }
+        first = false;
+        if index + 2 < pattern.len() && pattern[index + 1] == '-' && pattern[index + 2] != ']' {
+            matched |= pattern[index] <= c && c <= pattern[index + 2];
+            index += 3;
+        }
+        else {
+            matched |= pattern[index] == c;
+            index += 1;
+        }
+    }
+    return None
+}
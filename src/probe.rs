@@ -0,0 +1,101 @@
+// Probing the Operating System Hash Functions
+//
+// Some platforms ship a crypto library without every algorithm (such as Windows in FIPS mode or a stripped-down container). Each backend is probed the first time it is asked about by hashing `"abc"` and checking the digest against its known answer from the self test. The result is kept for the rest of the process.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use crate::hashers::os_hasher;
+use crate::selftest::abc_digest;
+use crate::{FileError, OsAlgorithm, ParanoidHash};
+
+// Every Operating System Hash Function, in the order of the caches below
+pub(crate) const OS_ALGORITHMS: &[OsAlgorithm] = &[
+    OsAlgorithm::SHA1,
+    OsAlgorithm::SHA224,
+    OsAlgorithm::SHA256,
+    OsAlgorithm::SHA512,
+    OsAlgorithm::SHA512_256,
+    #[cfg(feature = "insecure-md5")]
+    OsAlgorithm::MD5,
+];
+
+// One slot for each algorithm, including MD5 when it is compiled out
+
+static AVAILABLE: [OnceLock<bool>; 6] = [OnceLock::new(), OnceLock::new(), OnceLock::new(), OnceLock::new(), OnceLock::new(), OnceLock::new()];
+
+// How often each backend was actually probed, for testing the cache
+static PROBES: [AtomicUsize; 6] = [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)];
+
+impl OsAlgorithm {
+    /// ## Is Available
+    /// 
+    /// Returns `true` if the operating system can compute this hash function, by hashing a known-answer vector with it. The pure Rust fallback (see `os_fallback()`) is never used, so this tells whether hashing without it would fail.
+    /// 
+    /// The first call for each algorithm runs the probe and later calls return the cached result. SHA224 and SHA512_256 are always computed in pure Rust and are always available.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::OsAlgorithm;
+    /// 
+    /// assert!(OsAlgorithm::SHA256.is_available());
+    /// ```
+    pub fn is_available(&self) -> bool {
+        let index = self.index();
+        return *AVAILABLE[index].get_or_init(|| {
+            PROBES[index].fetch_add(1, Ordering::SeqCst);
+            return probe(self)
+        })
+    }
+    /// The number of times the backend of this algorithm has been probed by this process. Only intended for testing the cache.
+    #[doc(hidden)]
+    pub fn probe_count(&self) -> usize {
+        return PROBES[self.index()].load(Ordering::SeqCst)
+    }
+    fn index(&self) -> usize {
+        return OS_ALGORITHMS.iter().position(|os_hash| os_hash == self).expect("[Error] Every Operating System Hash Function is listed")
+    }
+}
+
+// Hashes "abc" with the operating system backend alone and compares the digest with the known answer. A panic counts as unavailable.
+fn probe(os_hash: &OsAlgorithm) -> bool {
+    let digest = panic::catch_unwind(AssertUnwindSafe(|| -> Result<Vec<u8>,FileError> {
+        let mut backend = os_hasher(os_hash)?;
+        backend.update(b"abc")?;
+        return backend.finish()
+    }));
+    match digest {
+        Ok(Ok(digest)) => return hex::encode_upper(digest) == abc_digest(os_hash),
+        _ => return false,
+    }
+}
+
+impl ParanoidHash {
+    /// # Supported OS Algorithms
+    /// 
+    /// Every Operating System Hash Function for which `is_available()` returns `true`, in the order SHA1, SHA224, SHA256, SHA512, SHA512_256, MD5 (with the `insecure-md5` feature).
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    /// 
+    /// assert!(ParanoidHash::supported_os_algorithms().contains(&OsAlgorithm::SHA256));
+    /// ```
+    pub fn supported_os_algorithms() -> Vec<OsAlgorithm> {
+        return OS_ALGORITHMS.iter().filter(|os_hash| os_hash.is_available()).cloned().collect()
+    }
+    /// ## Require Available OS Algorithm
+    /// 
+    /// Returns this context if its Operating System Hash Function `is_available()`, or `FileError::OsAlgorithmUnavailable` so an unsupported configuration is refused up front instead of failing on the first hash. Unavailable algorithms still work with `os_fallback(true)`, so a context with the fallback enabled is always accepted.
+    /// 
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    /// 
+    /// let context = ParanoidHash::new(64,OsAlgorithm::SHA256).require_available_os_algorithm().unwrap();
+    /// ```
+    pub fn require_available_os_algorithm(self) -> Result<Self,FileError> {
+        if self.os_options.fallback || self.os_hash_function.is_available() {
+            return Ok(self)
+        }
+        return Err(FileError::OsAlgorithmUnavailable(self.os_hash_function))
+    }
+}
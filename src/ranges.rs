@@ -0,0 +1,98 @@
+// Byte Ranges
+//
+// Hashes selected regions of a file, such as the data blocks of a format while skipping its metadata. The file is opened once and each range is read by seeking to it.
+
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::hashers::CHUNK_SIZE;
+use crate::retry::RetryReader;
+use crate::{long_path, FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read Ranges
+    ///
+    /// Hashes each byte range of the file separately and returns the digests in the same order as `ranges`.
+    ///
+    /// Every range must lie within the file and ranges must not overlap, although they can be in any order and empty ranges are allowed. Otherwise `FileError::InvalidRange` is returned with the index of the first offending range, before anything is hashed. `max_file_size()` and `reject_empty_input()` apply to each range.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let contents = std::fs::read("examples/example_file.txt").unwrap();
+    ///
+    /// let digests = context.read_ranges("examples/example_file.txt",&[0..10,100..200]).unwrap();
+    /// assert_eq!(digests[1],context.read_bytes(&contents[100..200]));
+    /// ```
+    pub fn read_ranges<T: AsRef<Path>>(&self, path: T, ranges: &[Range<u64>]) -> Result<Vec<(String,String)>,FileError> {
+        let mut file = self.open_ranges(path.as_ref(), ranges)?;
+
+        let mut digests = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            file.seek(SeekFrom::Start(range.start)).map_err(|e| FileError::Io(e.kind()))?;
+            let (pair, stats) = self.hash_reader((&mut file).take(range.end - range.start))?;
+            check_complete(range, stats.bytes)?;
+            digests.push(pair);
+        }
+        return Ok(digests)
+    }
+    /// # Read Ranges Combined
+    ///
+    /// Same as `read_ranges()`, but feeds every range into a single pair of digests in the order given. The digests are the same as `read_bytes()` of the ranges concatenated.
+    ///
+    /// `max_file_size()` and `reject_empty_input()` apply to the total length of the ranges.
+    pub fn read_ranges_combined<T: AsRef<Path>>(&self, path: T, ranges: &[Range<u64>]) -> Result<(String,String),FileError> {
+        let mut file = self.open_ranges(path.as_ref(), ranges)?;
+
+        let mut hasher = self.pair_hasher(None)?;
+        let mut total: u64 = 0;
+        for range in ranges {
+            file.seek(SeekFrom::Start(range.start)).map_err(|e| FileError::Io(e.kind()))?;
+            let reader = BufReader::with_capacity(CHUNK_SIZE, RetryReader::new((&mut file).take(range.end - range.start), self.retry_policy));
+            let length = hasher.update_reader_checked(reader, |read| self.check_size(total + read))?;
+            check_complete(range, length)?;
+            total += length;
+        }
+        self.check_empty(total)?;
+
+        return Ok(self.encode_pair(hasher.finalize_pair()?.0))
+    }
+    // Opens the file and checks the ranges against its length and each other
+    fn open_ranges(&self, path: &Path, ranges: &[Range<u64>]) -> Result<File,FileError> {
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !long_path::extended(path).exists() {
+            return Err(FileError::FileNotFound)
+        }
+
+        let file = self.open_file(path)?;
+        let length = file.metadata().map_err(|e| FileError::Io(e.kind()))?.len();
+
+        if let Some(index) = ranges.iter().position(|range| range.start > range.end || range.end > length) {
+            return Err(FileError::InvalidRange(index))
+        }
+
+        // After sorting by start, a range overlaps another if it starts before the previous one ends
+        let mut sorted: Vec<usize> = (0..ranges.len()).filter(|index| !ranges[*index].is_empty()).collect();
+        sorted.sort_by_key(|index| ranges[*index].start);
+        let overlapping = sorted.windows(2)
+            .filter(|pair| ranges[pair[0]].end > ranges[pair[1]].start)
+            .map(|pair| pair[0].max(pair[1]))
+            .min();
+        if let Some(index) = overlapping {
+            return Err(FileError::InvalidRange(index))
+        }
+        return Ok(file)
+    }
+}
+
+// The file was truncated while it was being read
+fn check_complete(range: &Range<u64>, length: u64) -> Result<(),FileError> {
+    if length != range.end - range.start {
+        return Err(FileError::Io(io::ErrorKind::UnexpectedEof))
+    }
+    return Ok(())
+}
@@ -0,0 +1,83 @@
+// Glob Hashing
+//
+// Hashes every file matching a glob pattern such as `releases/**/*.tar.gz`. The pattern is expanded by the `glob` crate and the matches are hashed like `read_files()`.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::{FileError, ParanoidHash};
+
+/// # Glob Error
+///
+/// Returned when a glob pattern cannot be expanded.
+#[derive(Debug,Clone,PartialEq,Eq,PartialOrd,Hash)]
+pub enum GlobError {
+    /// The pattern is not a valid glob. `position` is the byte offset of the problem.
+    InvalidPattern { position: usize, message: String },
+    /// No file matches the pattern and `require_glob_match()` is enabled
+    NoMatch,
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobError::InvalidPattern { position, message } => write!(f, "invalid glob pattern at position {}: {}", position, message),
+            GlobError::NoMatch => write!(f, "no file matches the glob pattern"),
+        }
+    }
+}
+
+impl std::error::Error for GlobError {}
+
+impl ParanoidHash {
+    /// # Read Glob
+    ///
+    /// Requires the `glob` feature.
+    ///
+    /// Hashes every file matching `pattern` and returns one result per file, sorted by path. Relative patterns are relative to the current directory.
+    ///
+    /// * `*` matches anything within one path component, `?` matches one character and `[a-z]` / `[!a-z]` match a character class
+    /// * `**` as a whole component matches any number of directories (including none)
+    /// * Matching is case sensitive and `*` also matches names starting with a `.`
+    ///
+    /// Directories that match are skipped. As with `read_files()`, a file that fails to hash keeps its error in its result, and so does a directory that cannot be read while the pattern is expanded. A pattern that matches nothing returns an empty list, or `GlobError::NoMatch` with `require_glob_match(true)`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let results = ParanoidHash::default().read_glob("tests/fixtures/report/**/*.txt").unwrap();
+    /// assert_eq!(results.len(),2);
+    /// assert!(results.iter().all(|(_,result)| result.is_ok()));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn read_glob(&self, pattern: &str) -> Result<Vec<(PathBuf,Result<(String,String),FileError>)>,GlobError> {
+        let paths = glob::glob(pattern).map_err(|e| GlobError::InvalidPattern { position: e.pos, message: e.msg.to_string() })?;
+
+        let mut files: Vec<PathBuf> = Vec::new();
+        let mut unreadable: Vec<(PathBuf,Result<(String,String),FileError>)> = Vec::new();
+        for path in paths {
+            match path {
+                Ok(path) if path.is_dir() => continue,
+                Ok(path) => files.push(path),
+                Err(e) => unreadable.push((e.path().to_path_buf(), Err(FileError::Io(e.error().kind())))),
+            }
+        }
+        if files.is_empty() && unreadable.is_empty() && self.require_glob_match {
+            return Err(GlobError::NoMatch)
+        }
+
+        let mut results = self.read_files(&files);
+        results.extend(unreadable);
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        return Ok(results)
+    }
+    /// ## Require Glob Match
+    ///
+    /// Requires the `glob` feature.
+    ///
+    /// When set to `true`, `read_glob()` returns `GlobError::NoMatch` instead of an empty list if no file matches the pattern. Disabled by default.
+    pub fn require_glob_match(mut self, require: bool) -> Self {
+        self.require_glob_match = require;
+        return self
+    }
+}
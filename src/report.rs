@@ -0,0 +1,230 @@
+//! # Reports
+//!
+//! Structured results for operations over more than one file.
+//!
+//! With the `serde` feature enabled, a [`HashReport`] can be written out as JSON using [`HashReport::to_json_writer()`].
+//!
+//! ## JSON Schema
+//!
+//! The JSON output is considered **stable** for a given `format_version`. Fields may be added in later versions but existing fields will not be renamed, removed or change meaning without `format_version` being increased.
+//!
+//! ```text
+//! {
+//!   "format_version": 1,
+//!   "library_algorithm": "BLAKE2B",  // or "BLAKE2BP" / "BLAKE2SP" / "SHAKE256"
+//!   "blake2b_digest_size": 64,       // the digest size of the library hash function
+//!   "entries": [
+//!     {
+//!       "path": "dir/file.txt",      // lossy UTF-8, as given
+//!       "resolved_path": "/home/user/dir/file.txt", // lossy UTF-8, canonical unless "canonical" is false
+//!       "canonical": true,           // false if "resolved_path" is the path as given
+//!       "size": 6,                   // null if the metadata could not be read
+//!       "blake2b": "UPPERHEX",       // null on error
+//!       "os_algorithm": "SHA512",
+//!       "os_digest": "UPPERHEX",     // null on error
+//!       "duration_us": 12,           // time spent opening and hashing, in microseconds
+//!       "error": null                // message if this file failed
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! ## CSV
+//!
+//! [`HashReport::to_csv_writer()`] writes one row per entry, in the order of the report, with these columns:
+//!
+//! ```text
+//! path,size,blake2b_digest_size,blake2b,os_algorithm,os_digest,error
+//! dir/file.txt,6,64,UPPERHEX,SHA512,UPPERHEX,
+//! "dir/a, ""quoted"" name.txt",,64,,SHA512,,file not found
+//! ```
+//!
+//! Fields follow RFC 4180: rows end with CRLF, and a field containing a comma, a double quote, a carriage return or a line feed is enclosed in double quotes with its double quotes doubled. Missing values (the size, the digests or the error) are empty fields. Paths are lossy UTF-8, as given.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::batch::map_threaded;
+use crate::{walk, FileError, HashStats, LibAlgorithm, OsAlgorithm, ParanoidHash};
+
+/// The version of the JSON format written by [`HashReport::to_json_writer()`].
+pub const REPORT_FORMAT_VERSION: u32 = 1;
+
+/// # Hash Report
+///
+/// The result of hashing multiple files. Files that failed are kept as entries with their `error` set instead of being dropped.
+#[derive(Debug,Clone,PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HashReport {
+    pub format_version: u32,
+    pub library_algorithm: LibAlgorithm,
+    pub blake2b_digest_size: usize,
+    pub entries: Vec<ReportEntry>,
+}
+
+/// # Report Entry
+///
+/// A single file in a [`HashReport`].
+#[derive(Debug,Clone,PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ReportEntry {
+    /// The path as given
+    #[cfg_attr(feature = "serde", serde(serialize_with = "json::lossy_path"))]
+    pub path: PathBuf,
+    /// The canonical absolute path that was read, with symbolic links and relative components resolved. It is `path` unchanged if `canonical` is `false`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "json::lossy_path"))]
+    pub resolved_path: PathBuf,
+    /// `false` if canonicalization was disabled with `canonicalize_paths(false)` or failed (such as for a missing file or on some network mounts)
+    pub canonical: bool,
+    pub size: Option<u64>,
+    pub blake2b: Option<String>,
+    pub os_algorithm: OsAlgorithm,
+    pub os_digest: Option<String>,
+    /// Total time spent on this file, including opening it
+    #[cfg_attr(feature = "serde", serde(rename = "duration_us", serialize_with = "json::micros"))]
+    pub duration: Duration,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "json::error_message"))]
+    pub error: Option<FileError>,
+    /// Detailed timing for files that were hashed successfully. Not part of the JSON output.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub stats: Option<HashStats>,
+}
+
+impl HashReport {
+    /// ## Has Errors
+    ///
+    /// Returns true if any file in the report failed to hash.
+    pub fn has_errors(&self) -> bool {
+        return self.entries.iter().any(|entry| entry.error.is_some())
+    }
+    /// ## To JSON Writer
+    ///
+    /// Serializes the report as JSON into `w`. See the [module documentation](crate::report) for the schema.
+    #[cfg(feature = "serde")]
+    pub fn to_json_writer(&self, w: impl std::io::Write) -> std::io::Result<()> {
+        serde_json::to_writer(w, self)?;
+        return Ok(())
+    }
+    /// ## To CSV Writer
+    ///
+    /// Writes the report as CSV into `w`, starting with a header row if `header` is `true`. See the [module documentation](crate::report) for the columns and quoting.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let report = ParanoidHash::default().report_files(&["examples/example_file.txt"]);
+    /// let mut csv = Vec::new();
+    /// report.to_csv_writer(&mut csv,true).unwrap();
+    /// assert!(String::from_utf8(csv).unwrap().starts_with("path,size,blake2b_digest_size,blake2b,os_algorithm,os_digest,error\r\n"));
+    /// ```
+    pub fn to_csv_writer(&self, mut w: impl Write, header: bool) -> io::Result<()> {
+        if header {
+            write!(w, "{}\r\n", CSV_COLUMNS.join(","))?;
+        }
+        for entry in self.entries.iter() {
+            let fields = [
+                entry.path.to_string_lossy().into_owned(),
+                entry.size.map(|size| size.to_string()).unwrap_or_default(),
+                self.blake2b_digest_size.to_string(),
+                entry.blake2b.clone().unwrap_or_default(),
+                format!("{:?}", entry.os_algorithm),
+                entry.os_digest.clone().unwrap_or_default(),
+                entry.error.as_ref().map(|e| e.to_string()).unwrap_or_default(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            write!(w, "{}\r\n", fields.join(","))?;
+        }
+        return w.flush()
+    }
+}
+
+// The columns written by `to_csv_writer()`
+const CSV_COLUMNS: [&str; 7] = ["path", "size", "blake2b_digest_size", "blake2b", "os_algorithm", "os_digest", "error"];
+
+// Quotes a field as required by RFC 4180
+fn csv_field(field: &str) -> String {
+    if !field.contains([',', '"', '\r', '\n']) {
+        return field.to_string()
+    }
+    return format!("\"{}\"", field.replace('"', "\"\""))
+}
+
+impl ParanoidHash {
+    /// # Report Files
+    ///
+    /// Hashes every path and collects the results into a [`HashReport`], in the order given. Up to `threads()` files are hashed at once. A failure on one file is recorded in its entry and does not stop the rest.
+    pub fn report_files<P: AsRef<Path>>(&self, paths: &[P]) -> HashReport {
+        let paths: Vec<&Path> = paths.iter().map(|path| path.as_ref()).collect();
+        let entries = map_threaded(&paths, self.thread_count(), || (), |_, path| self.report_entry(path));
+        return self.new_report(entries)
+    }
+    /// # Report Directory
+    ///
+    /// Recursively hashes every regular file inside `dir` (sorted by the UTF-8 bytes of their relative path, symbolic links are not followed) and collects the results into a [`HashReport`].
+    pub fn report_dir<T: AsRef<Path>>(&self, dir: T) -> Result<HashReport,FileError> {
+        let files = walk::walk_files(dir.as_ref(), &self.walk_options)?;
+        return Ok(self.report_files(&files))
+    }
+    fn new_report(&self, entries: Vec<ReportEntry>) -> HashReport {
+        return HashReport {
+            format_version: REPORT_FORMAT_VERSION,
+            library_algorithm: self.library_hash_function.clone(),
+            blake2b_digest_size: self.digest_size,
+            entries,
+        }
+    }
+    fn report_entry(&self, path: &Path) -> ReportEntry {
+        let size = fs::metadata(path).ok().map(|metadata| metadata.len());
+        let canonical = match self.canonical_paths {
+            true => fs::canonicalize(path).ok(),
+            false => None,
+        };
+
+        let start = Instant::now();
+        let result = self.read_with_stats(path);
+        let duration = start.elapsed();
+
+        let (blake2b, os_digest, error, stats) = match result {
+            Ok(((blake2b, os_digest), stats)) => (Some(blake2b), Some(os_digest), None, Some(stats)),
+            Err(e) => (None, None, Some(e), None),
+        };
+
+        return ReportEntry {
+            path: path.to_path_buf(),
+            canonical: canonical.is_some(),
+            resolved_path: canonical.unwrap_or_else(|| path.to_path_buf()),
+            size,
+            blake2b,
+            os_algorithm: self.os_hash_function.clone(),
+            os_digest,
+            duration,
+            error,
+            stats,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) mod json {
+    use std::path::Path;
+    use std::time::Duration;
+
+    use serde::Serializer;
+
+    use crate::FileError;
+
+    pub fn lossy_path<S: Serializer>(path: &Path, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&path.to_string_lossy())
+    }
+    pub fn micros<S: Serializer>(duration: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(duration.as_micros() as u64)
+    }
+    pub fn error_message<S: Serializer>(error: &Option<FileError>, s: S) -> Result<S::Ok, S::Error> {
+        match error {
+            Some(e) => s.serialize_str(&e.to_string()),
+            None => s.serialize_none(),
+        }
+    }
+}
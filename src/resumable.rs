@@ -0,0 +1,404 @@
+// Resumable Hashing
+//
+// An incremental hasher whose state can be saved to bytes and restored later, so hashing a very large input can continue after the process restarts.
+
+use std::convert::TryInto;
+use std::slice;
+
+use sha2::digest::generic_array::GenericArray;
+
+use crate::{FileError, LibAlgorithm, OsAlgorithm, ParanoidHash};
+
+/// The version of the format written by [`ParanoidHasher::save_state()`].
+pub const STATE_FORMAT_VERSION: u8 = 1;
+
+/// # Resumable Hasher
+///
+/// An incremental hasher created with `ParanoidHash::hasher()`. Its state can be written out with `save_state()` at any point and loaded into a new hasher with `restore_state()`, after which hashing continues as if it had never stopped.
+///
+/// Neither the operating system nor the hashing crates can export their internal state, so in this mode both BLAKE2B and the Operating System Hash Function are computed in pure Rust: BLAKE2B inside this crate and the SHA-2 functions with the compression function of the `sha2` crate, with their state kept here. The digests are the same as those of `read_bytes()`. Only BLAKE2B and the SHA-2 Operating System Hash Functions (SHA224, SHA256, SHA512 and SHA512_256) can be resumed.
+///
+/// The state holds the digest size and both algorithms, but not the key, encoding or other options of the `ParanoidHash`. It contains no more than the hashed data's partial last block, yet it should be stored as carefully as the data itself.
+///
+/// ```rust
+/// use paranoid_hash::ParanoidHash;
+///
+/// let context = ParanoidHash::default();
+///
+/// let mut hasher = context.hasher().unwrap();
+/// hasher.update(b"Hello, ");
+/// let saved = hasher.save_state();
+///
+/// // Later, possibly in another process
+/// let mut hasher = context.hasher().unwrap();
+/// hasher.restore_state(&saved).unwrap();
+/// hasher.update(b"World!");
+///
+/// assert_eq!(hasher.finalize().unwrap(),context.read_str("Hello, World!"));
+/// ```
+#[derive(Debug,Clone)]
+pub struct ParanoidHasher {
+    config: ParanoidHash,
+    bytes: u64,
+    blake2b: Blake2bState,
+    os: Sha2State,
+}
+
+impl ParanoidHash {
+    /// # Resumable Hasher
+    ///
+    /// Creates a `ParanoidHasher` with this configuration.
+    ///
+    /// Returns `FileError::NotResumable` if the library hash function is not BLAKE2B or the Operating System Hash Function is not one of the SHA-2 functions.
+    pub fn hasher(&self) -> Result<ParanoidHasher,FileError> {
+        if self.library_hash_function != LibAlgorithm::BLAKE2B {
+            return Err(FileError::NotResumable)
+        }
+        let os = Sha2State::new(&self.os_hash_function).ok_or(FileError::NotResumable)?;
+        return Ok(ParanoidHasher {
+            config: self.clone(),
+            bytes: 0,
+            blake2b: Blake2bState::new(self.digest_size),
+            os,
+        })
+    }
+}
+
+impl ParanoidHasher {
+    /// ## Return Config
+    pub fn return_config(&self) -> &ParanoidHash {
+        return &self.config
+    }
+    /// ## Return Bytes Hashed
+    ///
+    /// The number of bytes hashed so far, including those hashed before the state was saved.
+    pub fn return_bytes_hashed(&self) -> u64 {
+        return self.bytes
+    }
+    /// # Update
+    ///
+    /// Hashes `data` with both hash functions.
+    pub fn update(&mut self, data: &[u8]) {
+        self.blake2b.update(data);
+        self.os.update(data);
+        self.bytes += data.len() as u64;
+    }
+    /// # Finalize
+    ///
+    /// Returns the BLAKE2B and Operating System digests in the configured encoding, like `try_read_bytes()`.
+    pub fn finalize(self) -> Result<(String,String),FileError> {
+        self.config.check_empty(self.bytes)?;
+        return Ok(self.config.encode_pair((self.blake2b.finalize(), self.os.finalize())))
+    }
+    /// # Save State
+    ///
+    /// Writes the state of both hash functions, starting with `STATE_FORMAT_VERSION`.
+    pub fn save_state(&self) -> Vec<u8> {
+        let tag = self.config.algorithms_tag();
+        let mut state = Vec::with_capacity(512);
+        state.push(STATE_FORMAT_VERSION);
+        state.push(tag.len() as u8);
+        state.extend_from_slice(tag.as_bytes());
+        state.extend_from_slice(&self.bytes.to_be_bytes());
+        self.blake2b.save(&mut state);
+        self.os.save(&mut state);
+        return state
+    }
+    /// # Restore State
+    ///
+    /// Replaces the state of this hasher with one written by `save_state()`.
+    ///
+    /// Returns `FileError::UnsupportedStateVersion` for a format version this crate does not understand and `FileError::InvalidHasherState` if the state is malformed or was saved with a different digest size or different algorithms. The hasher is left unchanged on error.
+    pub fn restore_state(&mut self, state: &[u8]) -> Result<(),FileError> {
+        let mut reader = StateReader(state);
+        let version = reader.take(1)?[0];
+        if version != STATE_FORMAT_VERSION {
+            return Err(FileError::UnsupportedStateVersion(version))
+        }
+        let tag_len = reader.take(1)?[0] as usize;
+        if reader.take(tag_len)? != self.config.algorithms_tag().as_bytes() {
+            return Err(FileError::InvalidHasherState)
+        }
+        let bytes = reader.u64()?;
+
+        let mut blake2b = self.blake2b.clone();
+        blake2b.restore(&mut reader)?;
+        let mut os = self.os.clone();
+        os.restore(&mut reader)?;
+
+        if !reader.0.is_empty() || blake2b.length() != bytes as u128 || os.length != bytes as u128 {
+            return Err(FileError::InvalidHasherState)
+        }
+        self.bytes = bytes;
+        self.blake2b = blake2b;
+        self.os = os;
+        return Ok(())
+    }
+}
+
+// Reads the fields of a saved state in order
+struct StateReader<'a>(&'a [u8]);
+
+impl<'a> StateReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8],FileError> {
+        if self.0.len() < len {
+            return Err(FileError::InvalidHasherState)
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        return Ok(head)
+    }
+    fn u64(&mut self) -> Result<u64,FileError> {
+        return Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+    fn u128(&mut self) -> Result<u128,FileError> {
+        return Ok(u128::from_be_bytes(self.take(16)?.try_into().unwrap()))
+    }
+    // A length prefixed partial block of at most `max` bytes
+    fn block(&mut self, max: usize) -> Result<&'a [u8],FileError> {
+        let len = self.take(1)?[0] as usize;
+        if len > max {
+            return Err(FileError::InvalidHasherState)
+        }
+        return self.take(len)
+    }
+}
+
+// BLAKE2B (RFC 7693) without a key
+
+// The same as the SHA512 initial hash values
+const BLAKE2B_IV: [u64; 8] = H512;
+
+const SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+const BLAKE2B_BLOCK: usize = 128;
+
+#[derive(Debug,Clone)]
+struct Blake2bState {
+    h: [u64; 8],
+    // Bytes compressed so far
+    counter: u128,
+    buffer: Vec<u8>,
+    digest_size: usize,
+}
+
+impl Blake2bState {
+    fn new(digest_size: usize) -> Self {
+        let mut h = BLAKE2B_IV;
+        h[0] ^= 0x0101_0000 ^ digest_size as u64;
+        return Blake2bState { h, counter: 0, buffer: Vec::with_capacity(BLAKE2B_BLOCK), digest_size }
+    }
+    fn length(&self) -> u128 {
+        return self.counter + self.buffer.len() as u128
+    }
+    // The last block is only compressed in `finalize()`, so a full buffer is kept until more data arrives
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.buffer.len() == BLAKE2B_BLOCK {
+                self.counter += BLAKE2B_BLOCK as u128;
+                let block = std::mem::take(&mut self.buffer);
+                self.compress(&block, false);
+                self.buffer = block;
+                self.buffer.clear();
+            }
+            let len = (BLAKE2B_BLOCK - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..len]);
+            data = &data[len..];
+        }
+    }
+    fn finalize(mut self) -> Vec<u8> {
+        self.counter += self.buffer.len() as u128;
+        let mut block = std::mem::take(&mut self.buffer);
+        block.resize(BLAKE2B_BLOCK, 0);
+        self.compress(&block, true);
+        let mut digest: Vec<u8> = self.h.iter().flat_map(|word| word.to_le_bytes()).collect();
+        digest.truncate(self.digest_size);
+        return digest
+    }
+    fn compress(&mut self, block: &[u8], last: bool) {
+        let mut m = [0u64; 16];
+        for (word, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(&self.h);
+        v[8..].copy_from_slice(&BLAKE2B_IV);
+        v[12] ^= self.counter as u64;
+        v[13] ^= (self.counter >> 64) as u64;
+        if last {
+            v[14] = !v[14];
+        }
+        for s in SIGMA.iter() {
+            g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+            g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+            g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+            g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+            g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+            g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+            g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+            g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+        for i in 0..8 {
+            self.h[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+    fn save(&self, state: &mut Vec<u8>) {
+        for word in self.h.iter() {
+            state.extend_from_slice(&word.to_be_bytes());
+        }
+        state.extend_from_slice(&self.counter.to_be_bytes());
+        state.push(self.buffer.len() as u8);
+        state.extend_from_slice(&self.buffer);
+    }
+    fn restore(&mut self, reader: &mut StateReader) -> Result<(),FileError> {
+        for word in self.h.iter_mut() {
+            *word = reader.u64()?;
+        }
+        self.counter = reader.u128()?;
+        self.buffer = reader.block(BLAKE2B_BLOCK)?.to_vec();
+        // Only whole blocks are compressed and a full buffer is kept until more data arrives
+        if !self.counter.is_multiple_of(BLAKE2B_BLOCK as u128) || (self.buffer.is_empty() && self.counter != 0) {
+            return Err(FileError::InvalidHasherState)
+        }
+        return Ok(())
+    }
+}
+
+fn g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+// SHA-2 (FIPS 180-4): SHA224 and SHA256 on 32-bit words, SHA512 and SHA512_256 on 64-bit words. Only the buffering, padding and saved state are kept here; the blocks are compressed by the `sha2` crate.
+
+const H224: [u32; 8] = [0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4];
+const H256: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+const H512: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+const H512_256: [u64; 8] = [
+    0x22312194fc2bf72c, 0x9f555fa3c84c64c2, 0x2393b86b6f53b151, 0x963877195940eabd,
+    0x96283ee2a88effe3, 0xbe5e1e2553863992, 0x2b0199fc2c85b8aa, 0x0eb72ddc81c52ca2,
+];
+
+#[derive(Debug,Clone)]
+enum Sha2Words {
+    W32([u32; 8]),
+    W64([u64; 8]),
+}
+
+#[derive(Debug,Clone)]
+struct Sha2State {
+    words: Sha2Words,
+    // Bytes hashed so far, including those in the buffer
+    length: u128,
+    buffer: Vec<u8>,
+    digest_size: usize,
+}
+
+impl Sha2State {
+    fn new(os_hash: &OsAlgorithm) -> Option<Self> {
+        let (words, digest_size) = match os_hash {
+            OsAlgorithm::SHA224 => (Sha2Words::W32(H224), 28),
+            OsAlgorithm::SHA256 => (Sha2Words::W32(H256), 32),
+            OsAlgorithm::SHA512 => (Sha2Words::W64(H512), 64),
+            OsAlgorithm::SHA512_256 => (Sha2Words::W64(H512_256), 32),
+            _ => return None,
+        };
+        return Some(Sha2State { words, length: 0, buffer: Vec::with_capacity(128), digest_size })
+    }
+    fn block_size(&self) -> usize {
+        match self.words {
+            Sha2Words::W32(_) => return 64,
+            Sha2Words::W64(_) => return 128,
+        }
+    }
+    fn update(&mut self, mut data: &[u8]) {
+        let block_size = self.block_size();
+        self.length += data.len() as u128;
+        while !data.is_empty() {
+            let len = (block_size - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..len]);
+            data = &data[len..];
+            if self.buffer.len() == block_size {
+                let block = std::mem::take(&mut self.buffer);
+                self.compress(&block);
+                self.buffer = block;
+                self.buffer.clear();
+            }
+        }
+    }
+    fn finalize(mut self) -> Vec<u8> {
+        let block_size = self.block_size();
+        // The length in bits takes 8 bytes for 64 byte blocks and 16 bytes for 128 byte blocks
+        let length_size = block_size / 8;
+        let bits = (self.length << 3).to_be_bytes();
+        let mut tail = std::mem::take(&mut self.buffer);
+        tail.push(0x80);
+        while tail.len() % block_size != block_size - length_size {
+            tail.push(0);
+        }
+        tail.extend_from_slice(&bits[16 - length_size..]);
+        for block in tail.chunks_exact(block_size) {
+            self.compress(block);
+        }
+        let mut digest: Vec<u8> = match self.words {
+            Sha2Words::W32(h) => h.iter().flat_map(|word| word.to_be_bytes()).collect(),
+            Sha2Words::W64(h) => h.iter().flat_map(|word| word.to_be_bytes()).collect(),
+        };
+        digest.truncate(self.digest_size);
+        return digest
+    }
+    fn compress(&mut self, block: &[u8]) {
+        match &mut self.words {
+            Sha2Words::W32(h) => sha2::compress256(h, slice::from_ref(GenericArray::from_slice(block))),
+            Sha2Words::W64(h) => sha2::compress512(h, slice::from_ref(GenericArray::from_slice(block))),
+        }
+    }
+    fn save(&self, state: &mut Vec<u8>) {
+        match &self.words {
+            Sha2Words::W32(h) => h.iter().for_each(|word| state.extend_from_slice(&word.to_be_bytes())),
+            Sha2Words::W64(h) => h.iter().for_each(|word| state.extend_from_slice(&word.to_be_bytes())),
+        }
+        state.extend_from_slice(&self.length.to_be_bytes());
+        state.push(self.buffer.len() as u8);
+        state.extend_from_slice(&self.buffer);
+    }
+    fn restore(&mut self, reader: &mut StateReader) -> Result<(),FileError> {
+        match &mut self.words {
+            Sha2Words::W32(h) => for word in h.iter_mut() {
+                *word = u32::from_be_bytes(reader.take(4)?.try_into().unwrap());
+            },
+            Sha2Words::W64(h) => for word in h.iter_mut() {
+                *word = reader.u64()?;
+            },
+        }
+        self.length = reader.u128()?;
+        // A full buffer is always compressed, so at most one byte less than a block is kept
+        self.buffer = reader.block(self.block_size() - 1)?.to_vec();
+        if self.length % self.block_size() as u128 != self.buffer.len() as u128 {
+            return Err(FileError::InvalidHasherState)
+        }
+        return Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+// Retrying Transient I/O Errors
+//
+// Files on network filesystems can fail to open or read for a moment (a timeout or a stale NFS handle) and then work again. With a `RetryPolicy` the open and every read are tried again after a pause, and the read carries on from where it failed. `ErrorKind::Interrupted` is always retried straight away, with or without a policy.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, ErrorKind, Read};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::FileError;
+
+/// # Retry Policy
+///
+/// How often a failed file open or read is tried before giving up, set with `ParanoidHash::retry_policy()`.
+///
+/// * `max_attempts` is the number of tries of a single open or read, including the first
+/// * `backoff` is the pause before the first retry. It doubles before each retry after that.
+///
+/// Only errors that can go away by themselves are retried: `TimedOut`, `WouldBlock`, `ResourceBusy`, `StaleNetworkFileHandle`, `ConnectionReset`, `ConnectionAborted`, `BrokenPipe`, `NetworkDown`, `NetworkUnreachable` and `HostUnreachable`. Every other error (such as `NotFound`, `PermissionDenied` or `UnexpectedEof`) is returned straight away. When the attempts run out the error is returned as `FileError::RetriesExhausted`.
+///
+/// **Default** is 3 attempts with a backoff of 100 milliseconds
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self { RetryPolicy { max_attempts: 3, backoff: Duration::from_millis(100) } }
+}
+
+impl RetryPolicy {
+    // Calls `operation` until it succeeds, fails with an error that is not transient or runs out of attempts
+    fn run<T, F: FnMut() -> io::Result<T>>(&self, mut operation: F) -> io::Result<T> {
+        let mut attempts: u32 = 0;
+        let mut backoff = self.backoff;
+        loop {
+            let error = match operation() {
+                Ok(value) => return Ok(value),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => e,
+            };
+            attempts += 1;
+            if !transient(error.kind()) {
+                return Err(error)
+            }
+            if attempts >= self.max_attempts {
+                return Err(io::Error::new(error.kind(), Exhausted { attempts }))
+            }
+            thread::sleep(backoff);
+            backoff = backoff.saturating_mul(2);
+        }
+    }
+}
+
+// `Interrupted` is handled by `run()` before this is asked
+fn transient(kind: ErrorKind) -> bool {
+    match kind {
+        ErrorKind::TimedOut | ErrorKind::WouldBlock | ErrorKind::ResourceBusy | ErrorKind::StaleNetworkFileHandle => return true,
+        ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted | ErrorKind::BrokenPipe => return true,
+        ErrorKind::NetworkDown | ErrorKind::NetworkUnreachable | ErrorKind::HostUnreachable => return true,
+        _ => return false,
+    }
+}
+
+// Carried inside the `io::Error` of an operation that ran out of attempts, so the count survives the `Read` trait
+#[derive(Debug)]
+struct Exhausted {
+    attempts: u32,
+}
+
+impl fmt::Display for Exhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "gave up after {} attempts", self.attempts)
+    }
+}
+
+impl Error for Exhausted {}
+
+// Converts an I/O error into a `FileError`, keeping the attempt count of a retried operation
+pub(crate) fn io_error(error: io::Error) -> FileError {
+    match error.get_ref().and_then(|inner| inner.downcast_ref::<Exhausted>()) {
+        Some(exhausted) => return FileError::RetriesExhausted { kind: error.kind(), attempts: exhausted.attempts },
+        None => return FileError::Io(error.kind()),
+    }
+}
+
+// Opens the file, retrying transient errors under `policy`
+pub(crate) fn open(path: &Path, policy: Option<&RetryPolicy>) -> Result<File,FileError> {
+    match policy {
+        Some(policy) => return policy.run(|| File::open(path)).map_err(io_error),
+        None => return File::open(path).map_err(|e| FileError::Io(e.kind())),
+    }
+}
+
+// A reader whose failed reads are retried under the policy
+pub(crate) struct RetryReader<R> {
+    inner: R,
+    policy: Option<RetryPolicy>,
+}
+
+impl<R: Read> RetryReader<R> {
+    pub(crate) fn new(inner: R, policy: Option<RetryPolicy>) -> Self {
+        return RetryReader { inner, policy }
+    }
+}
+
+impl<R: Read> Read for RetryReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let inner = &mut self.inner;
+        match &self.policy {
+            Some(policy) => return policy.run(|| inner.read(buffer)),
+            None => return inner.read(buffer),
+        }
+    }
+}
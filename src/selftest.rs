@@ -0,0 +1,198 @@
+// Known-Answer Self Test
+//
+// Half of the hashing is done by the operating system's crypto library, so it is worth checking at runtime that this particular machine produces correct digests.
+
+use std::fmt;
+
+use crate::hashers::{Digester, OsOptions};
+use crate::{Algo, FileError, LibAlgorithm, OsAlgorithm, ParanoidHash};
+
+/// # Self Test Error
+///
+/// Returned by `ParanoidHash::self_test()` when a known-answer vector does not produce the expected digest.
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
+pub struct SelfTestError {
+    /// The hash function that failed
+    pub algorithm: Algo,
+    /// The name of the vector that failed
+    pub vector: &'static str,
+    /// The expected digest in upper hexadecimal
+    pub expected: &'static str,
+    /// The digest that was actually produced in upper hexadecimal
+    pub actual: String,
+}
+
+impl fmt::Display for SelfTestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "self test failed for {:?} on vector \"{}\": expected {}, got {}", self.algorithm, self.vector, self.expected, self.actual)
+    }
+}
+
+impl std::error::Error for SelfTestError {}
+
+// The message of the second NIST vector for SHA1, SHA224 and SHA256 (448 bits)
+const NIST_448: &[u8] = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+// The message of the second NIST vector for SHA512 (896 bits)
+const NIST_896: &[u8] = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+enum Input {
+    Bytes(&'static [u8]),
+    // The bytes 0, 1, 2, ... n-1 as used by the official BLAKE2 test vectors
+    Sequence(usize),
+}
+
+impl Input {
+    fn to_vec(&self) -> Vec<u8> {
+        match self {
+            Input::Bytes(bytes) => bytes.to_vec(),
+            Input::Sequence(length) => (0..*length).map(|i| i as u8).collect(),
+        }
+    }
+}
+
+struct Vector {
+    name: &'static str,
+    algorithm: Algo,
+    // Length of a sequential key (0x00, 0x01, ...) used with BLAKE2B
+    key: Option<usize>,
+    input: Input,
+    expected: &'static str,
+}
+
+const VECTORS: &[Vector] = &[
+    // RFC 7693 Appendix A
+    Vector { name: "RFC 7693 \"abc\"", algorithm: Algo::Blake2b(64), key: None, input: Input::Bytes(b"abc"), expected: "BA80A53F981C4D0D6A2797B69F12F6E94C212F14685AC4B74B12BB6FDBFFA2D17D87C5392AAB792DC252D5DE4533CC9518D38AA8DBF1925AB92386EDD4009923" },
+    Vector { name: "empty", algorithm: Algo::Blake2b(64), key: None, input: Input::Bytes(b""), expected: "786A02F742015903C6C6FD852552D272912F4740E15847618A86E217F71F5419D25E1031AFEE585313896444934EB04B903A685B1448B755D56F701AFE9BE2CE" },
+    Vector { name: "\"abc\" (32 bytes)", algorithm: Algo::Blake2b(32), key: None, input: Input::Bytes(b"abc"), expected: "BDDD813C634239723171EF3FEE98579B94964E3BB1CB3E427262C8C068D52319" },
+    Vector { name: "\"abc\" (20 bytes)", algorithm: Algo::Blake2b(20), key: None, input: Input::Bytes(b"abc"), expected: "384264F676F39536840523F284921CDC68B6846B" },
+    Vector { name: "\"abc\" (1 byte)", algorithm: Algo::Blake2b(1), key: None, input: Input::Bytes(b"abc"), expected: "6B" },
+    // Official BLAKE2 keyed test vectors (blake2b-kat.txt)
+    Vector { name: "keyed, empty", algorithm: Algo::Blake2b(64), key: Some(64), input: Input::Sequence(0), expected: "10EBB67700B1868EFB4417987ACF4690AE9D972FB7A590C2F02871799AAA4786B5E996E8F0F4EB981FC214B005F42D2FF4233499391653DF7AEFCBC13FC51568" },
+    Vector { name: "keyed, 1 byte", algorithm: Algo::Blake2b(64), key: Some(64), input: Input::Sequence(1), expected: "961F6DD1E4DD30F63901690C512E78E4B45E4742ED197C3C5E45C549FD25F2E4187B0BC9FE30492B16B0D0BC4EF9B0F34C7003FAC09A5EF1532E69430234CEBD" },
+    Vector { name: "keyed, 255 bytes", algorithm: Algo::Blake2b(64), key: Some(64), input: Input::Sequence(255), expected: "142709D62E28FCCCD0AF97FAD0F8465B971E82201DC51070FAA0372AA43E92484BE1C1E73BA10906D5D1853DB6A4106E0A7BF9800D373D6DEE2D46D62EF2A461" },
+    // FIPS 180 Examples
+    Vector { name: "NIST empty", algorithm: Algo::Os(OsAlgorithm::SHA1), key: None, input: Input::Bytes(b""), expected: "DA39A3EE5E6B4B0D3255BFEF95601890AFD80709" },
+    Vector { name: "NIST \"abc\"", algorithm: Algo::Os(OsAlgorithm::SHA1), key: None, input: Input::Bytes(b"abc"), expected: "A9993E364706816ABA3E25717850C26C9CD0D89D" },
+    Vector { name: "NIST 448 bits", algorithm: Algo::Os(OsAlgorithm::SHA1), key: None, input: Input::Bytes(NIST_448), expected: "84983E441C3BD26EBAAE4AA1F95129E5E54670F1" },
+    Vector { name: "NIST empty", algorithm: Algo::Os(OsAlgorithm::SHA224), key: None, input: Input::Bytes(b""), expected: "D14A028C2A3A2BC9476102BB288234C415A2B01F828EA62AC5B3E42F" },
+    Vector { name: "NIST \"abc\"", algorithm: Algo::Os(OsAlgorithm::SHA224), key: None, input: Input::Bytes(b"abc"), expected: "23097D223405D8228642A477BDA255B32AADBCE4BDA0B3F7E36C9DA7" },
+    Vector { name: "NIST 448 bits", algorithm: Algo::Os(OsAlgorithm::SHA224), key: None, input: Input::Bytes(NIST_448), expected: "75388B16512776CC5DBA5DA1FD890150B0C6455CB4F58B1952522525" },
+    Vector { name: "NIST empty", algorithm: Algo::Os(OsAlgorithm::SHA256), key: None, input: Input::Bytes(b""), expected: "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855" },
+    Vector { name: "NIST \"abc\"", algorithm: Algo::Os(OsAlgorithm::SHA256), key: None, input: Input::Bytes(b"abc"), expected: "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD" },
+    Vector { name: "NIST 448 bits", algorithm: Algo::Os(OsAlgorithm::SHA256), key: None, input: Input::Bytes(NIST_448), expected: "248D6A61D20638B8E5C026930C3E6039A33CE45964FF2167F6ECEDD419DB06C1" },
+    Vector { name: "NIST empty", algorithm: Algo::Os(OsAlgorithm::SHA512), key: None, input: Input::Bytes(b""), expected: "CF83E1357EEFB8BDF1542850D66D8007D620E4050B5715DC83F4A921D36CE9CE47D0D13C5D85F2B0FF8318D2877EEC2F63B931BD47417A81A538327AF927DA3E" },
+    Vector { name: "NIST \"abc\"", algorithm: Algo::Os(OsAlgorithm::SHA512), key: None, input: Input::Bytes(b"abc"), expected: "DDAF35A193617ABACC417349AE20413112E6FA4E89A97EA20A9EEEE64B55D39A2192992A274FC1A836BA3C23A3FEEBBD454D4423643CE80E2A9AC94FA54CA49F" },
+    Vector { name: "NIST 896 bits", algorithm: Algo::Os(OsAlgorithm::SHA512), key: None, input: Input::Bytes(NIST_896), expected: "8E959B75DAE313DA8CF4F72814FC143F8F7779C6EB9F7FA17299AEADB6889018501D289E4900F7E4331B99DEC4B5433AC7D329EEB6DD26545E96E55B874BE909" },
+    Vector { name: "NIST empty", algorithm: Algo::Os(OsAlgorithm::SHA512_256), key: None, input: Input::Bytes(b""), expected: "C672B8D1EF56ED28AB87C3622C5114069BDD3AD7B8F9737498D0C01ECEF0967A" },
+    Vector { name: "NIST \"abc\"", algorithm: Algo::Os(OsAlgorithm::SHA512_256), key: None, input: Input::Bytes(b"abc"), expected: "53048E2681941EF99B2E29B76B4C7DABE4C2D0C634FC6D46E0E2F13107E7AF23" },
+    Vector { name: "NIST 896 bits", algorithm: Algo::Os(OsAlgorithm::SHA512_256), key: None, input: Input::Bytes(NIST_896), expected: "3928E184FB8690F840DA3988121D31BE65CB9D3EF83EE6146FEAC861E19B563A" },
+    // RFC 1321 Appendix A.5
+    #[cfg(feature = "insecure-md5")]
+    Vector { name: "RFC 1321 empty", algorithm: Algo::Os(OsAlgorithm::MD5), key: None, input: Input::Bytes(b""), expected: "D41D8CD98F00B204E9800998ECF8427E" },
+    #[cfg(feature = "insecure-md5")]
+    Vector { name: "RFC 1321 \"abc\"", algorithm: Algo::Os(OsAlgorithm::MD5), key: None, input: Input::Bytes(b"abc"), expected: "900150983CD24FB0D6963F7D28E17F72" },
+    #[cfg(feature = "insecure-md5")]
+    Vector { name: "RFC 1321 \"message digest\"", algorithm: Algo::Os(OsAlgorithm::MD5), key: None, input: Input::Bytes(b"message digest"), expected: "F96B697D7CB7938D525A2F31AAF161D0" },
+];
+
+// The expected digest of "abc", used to probe whether the operating system provides `os_hash`
+pub(crate) fn abc_digest(os_hash: &OsAlgorithm) -> &'static str {
+    let vector = VECTORS.iter().find(|vector| vector.algorithm == Algo::Os(os_hash.clone()) && matches!(vector.input, Input::Bytes(b"abc")));
+    return vector.expect("[Error] Every Operating System Hash Function has an \"abc\" vector").expected
+}
+
+// BLAKE2B alone, so a broken Operating System Hash Function is never blamed on it
+fn hash_library_vector(digest_size: usize, key: Option<&[u8]>, input: &[u8]) -> Result<String,FileError> {
+    let mut digester = Digester::library(LibAlgorithm::BLAKE2B, digest_size, key, 1)?;
+    digester.update(input)?;
+    return Ok(hex::encode_upper(digester.finalize()?.0))
+}
+
+// Always uses the operating system, never the pure Rust fallback. `options` only differs from the default in the crate's own tests.
+fn hash_os_vector(os_hash: &OsAlgorithm, input: &[u8], options: OsOptions) -> Result<String,FileError> {
+    let mut digester = Digester::os(os_hash, options)?;
+    digester.update(input)?;
+    return Ok(hex::encode_upper(digester.finalize()?.0))
+}
+
+impl ParanoidHash {
+    /// # Self Test
+    ///
+    /// Runs a set of embedded known-answer vectors through the same code used by `read_bytes()` and `read_with_key()`:
+    ///
+    /// * RFC 7693 and the official BLAKE2 vectors for BLAKE2B (including keyed hashing and several digest sizes)
+    /// * NIST FIPS 180 vectors for SHA1, SHA224, SHA256, SHA512 and SHA512_256
+    /// * RFC 1321 vectors for MD5 (with the `insecure-md5` feature)
+    ///
+    /// Every `OsAlgorithm` is covered. Call this once when your program starts and refuse to continue if it fails.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// ParanoidHash::self_test().expect("hash functions on this machine are broken");
+    /// ```
+    ///
+    /// If the Operating System Hash Function fails outright, `actual` holds the error message instead of a digest.
+    pub fn self_test() -> Result<(),SelfTestError> {
+        return run_self_test(OsOptions::default())
+    }
+}
+
+fn run_self_test(options: OsOptions) -> Result<(),SelfTestError> {
+    for vector in VECTORS {
+        let input = vector.input.to_vec();
+        let key: Option<Vec<u8>> = vector.key.map(|length| (0..length).map(|i| i as u8).collect());
+
+        let actual = match &vector.algorithm {
+            Algo::Blake2b(digest_size) => hash_library_vector(*digest_size, key.as_deref(), &input),
+            Algo::Os(os_hash) => hash_os_vector(os_hash, &input, options),
+            #[cfg(feature = "blake2-parallel")]
+            Algo::Blake2bp(_) | Algo::Blake2sp(_) => unreachable!(),
+            #[cfg(feature = "xof")]
+            Algo::Shake256(_) => unreachable!(),
+        };
+        // A failing Operating System Hash Function is reported as the vector's output
+        let actual = actual.unwrap_or_else(|e| e.to_string());
+
+        if actual != vector.expected {
+            return Err(SelfTestError {
+                algorithm: vector.algorithm.clone(),
+                vector: vector.name,
+                expected: vector.expected,
+                actual,
+            })
+        }
+    }
+    return Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_self_test;
+    use crate::hashers::OsOptions;
+    use crate::{Algo, FileError, OsAlgorithm, SelfTestError};
+
+    #[test]
+    fn wrong_digest_is_reported(){
+        // The first bit of every operating system digest is flipped, so the BLAKE2B vectors still pass and the first SHA1 vector fails
+        let options = OsOptions { simulate_mismatch: true, ..OsOptions::default() };
+        let error = run_self_test(options).unwrap_err();
+        assert_eq!(error,SelfTestError {
+            algorithm: Algo::Os(OsAlgorithm::SHA1),
+            vector: "NIST empty",
+            expected: "DA39A3EE5E6B4B0D3255BFEF95601890AFD80709",
+            actual: "5A39A3EE5E6B4B0D3255BFEF95601890AFD80709".to_string(),
+        });
+        assert_eq!(error.to_string(),"self test failed for Os(SHA1) on vector \"NIST empty\": expected DA39A3EE5E6B4B0D3255BFEF95601890AFD80709, got 5A39A3EE5E6B4B0D3255BFEF95601890AFD80709");
+    }
+
+    #[test]
+    fn failing_backend_is_reported(){
+        // The BLAKE2B vectors do not use the operating system, so the failure is reported against the first SHA1 vector
+        let options = OsOptions { simulate_failure: true, ..OsOptions::default() };
+        let error = run_self_test(options).unwrap_err();
+        assert_eq!(error.algorithm,Algo::Os(OsAlgorithm::SHA1));
+        assert_eq!(error.vector,"NIST empty");
+        assert_eq!(error.actual,FileError::OsHashingError.to_string());
+    }
+}
@@ -0,0 +1,74 @@
+// Hashing Session
+//
+// Keeps the output strings between calls so hashing many small inputs does not allocate a new pair of `String`s every time.
+
+use crate::hashers::Digester;
+use crate::{FileError, ParanoidHash};
+
+/// # Hashing Session
+///
+/// A reusable hasher for hot loops, created with `ParanoidHash::session()`. The digests are written into buffers owned by the session, so the strings returned by one call are only valid until the next.
+///
+/// Compared to `read_bytes()`, a session does not allocate the two digest strings or the BLAKE2B digest. The Operating System Hash Function still allocates its own context and digest on every call.
+///
+/// ```rust
+/// use paranoid_hash::ParanoidHash;
+///
+/// let context = ParanoidHash::default();
+/// let mut session = context.session();
+///
+/// for message in ["first","second","third"] {
+///     let (blake2b,sha512) = session.hash_bytes_into(message.as_bytes()).unwrap();
+///     assert_eq!((blake2b.to_string(),sha512.to_string()),context.read_str(message));
+/// }
+/// ```
+#[derive(Debug,Clone)]
+pub struct ParanoidHashSession {
+    config: ParanoidHash,
+    library: String,
+    os: String,
+}
+
+impl ParanoidHash {
+    /// # Session
+    ///
+    /// Creates a `ParanoidHashSession` with this configuration.
+    pub fn session(&self) -> ParanoidHashSession {
+        return ParanoidHashSession::new(self.clone())
+    }
+}
+
+impl ParanoidHashSession {
+    /// ## New Session
+    pub fn new(config: ParanoidHash) -> Self {
+        return ParanoidHashSession {
+            config,
+            library: String::new(),
+            os: String::new(),
+        }
+    }
+    /// ## Return Config
+    pub fn return_config(&self) -> &ParanoidHash {
+        return &self.config
+    }
+    /// # Hash Bytes Into
+    ///
+    /// Same as `try_read_bytes()`, but writes the digests into the session's buffers and returns them borrowed. The returned strings are overwritten by the next call.
+    pub fn hash_bytes_into(&mut self, data: &[u8]) -> Result<(&str,&str),FileError> {
+        self.config.check_empty(data.len() as u64)?;
+
+        let mut library = Digester::new(&self.config.library_algo(), self.config.thread_count())?;
+        library.update(data)?;
+        let (library, _) = library.finalize()?;
+
+        let mut os = Digester::os(&self.config.os_hash_function, self.config.os_options)?;
+        os.update(data)?;
+        let (os, _) = os.finalize()?;
+
+        self.library.clear();
+        self.os.clear();
+        self.config.encoding.encode_into(&library, &mut self.library);
+        self.config.encoding.encode_into(&os, &mut self.os);
+        return Ok((&self.library, &self.os))
+    }
+}
@@ -0,0 +1,132 @@
+// Sidecar Checksum Files
+//
+// Verifies a file against the digests published next to it, such as `file.iso.sha256` or `file.iso.b2`. A sidecar holds a bare digest or a single GNU line (`<hex digest>  <name>`). All the sidecars found are checked against one pass over the file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::checksum_file::parse_line;
+use crate::digest::constant_time_eq;
+use crate::{Algo, FileError, LibAlgorithm, OsAlgorithm, ParanoidHash, ParanoidHashMulti};
+
+// The extensions looked for, in the order they are checked. `None` means BLAKE2B with the digest size taken from the digest (as written by `b2sum -l`).
+const SIDECAR_EXTENSIONS: &[(&str, Option<OsAlgorithm>)] = &[
+    #[cfg(feature = "insecure-md5")]
+    ("md5", Some(OsAlgorithm::MD5)),
+    ("sha1", Some(OsAlgorithm::SHA1)),
+    ("sha224", Some(OsAlgorithm::SHA224)),
+    ("sha256", Some(OsAlgorithm::SHA256)),
+    ("sha512", Some(OsAlgorithm::SHA512)),
+    ("b2", None),
+];
+
+/// # Sidecar Outcome
+///
+/// The result of checking a file against its sidecar checksum files.
+#[derive(Debug,Clone,PartialEq,Eq,PartialOrd,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SidecarOutcome {
+    /// Every sidecar matches the file. Holds the sidecars checked.
+    Match(Vec<PathBuf>),
+    /// At least one sidecar does not match the file. Holds the sidecars that do not match.
+    Mismatch(Vec<PathBuf>),
+    /// There is no sidecar next to the file
+    NoSidecarFound,
+}
+
+impl ParanoidHash {
+    /// # Verify With Sidecar
+    ///
+    /// Looks for sidecar checksum files next to `path` (its name with `.sha1`, `.sha224`, `.sha256`, `.sha512` or `.b2` appended, and `.md5` with the `insecure-md5` feature), hashes the file once with every hash function they need and checks each of them.
+    ///
+    /// * A sidecar holds a bare hexadecimal digest or a GNU line (`<hex digest>  <name>`, as written by `sha256sum`). Blank lines, comments (`#`), surrounding whitespace and line endings are ignored. If the sidecar lists several names, the line naming the file is used.
+    /// * The extension picks the hash function. `.b2` is BLAKE2B with the digest size taken from the length of the digest.
+    ///
+    /// The hash function, digest size and Output Encoding of this context do not apply; `max_file_size()` does. A sidecar that cannot be read returns `FileError::AtPath` with its error, and one without a usable digest (or with a digest of the wrong length for its extension) returns `FileError::AtPath` holding `FileError::InvalidDigest`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm,SidecarOutcome};
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let file = dir.path().join("data.txt");
+    /// std::fs::write(&file,"data").unwrap();
+    ///
+    /// let context = ParanoidHash::default();
+    /// assert_eq!(context.verify_with_sidecar(&file).unwrap(),SidecarOutcome::NoSidecarFound);
+    ///
+    /// let sha256 = ParanoidHash::new(64,OsAlgorithm::SHA256).read(&file).unwrap().1;
+    /// std::fs::write(dir.path().join("data.txt.sha256"),format!("{}  data.txt\n",sha256)).unwrap();
+    /// assert_eq!(context.verify_with_sidecar(&file).unwrap(),SidecarOutcome::Match(vec![dir.path().join("data.txt.sha256")]));
+    /// ```
+    pub fn verify_with_sidecar<T: AsRef<Path>>(&self, path: T) -> Result<SidecarOutcome,FileError> {
+        let path = path.as_ref();
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+
+        let mut sidecars: Vec<(PathBuf,Algo,Vec<u8>)> = Vec::new();
+        for (extension, os_hash) in SIDECAR_EXTENSIONS.iter() {
+            let mut name = path.as_os_str().to_owned();
+            name.push(".");
+            name.push(extension);
+            let sidecar = PathBuf::from(name);
+            if !sidecar.is_file() {
+                continue
+            }
+            let (algo, expected) = read_sidecar(&sidecar, path, os_hash.as_ref()).map_err(|e| FileError::AtPath(sidecar.clone(), Box::new(e)))?;
+            sidecars.push((sidecar, algo, expected));
+        }
+        if sidecars.is_empty() {
+            return Ok(SidecarOutcome::NoSidecarFound)
+        }
+
+        self.check_file_size(path)?;
+        let algorithms: Vec<Algo> = sidecars.iter().map(|(_, algo, _)| algo.clone()).collect();
+        let digests = ParanoidHashMulti::new(&algorithms).read_raw(path)?;
+
+        let mut mismatched = Vec::new();
+        for (sidecar, algo, expected) in sidecars.iter() {
+            let matches = digests.iter().any(|(computed, digest)| computed == algo && constant_time_eq(expected, digest));
+            if !matches {
+                mismatched.push(sidecar.clone());
+            }
+        }
+        if !mismatched.is_empty() {
+            return Ok(SidecarOutcome::Mismatch(mismatched))
+        }
+        return Ok(SidecarOutcome::Match(sidecars.into_iter().map(|(sidecar, _, _)| sidecar).collect()))
+    }
+}
+
+// The hash function and expected digest of a sidecar of `file`
+fn read_sidecar(sidecar: &Path, file: &Path, os_hash: Option<&OsAlgorithm>) -> Result<(Algo,Vec<u8>),FileError> {
+    let contents = fs::read_to_string(sidecar).map_err(|e| FileError::Io(e.kind()))?;
+    let digest = sidecar_digest(&contents, file).ok_or(FileError::InvalidDigest)?;
+    let expected = hex::decode(digest).map_err(|_| FileError::InvalidDigest)?;
+
+    let algo = match os_hash {
+        Some(os_hash) if expected.len() == os_hash.digest_len() => Algo::Os(os_hash.clone()),
+        Some(_) => return Err(FileError::InvalidDigest),
+        None if (1..=LibAlgorithm::BLAKE2B.max_digest_size()).contains(&expected.len()) => Algo::Blake2b(expected.len()),
+        None => return Err(FileError::InvalidDigest),
+    };
+    return Ok((algo, expected))
+}
+
+// The digest on the first bare line or the first GNU line naming `file`
+fn sidecar_digest<'a>(contents: &'a str, file: &Path) -> Option<&'a str> {
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue
+        }
+        match parse_line(line) {
+            Some((digest, name)) if Path::new(name.as_ref()).file_name() == file.file_name() => return Some(digest),
+            Some(_) => continue,
+            None if !line.contains(char::is_whitespace) => return Some(line),
+            None => continue,
+        }
+    }
+    return None
+}
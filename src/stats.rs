@@ -0,0 +1,125 @@
+// Hashing Statistics
+//
+// Timing is only measured by the `*_with_stats()` functions. The digests are the same as the functions without stats.
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use filebuffer::FileBuffer;
+
+use crate::{long_path, trace, FileError, ParanoidHash};
+
+/// # Hash Statistics
+///
+/// How much data was hashed and how long it took.
+#[derive(Debug,Clone,Copy,PartialEq,PartialOrd)]
+pub struct HashStats {
+    /// The number of bytes hashed. For a file this is its length; for a reader, everything read from it up to the end.
+    pub bytes: u64,
+    /// Time spent opening the file (zero when hashing data that is already in memory)
+    pub open_elapsed: Duration,
+    /// Time spent hashing, not including opening the file
+    pub elapsed: Duration,
+    /// Hashing throughput in megabytes (10^6 bytes) per second, based on `elapsed`. Zero if `elapsed` is too small to measure.
+    pub mb_per_sec: f64,
+    /// `true` if the Operating System Hash Function failed and its digest was computed by the pure Rust fallback (see `os_fallback()`)
+    pub os_fallback: bool,
+    /// `true` if the file's size or modification time changed while it was hashed, with `ModificationCheck::Flag` (see `modification_check()`)
+    pub modified: bool,
+}
+
+impl HashStats {
+    pub(crate) fn new(bytes: u64, open_elapsed: Duration, elapsed: Duration) -> Self {
+        let seconds = elapsed.as_secs_f64();
+        let mb_per_sec = if seconds > 0.0 { bytes as f64 / 1_000_000.0 / seconds } else { 0.0 };
+
+        return HashStats {
+            bytes,
+            open_elapsed,
+            elapsed,
+            mb_per_sec,
+            os_fallback: false,
+            modified: false,
+        }
+    }
+}
+
+impl ParanoidHash {
+    /// # Read With Stats
+    ///
+    /// Same as `read()`, but also returns how many bytes were hashed and how long opening and hashing the file took.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let ((blake2b,sha512),stats) = context.read_with_stats("examples/example_file.txt").unwrap();
+    /// println!("hashed {} bytes at {:.1} MB/s",stats.bytes,stats.mb_per_sec);
+    /// ```
+    pub fn read_with_stats<T: AsRef<Path>>(&self, path: T) -> Result<((String,String),HashStats),FileError> {
+        return self.hash_path(path.as_ref(), None)
+    }
+    /// # Read In Parallel With Stats
+    ///
+    /// Same as `read_parallel()`, but also returns how many bytes were hashed and how long opening and hashing the file took.
+    pub fn read_parallel_with_stats<T: AsRef<Path>>(&self, path: T) -> Result<((String,String),HashStats),FileError> {
+        let path = long_path::extended(path.as_ref());
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.as_ref().exists() {
+            return Err(FileError::FileNotFound)
+        }
+        self.check_file_size(path.as_ref())?;
+
+        let open_start = Instant::now();
+        let fbuffer = match fs::metadata(path.as_ref()) {
+            Ok(metadata) if metadata.is_file() && metadata.len() <= self.mmap_limit() => FileBuffer::open(path.as_ref()).ok(),
+            _ => None,
+        };
+        let fbuffer = match fbuffer {
+            Some(fbuffer) => fbuffer,
+            // Too large to map, hashed sequentially in windows
+            None => return self.read_with_stats(path),
+        };
+        let open_elapsed = open_start.elapsed();
+        self.check_empty(fbuffer.len() as u64)?;
+
+        let hash_start = Instant::now();
+        let (digests, os_fallback) = self.hash_bytes_parallel(&fbuffer)?;
+        let mut stats = HashStats::new(fbuffer.len() as u64, open_elapsed, hash_start.elapsed());
+        stats.os_fallback = os_fallback;
+        return Ok((digests, stats))
+    }
+    /// # Read From Reader With Stats
+    ///
+    /// Same as `read_from_reader()`, but also returns how many bytes were read from `reader` and how long hashing them took.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let (digests,stats) = context.read_from_reader_with_stats(&b"abc"[..]).unwrap();
+    /// assert_eq!(digests,context.read_str("abc"));
+    /// assert_eq!(stats.bytes,3);
+    /// ```
+    pub fn read_from_reader_with_stats<R: Read>(&self, reader: R) -> Result<((String,String),HashStats),FileError> {
+        let span = trace::Span::reader(self);
+        let result = span.in_scope(|| self.hash_reader(reader));
+        span.finish(result.as_ref().map(|(_, stats)| stats.bytes));
+        return result
+    }
+    /// # Read Bytes With Stats
+    ///
+    /// Same as `read_bytes()`, but also returns how long hashing took.
+    /// 
+    /// Panics if the Operating System Hash Function fails and `os_fallback()` is not enabled.
+    pub fn read_bytes_with_stats(&self, bytes: &[u8]) -> ((String,String),HashStats) {
+        let start = Instant::now();
+        let (digests, os_fallback) = self.hash_bytes(bytes).expect("[Error] Failed To Hash Using Operating System Hash Function");
+        let mut stats = HashStats::new(bytes.len() as u64, Duration::from_secs(0), start.elapsed());
+        stats.os_fallback = os_fallback;
+        return (digests, stats)
+    }
+}
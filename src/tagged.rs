@@ -0,0 +1,122 @@
+// Tagged Digests
+//
+// A self-describing string holding both digests along with the hash functions and digest size that produced them, so they can be verified later without remembering the configuration.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::{FileError, LibAlgorithm, OsAlgorithm, OutputEncoding, ParanoidHash};
+
+/// # Tagged Parse Error
+///
+/// Returned when a tagged digest string cannot be read or written.
+#[derive(Debug,Clone,PartialEq,Eq,PartialOrd,Hash)]
+pub enum TaggedParseError {
+    /// The string is not two `tag:digest` parts separated by `;`
+    Malformed,
+    /// A tag names a hash function this crate does not know
+    UnknownAlgorithm(String),
+    /// The library digest size is not a number from 1 to 64 (1 to 32 for BLAKE2SP)
+    InvalidDigestSize,
+    /// A digest is not hexadecimal or its length does not match its tag
+    InvalidDigest,
+}
+
+impl fmt::Display for TaggedParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaggedParseError::Malformed => write!(f, "tagged digest is malformed"),
+            TaggedParseError::UnknownAlgorithm(tag) => write!(f, "unknown algorithm tag {:?}", tag),
+            TaggedParseError::InvalidDigestSize => write!(f, "invalid library digest size"),
+            TaggedParseError::InvalidDigest => write!(f, "digest does not match its tag"),
+        }
+    }
+}
+
+impl std::error::Error for TaggedParseError {}
+
+impl ParanoidHash {
+    /// # To Tagged String
+    ///
+    /// Writes the digests returned by this context (in its Output Encoding) as a self-describing string:
+    ///
+    /// ```text
+    /// blake2b-<digest size>:<BLAKE2B digest>;<os tag>:<Operating System digest>
+    /// ```
+    ///
+    /// The Operating System tag is one of `sha1`, `sha256`, `sha512`, `sha512_256` or `md5` (with the `insecure-md5` feature). With the `blake2-parallel` feature the library tag can also be `blake2bp` or `blake2sp`, and with the `xof` feature `shake256`. Digests are always written in upper hexadecimal.
+    ///
+    /// **This format is stable.** Strings written by this version will be accepted by `parse_tagged()` in every future version.
+    ///
+    /// Returns `TaggedParseError::InvalidDigest` if a digest was not produced by this configuration.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    ///
+    /// let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    /// let (blake2b,sha256) = context.read_str("Hello");
+    /// let tagged = context.to_tagged_string(&blake2b,&sha256).unwrap();
+    /// assert!(tagged.starts_with("blake2b-32:"));
+    /// assert!(tagged.contains(";sha256:"));
+    /// ```
+    pub fn to_tagged_string<A: AsRef<str>, B: AsRef<str>>(&self, blake2b: A, os: B) -> Result<String,TaggedParseError> {
+        let blake2b = decode_sized(self.encoding, blake2b.as_ref(), self.digest_size)?;
+        let os = decode_sized(self.encoding, os.as_ref(), self.os_hash_function.digest_len())?;
+
+        return Ok(self.tag(&blake2b, &os))
+    }
+    /// # To Tagged String For File
+    ///
+    /// Hashes the file and returns its digests as a tagged string (see `to_tagged_string()`).
+    pub fn to_tagged_string_for_file<T: AsRef<Path>>(&self, path: T) -> Result<String,FileError> {
+        let ((blake2b, os), _) = self.hash_path_raw(path.as_ref(), None)?;
+        return Ok(self.tag(&blake2b, &os))
+    }
+    /// # Parse Tagged
+    ///
+    /// Reads a string written by `to_tagged_string()` and returns a `ParanoidHash` configured with the same library hash function, digest size and Operating System Hash Function, along with the expected library and Operating System digests in upper hexadecimal.
+    ///
+    /// Tags are case-insensitive, digests can be in either case and surrounding whitespace is ignored. An unknown tag is rejected with `TaggedParseError::UnknownAlgorithm`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,VerifyPolicy};
+    ///
+    /// let tagged = ParanoidHash::default().to_tagged_string_for_file("examples/example_file.txt").unwrap();
+    /// let (context,(blake2b,os)) = ParanoidHash::parse_tagged(&tagged).unwrap();
+    /// let verification = context.verify_file("examples/example_file.txt",Some(&blake2b),Some(&os),VerifyPolicy::Both).unwrap();
+    /// assert!(verification.passed);
+    /// ```
+    pub fn parse_tagged<T: AsRef<str>>(s: T) -> Result<(ParanoidHash,(String,String)),TaggedParseError> {
+        let (library, os) = s.as_ref().trim().split_once(';').ok_or(TaggedParseError::Malformed)?;
+        let (library_tag, library_digest) = library.split_once(':').ok_or(TaggedParseError::Malformed)?;
+        let (os_tag, os_digest) = os.split_once(':').ok_or(TaggedParseError::Malformed)?;
+
+        let (library_algorithm, digest_size) = match library_tag.to_ascii_lowercase().rsplit_once('-') {
+            Some((name, size)) => match (LibAlgorithm::from_tag(name), size.parse::<usize>()) {
+                (Some(library), Ok(size)) if size > 0 && size <= library.max_digest_size() => (library, size),
+                (Some(_), _) => return Err(TaggedParseError::InvalidDigestSize),
+                (None, _) => return Err(TaggedParseError::UnknownAlgorithm(library_tag.to_string())),
+            },
+            None => return Err(TaggedParseError::UnknownAlgorithm(library_tag.to_string())),
+        };
+        let os_algorithm = os_tag.parse::<OsAlgorithm>()?;
+
+        let library_digest = decode_sized(OutputEncoding::HexUpper, library_digest, digest_size)?;
+        let os_digest = decode_sized(OutputEncoding::HexUpper, os_digest, os_algorithm.digest_len())?;
+
+        // The algorithms were chosen when the digests were recorded, so weak ones are accepted
+        let context = ParanoidHash::build(library_algorithm, digest_size, os_algorithm);
+        return Ok((context, (hex::encode_upper(library_digest), hex::encode_upper(os_digest))))
+    }
+    fn tag(&self, blake2b: &[u8], os: &[u8]) -> String {
+        return format!("{}-{}:{};{}:{}", self.library_hash_function.tag(), self.digest_size, hex::encode_upper(blake2b), self.os_hash_function.tag(), hex::encode_upper(os))
+    }
+}
+
+// Decodes a digest that must be `length` bytes long
+fn decode_sized(encoding: OutputEncoding, digest: &str, length: usize) -> Result<Vec<u8>,TaggedParseError> {
+    match encoding.decode(digest) {
+        Some(bytes) if bytes.len() == length => return Ok(bytes),
+        _ => return Err(TaggedParseError::InvalidDigest),
+    }
+}
@@ -0,0 +1,51 @@
+// Hashing With A Timeout
+//
+// Puts an upper bound on how long a file is read, for network filesystems and FUSE mounts that can stall on a dead server. The deadline is checked between chunks, like cancellation.
+
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::hashers::CHUNK_SIZE;
+use crate::retry::RetryReader;
+use crate::{FileError, ParanoidHash};
+
+impl ParanoidHash {
+    /// # Read With Timeout
+    ///
+    /// Streams the file through both hash functions, returning `FileError::TimedOut` with the number of bytes hashed so far once `timeout` has passed. The partial hash state is discarded.
+    ///
+    /// The time is checked between chunks, so a single read that blocks (such as on an unresponsive network filesystem) is not interrupted and can still run past the timeout. Opening the file is not covered by the timeout either.
+    ///
+    /// Otherwise the file is opened and read like `read_using_std()`, with the same size limit, Retry Policy, Read Tuning and Modification Check.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    /// use std::time::Duration;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let digests = context.read_with_timeout("examples/example_file.txt",Duration::from_secs(60)).unwrap();
+    /// assert_eq!(digests,context.read("examples/example_file.txt").unwrap());
+    /// ```
+    pub fn read_with_timeout<T: AsRef<Path>>(&self, path: T, timeout: Duration) -> Result<(String,String),FileError> {
+        return self.read_file_with(path.as_ref(), |reader| self.read_from_reader_with_timeout(reader, timeout)).map(|(digests, _)| digests)
+    }
+    /// # Read From Reader With Timeout
+    ///
+    /// Same as `read_from_reader()`, but returns `FileError::TimedOut` once `timeout` has passed, checked between chunks.
+    pub fn read_from_reader_with_timeout<R: Read>(&self, reader: R, timeout: Duration) -> Result<(String,String),FileError> {
+        let start = Instant::now();
+        let mut hasher = self.pair_hasher(None)?;
+
+        let reader = RetryReader::new(reader, self.retry_policy);
+        let length = hasher.update_reader_checked(BufReader::with_capacity(CHUNK_SIZE, reader), |total| {
+            if start.elapsed() > timeout {
+                return Err(FileError::TimedOut { bytes: total })
+            }
+            return self.check_size(total)
+        })?;
+        self.check_empty(length)?;
+
+        return Ok(self.encode_pair(hasher.finalize_pair()?.0))
+    }
+}
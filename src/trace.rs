@@ -0,0 +1,134 @@
+// Tracing
+//
+// Spans and events for the `tracing` feature. Every span is at the DEBUG level with the target `paranoid_hash` and carries the path (if any), the hash functions as `<library tag>-<digest size>+<os tag>` and, once it closes, the number of bytes hashed and the duration in microseconds. Reads of a single file record whether it was memory mapped (`mmap`), read in windows (`window`), streamed (`stream`) or read with `read_using_std()` (`std`), and `verify_file` spans record whether the verification passed.
+//
+// Without the feature `Span` is empty and every function does nothing, so the calls compile away and `tracing` is not a dependency.
+
+use std::path::Path;
+
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+use crate::{FileError, ParanoidHash};
+
+/// An operation being traced.
+pub(crate) struct Span {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: Instant,
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! span {
+    ($name:expr, $context:expr, $path:expr $(, $field:ident)*) => {
+        Span {
+            span: tracing::debug_span!(target: "paranoid_hash", $name, path = %$path.display(), algorithm = %$context.algorithms_tag(), backend = tracing::field::Empty, bytes = tracing::field::Empty, duration_us = tracing::field::Empty $(, $field = tracing::field::Empty)*),
+            start: Instant::now(),
+        }
+    };
+}
+
+#[cfg(feature = "tracing")]
+impl Span {
+    pub(crate) fn read(context: &ParanoidHash, path: &Path) -> Span {
+        return span!("read", context, path)
+    }
+    pub(crate) fn read_dir(context: &ParanoidHash, dir: &Path) -> Span {
+        return span!("read_dir", context, dir)
+    }
+    pub(crate) fn verify(context: &ParanoidHash, path: &Path) -> Span {
+        return span!("verify_file", context, path, passed)
+    }
+    pub(crate) fn reader(context: &ParanoidHash) -> Span {
+        return Span {
+            span: tracing::debug_span!(target: "paranoid_hash", "read_from_reader", algorithm = %context.algorithms_tag(), backend = "stream", bytes = tracing::field::Empty, duration_us = tracing::field::Empty),
+            start: Instant::now(),
+        }
+    }
+    pub(crate) fn in_scope<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        return self.span.in_scope(f)
+    }
+    pub(crate) fn backend(&self, backend: &'static str) {
+        self.span.record("backend", backend);
+    }
+    pub(crate) fn verified(&self, passed: bool) {
+        self.span.record("passed", passed);
+    }
+    // Records the bytes hashed and the duration, or emits an event for the error
+    pub(crate) fn finish(&self, bytes: Result<u64,&FileError>) {
+        match bytes {
+            Ok(bytes) => { self.span.record("bytes", bytes); }
+            Err(error) => self.span.in_scope(|| tracing::debug!(target: "paranoid_hash", error = %error, "hashing failed")),
+        }
+        self.span.record("duration_us", self.start.elapsed().as_micros() as u64);
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl Span {
+    #[inline(always)]
+    pub(crate) fn read(_context: &ParanoidHash, _path: &Path) -> Span { Span {} }
+    #[inline(always)]
+    pub(crate) fn read_dir(_context: &ParanoidHash, _dir: &Path) -> Span { Span {} }
+    #[inline(always)]
+    pub(crate) fn verify(_context: &ParanoidHash, _path: &Path) -> Span { Span {} }
+    #[inline(always)]
+    pub(crate) fn reader(_context: &ParanoidHash) -> Span { Span {} }
+    #[inline(always)]
+    pub(crate) fn in_scope<T, F: FnOnce() -> T>(&self, f: F) -> T { f() }
+    #[inline(always)]
+    pub(crate) fn backend(&self, _backend: &'static str) {}
+    #[inline(always)]
+    pub(crate) fn verified(&self, _passed: bool) {}
+    #[inline(always)]
+    pub(crate) fn finish(&self, _bytes: Result<u64,&FileError>) {}
+}
+
+/// Emitted when the Operating System Hash Function fails. `fallback` is whether the pure Rust fallback takes over.
+#[inline(always)]
+pub(crate) fn os_failure(fallback: bool) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(target: "paranoid_hash", fallback, "operating system hash function failed");
+    #[cfg(not(feature = "tracing"))]
+    let _ = fallback;
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use crate::ParanoidHash;
+
+    // Collects everything the subscriber writes
+    #[derive(Clone,Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            return Ok(bytes.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            return Ok(())
+        }
+    }
+
+    #[test]
+    fn os_failure_event(){
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_max_level(tracing::Level::DEBUG)
+            .without_time()
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(ParanoidHash::default().simulate_os_failure(true).read("examples/example_file.txt").is_err());
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("operating system hash function failed fallback=false"),"{}",output);
+    }
+}
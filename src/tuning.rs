@@ -0,0 +1,66 @@
+// Read Tuning
+//
+// Hints to the operating system about how a file is about to be read. Reading a large file that is not in the page cache is faster when the kernel knows to read ahead aggressively, and dropping the file's pages afterwards keeps a one-off hash from pushing everything else out of the cache.
+
+use std::fs::File;
+
+/// # Read Tuning
+///
+/// Access pattern hints given to the operating system for files that are streamed rather than memory mapped, set with `ParanoidHash::read_tuning()`.
+///
+/// * `sequential` advises that the file will be read from start to end once (`POSIX_FADV_SEQUENTIAL`), so the kernel reads further ahead
+/// * `drop_cache` advises that the file's pages will not be needed again once it has been hashed (`POSIX_FADV_DONTNEED`), so hashing a large cold file does not evict more useful data from the page cache
+///
+/// The hints only take effect on Linux and Android with the `fadvise` feature, which calls `posix_fadvise` through the `rustix` crate so this crate itself stays free of `unsafe` code. Everywhere else they are ignored. Failed hints (such as on a pipe) are ignored too. The digests never depend on the hints.
+///
+/// **Default** gives no hints
+///
+/// ```rust
+/// use paranoid_hash::{ParanoidHash,ReadTuning};
+///
+/// let tuning = ReadTuning { sequential: true, drop_cache: true };
+/// let context = ParanoidHash::default().read_tuning(tuning);
+/// assert_eq!(context.read_using_std("examples/example_file.txt"),ParanoidHash::default().read_using_std("examples/example_file.txt"));
+/// ```
+#[derive(Debug,Clone,Copy,Default,PartialEq,Eq,PartialOrd,Hash)]
+pub struct ReadTuning {
+    pub sequential: bool,
+    pub drop_cache: bool,
+}
+
+impl ReadTuning {
+    // Called once the file is open, before the first read
+    pub(crate) fn before_read(&self, file: &File) {
+        if self.sequential {
+            advise::sequential(file);
+        }
+    }
+    // Called once the file has been read, whether or not hashing succeeded
+    pub(crate) fn after_read(&self, file: &File) {
+        if self.drop_cache {
+            advise::dont_need(file);
+        }
+    }
+}
+
+#[cfg(all(feature = "fadvise", any(target_os = "linux", target_os = "android")))]
+mod advise {
+    use rustix::fs::{fadvise, Advice};
+    use std::fs::File;
+
+    // The whole file (a length of `None` reaches the end)
+    pub(super) fn sequential(file: &File) {
+        let _ = fadvise(file, 0, None, Advice::Sequential);
+    }
+    pub(super) fn dont_need(file: &File) {
+        let _ = fadvise(file, 0, None, Advice::DontNeed);
+    }
+}
+
+#[cfg(not(all(feature = "fadvise", any(target_os = "linux", target_os = "android"))))]
+mod advise {
+    use std::fs::File;
+
+    pub(super) fn sequential(_file: &File) {}
+    pub(super) fn dont_need(_file: &File) {}
+}
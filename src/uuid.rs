@@ -0,0 +1,78 @@
+// UUIDs From Digests
+//
+// Derives a deterministic UUID from a digest, so content-addressed records can be keyed in systems that expect UUIDs. The construction only truncates the digest and sets the version and variant bits, so it can be reproduced in any language.
+
+use crate::hex_input::prefix_array;
+use crate::{HashDigest, HashOutput, HexError};
+
+/// # UUID From Digest
+///
+/// A UUID derived from the first 16 bytes of `digest`, written in the lower case `8-4-4-4-12` form of RFC 9562 (which replaces RFC 4122). Returns `HexError::TooShort` if the digest is shorter than 16 bytes.
+///
+/// The construction is:
+///
+/// 1. Take the first 16 bytes of the digest, `b[0]` to `b[15]`
+/// 2. Set the version to 8: `b[6] = (b[6] & 0x0F) | 0x80`
+/// 3. Set the variant to RFC 9562: `b[8] = (b[8] & 0x3F) | 0x80`
+/// 4. Write the bytes as lower case hexadecimal, with hyphens after bytes 4, 6, 8 and 10
+///
+/// Version 8 is the version RFC 9562 sets aside for name-based UUIDs from hash functions other than MD5 (version 3) and SHA-1 (version 5), so these UUIDs can never collide with UUIDs of another version. 122 bits of the digest are kept.
+///
+/// ```rust
+/// use paranoid_hash::uuid_from_digest;
+///
+/// assert_eq!(uuid_from_digest(&[0xFF; 16]).unwrap(),"ffffffff-ffff-8fff-bfff-ffffffffffff");
+/// assert!(uuid_from_digest(&[0xFF; 15]).is_err());
+/// ```
+pub fn uuid_from_digest(digest: &[u8]) -> Result<String,HexError> {
+    let bytes: [u8; 16] = prefix_array(digest)?;
+    return Ok(format_uuid(bytes))
+}
+
+fn format_uuid(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0F) | 0x80;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+
+    let hex = hex::encode(bytes);
+    return format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+impl HashDigest {
+    /// ## To UUID
+    ///
+    /// The UUID derived from this digest by `uuid_from_digest()`. Returns `HexError::TooShort` if the digest is shorter than 16 bytes.
+    pub fn to_uuid(&self) -> Result<String,HexError> {
+        return uuid_from_digest(self.as_bytes())
+    }
+}
+
+impl HashOutput {
+    /// ## To UUID
+    ///
+    /// The UUID derived from the BLAKE2B digest by `uuid_from_digest()`. Returns `HexError::TooShort` if the digest size is less than 16 bytes.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm};
+    ///
+    /// let output = ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes_output(b"abc").unwrap();
+    /// assert_eq!(output.to_uuid().unwrap(),"bddd813c-6342-8972-b171-ef3fee98579b");
+    /// ```
+    pub fn to_uuid(&self) -> Result<String,HexError> {
+        return self.blake2b.to_uuid()
+    }
+    /// ## To UUID Combined
+    ///
+    /// A UUID derived from both digests, for when the UUID should stay unpredictable even if one of the hash functions is broken. The first 16 bytes of the BLAKE2B digest are XORed with the first 16 bytes of the Operating System digest, and the result goes through steps 2 to 4 of `uuid_from_digest()`.
+    ///
+    /// Returns `HexError::TooShort` if either digest is shorter than 16 bytes.
+    pub fn to_uuid_combined(&self) -> Result<String,HexError> {
+        let blake2b: [u8; 16] = prefix_array(self.blake2b.as_bytes())?;
+        let os: [u8; 16] = prefix_array(self.os.as_bytes())?;
+
+        let mut combined = [0u8; 16];
+        for (i, byte) in combined.iter_mut().enumerate() {
+            *byte = blake2b[i] ^ os[i];
+        }
+        return Ok(format_uuid(combined))
+    }
+}
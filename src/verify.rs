@@ -0,0 +1,324 @@
+// Verification
+//
+// Compares freshly computed digests against expected ones. Which comparisons have to pass is decided by a `VerifyPolicy`, and the outcome of every comparison is kept so a caller can see what passed and what did not.
+
+use std::io::Read;
+use std::path::Path;
+
+use crate::digest::constant_time_eq;
+use crate::encoding::decode_expected;
+use crate::hashers::DigestPair;
+use crate::{detect_algorithm, trace, Algo, FileError, ParanoidHash, ParanoidHashMulti};
+
+/// # Verify Policy
+///
+/// Which digests must match for a verification to pass.
+///
+/// **Default** is `Both`
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash,Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum VerifyPolicy {
+    /// The BLAKE2B and Operating System digests must both match
+    #[default]
+    Both,
+    /// At least one of the digests must match
+    Either,
+    /// Only the BLAKE2B digest is compared
+    LibraryOnly,
+    /// Only the Operating System digest is compared
+    OsOnly,
+}
+
+/// # Digest Status
+///
+/// The outcome of comparing a single digest.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum DigestStatus {
+    Match,
+    Mismatch,
+    /// No expected digest was given
+    NotChecked,
+}
+
+impl DigestStatus {
+    // The expected digest can be in any supported encoding
+    fn compare(expected: Option<&str>, actual: &[u8]) -> Self {
+        let expected = match expected {
+            Some(expected) => decode_expected(expected, actual.len()),
+            None => return DigestStatus::NotChecked,
+        };
+        match expected {
+            Some(expected) if constant_time_eq(&expected, actual) => return DigestStatus::Match,
+            _ => return DigestStatus::Mismatch,
+        }
+    }
+}
+
+/// # Verification
+///
+/// The result of a verification. `passed` is decided by `policy`, while `blake2b` and `os` always record each comparison, so a file that passed under `VerifyPolicy::Either` with a mismatching BLAKE2B digest can still be spotted.
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Verification {
+    pub policy: VerifyPolicy,
+    /// Whether the verification passed under `policy`
+    pub passed: bool,
+    /// The BLAKE2B comparison
+    pub blake2b: DigestStatus,
+    /// The Operating System comparison
+    pub os: DigestStatus,
+}
+
+impl Verification {
+    pub(crate) fn new(policy: VerifyPolicy, digests: &DigestPair, expected_blake2b: Option<&str>, expected_os: Option<&str>) -> Self {
+        let blake2b = DigestStatus::compare(expected_blake2b, &digests.0);
+        let os = DigestStatus::compare(expected_os, &digests.1);
+
+        let passed = match policy {
+            VerifyPolicy::Both => blake2b == DigestStatus::Match && os == DigestStatus::Match,
+            VerifyPolicy::Either => blake2b == DigestStatus::Match || os == DigestStatus::Match,
+            VerifyPolicy::LibraryOnly => blake2b == DigestStatus::Match,
+            VerifyPolicy::OsOnly => os == DigestStatus::Match,
+        };
+
+        return Verification {
+            policy,
+            passed,
+            blake2b,
+            os,
+        }
+    }
+}
+
+/// # Verification Report
+///
+/// A `Verification` together with the digests that were computed, encoded in the encoding of the context, so they can be logged next to the expected ones.
+///
+/// `blake2b` and `os` tell which algorithm failed. One digest matching while the other does not (see `is_split()`) means the file matched one hash function and not the other, which should never happen by accident and is a sign that one of them is broken or that the file or the expected digests have been tampered with.
+#[derive(Debug,Clone,PartialEq,PartialOrd,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VerificationReport {
+    pub policy: VerifyPolicy,
+    /// Whether the verification passed under `policy`
+    pub passed: bool,
+    /// The BLAKE2B comparison
+    pub blake2b: DigestStatus,
+    /// The Operating System comparison
+    pub os: DigestStatus,
+    /// The computed BLAKE2B digest
+    pub blake2b_digest: String,
+    /// The computed Operating System digest
+    pub os_digest: String,
+    /// The number of bytes that were hashed
+    pub bytes: u64,
+}
+
+impl VerificationReport {
+    fn new(context: &ParanoidHash, verification: Verification, digests: DigestPair, bytes: u64) -> Self {
+        let (blake2b_digest, os_digest) = context.encode_pair(digests);
+        return VerificationReport {
+            policy: verification.policy,
+            passed: verification.passed,
+            blake2b: verification.blake2b,
+            os: verification.os,
+            blake2b_digest,
+            os_digest,
+            bytes,
+        }
+    }
+    /// ## Is Split
+    ///
+    /// Returns `true` if one digest matched and the other mismatched. Digests that were not checked do not count.
+    pub fn is_split(&self) -> bool {
+        return matches!((self.blake2b, self.os), (DigestStatus::Match, DigestStatus::Mismatch) | (DigestStatus::Mismatch, DigestStatus::Match))
+    }
+}
+
+impl From<VerificationReport> for Verification {
+    fn from(report: VerificationReport) -> Self {
+        return Verification {
+            policy: report.policy,
+            passed: report.passed,
+            blake2b: report.blake2b,
+            os: report.os,
+        }
+    }
+}
+
+impl ParanoidHash {
+    /// # Verify File
+    ///
+    /// Hashes the file and compares the digests against the expected BLAKE2B and Operating System digests. Either expected digest can be left out, in which case its status is `DigestStatus::NotChecked` and it never counts as a match.
+    ///
+    /// Expected digests can be in any `OutputEncoding` (hexadecimal in either case or Base32), regardless of the encoding of this context. Leading and trailing whitespace (such as the newline of a digest read from a file) is ignored, as in `compare_hash_trimmed()`. An expected digest that does not decode to the length of its hash function in any encoding returns `FileError::InvalidDigest` before the file is hashed; see `is_valid_hex_digest()` to find out why a hexadecimal digest is rejected.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,OsAlgorithm,VerifyPolicy};
+    ///
+    /// // Only a SHA256 digest is on record
+    /// let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    /// let sha256 = context.read("examples/example_file.txt").unwrap().1;
+    ///
+    /// let verification = context.verify_file("examples/example_file.txt",None,Some(&sha256),VerifyPolicy::OsOnly).unwrap();
+    /// assert!(verification.passed);
+    /// ```
+    pub fn verify_file<T: AsRef<Path>>(&self, path: T, expected_blake2b: Option<&str>, expected_os: Option<&str>, policy: VerifyPolicy) -> Result<Verification,FileError> {
+        return Ok(self.verify_file_report(path, expected_blake2b, expected_os, policy)?.into())
+    }
+    /// # Verify File Report
+    ///
+    /// Same as `verify_file()`, but returns a `VerificationReport` with the computed digests.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,DigestStatus,VerifyPolicy};
+    ///
+    /// let context = ParanoidHash::default();
+    /// let (blake2b,_) = context.read("examples/example_file.txt").unwrap();
+    /// // A SHA512 digest that does not match
+    /// let wrong = "0".repeat(128);
+    ///
+    /// let report = context.verify_file_report("examples/example_file.txt",Some(&blake2b),Some(&wrong),VerifyPolicy::Either).unwrap();
+    /// assert!(report.passed);
+    /// assert_eq!((report.blake2b,report.os),(DigestStatus::Match,DigestStatus::Mismatch));
+    /// assert!(report.is_split());
+    /// assert_eq!(report.blake2b_digest,blake2b);
+    /// ```
+    pub fn verify_file_report<T: AsRef<Path>>(&self, path: T, expected_blake2b: Option<&str>, expected_os: Option<&str>, policy: VerifyPolicy) -> Result<VerificationReport,FileError> {
+        check_expected(expected_blake2b, self.digest_size)?;
+        check_expected(expected_os, self.os_hash_function.digest_len())?;
+
+        let span = trace::Span::verify(self, path.as_ref());
+        let result = span.in_scope(|| self.hash_path_raw(path.as_ref(), None));
+        span.finish(result.as_ref().map(|(_, stats)| stats.bytes));
+
+        let (digests, stats) = result?;
+        let verification = Verification::new(policy, &digests, expected_blake2b, expected_os);
+        span.verified(verification.passed);
+        return Ok(VerificationReport::new(self, verification, digests, stats.bytes))
+    }
+    /// # Verify Bytes
+    ///
+    /// Hashes `data` once and compares the digests against whichever expected digests are given, in constant time. The policy follows from what is given: `VerifyPolicy::Both` for both digests, `VerifyPolicy::LibraryOnly` for only BLAKE2B and `VerifyPolicy::OsOnly` for only the Operating System digest. A digest that is not given is `DigestStatus::NotChecked`.
+    ///
+    /// Expected digests are accepted as in `verify_file()`. Returns `FileError::NoExpectedDigest` if neither is given, `FileError::InvalidDigest` if one cannot match before anything is hashed, and honors `reject_empty_input()`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::{ParanoidHash,DigestStatus,VerifyPolicy};
+    ///
+    /// let context = ParanoidHash::default();
+    /// let (blake2b,_) = context.read_bytes(b"payload");
+    ///
+    /// let verification = context.verify_bytes(b"payload",Some(&blake2b),None).unwrap();
+    /// assert!(verification.passed);
+    /// assert_eq!(verification.policy,VerifyPolicy::LibraryOnly);
+    /// assert_eq!(verification.os,DigestStatus::NotChecked);
+    /// ```
+    pub fn verify_bytes(&self, data: &[u8], expected_blake2b: Option<&str>, expected_os: Option<&str>) -> Result<Verification,FileError> {
+        return Ok(self.verify_bytes_report(data, expected_blake2b, expected_os)?.into())
+    }
+    /// # Verify Bytes Report
+    ///
+    /// Same as `verify_bytes()`, but returns a `VerificationReport` with the computed digests.
+    pub fn verify_bytes_report(&self, data: &[u8], expected_blake2b: Option<&str>, expected_os: Option<&str>) -> Result<VerificationReport,FileError> {
+        let policy = self.expected_policy(expected_blake2b, expected_os)?;
+        self.check_empty(data.len() as u64)?;
+
+        let (digests, _) = self.hash_bytes_raw(data)?;
+        let verification = Verification::new(policy, &digests, expected_blake2b, expected_os);
+        return Ok(VerificationReport::new(self, verification, digests, data.len() as u64))
+    }
+    /// # Verify Reader
+    ///
+    /// Streams the reader through both hash functions until it reaches the end and compares the digests against whichever expected digests are given, in constant time. Nothing is buffered beyond a single chunk, so a download can be verified as it arrives.
+    ///
+    /// Expected digests and the policy work as in `verify_bytes()`, and are checked before anything is read. `VerificationReport::bytes` is the number of bytes read, which can be compared against a `Content-Length`. Errors from the reader are returned as `FileError::Io` and `max_file_size()`, `reject_empty_input()` and `retry_policy()` are honored as in `read_from_reader()`.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    /// use std::io::Cursor;
+    ///
+    /// let context = ParanoidHash::default();
+    /// let (blake2b,sha512) = context.read_bytes(b"artifact");
+    ///
+    /// let report = context.verify_reader(Cursor::new(b"artifact"),Some(&blake2b),Some(&sha512)).unwrap();
+    /// assert!(report.passed);
+    /// assert_eq!(report.bytes,8);
+    /// ```
+    pub fn verify_reader<R: Read>(&self, reader: R, expected_blake2b: Option<&str>, expected_os: Option<&str>) -> Result<VerificationReport,FileError> {
+        let policy = self.expected_policy(expected_blake2b, expected_os)?;
+
+        let (digests, stats) = self.hash_reader_raw(reader)?;
+        let verification = Verification::new(policy, &digests, expected_blake2b, expected_os);
+        return Ok(VerificationReport::new(self, verification, digests, stats.bytes))
+    }
+    // The policy for whichever expected digests are given, once they have been checked
+    fn expected_policy(&self, expected_blake2b: Option<&str>, expected_os: Option<&str>) -> Result<VerifyPolicy,FileError> {
+        let policy = match (expected_blake2b, expected_os) {
+            (Some(_), Some(_)) => VerifyPolicy::Both,
+            (Some(_), None) => VerifyPolicy::LibraryOnly,
+            (None, Some(_)) => VerifyPolicy::OsOnly,
+            (None, None) => return Err(FileError::NoExpectedDigest),
+        };
+        check_expected(expected_blake2b, self.digest_size)?;
+        check_expected(expected_os, self.os_hash_function.digest_len())?;
+        return Ok(policy)
+    }
+    /// # Verify Str
+    ///
+    /// Same as `verify_bytes()` for the UTF-8 bytes of a string.
+    pub fn verify_str<T: AsRef<str>>(&self, data: T, expected_blake2b: Option<&str>, expected_os: Option<&str>) -> Result<Verification,FileError> {
+        return self.verify_bytes(data.as_ref().as_bytes(), expected_blake2b, expected_os)
+    }
+    /// # Verify File Unlabeled
+    ///
+    /// Verifies a file against a bare hexadecimal digest without knowing which hash function produced it. Every candidate from `detect_algorithm()` is computed in a single pass and the verification passes if any of them matches (the policy is always `VerifyPolicy::Either`).
+    ///
+    /// The digest size and Operating System Hash Function of a `ParanoidHash` do not apply, so this does not take `self`.
+    ///
+    /// Leading and trailing whitespace is ignored. Returns `FileError::InvalidDigest` if the digest is not hexadecimal or its length matches no hash function.
+    ///
+    /// ```rust
+    /// use paranoid_hash::ParanoidHash;
+    ///
+    /// let sha1 = "A9993E364706816ABA3E25717850C26C9CD0D89D";
+    /// let verification = ParanoidHash::verify_file_unlabeled("examples/example_file.txt",sha1).unwrap();
+    /// assert!(!verification.passed);
+    /// ```
+    pub fn verify_file_unlabeled<T: AsRef<Path>>(path: T, expected: &str) -> Result<Verification,FileError> {
+        let expected = expected.trim_ascii();
+        let candidates = detect_algorithm(expected);
+        if candidates.is_empty() {
+            return Err(FileError::InvalidDigest)
+        }
+
+        let mut blake2b = DigestStatus::NotChecked;
+        let mut os = DigestStatus::NotChecked;
+        for (algo, digest) in ParanoidHashMulti::new(&candidates).read_raw(path.as_ref())? {
+            let status = DigestStatus::compare(Some(expected), &digest);
+            let current = match algo {
+                Algo::Os(_) => &mut os,
+                _ => &mut blake2b,
+            };
+            // SHA256 and SHA512_256 are both candidates for the same length, so a match is kept
+            if *current != DigestStatus::Match {
+                *current = status;
+            }
+        }
+
+        return Ok(Verification {
+            policy: VerifyPolicy::Either,
+            passed: blake2b == DigestStatus::Match || os == DigestStatus::Match,
+            blake2b,
+            os,
+        })
+    }
+}
+
+// An expected digest that cannot match is rejected before hashing
+pub(crate) fn check_expected(expected: Option<&str>, length: usize) -> Result<(),FileError> {
+    match expected {
+        Some(expected) if decode_expected(expected, length).is_none() => return Err(FileError::InvalidDigest),
+        _ => return Ok(()),
+    }
+}
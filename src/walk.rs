@@ -0,0 +1,181 @@
+// Directory Walking
+//
+// Used by every directory-level API so that they all see the same set of files in the same order.
+//
+// The order is fully specified so results never depend on the filesystem: each file is keyed by its path relative to the root, with components joined by `/`, and files are sorted by the UTF-8 bytes of that key. File names that are not valid UTF-8 cannot be keyed and are reported as `FileError::AtPath` holding `FileError::Io(ErrorKind::InvalidData)`.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::pattern::Pattern;
+use crate::{long_path, FileError};
+
+/// # Walk Options
+///
+/// Which files are visited by the directory-level APIs (`read_dir()`, `manifest_dir()`, `report_dir()`, `find_duplicates()` and `compare_dirs()`). Set with `ParanoidHash::walk_options()`.
+///
+/// Patterns use gitignore-style globs and are matched against the path relative to the directory, with `/` as the separator:
+///
+/// * `*` matches anything within one path component, `?` matches one character and `[a-z]` / `[!a-z]` match a character class
+/// * `**` as a whole component matches any number of components, such as `**/*.swp` or `docs/**`
+/// * A pattern without a `/` (such as `target` or `*.swp`) matches a name at any depth. A pattern with a `/` is anchored to the directory (a leading `/` is ignored).
+/// * A trailing `/` (such as `.git/`) only matches directories
+///
+/// A file is visited if it matches no `exclude` pattern and, when `include` is not empty, it or one of its parent directories matches an `include` pattern. Exclude always wins. A directory that matches an `exclude` pattern is skipped without being read.
+///
+/// With `include_hidden` set to `false`, hidden files and everything inside hidden directories are skipped. A name is hidden if it starts with a `.` and, on Windows, also if it has the hidden attribute.
+///
+/// `max_depth` limits how many levels of subdirectories are entered: `Some(0)` only visits the files directly inside the directory, `Some(1)` also the files of its subdirectories, and so on.
+///
+/// With `follow_symlinks` set to `true`, symbolic links to files and directories are visited under the name of the link, and broken links are skipped. A link to a directory that is already being walked (such as `a/b -> ../..`) would never end, so the walk stops with `FileError::AtPath` holding `FileError::SymlinkLoop` and the path of the link.
+///
+/// **Default** visits every file at any depth, including hidden ones, without following symbolic links
+#[derive(Debug,Clone,PartialEq,Eq,PartialOrd,Hash)]
+pub struct WalkOptions {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// Whether hidden files and directories are visited
+    pub include_hidden: bool,
+    /// How many levels of subdirectories are entered, or `None` for no limit
+    pub max_depth: Option<usize>,
+    /// Whether symbolic links are followed
+    pub follow_symlinks: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self { WalkOptions { include: Vec::new(), exclude: Vec::new(), include_hidden: true, max_depth: None, follow_symlinks: false } }
+}
+
+// A directory waiting to be read, with its depth below the root and, when following symbolic links, the canonical paths of itself and the directories above it
+struct Pending {
+    prefix: String,
+    dir: PathBuf,
+    depth: usize,
+    ancestors: Vec<PathBuf>,
+}
+
+// The patterns of `WalkOptions`, parsed once per walk
+struct Filter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl Filter {
+    fn new(options: &WalkOptions) -> Self {
+        return Filter {
+            include: options.include.iter().map(|p| Pattern::new(p)).collect(),
+            exclude: options.exclude.iter().map(|p| Pattern::new(p)).collect(),
+        }
+    }
+    fn excludes(&self, components: &[&str], is_dir: bool) -> bool {
+        return self.exclude.iter().any(|pattern| pattern.matches(components, is_dir))
+    }
+    fn includes(&self, components: &[&str]) -> bool {
+        if self.include.is_empty() {
+            return true
+        }
+        // The file itself or any of its parent directories
+        return (1..=components.len()).any(|length| {
+            let is_dir = length < components.len();
+            self.include.iter().any(|pattern| pattern.matches(&components[..length], is_dir))
+        })
+    }
+}
+
+/// Recursively collects every regular file below `root` that passes `options`, sorted by relative path (see above).
+///
+/// Symbolic links are only followed with `follow_symlinks`.
+pub(crate) fn walk_files(root: &Path, options: &WalkOptions) -> Result<Vec<PathBuf>, FileError> {
+    return Ok(walk_entries(root, options)?.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Same as `walk_files()` but also returns the `/` separated relative path of each file.
+pub(crate) fn walk_entries(root: &Path, options: &WalkOptions) -> Result<Vec<(String, PathBuf)>, FileError> {
+    if !root.is_dir() {
+        return Err(FileError::FileNotFound)
+    }
+
+    let filter = Filter::new(options);
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    let mut pending: Vec<Pending> = vec![Pending {
+        prefix: String::new(),
+        dir: root.to_path_buf(),
+        depth: 0,
+        ancestors: match options.follow_symlinks {
+            true => vec![fs::canonicalize(root).map_err(|e| FileError::Io(e.kind()))?],
+            false => Vec::new(),
+        },
+    }];
+
+    while let Some(Pending { prefix, dir, depth, ancestors }) = pending.pop() {
+        let entries = fs::read_dir(long_path::extended(&dir)).map_err(|e| FileError::Io(e.kind()))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| FileError::Io(e.kind()))?;
+            let path = entry.path();
+            let mut file_type = entry.file_type().map_err(|e| FileError::Io(e.kind()))?;
+            let is_link = file_type.is_symlink();
+            if is_link && options.follow_symlinks {
+                // Broken links are skipped like other special files
+                file_type = match fs::metadata(long_path::extended(&path)) {
+                    Ok(metadata) => metadata.file_type(),
+                    Err(_) => continue,
+                };
+            }
+            if !file_type.is_dir() && !file_type.is_file() {
+                continue
+            }
+
+            let key = match entry.file_name().to_str() {
+                Some(name) if prefix.is_empty() => name.to_string(),
+                Some(name) => format!("{}/{}", prefix, name),
+                None => return Err(FileError::AtPath(path, Box::new(FileError::Io(ErrorKind::InvalidData)))),
+            };
+            let components: Vec<&str> = key.split('/').collect();
+
+            if !options.include_hidden && is_hidden(&entry) {
+                continue
+            }
+            if filter.excludes(&components, file_type.is_dir()) {
+                continue
+            }
+            if file_type.is_dir() {
+                if options.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                    continue
+                }
+                let mut below = ancestors.clone();
+                if options.follow_symlinks {
+                    let canonical = fs::canonicalize(&path).map_err(|e| FileError::AtPath(path.clone(), Box::new(FileError::Io(e.kind()))))?;
+                    if is_link && ancestors.contains(&canonical) {
+                        return Err(FileError::AtPath(path, Box::new(FileError::SymlinkLoop)))
+                    }
+                    below.push(canonical);
+                }
+                pending.push(Pending { prefix: key, dir: path, depth: depth + 1, ancestors: below });
+            }
+            else if filter.includes(&components) {
+                files.push((key, path));
+            }
+        }
+    }
+
+    // `String` compares by its UTF-8 bytes
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    return Ok(files)
+}
+
+// A leading dot on every platform, or the hidden attribute on Windows
+fn is_hidden(entry: &fs::DirEntry) -> bool {
+    if entry.file_name().to_string_lossy().starts_with('.') {
+        return true
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if let Ok(metadata) = entry.metadata() {
+            return metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0
+        }
+    }
+    return false
+}
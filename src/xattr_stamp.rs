@@ -0,0 +1,138 @@
+// Extended Attribute Stamps
+//
+// Stores the digests of a file in an extended attribute of the file itself (`user.paranoid-hash`), so it can be checked later without a separate manifest. The attribute is a single line of ASCII:
+//
+// ```text
+// paranoid-hash-xattr v1 blake2b-64+sha512 <library digest> <os digest>
+// ```
+//
+// The second field is the format version, the third names the library hash function, its digest size and the Operating System Hash Function, and the digests are in lower case hexadecimal whatever the Output Encoding. Only Linux, macOS and the BSDs have extended attributes; on other platforms every file is treated as being on a filesystem without them.
+
+use std::path::Path;
+
+use crate::digest::constant_time_eq;
+use crate::{FileError, ParanoidHash};
+
+/// The name of the extended attribute holding the stamp
+pub const XATTR_NAME: &str = "user.paranoid-hash";
+
+// The first field of a stamp
+const XATTR_LABEL: &str = "paranoid-hash-xattr";
+// The format version written by `store_xattr()`
+const XATTR_VERSION: &str = "v1";
+
+/// # Xattr Outcome
+///
+/// The result of checking a file against the digests stamped on it by `store_xattr()`.
+#[derive(Debug,Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum XattrOutcome {
+    /// Both digests match the file
+    Match,
+    /// At least one digest does not match, so the file changed after it was stamped
+    Mismatch,
+    /// The file has no stamp
+    NotStamped,
+    /// The filesystem (or platform) does not support extended attributes
+    UnsupportedFilesystem,
+}
+
+impl ParanoidHash {
+    /// # Store Xattr
+    ///
+    /// Requires the `xattr` feature.
+    ///
+    /// Hashes the file and stores both digests, the hash functions and a format version in the `user.paranoid-hash` extended attribute of the file, replacing any earlier stamp. Symbolic links are followed. `verify_xattr()` checks the file against it later.
+    ///
+    /// Returns `FileError::XattrUnsupported` if the filesystem or platform (such as Windows) has no extended attributes, and `FileError::Io` if the attribute cannot be written (for example when the file is read-only to this user).
+    ///
+    /// ```rust,no_run
+    /// use paranoid_hash::{ParanoidHash,XattrOutcome};
+    ///
+    /// let context = ParanoidHash::default();
+    /// context.store_xattr("examples/example_file.txt").unwrap();
+    /// assert_eq!(context.verify_xattr("examples/example_file.txt").unwrap(),XattrOutcome::Match);
+    /// ```
+    pub fn store_xattr<T: AsRef<Path>>(&self, path: T) -> Result<(),FileError> {
+        let path = path.as_ref();
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+        let (digests, _) = self.hash_path_raw(path, None)?;
+        let stamp = format!("{} {} {} {} {}", XATTR_LABEL, XATTR_VERSION, self.algorithms_tag(), hex::encode(&digests.0), hex::encode(&digests.1));
+        return set_stamp(path, stamp.as_bytes())
+    }
+    /// # Verify Xattr
+    ///
+    /// Requires the `xattr` feature.
+    ///
+    /// Hashes the file and compares both digests with the stamp written by `store_xattr()`, in constant time. Symbolic links are followed.
+    ///
+    /// A filesystem without extended attributes is reported as `XattrOutcome::UnsupportedFilesystem` rather than an error. A stamp that is malformed, from a newer format version or written with other hash functions (or another digest size) than this context returns `FileError::InvalidDigest`.
+    pub fn verify_xattr<T: AsRef<Path>>(&self, path: T) -> Result<XattrOutcome,FileError> {
+        let path = path.as_ref();
+
+        // Checks whether file exists. If file does not exist, returns error as FileError.
+        if !path.exists() {
+            return Err(FileError::FileNotFound)
+        }
+        let stamp = match get_stamp(path) {
+            Ok(Some(stamp)) => stamp,
+            Ok(None) => return Ok(XattrOutcome::NotStamped),
+            Err(FileError::XattrUnsupported) => return Ok(XattrOutcome::UnsupportedFilesystem),
+            Err(e) => return Err(e),
+        };
+        let (library, os) = self.parse_stamp(&stamp).ok_or(FileError::InvalidDigest)?;
+
+        let (digests, _) = self.hash_path_raw(path, None)?;
+        // Both are compared so the time taken does not depend on which one differs
+        let library_matches = constant_time_eq(&library, &digests.0);
+        let os_matches = constant_time_eq(&os, &digests.1);
+        match library_matches & os_matches {
+            true => return Ok(XattrOutcome::Match),
+            false => return Ok(XattrOutcome::Mismatch),
+        }
+    }
+    // The library and operating system digests of a stamp written by this configuration
+    fn parse_stamp(&self, stamp: &[u8]) -> Option<(Vec<u8>,Vec<u8>)> {
+        let stamp = std::str::from_utf8(stamp).ok()?;
+        let fields: Vec<&str> = stamp.trim_end().split(' ').collect();
+        match fields.as_slice() {
+            [label, version, tag, library, os] if *label == XATTR_LABEL && *version == XATTR_VERSION && *tag == self.algorithms_tag() => {
+                return Some((hex::decode(library).ok()?, hex::decode(os).ok()?))
+            }
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn set_stamp(path: &Path, stamp: &[u8]) -> Result<(),FileError> {
+    return xattr::set_deref(path, XATTR_NAME, stamp).map_err(xattr_error)
+}
+
+#[cfg(unix)]
+fn get_stamp(path: &Path) -> Result<Option<Vec<u8>>,FileError> {
+    return xattr::get_deref(path, XATTR_NAME).map_err(xattr_error)
+}
+
+// `ENOTSUP` (and `ENOSYS` on platforms without the system calls) become `ErrorKind::Unsupported`
+#[cfg(unix)]
+fn xattr_error(error: std::io::Error) -> FileError {
+    match error.kind() {
+        std::io::ErrorKind::Unsupported => return FileError::XattrUnsupported,
+        kind => return FileError::Io(kind),
+    }
+}
+
+#[cfg(not(unix))]
+fn set_stamp(_path: &Path, _stamp: &[u8]) -> Result<(),FileError> {
+    return Err(FileError::XattrUnsupported)
+}
+
+#[cfg(not(unix))]
+fn get_stamp(_path: &Path) -> Result<Option<Vec<u8>>,FileError> {
+    return Err(FileError::XattrUnsupported)
+}
@@ -0,0 +1,35 @@
+// Extendable-Output Functions
+//
+// SHAKE256 as a library hash function for digests longer than the 64 bytes BLAKE2B can produce. It is implemented by the RustCrypto `sha3` crate.
+
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use crate::custom::LibraryHasher;
+
+/// SHAKE256 squeezed to `digest_size` bytes.
+#[derive(Clone)]
+pub(crate) struct Shake256Hasher {
+    state: Shake256,
+    digest_size: usize,
+}
+
+impl Shake256Hasher {
+    pub(crate) fn new(digest_size: usize) -> Self {
+        return Shake256Hasher { state: Shake256::default(), digest_size }
+    }
+}
+
+impl LibraryHasher for Shake256Hasher {
+    fn update(&mut self, bytes: &[u8]) {
+        Update::update(&mut self.state, bytes);
+    }
+    fn finalize(self) -> Vec<u8> {
+        let mut digest = vec![0u8; self.digest_size];
+        self.state.finalize_xof().read(&mut digest);
+        return digest
+    }
+    fn output_len(&self) -> usize {
+        return self.digest_size
+    }
+}
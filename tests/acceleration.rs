@@ -0,0 +1,51 @@
+use std::time::{Duration, Instant};
+
+use paranoid_hash::{AccelBackend, OsAlgorithm, ParanoidHash};
+
+#[test]
+fn populated(){
+    let info = ParanoidHash::acceleration_info();
+    let algorithms: Vec<OsAlgorithm> = info.algorithms.iter().map(|accel| accel.algorithm.clone()).collect();
+    let expected = [
+        OsAlgorithm::SHA1,OsAlgorithm::SHA224,OsAlgorithm::SHA256,OsAlgorithm::SHA512,OsAlgorithm::SHA512_256,
+        #[cfg(feature = "insecure-md5")]
+        OsAlgorithm::MD5,
+    ];
+    assert_eq!(algorithms,expected.to_vec());
+
+    for accel in info.algorithms.iter() {
+        assert_eq!(accel.available,accel.algorithm.is_available());
+        // Nothing is measured without a benchmark
+        assert_eq!((accel.throughput,accel.relative_throughput),(None,None));
+    }
+    assert_eq!(info.get(&OsAlgorithm::SHA224).unwrap().backend,AccelBackend::PureRust);
+    assert_eq!(info.get(&OsAlgorithm::SHA256).unwrap().backend,AccelBackend::OperatingSystem);
+    #[cfg(feature = "insecure-md5")]
+    assert!(!info.get(&OsAlgorithm::MD5).unwrap().hardware);
+
+    // SHA1, SHA224 and SHA256 share the same instructions
+    let sha256 = info.get(&OsAlgorithm::SHA256).unwrap().hardware;
+    assert_eq!(info.get(&OsAlgorithm::SHA1).unwrap().hardware,sha256);
+    assert_eq!(info.get(&OsAlgorithm::SHA224).unwrap().hardware,sha256);
+    assert_eq!(sha256,info.cpu_features.iter().any(|feature| *feature == "sha" || *feature == "sha2"));
+}
+
+#[test]
+fn benchmark(){
+    let start = Instant::now();
+    let info = ParanoidHash::acceleration_info().benchmark(Duration::from_millis(120));
+    // Bounded by the budget plus one pass per algorithm, with generous slack for slow machines
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    for accel in info.algorithms.iter() {
+        match accel.available {
+            true => {
+                assert!(accel.throughput.unwrap() > 0.0);
+                let relative = accel.relative_throughput.unwrap();
+                assert!(relative > 0.0 && relative <= 1.0);
+            },
+            false => assert_eq!(accel.throughput,None),
+        }
+    }
+    assert!(info.algorithms.iter().any(|accel| accel.relative_throughput == Some(1.0)));
+}
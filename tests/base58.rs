@@ -0,0 +1,48 @@
+#![cfg(feature = "base58")]
+
+use paranoid_hash::{decode_base58, encode_base58, OsAlgorithm, OutputEncoding, ParanoidHash};
+
+#[test]
+fn known_vectors(){
+    assert_eq!(encode_base58(b"Hello World!"),"2NEpo7TZRRrLZSi2U");
+    assert_eq!(decode_base58("2NEpo7TZRRrLZSi2U").unwrap(),b"Hello World!".to_vec());
+    assert_eq!(encode_base58(b""),"");
+    assert_eq!(decode_base58("").unwrap(),Vec::<u8>::new());
+}
+
+#[test]
+fn leading_zero_bytes(){
+    assert_eq!(encode_base58([0u8]),"1");
+    assert_eq!(encode_base58([0u8,0,0]),"111");
+    assert_eq!(encode_base58([0u8,0,0,0x28,0x7f,0xb4,0xcd]),"111233QC4");
+
+    assert_eq!(decode_base58("111").unwrap(),vec![0,0,0]);
+    assert_eq!(decode_base58("111233QC4").unwrap(),vec![0,0,0,0x28,0x7f,0xb4,0xcd]);
+}
+
+#[test]
+fn round_trips(){
+    let inputs: Vec<Vec<u8>> = vec![vec![0xff;64],vec![0,0xff,0],(0..=255).collect(),vec![0;32]];
+    for input in inputs {
+        assert_eq!(decode_base58(encode_base58(&input)).unwrap(),input);
+    }
+}
+
+#[test]
+fn rejects_characters_outside_alphabet(){
+    for invalid in ["0","O","I","l","2NEpo7TZRR+LZSi2U"," 2NEpo7TZRRrLZSi2U","é"] {
+        assert_eq!(decode_base58(invalid),None);
+    }
+}
+
+#[test]
+fn output_encoding(){
+    // blake2b-256("Hello") and sha256("Hello") from Python's hashlib, encoded with the Bitcoin alphabet
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256).output_encoding(OutputEncoding::Base58);
+    let (blake2b,sha256) = context.read_str("Hello");
+    assert_eq!(blake2b,"APVv8QSYc9guAUfkHUKryLe4bzVeRTadnRjrTqKK3ogq");
+    assert_eq!(sha256,"2e9Hyqo5irxptytA7gydep9J4gjwEW4f5aWtKRwyPjpk");
+
+    assert_eq!(OutputEncoding::Base58.decode(&sha256),OutputEncoding::decode_any(&sha256));
+    assert_eq!(OutputEncoding::Base58.decode(&sha256).unwrap().len(),32);
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "blake2-parallel")]
+
+use paranoid_hash::{Algo, FileError, LibAlgorithm, Manifest, OsAlgorithm, ParanoidHash, ParanoidHashMulti};
+use std::fs;
+
+// Unkeyed vectors computed with the BLAKE2 tree parameters of Python's hashlib (the reference implementation). The empty vectors match the official KATs.
+const BLAKE2BP_EMPTY: &str = "B5EF811A8038F70B628FA8B294DAAE7492B1EBE343A80EAABBF1F6AE664DD67B9D90B0120791EAB81DC96985F28849F6A305186A85501B405114BFA678DF9380";
+const BLAKE2BP_ABC: &str = "B91A6B66AE87526C400B0A8B53774DC65284AD8F6575F8148FF93DFF943A6ECD8362130F22D6DAE633AA0F91DF4AC89AAFF31D0F1B923C898E82025DEDBDAD6E";
+const BLAKE2BP_SEQUENCE_255: &str = "3F35C45D24FCFB4ACCA651076C08000E279EBBFF37A1333CE19FD577202DBD24B58C514E36DD9BA64AF4D78EEA4E2DD13BC18D798887DD971376BCAE0087E17E";
+const BLAKE2SP_EMPTY: &str = "DD0E891776933F43C7D032B08A917E25741F8AA9A12C12E1CAC8801500F2CA4F";
+const BLAKE2SP_ABC: &str = "70F75B58F1FECAB821DB43C88AD84EDDE5A52600616CD22517B7BB14D440A7D5";
+const BLAKE2SP_SEQUENCE_255: &str = "25059F10605E67ADFE681350666E15AE976A5A571C13CF5BC8053F430E120A52";
+
+// Official keyed KATs (key 00..3F, truncated to 32 bytes for BLAKE2SP) for the empty input
+const BLAKE2BP_KEYED_EMPTY: &str = "9D9461073E4EB640A255357B839F394B838C6FF57C9B686A3F76107C1066728F3C9956BD785CBC3BF79DC2AB578C5A0C063B9D9C405848DE1DBE821CD05C940A";
+const BLAKE2SP_KEYED_EMPTY: &str = "715CB13895AEB678F6124160BFF21465B30F4F6874193FC851B4621043F09CC6";
+
+// 3 MiB + 777 bytes of `i % 251`, large enough to be hashed on several threads
+const LARGE_SIZE: usize = 3 * 1024 * 1024 + 777;
+const BLAKE2BP_LARGE: &str = "F026D94ABEC1651279B0F0AFD7F9E80018AB27370E84746B119E3B74D86ACC92153C03EBC14348F882DFE9A34F72FA4FFC6D854E75BB89445EEF2D625156B925";
+const BLAKE2SP_LARGE: &str = "D7A6C382A18D9A1DD9E45CADD6DF3421ACCBD62201F8D645C801F5766AF457AD";
+const SHA256_LARGE: &str = "CACFAC8C5D35BA1F96F753076202089DED992CF6CDA1B387DB29C3F961BAE806";
+
+fn blake2bp() -> ParanoidHash {
+    return ParanoidHash::new(64,OsAlgorithm::SHA256).library_algorithm(LibAlgorithm::BLAKE2BP)
+}
+
+fn blake2sp() -> ParanoidHash {
+    return ParanoidHash::new(32,OsAlgorithm::SHA256).library_algorithm(LibAlgorithm::BLAKE2SP)
+}
+
+fn large() -> Vec<u8> {
+    return (0..LARGE_SIZE).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn known_answers(){
+    let sequence: Vec<u8> = (0..255).collect();
+
+    assert_eq!(blake2bp().read_bytes(b"").0,BLAKE2BP_EMPTY);
+    assert_eq!(blake2bp().read_bytes(b"abc").0,BLAKE2BP_ABC);
+    assert_eq!(blake2bp().read_bytes(&sequence).0,BLAKE2BP_SEQUENCE_255);
+
+    assert_eq!(blake2sp().read_bytes(b"").0,BLAKE2SP_EMPTY);
+    assert_eq!(blake2sp().read_bytes(b"abc").0,BLAKE2SP_ABC);
+    assert_eq!(blake2sp().read_bytes(&sequence).0,BLAKE2SP_SEQUENCE_255);
+}
+
+#[test]
+fn large_input_on_threads(){
+    let data = large();
+    assert_eq!(blake2bp().read_bytes(&data),(BLAKE2BP_LARGE.to_string(),SHA256_LARGE.to_string()));
+    assert_eq!(blake2sp().read_bytes(&data).0,BLAKE2SP_LARGE);
+    assert_eq!(blake2bp().read_bytes_parallel(&data).0,BLAKE2BP_LARGE);
+
+    // Streamed in chunks that do not line up with the leaves
+    assert_eq!(blake2bp().read_from_reader(&data[..]).unwrap().0,BLAKE2BP_LARGE);
+    assert_eq!(blake2sp().read_from_reader(&data[..]).unwrap().0,BLAKE2SP_LARGE);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("large.bin");
+    fs::write(&path,&data).unwrap();
+    assert_eq!(blake2bp().read(&path).unwrap().0,BLAKE2BP_LARGE);
+    assert_eq!(blake2sp().window_size(100_000).read(&path).unwrap().0,BLAKE2SP_LARGE);
+}
+
+#[test]
+fn keyed(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("empty");
+    fs::write(&path,b"").unwrap();
+    let key: Vec<u8> = (0..64).collect();
+
+    assert_eq!(blake2bp().read_with_key(&path,&key).unwrap().0,BLAKE2BP_KEYED_EMPTY);
+    assert_eq!(blake2sp().read_with_key(&path,&key[..32]).unwrap().0,BLAKE2SP_KEYED_EMPTY);
+
+    assert_eq!(blake2sp().read_with_key(&path,&key),Err(FileError::InvalidKey));
+    assert_eq!(blake2bp().read_with_key(&path,[0u8;65]),Err(FileError::InvalidKey));
+}
+
+#[test]
+fn digest_sizes(){
+    let context = ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1).library_algorithm(LibAlgorithm::BLAKE2SP);
+    assert_eq!(context.return_library_algorithm(),LibAlgorithm::BLAKE2SP);
+    assert_eq!(context.read_str("Hello").0.len(),40);
+
+    let (blake2bp,_) = ParanoidHash::new(32,OsAlgorithm::SHA256).library_algorithm(LibAlgorithm::BLAKE2BP).read_bytes_digests(b"abc").unwrap();
+    assert_eq!(blake2bp.algorithm(),&Algo::Blake2bp(32));
+    assert_eq!(blake2bp.as_bytes().len(),32);
+}
+
+#[test]
+#[should_panic]
+fn blake2sp_digest_too_large(){
+    let _ = ParanoidHash::new(64,OsAlgorithm::SHA512).library_algorithm(LibAlgorithm::BLAKE2SP);
+}
+
+#[test]
+fn multi_algorithm(){
+    let digests = ParanoidHashMulti::new(&[Algo::Blake2bp(64),Algo::Blake2sp(32),Algo::Blake2b(64)]).read_bytes(b"abc");
+    assert_eq!(digests[0],(Algo::Blake2bp(64),BLAKE2BP_ABC.to_string()));
+    assert_eq!(digests[1],(Algo::Blake2sp(32),BLAKE2SP_ABC.to_string()));
+    assert_ne!(digests[2].1,BLAKE2BP_ABC);
+}
+
+#[test]
+fn tagged_and_manifest(){
+    let tagged = blake2sp().to_tagged_string_for_file("examples/example_file.txt").unwrap();
+    assert!(tagged.starts_with("blake2sp-32:"));
+    let (context,(library,_)) = ParanoidHash::parse_tagged(&tagged).unwrap();
+    assert_eq!(context.return_library_algorithm(),LibAlgorithm::BLAKE2SP);
+    assert_eq!(library,blake2sp().read("examples/example_file.txt").unwrap().0);
+
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.txt"),b"alpha\n").unwrap();
+    let manifest = blake2bp().manifest_dir(dir.path()).unwrap();
+
+    let out = tempfile::tempdir().unwrap();
+    let path = out.path().join("MANIFEST");
+    manifest.save(&path).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("\nblake2bp 64\n"));
+    let loaded = Manifest::load(&path).unwrap();
+    assert_eq!(loaded,manifest);
+    assert_eq!(loaded.config().return_library_algorithm(),LibAlgorithm::BLAKE2BP);
+}
+
+#[test]
+fn leaves_on_fewer_threads(){
+    let data = large();
+    for threads in [1,2,3,8] {
+        assert_eq!(blake2bp().threads(threads).read_bytes(&data).0,BLAKE2BP_LARGE);
+        assert_eq!(blake2sp().threads(threads).read_bytes(&data).0,BLAKE2SP_LARGE);
+    }
+}
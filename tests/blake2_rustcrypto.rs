@@ -0,0 +1,82 @@
+#![cfg(feature = "blake2-rustcrypto")]
+
+// With the `blake2-rustcrypto` feature `Blake2bHasher` (and every BLAKE2B digest of the crate) comes from the RustCrypto `blake2` crate. `blake2-rfc` is still a dependency, so both backends are run over the same inputs here.
+
+use blake2_rfc::blake2b::Blake2b;
+use paranoid_hash::{Blake2bHasher, LibraryHasher, OsAlgorithm, ParanoidHash};
+use std::io::Write;
+
+// Around the 128 byte block size, where the last block is handled differently
+const INPUT_SIZES: [usize; 14] = [0, 1, 3, 63, 64, 65, 127, 128, 129, 255, 256, 257, 1000, 100_003];
+
+fn input(size: usize) -> Vec<u8> {
+    return (0..size).map(|i| (i % 251) as u8).collect()
+}
+
+fn keys() -> Vec<Vec<u8>> {
+    return vec![vec![], vec![0x42], (0..32).collect(), (0..64).collect()]
+}
+
+fn rfc(digest_size: usize, key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut context = Blake2b::with_key(digest_size, key);
+    context.update(data);
+    return context.finalize().as_bytes().to_vec()
+}
+
+#[test]
+fn every_digest_size_and_key_matches_blake2_rfc(){
+    for size in INPUT_SIZES.iter() {
+        let data = input(*size);
+        for key in keys() {
+            for digest_size in 1..=64 {
+                let mut hasher = Blake2bHasher::with_key(digest_size, &key);
+                hasher.update(&data);
+                assert_eq!(hasher.finalize(), rfc(digest_size, &key, &data), "input {} bytes, key {} bytes, digest {} bytes", size, key.len(), digest_size);
+            }
+        }
+    }
+}
+
+#[test]
+fn unkeyed_matches_blake2_rfc(){
+    for size in INPUT_SIZES.iter() {
+        let data = input(*size);
+        for digest_size in [1, 20, 32, 48, 64].iter() {
+            let mut hasher = Blake2bHasher::new(*digest_size);
+            hasher.update(&data);
+            let mut context = Blake2b::new(*digest_size);
+            context.update(&data);
+            assert_eq!(hasher.finalize(), context.finalize().as_bytes());
+        }
+    }
+}
+
+#[test]
+fn chunk_boundaries_do_not_matter(){
+    let data = input(1000);
+    for key in keys() {
+        for chunk in [1, 7, 127, 128, 129, 500].iter() {
+            let mut hasher = Blake2bHasher::with_key(64, &key);
+            for piece in data.chunks(*chunk) {
+                hasher.update(piece);
+            }
+            assert_eq!(hasher.finalize(), rfc(64, &key, &data), "key {} bytes, chunks of {}", key.len(), chunk);
+        }
+    }
+}
+
+#[test]
+fn files_and_keys_through_paranoid_hash(){
+    let data = input(100_003);
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&data).unwrap();
+    file.flush().unwrap();
+
+    for digest_size in [1, 32, 64].iter() {
+        let context = ParanoidHash::new(*digest_size, OsAlgorithm::SHA256);
+        assert_eq!(context.read(file.path()).unwrap().0, hex::encode_upper(rfc(*digest_size, &[], &data)));
+        for key in keys().iter().filter(|key| !key.is_empty()) {
+            assert_eq!(context.read_with_key(file.path(), key).unwrap().0, hex::encode_upper(rfc(*digest_size, key, &data)));
+        }
+    }
+}
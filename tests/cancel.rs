@@ -0,0 +1,74 @@
+use paranoid_hash::{FileError, ParanoidHash, RetryPolicy};
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn cancel_from_another_thread(){
+    let cancel = AtomicBool::new(false);
+    let context = ParanoidHash::default();
+
+    let result = thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(50));
+            cancel.store(true,Ordering::Relaxed);
+        });
+        // An endless reader only stops when cancelled
+        context.read_from_reader_cancellable(std::io::repeat(0x5A),&cancel)
+    });
+    assert_eq!(result,Err(FileError::Cancelled));
+}
+
+#[test]
+fn cancelled_before_start(){
+    let data = vec![1u8; 4 * 1024 * 1024];
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&data).unwrap();
+    file.flush().unwrap();
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_cancellable(file.path(),&AtomicBool::new(true)),Err(FileError::Cancelled));
+}
+
+#[test]
+fn not_cancelled(){
+    let context = ParanoidHash::default();
+    let cancel = AtomicBool::new(false);
+    assert_eq!(context.read_cancellable("tests/fixtures/report/alpha.txt",&cancel),context.read("tests/fixtures/report/alpha.txt"));
+}
+
+// Fails the first read with a transient error, then returns `data`
+struct FlakyReader<'a> {
+    data: &'a [u8],
+    failed: bool,
+}
+
+impl<'a> Read for FlakyReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.failed {
+            self.failed = true;
+            return Err(io::Error::from(io::ErrorKind::TimedOut))
+        }
+        return self.data.read(buf)
+    }
+}
+
+#[test]
+fn transient_errors_retried(){
+    let data = b"cancellable read from a flaky mount";
+    let cancel = AtomicBool::new(false);
+    let context = ParanoidHash::default().retry_policy(Some(RetryPolicy { max_attempts: 2, backoff: Duration::ZERO }));
+    assert_eq!(context.read_from_reader_cancellable(FlakyReader { data, failed: false },&cancel).unwrap(),context.read_bytes(data));
+
+    let result = ParanoidHash::default().read_from_reader_cancellable(FlakyReader { data, failed: false },&cancel);
+    assert_eq!(result,Err(FileError::Io(io::ErrorKind::TimedOut)));
+}
+
+#[test]
+fn max_file_size_checked_before_reading(){
+    let path = "tests/fixtures/report/alpha.txt";
+    let context = ParanoidHash::default().max_file_size(Some(1));
+    assert_eq!(context.read_cancellable(path,&AtomicBool::new(false)),context.read_using_std(path));
+    assert!(context.read_cancellable(path,&AtomicBool::new(false)).is_err());
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::PathBuf;
+
+use paranoid_hash::{FileError,ParanoidHash};
+
+#[test]
+fn relative_path_resolved(){
+    let report = ParanoidHash::default().report_files(&["tests/fixtures/report/nested/../alpha.txt"]);
+    let entry = &report.entries[0];
+    assert_eq!(entry.path,PathBuf::from("tests/fixtures/report/nested/../alpha.txt"));
+    assert!(entry.canonical);
+    assert!(entry.resolved_path.is_absolute());
+    assert_eq!(entry.resolved_path,fs::canonicalize("tests/fixtures/report/alpha.txt").unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_resolved_to_target(){
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("target.txt");
+    let link = dir.path().join("link.txt");
+    fs::write(&target,b"pointed at").unwrap();
+    std::os::unix::fs::symlink(&target,&link).unwrap();
+
+    let context = ParanoidHash::default();
+    let report = context.report_files(&[&link]);
+    let entry = &report.entries[0];
+    assert_eq!(entry.path,link);
+    assert!(entry.canonical);
+    assert_eq!(entry.resolved_path,fs::canonicalize(&target).unwrap());
+    assert_eq!((entry.blake2b.clone().unwrap(),entry.os_digest.clone().unwrap()),context.read(&target).unwrap());
+}
+
+#[test]
+fn per_entry_in_directory_reports(){
+    let report = ParanoidHash::default().report_dir("tests/fixtures/report").unwrap();
+    for entry in report.entries.iter() {
+        assert!(entry.canonical);
+        assert_eq!(entry.resolved_path,fs::canonicalize(&entry.path).unwrap());
+    }
+}
+
+#[test]
+fn original_path_when_failed_or_disabled(){
+    // A missing file cannot be canonicalized
+    let report = ParanoidHash::default().report_files(&["tests/fixtures/report/missing.txt"]);
+    assert_eq!(report.entries[0].error,Some(FileError::FileNotFound));
+    assert!(!report.entries[0].canonical);
+    assert_eq!(report.entries[0].resolved_path,PathBuf::from("tests/fixtures/report/missing.txt"));
+
+    let context = ParanoidHash::default().canonicalize_paths(false);
+    assert!(!context.return_canonicalize_paths());
+    let report = context.report_files(&["tests/fixtures/report/nested/../alpha.txt"]);
+    assert!(!report.entries[0].canonical);
+    assert_eq!(report.entries[0].resolved_path,report.entries[0].path);
+    assert!(report.entries[0].error.is_none());
+}
@@ -0,0 +1,28 @@
+use paranoid_hash::{OsAlgorithm, ParanoidHash};
+
+#[test]
+fn construction(){
+    let context = ParanoidHash::new(48,OsAlgorithm::SHA512);
+    let (blake2b,chained) = context.read_chained("tests/fixtures/report/alpha.txt").unwrap();
+
+    assert_eq!(blake2b,context.read_bytes(b"alpha\n").0);
+    assert_eq!(chained,context.read_bytes(&ParanoidHash::decode_from_hex(&blake2b)).1);
+    assert_eq!(context.read_bytes_chained(b"alpha\n").unwrap(),(blake2b,chained));
+}
+
+#[test]
+fn fixed_vectors(){
+    // Computed independently with Python hashlib
+    let (blake2b,sha256) = ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes_chained(b"abc").unwrap();
+    assert_eq!(blake2b,"BDDD813C634239723171EF3FEE98579B94964E3BB1CB3E427262C8C068D52319");
+    assert_eq!(sha256,"1CE43A7FF97AE291CB5B8E47E1FBB9A271DE90F2B28D719C8724780EA01EA597");
+
+    let (blake2b,sha512) = ParanoidHash::default().read_bytes_chained(b"").unwrap();
+    assert_eq!(blake2b,"786A02F742015903C6C6FD852552D272912F4740E15847618A86E217F71F5419D25E1031AFEE585313896444934EB04B903A685B1448B755D56F701AFE9BE2CE");
+    assert_eq!(sha512,"4DB36E2C56E9CCE352BB80C74255F501EA403EB326CB194E788FB78E8C54C3A41FEC4E7F639B0A750A41ADE9044C7D2519030F82B48736D137FFF48FA44B55A3");
+}
+
+#[test]
+fn missing_file(){
+    assert!(ParanoidHash::default().read_chained("tests/fixtures/report/missing.txt").is_err());
+}
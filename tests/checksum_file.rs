@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::Path;
+
+use paranoid_hash::{FileError, OsAlgorithm, ParanoidHash, VerifyOutcome};
+
+// SHA256("data") and SHA256("other")
+const DATA_SHA256: &str = "3a6eb0790f39ac87c94f3856b2dd2c5d110e6811602261a9a923d3bb23adc8b7";
+const OTHER_SHA256: &str = "d9298a10d1b0735837dc4bd85dac641b0f3cef27a47e5d53a54f2f3f5b2fcffa";
+
+fn setup(sums: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("data.txt"),"data").unwrap();
+    fs::write(dir.path().join("SHA256SUMS"),sums).unwrap();
+    return dir
+}
+
+fn verify(dir: &Path) -> Result<VerifyOutcome,FileError> {
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    return context.verify_against_checksum_file(dir.join("data.txt"),dir.join("SHA256SUMS"))
+}
+
+#[test]
+fn text_and_binary_markers(){
+    for sums in [format!("{}  data.txt\n",DATA_SHA256),format!("{} *data.txt\n",DATA_SHA256)] {
+        let dir = setup(&sums);
+        assert_eq!(verify(dir.path()),Ok(VerifyOutcome::Match));
+    }
+}
+
+#[test]
+fn dot_slash_prefix_and_upper_case(){
+    let dir = setup(&format!("{}  ./data.txt\n",DATA_SHA256.to_uppercase()));
+    assert_eq!(verify(dir.path()),Ok(VerifyOutcome::Match));
+}
+
+#[test]
+fn mismatch(){
+    let dir = setup(&format!("{} *data.txt\n",OTHER_SHA256));
+    assert_eq!(verify(dir.path()),Ok(VerifyOutcome::Mismatch));
+}
+
+#[test]
+fn not_listed(){
+    let dir = setup(&format!("{}  other.txt\n# data.txt\n\n",OTHER_SHA256));
+    assert_eq!(verify(dir.path()),Ok(VerifyOutcome::NotListed));
+}
+
+#[test]
+fn only_the_matching_entry_is_checked(){
+    let dir = setup(&format!("{}  other.txt\n{} *data.txt\n",OTHER_SHA256,DATA_SHA256));
+    assert_eq!(verify(dir.path()),Ok(VerifyOutcome::Match));
+}
+
+#[test]
+fn conflicting_duplicates(){
+    let dir = setup(&format!("{}  data.txt\n{} *./data.txt\n",DATA_SHA256,OTHER_SHA256));
+    assert_eq!(verify(dir.path()),Err(FileError::ConflictingChecksums));
+
+    // Repeating the same digest is not a conflict
+    let dir = setup(&format!("{}  data.txt\n{} *./data.txt\n",DATA_SHA256,DATA_SHA256.to_uppercase()));
+    assert_eq!(verify(dir.path()),Ok(VerifyOutcome::Match));
+}
+
+#[test]
+fn b2sums_with_the_same_context(){
+    let dir = setup("");
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    let blake2b = context.read(dir.path().join("data.txt")).unwrap().0;
+    fs::write(dir.path().join("SHA256SUMS"),format!("{}  data.txt\n",blake2b.to_lowercase())).unwrap();
+    assert_eq!(verify(dir.path()),Ok(VerifyOutcome::Match));
+}
+
+#[test]
+fn wrong_digest_length(){
+    let dir = setup("A9993E364706816ABA3E25717850C26C9CD0D89D  data.txt\n");
+    assert_eq!(verify(dir.path()),Err(FileError::InvalidDigest));
+}
+
+#[test]
+fn write_and_verify(){
+    let dir = setup("");
+    fs::write(dir.path().join("other.txt"),"other").unwrap();
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    let sums = dir.path().join("SHA256SUMS");
+
+    context.write_checksum_file(&[dir.path().join("data.txt"),dir.path().join("other.txt")],&sums).unwrap();
+    assert_eq!(fs::read_to_string(&sums).unwrap(),format!("{}  data.txt\n{}  other.txt\n",DATA_SHA256,OTHER_SHA256));
+    assert_eq!(verify(dir.path()),Ok(VerifyOutcome::Match));
+}
+
+#[test]
+fn write_missing_file(){
+    let dir = setup("");
+    let missing = dir.path().join("missing.txt");
+    let result = ParanoidHash::default().write_checksum_file(&[&missing],dir.path().join("SUMS"));
+    assert_eq!(result,Err(FileError::AtPath(missing,Box::new(FileError::FileNotFound))));
+    assert!(!dir.path().join("SUMS").exists());
+}
+
+#[test]
+fn escaped_names_are_parsed(){
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("sub")).unwrap();
+    fs::write(dir.path().join("sub").join("data.txt"),"data").unwrap();
+    fs::write(dir.path().join("SHA256SUMS"),format!("\\{}  sub/data\\\\.txt\n{}  sub/data.txt\n",OTHER_SHA256,DATA_SHA256)).unwrap();
+
+    // `sub/data\.txt` is a different file, so only the second line applies
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    assert_eq!(context.verify_against_checksum_file(dir.path().join("sub").join("data.txt"),dir.path().join("SHA256SUMS")),Ok(VerifyOutcome::Match));
+
+    // An unknown escape is not a valid line
+    fs::write(dir.path().join("SHA256SUMS"),format!("\\{}  sub/data\\t.txt\n",DATA_SHA256)).unwrap();
+    assert_eq!(context.verify_against_checksum_file(dir.path().join("sub").join("data.txt"),dir.path().join("SHA256SUMS")),Ok(VerifyOutcome::NotListed));
+}
+
+// Newlines and backslashes are not valid in Windows file names
+#[cfg(unix)]
+#[test]
+fn newline_and_backslash_names(){
+    let dir = tempfile::tempdir().unwrap();
+    let newline = dir.path().join("two\nlines.txt");
+    let backslash = dir.path().join("back\\slash.txt");
+    fs::write(&newline,"data").unwrap();
+    fs::write(&backslash,"other").unwrap();
+
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    let sums = dir.path().join("SHA256SUMS");
+    context.write_checksum_file(&[&newline,&backslash],&sums).unwrap();
+    assert_eq!(fs::read_to_string(&sums).unwrap(),format!("\\{}  two\\nlines.txt\n\\{}  back\\\\slash.txt\n",DATA_SHA256,OTHER_SHA256));
+
+    assert_eq!(context.verify_against_checksum_file(&newline,&sums),Ok(VerifyOutcome::Match));
+    assert_eq!(context.verify_against_checksum_file(&backslash,&sums),Ok(VerifyOutcome::Match));
+
+    // Checked by coreutils itself when it is installed
+    if let Ok(output) = std::process::Command::new("sha256sum").arg("-c").arg("SHA256SUMS").current_dir(dir.path()).output() {
+        assert!(output.status.success(),"{}",String::from_utf8_lossy(&output.stdout));
+    }
+}
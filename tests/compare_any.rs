@@ -0,0 +1,44 @@
+use paranoid_hash::ParanoidHash;
+
+const A: &str = "A9993E364706816ABA3E25717850C26C9CD0D89D";
+const B: &str = "DA39A3EE5E6B4B0D3255BFEF95601890AFD80709";
+const C: &str = "84983E441C3BD26EBAAE4AA1F95129E5E54670F1";
+
+#[test]
+fn no_candidates(){
+    assert!(!ParanoidHash::compare_hash_any(A,&[]));
+    assert_eq!(ParanoidHash::compare_hash_any_index(A,&[]),None);
+    assert_eq!(ParanoidHash::compare_bytes_any_index(b"abc",&[]),None);
+}
+
+#[test]
+fn one_candidate(){
+    assert_eq!(ParanoidHash::compare_hash_any_index(A,&[A]),Some(0));
+    assert_eq!(ParanoidHash::compare_hash_any_index(A,&[&A.to_lowercase()]),Some(0));
+    assert_eq!(ParanoidHash::compare_hash_any_index(A,&[B]),None);
+    assert_eq!(ParanoidHash::compare_hash_any_index(A,&[&A[..38]]),None);
+}
+
+#[test]
+fn match_position(){
+    let candidates = [B,C,B,C,B];
+    for position in 0..candidates.len() {
+        let mut with_match = candidates;
+        with_match[position] = A;
+        assert_eq!(ParanoidHash::compare_hash_any_index(A,&with_match),Some(position));
+        assert!(ParanoidHash::compare_hash_any(A,&with_match));
+    }
+    assert!(!ParanoidHash::compare_hash_any(A,&candidates));
+
+    // The first of several matches
+    assert_eq!(ParanoidHash::compare_hash_any_index(A,&[B,A,C,A]),Some(1));
+}
+
+#[test]
+fn bytes(){
+    let candidates: [&[u8]; 4] = [b"one",b"two",b"three",b"tw"];
+    assert_eq!(ParanoidHash::compare_bytes_any_index(b"two",&candidates),Some(1));
+    assert_eq!(ParanoidHash::compare_bytes_any_index(b"three",&candidates),Some(2));
+    assert!(!ParanoidHash::compare_bytes_any(b"TWO",&candidates));
+    assert!(ParanoidHash::compare_bytes_any(b"",&[b"x",b""]));
+}
@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use paranoid_hash::{FileError,ParanoidHash,WalkOptions};
+
+fn write(root: &Path, name: &str, contents: &str) {
+    let path = root.join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path,contents).unwrap();
+}
+
+// Two trees with a file in each report category
+fn trees() -> (tempfile::TempDir,PathBuf,PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let (a,b) = (dir.path().join("a"),dir.path().join("b"));
+    for root in [&a,&b].iter() {
+        write(root,"same.txt","same");
+        write(root,"nested/same.txt","nested");
+    }
+    write(&a,"only_a.txt","a");
+    write(&b,"nested/only_b.txt","b");
+    write(&a,"resized.txt","short");
+    write(&b,"resized.txt","longer");
+    write(&a,"edited.txt","paranoid");
+    write(&b,"edited.txt","paranoiD");
+    return (dir,a,b)
+}
+
+#[test]
+fn identical(){
+    let (_dir,a,_) = trees();
+    let report = ParanoidHash::default().compare_dirs(&a,&a).unwrap();
+    assert!(report.is_identical());
+    assert_eq!(report.matched,5);
+}
+
+#[test]
+fn every_category(){
+    let (_dir,a,b) = trees();
+    let context = ParanoidHash::default();
+    let report = context.compare_dirs(&a,&b).unwrap();
+
+    assert!(!report.is_identical());
+    assert_eq!(report.only_in_a,vec![PathBuf::from("only_a.txt")]);
+    assert_eq!(report.only_in_b,vec![PathBuf::from("nested/only_b.txt")]);
+    assert_eq!(report.matched,2);
+    assert_eq!(report.mismatched.len(),2);
+
+    // Same size, so both sides are hashed
+    let edited = &report.mismatched[0];
+    assert_eq!(edited.path,PathBuf::from("edited.txt"));
+    assert_eq!((edited.size_a,edited.size_b),(8,8));
+    assert_eq!(edited.digests_a,Some(context.read_str("paranoid")));
+    assert_eq!(edited.digests_b,Some(context.read_str("paranoiD")));
+
+    // Different sizes are not hashed
+    let resized = &report.mismatched[1];
+    assert_eq!(resized.path,PathBuf::from("resized.txt"));
+    assert_eq!((resized.size_a,resized.size_b),(5,6));
+    assert_eq!(resized.digests_a,None);
+    assert_eq!(resized.digests_b,None);
+
+    // Swapping the directories swaps the sides
+    let swapped = context.compare_dirs(&b,&a).unwrap();
+    assert_eq!(swapped.only_in_a,report.only_in_b);
+    assert_eq!(swapped.only_in_b,report.only_in_a);
+}
+
+#[test]
+fn walk_options(){
+    let (_dir,a,b) = trees();
+    write(&a,".hidden","a");
+
+    let options = WalkOptions { exclude: vec!["only_*".to_string(),"resized.txt".to_string(),"edited.txt".to_string()], include_hidden: false, ..WalkOptions::default() };
+    let report = ParanoidHash::default().walk_options(options).compare_dirs(&a,&b).unwrap();
+    assert!(report.is_identical());
+    assert_eq!(report.matched,2);
+
+    let report = ParanoidHash::default().compare_dirs(&a,&b).unwrap();
+    assert!(report.only_in_a.contains(&PathBuf::from(".hidden")));
+}
+
+#[test]
+fn missing_directory(){
+    let (dir,a,_) = trees();
+    assert_eq!(ParanoidHash::default().compare_dirs(&a,dir.path().join("missing")),Err(FileError::FileNotFound));
+}
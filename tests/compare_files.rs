@@ -0,0 +1,36 @@
+use paranoid_hash::{FileError, ParanoidHash};
+use std::io::Write;
+
+fn temp_file(contents: &[u8]) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(contents).unwrap();
+    file.flush().unwrap();
+    return file
+}
+
+#[test]
+fn identical_files(){
+    let a = temp_file(b"The same contents");
+    let b = temp_file(b"The same contents");
+    assert_eq!(ParanoidHash::default().compare_files(a.path(),b.path()),Ok(true));
+}
+
+#[test]
+fn different_sizes(){
+    let a = temp_file(b"short");
+    let b = temp_file(b"a little longer");
+    assert_eq!(ParanoidHash::default().compare_files(a.path(),b.path()),Ok(false));
+}
+
+#[test]
+fn same_size_different_contents(){
+    let a = temp_file(b"contents A");
+    let b = temp_file(b"contents B");
+    assert_eq!(ParanoidHash::default().compare_files(a.path(),b.path()),Ok(false));
+}
+
+#[test]
+fn missing_file(){
+    let a = temp_file(b"contents");
+    assert_eq!(ParanoidHash::default().compare_files(a.path(),"tests/fixtures/report/missing.txt"),Err(FileError::FileNotFound));
+}
@@ -28,4 +28,18 @@ fn compare_invalid_str(){
 fn compare_invalid_case(){
     let invalid_case: bool = ParanoidHash::compare_hash("333fcb4ee1aa7c115355ec66ceac917c8bfd815bf7587d325aec1864edd24e34d5abe2c6b1b5ee3face62fed78dbef802f2a85cb91d455a8f5249d330853cb3c","333FCB4EE1AA7C115355EC66CEAC917C8BFD815BF7587D325AEC1864EDD24E34D5ABE2C6B1B5EE3FACE62FED78DBEF802F2A85CB91D455A8F5249D330853CB3C");
     assert_eq!(invalid_case,false);
-}
\ No newline at end of file
+}
+
+#[test]
+fn compare_mixed_types(){
+    use std::borrow::Cow;
+
+    let digest: String = String::from("333fcb4ee1aa7c115355ec66ceac917c8bfd815bf7587d325aec1864edd24e34d5abe2c6b1b5ee3face62fed78dbef802f2a85cb91d455a8f5249d330853cb3c");
+    let literal: &str = "333fcb4ee1aa7c115355ec66ceac917c8bfd815bf7587d325aec1864edd24e34d5abe2c6b1b5ee3face62fed78dbef802f2a85cb91d455a8f5249d330853cb3c";
+
+    assert!(ParanoidHash::compare_hash(literal,digest.clone()));
+    assert!(ParanoidHash::compare_hash(&digest,literal));
+    assert!(ParanoidHash::compare_hash(Cow::Borrowed(literal),&digest));
+    assert!(ParanoidHash::compare_hash(Cow::<str>::Owned(digest.clone()),literal));
+    assert!(!ParanoidHash::compare_hash("Hello",String::from("Hella")));
+}
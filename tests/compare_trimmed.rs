@@ -0,0 +1,51 @@
+use std::fs;
+
+use paranoid_hash::{OsAlgorithm,ParanoidHash,VerifyOutcome,VerifyPolicy};
+
+const SHA1: &str = "A9993E364706816ABA3E25717850C26C9CD0D89D";
+
+#[test]
+fn surrounding_whitespace(){
+    assert!(ParanoidHash::compare_hash_trimmed(format!("{}\n",SHA1),SHA1));
+    assert!(ParanoidHash::compare_hash_trimmed(format!("{}\r\n",SHA1),SHA1.to_lowercase()));
+    assert!(ParanoidHash::compare_hash_trimmed(format!("\t{}\t",SHA1),format!(" {} ",SHA1)));
+    assert!(!ParanoidHash::compare_hash_trimmed(format!("{}\n",SHA1),&SHA1[1..]));
+
+    // The untrimmed comparison is unchanged
+    assert!(!ParanoidHash::compare_hash(format!("{}\n",SHA1),SHA1));
+}
+
+#[test]
+fn internal_whitespace(){
+    let spaced = format!("{} {}",&SHA1[..20],&SHA1[20..]);
+    assert!(!ParanoidHash::compare_hash_trimmed(&spaced,SHA1));
+    assert!(!ParanoidHash::compare_hash_trimmed(format!("{}\t{}",&SHA1[..20],&SHA1[20..]),SHA1));
+}
+
+#[test]
+fn verify_file(){
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    let (blake2b,sha256) = context.read("examples/example_file.txt").unwrap();
+
+    let verification = context.verify_file("examples/example_file.txt",Some(&format!("{}\r\n",blake2b)),Some(&format!("\t{}\n",sha256)),VerifyPolicy::Both).unwrap();
+    assert!(verification.passed);
+
+    let spaced = format!("{} {}",&sha256[..32],&sha256[32..]);
+    assert!(context.verify_file("examples/example_file.txt",None,Some(&spaced),VerifyPolicy::OsOnly).is_err());
+
+    let unlabeled = ParanoidHash::verify_file_unlabeled("examples/example_file.txt",&format!("{}\n",sha256)).unwrap();
+    assert!(unlabeled.passed);
+}
+
+#[test]
+fn checksum_file(){
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("data.txt"),"data").unwrap();
+
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    let sha256 = context.read(dir.path().join("data.txt")).unwrap().1;
+    fs::write(dir.path().join("SHA256SUMS"),format!("  {} *data.txt\r\n",sha256.to_lowercase())).unwrap();
+
+    let outcome = context.verify_against_checksum_file(dir.path().join("data.txt"),dir.path().join("SHA256SUMS")).unwrap();
+    assert_eq!(outcome,VerifyOutcome::Match);
+}
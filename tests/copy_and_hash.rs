@@ -0,0 +1,73 @@
+use paranoid_hash::{FileError, OsAlgorithm, ParanoidHash};
+use std::fs;
+use std::io::ErrorKind;
+
+#[test]
+fn digests_match_source_and_copy(){
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    let data: Vec<u8> = (0..300_000u32).map(|i| (i % 253) as u8).collect();
+    fs::write(&src,&data).unwrap();
+
+    for (name,context) in [("a",ParanoidHash::default()),("b",ParanoidHash::new(32,OsAlgorithm::SHA256))] {
+        let dst = dir.path().join(name);
+        let digests = context.copy_and_hash(&src,&dst).unwrap();
+        assert_eq!(fs::read(&dst).unwrap(),data);
+        assert_eq!(digests,context.read(&src).unwrap());
+        assert_eq!(digests,context.read(&dst).unwrap());
+    }
+}
+
+#[test]
+fn refuses_to_overwrite(){
+    let dir = tempfile::tempdir().unwrap();
+    let (src,dst) = (dir.path().join("src"),dir.path().join("dst"));
+    fs::write(&src,"new contents").unwrap();
+    fs::write(&dst,"old contents").unwrap();
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.copy_and_hash(&src,&dst),Err(FileError::Io(ErrorKind::AlreadyExists)));
+    assert_eq!(fs::read_to_string(&dst).unwrap(),"old contents");
+
+    assert_eq!(context.copy_and_hash_overwrite(&src,&dst).unwrap(),context.read_str("new contents"));
+    assert_eq!(fs::read_to_string(&dst).unwrap(),"new contents");
+
+    assert_eq!(context.copy_and_hash_overwrite(&src,&src),Err(FileError::Io(ErrorKind::InvalidInput)));
+    assert_eq!(fs::read_to_string(&src).unwrap(),"new contents");
+}
+
+#[test]
+fn partial_destination_is_removed(){
+    let dir = tempfile::tempdir().unwrap();
+    let (src,dst) = (dir.path().join("src"),dir.path().join("dst"));
+    fs::write(&src,"").unwrap();
+
+    let context = ParanoidHash::default().reject_empty_input(true);
+    assert_eq!(context.copy_and_hash(&src,&dst),Err(FileError::EmptyFile));
+    assert!(!dst.exists());
+
+    assert_eq!(context.copy_and_hash(dir.path().join("missing"),&dst),Err(FileError::FileNotFound));
+    assert!(!dst.exists());
+}
+
+#[test]
+fn copy_is_verified(){
+    let dir = tempfile::tempdir().unwrap();
+    let (src,dst) = (dir.path().join("src"),dir.path().join("dst"));
+    fs::write(&src,"paranoid").unwrap();
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.copy_and_verify(&src,&dst).unwrap(),context.read(&src).unwrap());
+    assert_eq!(fs::read_to_string(&dst).unwrap(),"paranoid");
+}
+
+#[test]
+fn corrupted_copy_fails_verification(){
+    let dir = tempfile::tempdir().unwrap();
+    let (src,dst) = (dir.path().join("src"),dir.path().join("dst"));
+    fs::write(&src,"paranoid").unwrap();
+
+    let result = ParanoidHash::default().copy_and_verify_with_hook(&src,&dst,|copy| fs::write(copy,"paranoiD").unwrap());
+    assert_eq!(result,Err(FileError::VerificationFailed));
+    assert!(!dst.exists());
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "fallback")]
+
+use paranoid_hash::{OsAlgorithm, ParanoidHash};
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+const ALGORITHMS: &[OsAlgorithm] = &[
+    OsAlgorithm::SHA1, OsAlgorithm::SHA256, OsAlgorithm::SHA512, OsAlgorithm::SHA512_256,
+    #[cfg(feature = "insecure-md5")]
+    OsAlgorithm::MD5,
+];
+
+#[test]
+fn agreeing_implementations_pass(){
+    for os_hash in ALGORITHMS.iter() {
+        let plain = ParanoidHash::new_allowing_weak(64,os_hash.clone());
+        let checked = ParanoidHash::new_allowing_weak(64,os_hash.clone()).cross_check(true);
+        assert_eq!(checked.read(FILE),plain.read(FILE));
+        assert_eq!(checked.read_from_reader(&b"alpha\n"[..]),plain.read_from_reader(&b"alpha\n"[..]));
+        assert_eq!(checked.try_read_bytes_parallel(b"alpha\n"),plain.try_read_bytes_parallel(b"alpha\n"));
+    }
+}
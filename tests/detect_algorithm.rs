@@ -0,0 +1,56 @@
+use paranoid_hash::{detect_algorithm, Algo, DigestStatus, FileError, OsAlgorithm, ParanoidHash, TaggedParseError};
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+
+#[test]
+fn os_algorithm_from_length(){
+    assert_eq!(OsAlgorithm::from_hex_digest("DA39A3EE5E6B4B0D3255BFEF95601890AFD80709"),Some(OsAlgorithm::SHA1));
+    assert_eq!(OsAlgorithm::from_hex_digest(&"a".repeat(64)),Some(OsAlgorithm::SHA256));
+    assert_eq!(OsAlgorithm::from_hex_digest(&"0".repeat(128)),Some(OsAlgorithm::SHA512));
+    assert_eq!(OsAlgorithm::from_hex_digest(&"0".repeat(96)),None);
+    assert_eq!(OsAlgorithm::from_hex_digest(&"g".repeat(64)),None);
+    assert_eq!(OsAlgorithm::from_hex_digest(""),None);
+}
+
+#[test]
+fn candidates(){
+    assert_eq!(detect_algorithm(&"0".repeat(64)),vec![Algo::Os(OsAlgorithm::SHA256),Algo::Os(OsAlgorithm::SHA512_256),Algo::Blake2b(32)]);
+    assert_eq!(detect_algorithm(&"0".repeat(96)),vec![Algo::Blake2b(48)]);
+    assert!(detect_algorithm(&"0".repeat(63)).is_empty());
+    assert!(detect_algorithm(&"0".repeat(30)).is_empty());
+    assert!(detect_algorithm(&"z".repeat(64)).is_empty());
+}
+
+#[test]
+fn parse_names(){
+    assert_eq!("sha256".parse::<OsAlgorithm>(),Ok(OsAlgorithm::SHA256));
+    assert_eq!("SHA512_256".parse::<OsAlgorithm>(),Ok(OsAlgorithm::SHA512_256));
+    assert_eq!("Sha1".parse::<OsAlgorithm>(),Ok(OsAlgorithm::SHA1));
+    assert_eq!("sha3".parse::<OsAlgorithm>(),Err(TaggedParseError::UnknownAlgorithm(String::from("sha3"))));
+}
+
+// MD5 only exists with the `insecure-md5` feature (see tests/md5.rs)
+#[cfg(not(feature = "insecure-md5"))]
+#[test]
+fn md5_is_compiled_out(){
+    assert_eq!("md5".parse::<OsAlgorithm>(),Err(TaggedParseError::UnknownAlgorithm(String::from("md5"))));
+    assert_eq!(OsAlgorithm::from_hex_digest("d41d8cd98f00b204e9800998ecf8427e"),None);
+    assert!(detect_algorithm(&"0".repeat(32)).is_empty());
+}
+
+#[test]
+fn verify_unlabeled(){
+    let sha256 = ParanoidHash::new(64,OsAlgorithm::SHA256).read(FILE).unwrap().1;
+    let verification = ParanoidHash::verify_file_unlabeled(FILE,&sha256).unwrap();
+    assert!(verification.passed);
+    assert_eq!(verification.os,DigestStatus::Match);
+    assert_eq!(verification.blake2b,DigestStatus::Mismatch);
+
+    let blake2b = ParanoidHash::new(48,OsAlgorithm::SHA256).read(FILE).unwrap().0;
+    let verification = ParanoidHash::verify_file_unlabeled(FILE,&blake2b).unwrap();
+    assert!(verification.passed);
+    assert_eq!(verification.os,DigestStatus::NotChecked);
+    assert_eq!(verification.blake2b,DigestStatus::Match);
+
+    assert_eq!(ParanoidHash::verify_file_unlabeled(FILE,"xyz"),Err(FileError::InvalidDigest));
+}
@@ -0,0 +1,70 @@
+use paranoid_hash::{HashDiff, HexError, ParanoidHash};
+
+#[test]
+fn matching_ignores_case(){
+    let (blake2b,_) = ParanoidHash::default().read_str("Hello");
+    let diff = ParanoidHash::diff_hashes(&blake2b,blake2b.to_lowercase());
+    assert!(diff.is_match());
+    assert_eq!(diff,HashDiff { left_len: 128, right_len: 128, left_error: None, right_error: None, first_difference: None });
+    assert_eq!(diff.to_string(),"digests match");
+    assert!(ParanoidHash::compare_hash(&blake2b,blake2b.to_lowercase()));
+}
+
+#[test]
+fn first_differing_byte(){
+    let diff = ParanoidHash::diff_hashes("00112233","00112234");
+    assert!(!diff.is_match());
+    assert!(!diff.lengths_differ());
+    assert_eq!(diff.first_difference,Some(3));
+    assert_eq!(diff.to_string(),"first difference at byte 3");
+
+    assert_eq!(ParanoidHash::diff_hashes("FF112233","00112233").first_difference,Some(0));
+}
+
+#[test]
+fn lengths_differ(){
+    let diff = ParanoidHash::diff_hashes("0011","001122");
+    assert!(diff.lengths_differ());
+    assert_eq!((diff.left_len,diff.right_len),(4,6));
+    // One is the start of the other, so they differ where the shorter one ends
+    assert_eq!(diff.first_difference,Some(2));
+    assert_eq!(diff.to_string(),"lengths differ: left has 4 characters and right has 6\nfirst difference at byte 2");
+
+    let diff = ParanoidHash::diff_hashes("1111","222222");
+    assert_eq!(diff.first_difference,Some(0));
+}
+
+#[test]
+fn invalid_character(){
+    let diff = ParanoidHash::diff_hashes("00g0","0000");
+    assert_eq!(diff.left_error,Some(HexError::InvalidCharacter { character: 'g', index: 2 }));
+    assert_eq!(diff.right_error,None);
+    assert_eq!(diff.first_difference,None);
+    assert!(!diff.is_match());
+    assert_eq!(diff.to_string(),"left is not valid hexadecimal: invalid hexadecimal character 'g' at index 2");
+
+    // A trailing newline, as left behind when reading a digest from a file
+    let diff = ParanoidHash::diff_hashes("0000","0000\n");
+    assert_eq!(diff.right_error,Some(HexError::InvalidCharacter { character: '\n', index: 4 }));
+    assert!(diff.lengths_differ());
+
+    // `compare_hash()` does not accept a prefix either
+    let diff = ParanoidHash::diff_hashes("0xABCD","abcd");
+    assert_eq!(diff.left_error,Some(HexError::InvalidCharacter { character: 'x', index: 1 }));
+    assert!(!ParanoidHash::compare_hash("0xABCD","abcd"));
+}
+
+#[test]
+fn odd_length(){
+    let diff = ParanoidHash::diff_hashes("abc","abcd");
+    assert_eq!(diff.left_error,Some(HexError::OddLength));
+    assert_eq!(diff.first_difference,None);
+}
+
+#[test]
+fn both_invalid(){
+    let diff = ParanoidHash::diff_hashes("zz","zz");
+    assert_eq!(diff.left_error,Some(HexError::InvalidCharacter { character: 'z', index: 0 }));
+    assert_eq!(diff.right_error,Some(HexError::InvalidCharacter { character: 'z', index: 0 }));
+    assert!(!diff.is_match());
+}
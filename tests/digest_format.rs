@@ -0,0 +1,43 @@
+use paranoid_hash::{DigestFormatError, DigestKind, FileError, OsAlgorithm, ParanoidHash, VerifyPolicy};
+
+#[test]
+fn os_lengths(){
+    let algorithms = [(OsAlgorithm::SHA1,40),(OsAlgorithm::SHA256,64),(OsAlgorithm::SHA512_256,64),(OsAlgorithm::SHA512,128)];
+    for (os_hash,length) in algorithms.iter() {
+        let context = ParanoidHash::new_allowing_weak(64,os_hash.clone());
+        let digest = context.read_str("abc").1;
+        assert_eq!(digest.len(),*length);
+        assert_eq!(context.is_valid_hex_digest(&digest,DigestKind::Os),Ok(()));
+        assert_eq!(context.is_valid_hex_digest(&digest.to_lowercase(),DigestKind::Os),Ok(()));
+        assert_eq!(context.is_valid_hex_digest(&digest[2..],DigestKind::Os),Err(DigestFormatError::Length { expected: *length, actual: length - 2 }));
+    }
+}
+
+#[test]
+fn library_digest_sizes(){
+    for size in [1,20,32,48,64].iter() {
+        let context = ParanoidHash::new(*size,OsAlgorithm::SHA256);
+        let digest = context.read_str("abc").0;
+        assert_eq!(context.is_valid_hex_digest(&digest,DigestKind::Library),Ok(()));
+        assert_eq!(context.is_valid_hex_digest(&format!("{}00",digest),DigestKind::Library),Err(DigestFormatError::Length { expected: size * 2, actual: size * 2 + 2 }));
+    }
+}
+
+#[test]
+fn invalid_characters_are_located(){
+    let context = ParanoidHash::new(4,OsAlgorithm::SHA256);
+    assert_eq!(context.is_valid_hex_digest("0123456g",DigestKind::Library),Err(DigestFormatError::InvalidCharacter { character: 'g', index: 7 }));
+    assert_eq!(context.is_valid_hex_digest(" 0123456",DigestKind::Library),Err(DigestFormatError::InvalidCharacter { character: ' ', index: 0 }));
+    // Reported before the length
+    assert_eq!(context.is_valid_hex_digest("xyz",DigestKind::Library),Err(DigestFormatError::InvalidCharacter { character: 'x', index: 0 }));
+}
+
+#[test]
+fn verification_fails_before_hashing(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    // The file does not exist, so InvalidDigest shows the digests were checked first
+    let missing = "tests/fixtures/report/missing.txt";
+    assert_eq!(context.verify_file(missing,Some("00"),None,VerifyPolicy::LibraryOnly),Err(FileError::InvalidDigest));
+    assert_eq!(context.verify_file(missing,None,Some("not a digest"),VerifyPolicy::OsOnly),Err(FileError::InvalidDigest));
+    assert_eq!(context.verify_file(missing,None,None,VerifyPolicy::OsOnly),Err(FileError::FileNotFound));
+}
@@ -0,0 +1,110 @@
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use paranoid_hash::{DigestKey, HexError, OsAlgorithm, ParanoidHash, MAX_KEY_BYTES};
+
+const FIXTURES: &str = "tests/fixtures/dedup";
+
+fn files(dir: &Path, found: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files(&path, found);
+        }
+        else {
+            found.push(path);
+        }
+    }
+}
+
+#[test]
+fn dedup_map_over_fixtures(){
+    let context = ParanoidHash::default();
+    let mut paths = Vec::new();
+    files(Path::new(FIXTURES), &mut paths);
+
+    let mut map: HashMap<DigestKey,Vec<PathBuf>> = HashMap::new();
+    for path in paths {
+        let (blake2b,_) = context.read_digests(&path).unwrap();
+        map.entry(DigestKey::try_from(blake2b).unwrap()).or_default().push(path);
+    }
+    assert_eq!(map.len(),2);
+
+    let mut groups: Vec<Vec<PathBuf>> = map.into_values().filter(|paths| paths.len() > 1).collect();
+    for group in groups.iter_mut() {
+        group.sort();
+    }
+    assert_eq!(groups,context.find_duplicates(FIXTURES).unwrap());
+
+    let root = Path::new(FIXTURES);
+    assert_eq!(groups,vec![vec![root.join("first.txt"),root.join("nested/third.txt"),root.join("second.txt")]]);
+}
+
+#[test]
+fn lookup_by_bytes_and_hex(){
+    let context = ParanoidHash::default();
+    let (blake2b,sha512) = context.read_bytes_digests(b"same contents\n").unwrap();
+    let mut map = HashMap::new();
+    map.insert(DigestKey::try_from(&blake2b).unwrap(),"blake2b");
+    map.insert(DigestKey::try_from(&sha512).unwrap(),"sha512");
+
+    assert_eq!(map.get(blake2b.as_bytes()),Some(&"blake2b"));
+    assert_eq!(map.get(sha512.as_bytes()),Some(&"sha512"));
+
+    let (blake2b_hex,sha512_hex) = context.read_bytes(b"same contents\n");
+    assert_eq!(map.get(&DigestKey::from_hex(&blake2b_hex).unwrap()),Some(&"blake2b"));
+    assert_eq!(map.get(&DigestKey::from_hex(sha512_hex.to_lowercase()).unwrap()),Some(&"sha512"));
+    assert_eq!(DigestKey::from_hex(&blake2b_hex).unwrap().to_string(),blake2b_hex);
+}
+
+#[test]
+fn from_output(){
+    let output = ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes_output(b"abc").unwrap();
+    let key = DigestKey::try_from(&output).unwrap();
+    assert_eq!(key,DigestKey::try_from(&output.blake2b).unwrap());
+    assert_eq!(key.as_bytes().len(),32);
+    assert_eq!(key.to_hex_upper(),"BDDD813C634239723171EF3FEE98579B94964E3BB1CB3E427262C8C068D52319");
+}
+
+#[test]
+fn ordered_as_bytes(){
+    let keys: BTreeSet<DigestKey> = [&[0x02][..], &[0x01, 0xFF], &[0x01], &[0x01, 0x00]].iter().map(|bytes| DigestKey::from_bytes(bytes).unwrap()).collect();
+    let bytes: Vec<&[u8]> = keys.iter().map(|key| key.as_bytes()).collect();
+    assert_eq!(bytes,vec![&[0x01][..], &[0x01, 0x00], &[0x01, 0xFF], &[0x02]]);
+
+    // A shorter key is not equal to a longer one padded with zeros
+    assert_ne!(DigestKey::from_bytes(&[0x01]),DigestKey::from_bytes(&[0x01, 0x00]));
+}
+
+#[test]
+fn invalid_lengths(){
+    assert_eq!(DigestKey::from_bytes(&[]),None);
+    assert_eq!(DigestKey::from_bytes(&[0xAB; MAX_KEY_BYTES + 1]),None);
+    assert!(DigestKey::from_bytes(&[0xAB; MAX_KEY_BYTES]).is_some());
+
+    assert_eq!(DigestKey::from_hex(""),Err(HexError::KeyLength { actual: 0 }));
+    assert_eq!(DigestKey::from_hex("00".repeat(65)),Err(HexError::KeyLength { actual: 65 }));
+    assert_eq!(DigestKey::from_hex("abc"),Err(HexError::OddLength));
+}
+
+#[test]
+fn compact(){
+    assert_eq!(std::mem::size_of::<DigestKey>(),MAX_KEY_BYTES + 1);
+}
+
+#[cfg(feature = "xof")]
+#[test]
+fn longer_digests_are_rejected(){
+    use paranoid_hash::LibAlgorithm;
+
+    let output = ParanoidHash::new_with_library(LibAlgorithm::SHAKE256,128,OsAlgorithm::SHA512).read_bytes_output(b"abc").unwrap();
+    assert_eq!(DigestKey::try_from(&output),Err(HexError::KeyLength { actual: 128 }));
+    assert_eq!(DigestKey::try_from(output.blake2b.clone()),Err(HexError::KeyLength { actual: 128 }));
+    // The SHA-512 digest still fits
+    assert_eq!(DigestKey::try_from(&output.os).unwrap().as_bytes(),output.os.as_bytes());
+
+    let output = ParanoidHash::new_with_library(LibAlgorithm::SHAKE256,64,OsAlgorithm::SHA512).read_bytes_output(b"abc").unwrap();
+    assert_eq!(DigestKey::try_from(&output).unwrap().as_bytes().len(),64);
+}
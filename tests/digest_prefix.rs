@@ -0,0 +1,41 @@
+use paranoid_hash::{HexError,OsAlgorithm,ParanoidHash};
+
+// SHA256("abc")
+const SHA256: &str = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+
+#[test]
+fn prefix_u64(){
+    assert_eq!(ParanoidHash::digest_prefix_u64(SHA256).unwrap(),13436514500253700074);
+    assert_eq!(ParanoidHash::digest_prefix_u64(SHA256.to_lowercase()).unwrap(),0xBA7816BF8F01CFEA);
+    assert_eq!(ParanoidHash::digest_prefix_u64("0x0000000000000001FF").unwrap(),1);
+}
+
+#[test]
+fn prefix_u128(){
+    assert_eq!(ParanoidHash::digest_prefix_u128(SHA256).unwrap(),0xBA7816BF8F01CFEA414140DE5DAE2223);
+    assert_eq!(ParanoidHash::digest_prefix_u128(&SHA256[..32]).unwrap(),0xBA7816BF8F01CFEA414140DE5DAE2223);
+}
+
+#[test]
+fn too_short(){
+    assert_eq!(ParanoidHash::digest_prefix_u64("BA7816BF8F01CF"),Err(HexError::TooShort { required: 8, actual: 7 }));
+    assert_eq!(ParanoidHash::digest_prefix_u128(&SHA256[..30]),Err(HexError::TooShort { required: 16, actual: 15 }));
+    assert_eq!(ParanoidHash::digest_prefix_u64(""),Err(HexError::TooShort { required: 8, actual: 0 }));
+}
+
+#[test]
+fn invalid_hex(){
+    assert_eq!(ParanoidHash::digest_prefix_u64("BA7816BF8F01CFEAG"),Err(HexError::InvalidCharacter { character: 'G', index: 16 }));
+    assert_eq!(ParanoidHash::digest_prefix_u64("BA7816BF8F01CFEA4"),Err(HexError::OddLength));
+}
+
+#[test]
+fn hash_digest(){
+    let (_,sha256) = ParanoidHash::new(64,OsAlgorithm::SHA256).read_bytes_digests(b"abc").unwrap();
+    assert_eq!(sha256.prefix_u64(),Some(0xBA7816BF8F01CFEA));
+    assert_eq!(sha256.prefix_u128(),Some(0xBA7816BF8F01CFEA414140DE5DAE2223));
+
+    let (blake2b,_) = ParanoidHash::new(4,OsAlgorithm::SHA256).read_bytes_digests(b"abc").unwrap();
+    assert_eq!(blake2b.prefix_u64(),None);
+    assert_eq!(blake2b.prefix_u128(),None);
+}
@@ -0,0 +1,67 @@
+use std::io::Write;
+
+use paranoid_hash::{FileError, OsAlgorithm, OutputEncoding, ParanoidHash};
+
+const DATA: &[u8] = b"one call with a shorter digest";
+
+fn file() -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(DATA).unwrap();
+    return file
+}
+
+#[test]
+fn override_matches_context_of_that_size(){
+    let file = file();
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    for size in [1,20,32,48,63,64] {
+        let expected = ParanoidHash::new(size,OsAlgorithm::SHA256);
+        assert_eq!(context.read_with_digest_size(file.path(),size).unwrap(),expected.read(file.path()).unwrap());
+        assert_eq!(context.read_bytes_with_digest_size(DATA,size).unwrap(),expected.read_bytes(DATA));
+    }
+}
+
+#[test]
+fn configuration_is_unchanged(){
+    let file = file();
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA512);
+    let before = context.clone();
+    let short = context.read_with_digest_size(file.path(),16).unwrap();
+
+    assert_eq!(context,before);
+    assert_eq!(context.return_digest_size(),64);
+    assert_eq!(short.0.len(),32);
+    assert_eq!(context.read(file.path()).unwrap().0.len(),128);
+    // The Operating System Hash Function stays as configured
+    assert_eq!(short.1,context.read(file.path()).unwrap().1);
+}
+
+#[test]
+fn other_settings_apply(){
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256).output_encoding(OutputEncoding::HexLower);
+    assert_eq!(context.read_bytes_with_digest_size(DATA,32).unwrap(),ParanoidHash::new(32,OsAlgorithm::SHA256).output_encoding(OutputEncoding::HexLower).read_bytes(DATA));
+}
+
+#[test]
+fn invalid_sizes(){
+    let file = file();
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_with_digest_size(file.path(),0),Err(FileError::InvalidDigestSize(0)));
+    assert_eq!(context.read_with_digest_size(file.path(),65),Err(FileError::InvalidDigestSize(65)));
+    assert_eq!(context.read_bytes_with_digest_size(DATA,0),Err(FileError::InvalidDigestSize(0)));
+    assert_eq!(context.read_bytes_with_digest_size(DATA,65),Err(FileError::InvalidDigestSize(65)));
+}
+
+#[test]
+fn missing_file_with_valid_size(){
+    assert_eq!(ParanoidHash::default().read_with_digest_size("does/not/exist",32),Err(FileError::FileNotFound));
+}
+
+#[cfg(feature = "blake2-parallel")]
+#[test]
+fn blake2sp_limit(){
+    use paranoid_hash::LibAlgorithm;
+    let context = ParanoidHash::new_with_library(LibAlgorithm::BLAKE2SP,32,OsAlgorithm::SHA256);
+    assert_eq!(context.read_bytes_with_digest_size(DATA,33),Err(FileError::InvalidDigestSize(33)));
+    assert_eq!(context.read_bytes_with_digest_size(DATA,16).unwrap(),ParanoidHash::new_with_library(LibAlgorithm::BLAKE2SP,16,OsAlgorithm::SHA256).read_bytes(DATA));
+}
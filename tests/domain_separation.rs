@@ -0,0 +1,44 @@
+use paranoid_hash::{OsAlgorithm, ParanoidHash};
+
+fn context() -> ParanoidHash {
+    return ParanoidHash::new(32,OsAlgorithm::SHA256)
+}
+
+#[test]
+fn contexts_separate(){
+    let data = b"identical payload";
+    let signing = context().read_bytes_with_context("signing",data);
+    let encryption = context().read_bytes_with_context("encryption",data);
+    assert_ne!(signing.0,encryption.0);
+    assert_ne!(signing.1,encryption.1);
+    assert_ne!(signing,context().read_bytes(data));
+}
+
+#[test]
+fn framing_is_unambiguous(){
+    // The same concatenation split differently between context and data
+    assert_ne!(context().read_bytes_with_context("ab",b"c"),context().read_bytes_with_context("a",b"bc"));
+    assert_ne!(context().read_bytes_with_context("",b"abc"),context().read_bytes_with_context("abc",b""));
+}
+
+#[test]
+fn matches_framed_bytes(){
+    let context_str = "long context string that does not fit in sixteen bytes";
+    let mut framed = (context_str.len() as u64).to_be_bytes().to_vec();
+    framed.extend_from_slice(context_str.as_bytes());
+    framed.extend_from_slice(b"data");
+    assert_eq!(context().read_bytes_with_context(context_str,b"data"),context().read_bytes(&framed));
+}
+
+// Fixed vectors pin the framing across releases
+#[test]
+fn fixed_vectors(){
+    assert_eq!(context().read_bytes_with_context("signing",b"payload"),(
+        String::from("AC715ECD243D74FFC497D793563DC5A74A152557A3503DC8E20875A46986383F"),
+        String::from("1F45E7865BD3DE85C2DC24537DC4D090A1611D965525A8D6A0220BB556F788BB"),
+    ));
+    assert_eq!(context().read_bytes_with_context("",b""),(
+        String::from("81E47A19E6B29B0A65B9591762CE5143ED30D0261E5D24A3201752506B20F15C"),
+        String::from("AF5570F5A1810B7AF78CAF4BC70A660F0DF51E42BAF91D4DE5B2328DE0E83DFC"),
+    ));
+}
@@ -0,0 +1,37 @@
+use std::fs;
+
+use paranoid_hash::{FileError,OsAlgorithm,ParanoidHash};
+
+#[test]
+fn passes_agree(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA512);
+    let path = "examples/example_file.txt";
+    assert_eq!(context.read_double_checked(path).unwrap(),context.read(path).unwrap());
+}
+
+#[test]
+fn empty_file(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("empty");
+    fs::write(&path,b"").unwrap();
+
+    assert_eq!(ParanoidHash::default().read_double_checked(&path).unwrap(),ParanoidHash::default().read_bytes(b""));
+    assert_eq!(ParanoidHash::default().reject_empty_input(true).read_double_checked(&path),Err(FileError::EmptyFile));
+}
+
+#[test]
+fn inconsistent_reads(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    fs::write(&path,"paranoid").unwrap();
+
+    let result = ParanoidHash::default().read_double_checked_with_hook(&path,|file| fs::write(file,"paranoiD").unwrap());
+    assert_eq!(result,Err(FileError::ReadInconsistency));
+}
+
+#[test]
+fn errors(){
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(ParanoidHash::default().read_double_checked(dir.path().join("missing")),Err(FileError::FileNotFound));
+    assert_eq!(ParanoidHash::default().read_double_checked(dir.path()),Err(FileError::Io(std::io::ErrorKind::InvalidInput)));
+}
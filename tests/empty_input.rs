@@ -0,0 +1,47 @@
+use paranoid_hash::{FileError, ParanoidHash};
+use std::io::Cursor;
+
+const EMPTY_BLAKE2B_64: &str = "786A02F742015903C6C6FD852552D272912F4740E15847618A86E217F71F5419D25E1031AFEE585313896444934EB04B903A685B1448B755D56F701AFE9BE2CE";
+const EMPTY_SHA512: &str = "CF83E1357EEFB8BDF1542850D66D8007D620E4050B5715DC83F4A921D36CE9CE47D0D13C5D85F2B0FF8318D2877EEC2F63B931BD47417A81A538327AF927DA3E";
+
+#[test]
+fn empty_input_digests(){
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let context = ParanoidHash::default();
+    let expected = (EMPTY_BLAKE2B_64.to_string(),EMPTY_SHA512.to_string());
+
+    assert_eq!(context.read(file.path()).unwrap(),expected);
+    assert_eq!(context.read_using_std(file.path()).unwrap(),expected);
+    assert_eq!(context.read_parallel(file.path()).unwrap(),expected);
+    assert_eq!(context.read_from_reader(Cursor::new(Vec::new())).unwrap(),expected);
+    assert_eq!(context.read_bytes(b""),expected);
+    assert_eq!(context.try_read_bytes(b"").unwrap(),expected);
+    assert_eq!(context.read_str(""),expected);
+
+    // Keyed BLAKE2B of the empty message differs, SHA512 does not
+    let (keyed,sha512) = context.read_with_key(file.path(),b"key").unwrap();
+    assert_ne!(keyed,EMPTY_BLAKE2B_64);
+    assert_eq!(sha512,EMPTY_SHA512);
+}
+
+#[test]
+fn strict_mode_rejects_empty_input(){
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let context = ParanoidHash::default().reject_empty_input(true);
+
+    assert_eq!(context.read(file.path()),Err(FileError::EmptyFile));
+    assert_eq!(context.read_with_key(file.path(),b"key"),Err(FileError::EmptyFile));
+    assert_eq!(context.read_using_std(file.path()),Err(FileError::EmptyFile));
+    assert_eq!(context.read_parallel(file.path()),Err(FileError::EmptyFile));
+    assert_eq!(context.read_from_reader(Cursor::new(Vec::new())),Err(FileError::EmptyFile));
+    assert_eq!(context.try_read_bytes(b""),Err(FileError::EmptyFile));
+}
+
+#[test]
+fn strict_mode_accepts_non_empty_input(){
+    let context = ParanoidHash::default().reject_empty_input(true);
+
+    assert_eq!(context.read("tests/fixtures/report/alpha.txt").unwrap(),context.read_bytes(b"alpha\n"));
+    assert_eq!(context.read_from_reader(Cursor::new(b"alpha\n")).unwrap(),context.read_bytes(b"alpha\n"));
+    assert_eq!(context.try_read_bytes(b"a").unwrap(),context.read_bytes(b"a"));
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+
+use paranoid_hash::ffi::*;
+
+// blake2b-256 and sha256 of examples/example_file.txt from Python's hashlib
+const EXAMPLE_BLAKE2B: &str = "411E9D7F0C658367A5102A0345583CC938AD79CB586B4188CE82343006E3C6DC";
+const EXAMPLE_SHA256: &str = "D33C098BE35262CBD8DFAF6CD89F642E4460AFCDDDD58DD8F598594526AE8452";
+
+fn digest(handle: *const ParanoidHashHandle, os: bool) -> Result<String,ParanoidHashStatus> {
+    let mut buffer = [0 as c_char; 129];
+    let status = unsafe {
+        match os {
+            false => paranoid_hash_blake2b_hex(handle, buffer.as_mut_ptr(), buffer.len()),
+            true => paranoid_hash_os_hex(handle, buffer.as_mut_ptr(), buffer.len()),
+        }
+    };
+    match status {
+        ParanoidHashStatus::Ok => return Ok(unsafe { std::ffi::CStr::from_ptr(buffer.as_ptr()) }.to_str().unwrap().to_string()),
+        status => return Err(status),
+    }
+}
+
+#[test]
+fn ffi_hash_file(){
+    let handle = paranoid_hash_new(32,PARANOID_HASH_SHA256);
+    assert!(!handle.is_null());
+    let path = CString::new("examples/example_file.txt").unwrap();
+    unsafe {
+        assert_eq!(paranoid_hash_file(handle,path.as_ptr()),ParanoidHashStatus::Ok);
+    }
+    assert_eq!(digest(handle,false).unwrap(),EXAMPLE_BLAKE2B);
+    assert_eq!(digest(handle,true).unwrap(),EXAMPLE_SHA256);
+    unsafe { paranoid_hash_free(handle) };
+}
+
+#[test]
+fn ffi_hash_bytes(){
+    let handle = paranoid_hash_new(32,PARANOID_HASH_SHA256);
+    unsafe {
+        assert_eq!(paranoid_hash_bytes(handle,b"abc".as_ptr(),3),ParanoidHashStatus::Ok);
+    }
+    assert_eq!(digest(handle,false).unwrap(),"BDDD813C634239723171EF3FEE98579B94964E3BB1CB3E427262C8C068D52319");
+    assert_eq!(digest(handle,true).unwrap(),"BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+    unsafe { paranoid_hash_free(handle) };
+}
+
+#[test]
+fn ffi_invalid_arguments(){
+    assert!(paranoid_hash_new(0,PARANOID_HASH_SHA256).is_null());
+    assert!(paranoid_hash_new(65,PARANOID_HASH_SHA256).is_null());
+    assert!(paranoid_hash_new(32,0).is_null());
+
+    let handle = paranoid_hash_new(64,PARANOID_HASH_SHA512);
+    let mut small = [0 as c_char; 128];
+    unsafe {
+        assert_eq!(paranoid_hash_file(ptr::null_mut(),ptr::null()),ParanoidHashStatus::NullPointer);
+        assert_eq!(paranoid_hash_file(handle,ptr::null()),ParanoidHashStatus::NullPointer);
+        assert_eq!(paranoid_hash_bytes(handle,ptr::null(),1),ParanoidHashStatus::NullPointer);
+        assert_eq!(paranoid_hash_os_hex(handle,ptr::null_mut(),0),ParanoidHashStatus::NullPointer);
+        assert_eq!(digest(handle,false),Err(ParanoidHashStatus::NoDigest));
+
+        // SHA512 needs 128 characters and the terminating NUL
+        assert_eq!(paranoid_hash_bytes(handle,ptr::null(),0),ParanoidHashStatus::Ok);
+        assert_eq!(paranoid_hash_os_hex(handle,small.as_mut_ptr(),small.len()),ParanoidHashStatus::BufferTooSmall);
+        assert_eq!(digest(handle,true).unwrap().len(),128);
+
+        // A failed call clears the previous digests
+        let missing = CString::new("tests/fixtures/does_not_exist").unwrap();
+        assert_eq!(paranoid_hash_file(handle,missing.as_ptr()),ParanoidHashStatus::FileNotFound);
+        assert_eq!(digest(handle,true),Err(ParanoidHashStatus::NoDigest));
+
+        paranoid_hash_free(handle);
+        paranoid_hash_free(ptr::null_mut());
+    }
+}
+
+// Compiles tests/fixtures/ffi/hash_file.c against the shared library and runs it. Skipped when no C compiler is installed.
+#[cfg(target_os = "linux")]
+#[test]
+fn ffi_c_program(){
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    let compiler = std::env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    if Command::new(&compiler).arg("--version").output().is_err() {
+        eprintln!("skipping ffi_c_program: {} not found",compiler);
+        return
+    }
+
+    // The shared library built with the features of this test sits next to it in target/<profile>/deps. The copy in target/<profile> is only refreshed when the library is rebuilt, so it can be from a build without the ffi feature.
+    let target: PathBuf = std::env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    assert!(target.join("libparanoid_hash.so").exists(),"the cdylib was not built");
+
+    let dir = tempfile::tempdir().unwrap();
+    let program = dir.path().join("hash_file");
+    let compiled = Command::new(&compiler)
+        .arg("tests/fixtures/ffi/hash_file.c")
+        .arg("-Iinclude")
+        .arg("-L").arg(&target)
+        .arg("-lparanoid_hash")
+        .arg("-o").arg(&program)
+        .status().unwrap();
+    assert!(compiled.success());
+
+    let output = Command::new(&program)
+        .arg("examples/example_file.txt")
+        .env("LD_LIBRARY_PATH",&target)
+        .output().unwrap();
+    assert!(output.status.success(),"{}",String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(),format!("{}\n{}\n",EXAMPLE_BLAKE2B,EXAMPLE_SHA256));
+}
+
+#[test]
+fn ffi_weak_algorithm(){
+    assert!(paranoid_hash_new(32,PARANOID_HASH_SHA1).is_null());
+
+    let handle = paranoid_hash_new_allowing_weak(32,PARANOID_HASH_SHA1);
+    assert!(!handle.is_null());
+    unsafe {
+        assert_eq!(paranoid_hash_bytes(handle,b"abc".as_ptr(),3),ParanoidHashStatus::Ok);
+    }
+    assert_eq!(digest(handle,true).unwrap(),"A9993E364706816ABA3E25717850C26C9CD0D89D");
+    unsafe { paranoid_hash_free(handle) };
+    assert!(paranoid_hash_new_allowing_weak(0,PARANOID_HASH_SHA1).is_null());
+}
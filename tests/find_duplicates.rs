@@ -0,0 +1,50 @@
+use paranoid_hash::ParanoidHash;
+use std::fs;
+use std::path::Path;
+
+fn write(path: &Path, contents: &[u8]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path,contents).unwrap();
+}
+
+#[test]
+fn groups_planted_duplicates(){
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+
+    write(&root.join("a.jpg"),b"photo one");
+    write(&root.join("nested/a_copy.jpg"),b"photo one");
+    write(&root.join("nested/deeper/a_copy2.jpg"),b"photo one");
+    write(&root.join("b.jpg"),b"photo two");
+    write(&root.join("b_copy.jpg"),b"photo two");
+    // Same size as "photo one" but different contents
+    write(&root.join("c.jpg"),b"photo 111");
+    write(&root.join("unique.jpg"),b"something else entirely");
+
+    let groups = ParanoidHash::default().find_duplicates(root).unwrap();
+    assert_eq!(groups,vec![
+        vec![root.join("a.jpg"),root.join("nested/a_copy.jpg"),root.join("nested/deeper/a_copy2.jpg")],
+        vec![root.join("b.jpg"),root.join("b_copy.jpg")],
+    ]);
+}
+
+#[test]
+fn no_duplicates(){
+    let dir = tempfile::tempdir().unwrap();
+    write(&dir.path().join("a"),b"1");
+    write(&dir.path().join("b"),b"2");
+
+    let duplicates = ParanoidHash::default().find_duplicates_with_errors(dir.path()).unwrap();
+    assert!(duplicates.groups.is_empty());
+    assert!(duplicates.skipped.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn hard_links_are_not_duplicates(){
+    let dir = tempfile::tempdir().unwrap();
+    write(&dir.path().join("original"),b"linked contents");
+    fs::hard_link(dir.path().join("original"),dir.path().join("link")).unwrap();
+
+    assert!(ParanoidHash::default().find_duplicates(dir.path()).unwrap().is_empty());
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
+
+use paranoid_hash::{FileError,ParanoidHash};
+
+fn set_modified(path: &std::path::Path, seconds: u64) {
+    let file = fs::OpenOptions::new().write(true).open(path).unwrap();
+    file.set_modified(UNIX_EPOCH + Duration::from_secs(seconds)).unwrap();
+}
+
+#[test]
+fn changes_with_mtime_but_not_content(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.txt");
+    fs::write(&path,b"unchanged contents").unwrap();
+    set_modified(&path,1_600_000_000);
+
+    let context = ParanoidHash::default();
+    let before = context.fingerprint_metadata(&path).unwrap();
+    let contents = context.read(&path).unwrap();
+    assert_eq!(context.fingerprint_metadata(&path).unwrap(),before);
+
+    set_modified(&path,1_600_000_001);
+    assert_ne!(context.fingerprint_metadata(&path).unwrap(),before);
+    assert_eq!(context.read(&path).unwrap(),contents);
+
+    // Back to the same metadata
+    set_modified(&path,1_600_000_000);
+    assert_eq!(context.fingerprint_metadata(&path).unwrap(),before);
+}
+
+#[test]
+fn changes_with_size(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.txt");
+    fs::write(&path,b"short").unwrap();
+    set_modified(&path,1_600_000_000);
+    let before = ParanoidHash::default().fingerprint_metadata(&path).unwrap();
+
+    fs::write(&path,b"longer").unwrap();
+    set_modified(&path,1_600_000_000);
+    assert_ne!(ParanoidHash::default().fingerprint_metadata(&path).unwrap(),before);
+}
+
+#[cfg(unix)]
+#[test]
+fn changes_with_permissions(){
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.txt");
+    fs::write(&path,b"data").unwrap();
+    fs::set_permissions(&path,fs::Permissions::from_mode(0o644)).unwrap();
+    let before = ParanoidHash::default().fingerprint_metadata(&path).unwrap();
+
+    fs::set_permissions(&path,fs::Permissions::from_mode(0o600)).unwrap();
+    assert_ne!(ParanoidHash::default().fingerprint_metadata(&path).unwrap(),before);
+}
+
+#[cfg(unix)]
+#[test]
+fn documented_encoding(){
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.txt");
+    fs::write(&path,b"12345").unwrap();
+    fs::set_permissions(&path,fs::Permissions::from_mode(0o644)).unwrap();
+    set_modified(&path,1_600_000_000);
+
+    // sha256 of the encoding from Python's hashlib and struct
+    let context = ParanoidHash::new(64,paranoid_hash::OsAlgorithm::SHA256);
+    assert_eq!(context.fingerprint_metadata(&path).unwrap().1,"736E2EFA1800BF8EC42D76B5C3384B68C838B11DAD668E9D1A0CD1E6E1D59144");
+}
+
+#[test]
+fn same_metadata_same_fingerprint(){
+    // The contents and name are not part of the fingerprint
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.txt");
+    let b = dir.path().join("b.txt");
+    fs::write(&a,b"aaaa").unwrap();
+    fs::write(&b,b"bbbb").unwrap();
+    fs::set_permissions(&b,fs::metadata(&a).unwrap().permissions()).unwrap();
+    set_modified(&a,1_234_567_890);
+    set_modified(&b,1_234_567_890);
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.fingerprint_metadata(&a).unwrap(),context.fingerprint_metadata(&b).unwrap());
+    assert_ne!(context.read(&a).unwrap(),context.read(&b).unwrap());
+}
+
+#[test]
+fn directory(){
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("nested")).unwrap();
+    fs::write(dir.path().join("b.txt"),b"b").unwrap();
+    fs::write(dir.path().join("nested/a.txt"),b"a").unwrap();
+
+    let context = ParanoidHash::default();
+    let fingerprints = context.fingerprint_metadata_dir(dir.path()).unwrap();
+    assert_eq!(fingerprints.iter().map(|(path,_)| path.clone()).collect::<Vec<_>>(),vec![PathBuf::from("b.txt"),PathBuf::from("nested/a.txt")]);
+    assert_eq!(fingerprints[1].1,context.fingerprint_metadata(dir.path().join("nested/a.txt")).unwrap());
+
+    set_modified(&dir.path().join("b.txt"),1_000_000_000);
+    let changed = context.fingerprint_metadata_dir(dir.path()).unwrap();
+    assert_ne!(changed[0].1,fingerprints[0].1);
+    assert_eq!(changed[1].1,fingerprints[1].1);
+}
+
+#[test]
+fn missing(){
+    assert_eq!(ParanoidHash::default().fingerprint_metadata("missing.txt"),Err(FileError::FileNotFound));
+    assert_eq!(ParanoidHash::default().fingerprint_metadata_dir("missing"),Err(FileError::FileNotFound));
+}
@@ -0,0 +1,37 @@
+use std::convert::TryFrom;
+
+use paranoid_hash::{DigestSizeMismatch, OsAlgorithm, OsDigestArray, ParanoidHash};
+
+#[test]
+fn matching_sizes(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    let (blake2b,sha256): ([u8; 32],OsDigestArray) = context.read_bytes_fixed(b"abc").unwrap();
+
+    // blake2b-256 and sha256 of "abc" from Python's hashlib
+    assert_eq!(hex::encode_upper(blake2b),"BDDD813C634239723171EF3FEE98579B94964E3BB1CB3E427262C8C068D52319");
+    assert_eq!(hex::encode_upper(sha256),"BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+    assert_eq!(sha256.algorithm(),OsAlgorithm::SHA256);
+
+    let (_,os) = ParanoidHash::new(64,OsAlgorithm::SHA512).read_bytes_fixed::<64>(b"abc").unwrap();
+    assert!(matches!(os,OsDigestArray::SHA512(_)));
+}
+
+#[test]
+fn mismatching_sizes(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    assert_eq!(context.read_bytes_fixed::<64>(b"abc"),Err(DigestSizeMismatch { expected: 32, requested: 64 }));
+    assert_eq!(context.read_bytes_fixed::<0>(b"abc"),Err(DigestSizeMismatch { expected: 32, requested: 0 }));
+}
+
+#[test]
+fn digest_to_array(){
+    let (blake2b,sha1) = ParanoidHash::new_allowing_weak(48,OsAlgorithm::SHA1).read_bytes_digests(b"abc").unwrap();
+
+    let array: [u8; 48] = blake2b.to_array().unwrap();
+    assert_eq!(&array[..],blake2b.as_bytes());
+    let array = <[u8; 20]>::try_from(&sha1).unwrap();
+    assert_eq!(&array[..],sha1.as_bytes());
+
+    assert_eq!(blake2b.to_array::<32>(),Err(DigestSizeMismatch { expected: 48, requested: 32 }));
+    assert_eq!(<[u8; 32]>::try_from(&sha1),Err(DigestSizeMismatch { expected: 20, requested: 32 }));
+}
@@ -0,0 +1,56 @@
+/* Hashes the file given as the first argument with BLAKE2B-256 and SHA256 through the C interface and prints both digests, one per line. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "paranoid_hash.h"
+
+int main(int argc, char **argv) {
+    char blake2b[129];
+    char sha256[129];
+    char small[8];
+    ParanoidHashStatus status;
+    ParanoidHashHandle *handle;
+
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <file>\n", argv[0]);
+        return 2;
+    }
+    if (paranoid_hash_new(0, PARANOID_HASH_SHA256) != NULL || paranoid_hash_new(32, 99) != NULL) {
+        fprintf(stderr, "invalid arguments were accepted\n");
+        return 1;
+    }
+
+    handle = paranoid_hash_new(32, PARANOID_HASH_SHA256);
+    if (handle == NULL) {
+        fprintf(stderr, "paranoid_hash_new failed\n");
+        return 1;
+    }
+    if (paranoid_hash_blake2b_hex(handle, blake2b, sizeof blake2b) != PARANOID_HASH_STATUS_NO_DIGEST) {
+        fprintf(stderr, "a digest was returned before hashing\n");
+        return 1;
+    }
+    if (paranoid_hash_file(handle, "this file does not exist") != PARANOID_HASH_STATUS_FILE_NOT_FOUND) {
+        fprintf(stderr, "a missing file was not reported\n");
+        return 1;
+    }
+
+    status = paranoid_hash_file(handle, argv[1]);
+    if (status != PARANOID_HASH_STATUS_OK) {
+        fprintf(stderr, "paranoid_hash_file failed with %d\n", (int)status);
+        return 1;
+    }
+    if (paranoid_hash_os_hex(handle, small, sizeof small) != PARANOID_HASH_STATUS_BUFFER_TOO_SMALL) {
+        fprintf(stderr, "a small buffer was accepted\n");
+        return 1;
+    }
+    if (paranoid_hash_blake2b_hex(handle, blake2b, sizeof blake2b) != PARANOID_HASH_STATUS_OK || paranoid_hash_os_hex(handle, sha256, sizeof sha256) != PARANOID_HASH_STATUS_OK) {
+        fprintf(stderr, "copying the digests failed\n");
+        return 1;
+    }
+
+    printf("%s\n%s\n", blake2b, sha256);
+    paranoid_hash_free(handle);
+    paranoid_hash_free(NULL);
+    return 0;
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "gzip")]
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use paranoid_hash::{FileError, ParanoidHash};
+use std::fs;
+use std::io::Write;
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    return encoder.finish().unwrap()
+}
+
+fn data() -> Vec<u8> {
+    return (0..200_000u32).map(|i| (i % 7 + i % 251) as u8).collect()
+}
+
+#[test]
+fn digests_are_of_the_uncompressed_data(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.gz");
+    fs::write(&path,gzip(&data())).unwrap();
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_gzip_decompressed(&path).unwrap(),context.read_bytes(&data()));
+}
+
+#[test]
+fn members_are_concatenated(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("multi.gz");
+    let mut joined = gzip(b"first member, ");
+    joined.extend_from_slice(&gzip(b"second member"));
+    fs::write(&path,joined).unwrap();
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_gzip_decompressed(&path).unwrap(),context.read_str("first member, second member"));
+}
+
+#[test]
+fn corrupt_streams_are_rejected(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("corrupt.gz");
+    let context = ParanoidHash::default();
+    let compressed = gzip(&data());
+
+    // Flipped bits in the compressed data
+    let mut flipped = compressed.clone();
+    let middle = flipped.len() / 2;
+    flipped[middle] ^= 0xff;
+    fs::write(&path,flipped).unwrap();
+    assert_eq!(context.read_gzip_decompressed(&path),Err(FileError::InvalidGzip));
+
+    // Truncated
+    fs::write(&path,&compressed[..compressed.len() - 10]).unwrap();
+    assert_eq!(context.read_gzip_decompressed(&path),Err(FileError::InvalidGzip));
+
+    // Not gzip at all
+    fs::write(&path,"plain text").unwrap();
+    assert_eq!(context.read_gzip_decompressed(&path),Err(FileError::InvalidGzip));
+
+    assert_eq!(context.read_gzip_decompressed(dir.path().join("missing.gz")),Err(FileError::FileNotFound));
+}
+
+#[test]
+fn limit_applies_to_decompressed_size(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bomb.gz");
+    fs::write(&path,gzip(&vec![0u8; 10_000_000])).unwrap();
+    assert!(fs::metadata(&path).unwrap().len() < 100_000);
+
+    let context = ParanoidHash::default().max_file_size(Some(1_000_000));
+    assert!(matches!(context.read_gzip_decompressed(&path),Err(FileError::FileTooLarge { limit: 1_000_000, .. })));
+}
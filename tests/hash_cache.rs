@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use paranoid_hash::{HashCache, ManifestStatus, OsAlgorithm, ParanoidHash};
+
+// Three files with fixed modification times, so rewriting one can move its time forward
+fn fixture() -> (tempfile::TempDir, Vec<PathBuf>) {
+    let dir = tempfile::tempdir().unwrap();
+    let paths: Vec<PathBuf> = ["alpha.txt","beta.txt","gamma.txt"].iter().map(|name| dir.path().join(name)).collect();
+    for path in paths.iter() {
+        fs::write(path,path.file_name().unwrap().to_str().unwrap()).unwrap();
+        touch(path,1_600_000_000);
+    }
+    return (dir, paths)
+}
+
+fn touch(path: &Path, seconds: u64) {
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)).unwrap();
+}
+
+#[test]
+fn second_run_hashes_nothing(){
+    let (_dir,paths) = fixture();
+    let context = ParanoidHash::default();
+    let mut cache = HashCache::new();
+
+    let first = context.read_files_cached(&paths,&mut cache);
+    assert_eq!(cache.return_stats().misses,3);
+    assert_eq!(cache.return_stats().bytes_hashed,9 + 8 + 9);
+    assert_eq!(cache.len(),3);
+
+    cache.reset_stats();
+    let second = context.read_files_cached(&paths,&mut cache);
+    assert_eq!(second,first);
+    assert_eq!(cache.return_stats().hits,3);
+    assert_eq!(cache.return_stats().bytes_hashed,0);
+    assert_eq!(second,context.read_files(&paths));
+}
+
+#[test]
+fn touched_file_is_rehashed(){
+    let (_dir,paths) = fixture();
+    let context = ParanoidHash::default();
+    let mut cache = HashCache::new();
+    context.read_files_cached(&paths,&mut cache);
+
+    // Same size, new contents and a later modification time
+    fs::write(&paths[1],"BETA.TXT").unwrap();
+    touch(&paths[1],1_600_000_001);
+
+    cache.reset_stats();
+    let results = context.read_files_cached(&paths,&mut cache);
+    assert_eq!(cache.return_stats().hits,2);
+    assert_eq!(cache.return_stats().misses,1);
+    assert_eq!(cache.return_stats().bytes_hashed,8);
+    assert_eq!(results[1].1,Ok(context.read_str("BETA.TXT")));
+}
+
+#[test]
+fn force_and_remove(){
+    let (_dir,paths) = fixture();
+    let context = ParanoidHash::default();
+    let mut cache = HashCache::new();
+    context.read_files_cached(&paths,&mut cache);
+
+    assert!(cache.remove(&paths[0]));
+    assert!(!cache.remove("tests/fixtures/does_not_exist"));
+    cache.reset_stats();
+    context.read_files_cached(&paths,&mut cache);
+    assert_eq!(cache.return_stats().misses,1);
+
+    let mut cache = cache.force(true);
+    cache.reset_stats();
+    context.read_files_cached(&paths,&mut cache);
+    assert_eq!(cache.return_stats().misses,3);
+    assert_eq!(cache.return_stats().hits,0);
+}
+
+#[test]
+fn other_configuration_invalidates(){
+    let (_dir,paths) = fixture();
+    let mut cache = HashCache::new();
+    ParanoidHash::new(64,OsAlgorithm::SHA512).read_files_cached(&paths,&mut cache);
+
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    cache.reset_stats();
+    let results = context.read_files_cached(&paths,&mut cache);
+    assert_eq!(cache.return_stats().hits,0);
+    assert_eq!(cache.len(),3);
+    assert_eq!(results,context.read_files(&paths));
+}
+
+#[test]
+fn errors_are_not_cached(){
+    let context = ParanoidHash::default();
+    let mut cache = HashCache::new();
+    let results = context.read_files_cached(&["tests/fixtures/does_not_exist"],&mut cache);
+    assert!(results[0].1.is_err());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn manifest_verify_cached(){
+    let (dir,paths) = fixture();
+    let context = ParanoidHash::default();
+    let mut cache = HashCache::new();
+    let manifest = context.manifest_dir_cached(dir.path(),&mut cache).unwrap();
+    assert_eq!(manifest,context.manifest_dir(dir.path()).unwrap());
+
+    cache.reset_stats();
+    assert!(manifest.verify_cached(dir.path(),&mut cache).iter().all(|(_,status)| *status == ManifestStatus::Match));
+    assert_eq!(cache.return_stats().bytes_hashed,0);
+
+    fs::write(&paths[2],"GAMMA.TXT").unwrap();
+    touch(&paths[2],1_600_000_002);
+    let statuses = manifest.verify_cached(dir.path(),&mut cache);
+    assert_eq!(statuses[2].1,ManifestStatus::Mismatch);
+    assert_eq!(statuses[0].1,ManifestStatus::Match);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn save_and_load(){
+    let (dir,paths) = fixture();
+    let context = ParanoidHash::default();
+    let mut cache = HashCache::new();
+    context.read_files_cached(&paths,&mut cache);
+
+    let saved = dir.path().join("cache.json");
+    cache.save(&saved).unwrap();
+    let mut loaded = HashCache::load(&saved).unwrap();
+    assert_eq!(loaded.len(),3);
+
+    context.read_files_cached(&paths,&mut loaded);
+    assert_eq!(loaded.return_stats().hits,3);
+    assert_eq!(loaded.return_stats().bytes_hashed,0);
+
+    fs::write(&saved,"{}").unwrap();
+    assert_eq!(HashCache::load(&saved),Err(paranoid_hash::FileError::Io(std::io::ErrorKind::InvalidData)));
+}
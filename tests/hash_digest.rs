@@ -0,0 +1,84 @@
+use paranoid_hash::{Algo, FileError, HashDigest, OsAlgorithm, ParanoidHash};
+use std::collections::HashMap;
+
+const ABC_SHA256: &str = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+
+#[test]
+fn matches_string_api(){
+    let context = ParanoidHash::new(48,OsAlgorithm::SHA256);
+    let (blake2b,sha256) = context.read("tests/fixtures/report/alpha.txt").unwrap();
+    let (blake2b_digest,sha256_digest) = context.read_digests("tests/fixtures/report/alpha.txt").unwrap();
+
+    assert_eq!(blake2b_digest.to_hex_upper(),blake2b);
+    assert_eq!(sha256_digest.to_string(),sha256);
+    assert_eq!(sha256_digest.to_hex_lower(),sha256.to_lowercase());
+    assert_eq!(blake2b_digest.algorithm(),&Algo::Blake2b(48));
+    assert_eq!(sha256_digest.as_bytes().len(),32);
+    assert_eq!(context.read_bytes_digests(b"alpha\n").unwrap(),(blake2b_digest,sha256_digest));
+}
+
+#[test]
+fn equality(){
+    let sha256 = Algo::Os(OsAlgorithm::SHA256);
+    let upper = HashDigest::from_hex(sha256.clone(),ABC_SHA256).unwrap();
+    let lower = HashDigest::from_hex(sha256.clone(),ABC_SHA256.to_lowercase()).unwrap();
+    assert_eq!(upper,lower);
+
+    let mut other = upper.as_bytes().to_vec();
+    other[31] ^= 1;
+    assert_ne!(upper,HashDigest::new(sha256,other).unwrap());
+
+    // Same bytes, different hash function
+    assert_ne!(upper,HashDigest::from_hex(Algo::Blake2b(32),ABC_SHA256).unwrap());
+}
+
+#[test]
+fn invalid(){
+    let sha256 = Algo::Os(OsAlgorithm::SHA256);
+    assert_eq!(HashDigest::from_hex(sha256.clone(),"zz").unwrap_err(),FileError::InvalidDigest);
+    assert_eq!(HashDigest::from_hex(sha256.clone(),&ABC_SHA256[..62]).unwrap_err(),FileError::InvalidDigest);
+    assert_eq!(HashDigest::new(sha256,vec![0;64]).unwrap_err(),FileError::InvalidDigest);
+}
+
+#[test]
+fn hash_map_and_debug(){
+    let digest = HashDigest::from_hex(Algo::Os(OsAlgorithm::SHA256),ABC_SHA256).unwrap();
+    let mut map = HashMap::new();
+    map.insert(digest.clone(),"abc");
+    assert_eq!(map.get(&digest),Some(&"abc"));
+
+    assert_eq!(format!("{:?}",digest),"HashDigest { algorithm: Os(SHA256), digest: \"BA7816BF8F01CFEA...\" }");
+}
+
+#[test]
+fn short_boundaries(){
+    let digest = HashDigest::from_hex(Algo::Os(OsAlgorithm::SHA256),ABC_SHA256).unwrap();
+
+    assert_eq!(digest.short(0),None);
+    assert_eq!(digest.short(1).unwrap(),"B");
+    assert_eq!(digest.short(16).unwrap(),"BA7816BF8F01CFEA");
+    assert_eq!(digest.short(64).unwrap(),ABC_SHA256);
+    assert_eq!(digest.short(65),None);
+
+    assert_eq!(digest.prefix_bytes(0),None);
+    assert_eq!(digest.prefix_bytes(2).unwrap(),&[0xBA,0x78]);
+    assert_eq!(digest.prefix_bytes(32).unwrap(),digest.as_bytes());
+    assert_eq!(digest.prefix_bytes(33),None);
+}
+
+#[test]
+fn prefix_matching(){
+    assert!(ParanoidHash::matches_prefix(ABC_SHA256,"ba7816bf"));
+    assert!(ParanoidHash::matches_prefix(ABC_SHA256,ABC_SHA256));
+    assert!(!ParanoidHash::matches_prefix(ABC_SHA256,"BA7816BE"));
+    assert!(!ParanoidHash::matches_prefix(ABC_SHA256,""));
+    assert!(!ParanoidHash::matches_prefix("BA78","BA7816"));
+    assert!(!ParanoidHash::matches_prefix(ABC_SHA256,"BA78zz"));
+    // The digest and prefix can be different string types
+    assert!(ParanoidHash::matches_prefix(ABC_SHA256.to_lowercase(),"BA7816BF"));
+    assert!(ParanoidHash::matches_prefix(ABC_SHA256,String::from("ba78")));
+
+    let digest = HashDigest::from_hex(Algo::Os(OsAlgorithm::SHA256),ABC_SHA256).unwrap();
+    assert!(digest.matches_prefix(digest.short(12).unwrap()));
+    assert!(!digest.matches_prefix("0"));
+}
@@ -0,0 +1,50 @@
+use paranoid_hash::{DigestSizeMismatch, OsAlgorithm, OutputSizeError, ParanoidHash};
+
+#[test]
+fn matches_string_api(){
+    for (size,os_hash) in [(64,OsAlgorithm::SHA512),(32,OsAlgorithm::SHA256),(28,OsAlgorithm::SHA224),(1,OsAlgorithm::SHA512_256)] {
+        let context = ParanoidHash::new(size,os_hash.clone());
+        let (blake2b, os) = context.read_bytes(b"a record");
+        let os_len = os.len() / 2;
+
+        let (mut blake2b_out, mut os_out) = (vec![0u8; size],vec![0u8; os_len]);
+        context.hash_bytes_into(b"a record",&mut blake2b_out,&mut os_out).unwrap();
+        assert_eq!(hex::encode_upper(&blake2b_out),blake2b);
+        assert_eq!(hex::encode_upper(&os_out),os);
+
+        let (mut blake2b_hex, mut os_hex) = (vec![0u8; size * 2],vec![0u8; os_len * 2]);
+        context.hash_bytes_into_hex(b"a record",&mut blake2b_hex,&mut os_hex).unwrap();
+        assert_eq!(String::from_utf8(blake2b_hex).unwrap(),blake2b);
+        assert_eq!(String::from_utf8(os_hex).unwrap(),os);
+    }
+}
+
+#[test]
+fn reused_buffers(){
+    let context = ParanoidHash::default();
+    let (mut blake2b, mut os) = ([0u8; 64],[0u8; 64]);
+    for record in ["first","second",""].iter() {
+        context.hash_bytes_into(record.as_bytes(),&mut blake2b,&mut os).unwrap();
+        assert_eq!((hex::encode_upper(blake2b),hex::encode_upper(os)),context.read_str(record));
+    }
+}
+
+#[test]
+fn size_mismatch(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    let (mut short, mut right, mut long) = ([0u8; 31],[0u8; 32],[0u8; 64]);
+
+    assert_eq!(context.hash_bytes_into(b"abc",&mut short,&mut right),Err(OutputSizeError::Blake2b(DigestSizeMismatch { expected: 32, requested: 31 })));
+    assert_eq!(context.hash_bytes_into(b"abc",&mut right,&mut long),Err(OutputSizeError::Os(DigestSizeMismatch { expected: 32, requested: 64 })));
+    // The BLAKE2B buffer is reported first
+    assert!(matches!(context.hash_bytes_into(b"abc",&mut short,&mut long),Err(OutputSizeError::Blake2b(_))));
+
+    // The hex variant needs twice the length
+    let mut right_hex = [0u8; 64];
+    assert_eq!(context.hash_bytes_into_hex(b"abc",&mut right,&mut right_hex),Err(OutputSizeError::Blake2b(DigestSizeMismatch { expected: 64, requested: 32 })));
+    let mut long_hex = [0u8; 64];
+    assert_eq!(context.hash_bytes_into_hex(b"abc",&mut long_hex,&mut right),Err(OutputSizeError::Os(DigestSizeMismatch { expected: 64, requested: 32 })));
+
+    // Nothing is written on error
+    assert_eq!(right,[0u8; 32]);
+}
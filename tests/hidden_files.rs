@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::PathBuf;
+
+use paranoid_hash::{Manifest, ParanoidHash, WalkOptions};
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join(".git")).unwrap();
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join(".DS_Store"),"finder").unwrap();
+    fs::write(dir.path().join(".gitignore"),"target/").unwrap();
+    fs::write(dir.path().join(".git/HEAD"),"ref").unwrap();
+    fs::write(dir.path().join("src/.hidden.rs"),"hidden").unwrap();
+    fs::write(dir.path().join("src/lib.rs"),"visible").unwrap();
+    fs::write(dir.path().join("README"),"visible").unwrap();
+    return dir
+}
+
+fn context(include_hidden: bool) -> ParanoidHash {
+    return ParanoidHash::default().walk_options(WalkOptions { include_hidden, ..WalkOptions::default() })
+}
+
+#[test]
+fn hidden_files_are_included_by_default(){
+    let dir = tree();
+    assert_eq!(ParanoidHash::default().manifest_dir(dir.path()).unwrap().entries.len(),6);
+    assert_eq!(ParanoidHash::default().read_dir(dir.path()).unwrap(),context(true).read_dir(dir.path()).unwrap());
+}
+
+#[test]
+fn digest_set_changes_with_the_flag(){
+    let dir = tree();
+    let visible = context(false).manifest_dir(dir.path()).unwrap();
+    let paths: Vec<PathBuf> = visible.entries.iter().map(|entry| entry.path.clone()).collect();
+    assert_eq!(paths,vec![PathBuf::from("README"),PathBuf::from("src/lib.rs")]);
+
+    assert_ne!(context(false).read_dir(dir.path()).unwrap(),context(true).read_dir(dir.path()).unwrap());
+
+    // Removing the hidden files gives the same digest as skipping them
+    let without_hidden = context(false).read_dir(dir.path()).unwrap();
+    fs::remove_dir_all(dir.path().join(".git")).unwrap();
+    for file in [".DS_Store",".gitignore","src/.hidden.rs"] {
+        fs::remove_file(dir.path().join(file)).unwrap();
+    }
+    assert_eq!(context(true).read_dir(dir.path()).unwrap(),without_hidden);
+}
+
+#[test]
+fn setting_is_recorded_in_saved_manifests(){
+    let dir = tree();
+    let manifest = context(false).manifest_dir(dir.path()).unwrap();
+    assert_eq!(manifest.include_hidden,false);
+
+    let out = tempfile::tempdir().unwrap();
+    let path = out.path().join("MANIFEST");
+    manifest.save(&path).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("\nhidden false\n"));
+
+    let loaded = Manifest::load(&path).unwrap();
+    assert_eq!(loaded,manifest);
+    assert_eq!(loaded.config().return_walk_options().include_hidden,false);
+}
+
+#[test]
+fn version_1_manifests_include_hidden_files(){
+    let out = tempfile::tempdir().unwrap();
+    let path = out.path().join("MANIFEST");
+    fs::write(&path,"paranoid-hash manifest 1\ncreated-by 0.5.0\nblake2b 64\nos SHA512\n").unwrap();
+    assert_eq!(Manifest::load(&path).unwrap().include_hidden,true);
+}
@@ -0,0 +1,44 @@
+use paranoid_hash::{OsAlgorithm, OutputEncoding, ParanoidHash};
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+
+// Applies `rounds` rounds by hand using `read_bytes()` on the decoded digests
+fn manual(context: &ParanoidHash, data: &[u8], rounds: u32) -> (String,String) {
+    let (mut blake2b,mut os) = context.read_bytes(data);
+    for _ in 1..rounds {
+        blake2b = context.read_bytes(&ParanoidHash::decode_from_hex(&blake2b)).0;
+        os = context.read_bytes(&ParanoidHash::decode_from_hex(&os)).1;
+    }
+    return (blake2b,os)
+}
+
+#[test]
+fn matches_manual_computation(){
+    for context in [ParanoidHash::default(),ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1),ParanoidHash::new(32,OsAlgorithm::SHA256)] {
+        for rounds in 1..=4 {
+            assert_eq!(context.read_bytes_iterated(b"alpha\n",rounds),manual(&context,b"alpha\n",rounds));
+            assert_eq!(context.read_iterated(FILE,rounds).unwrap(),manual(&context,b"alpha\n",rounds));
+        }
+    }
+}
+
+#[test]
+fn known_answer(){
+    // SHA256(SHA256("abc"))
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    assert_eq!(context.read_bytes_iterated(b"abc",2).1,"4F8B42C22DD3729B519BA6F68D2DA7CC5B2D606D05DAED5AD5128CC03E6C6358");
+}
+
+#[test]
+fn chains_raw_bytes_not_encoded_strings(){
+    let upper = ParanoidHash::default().read_bytes_iterated(b"abc",3);
+    let lower = ParanoidHash::default().output_encoding(OutputEncoding::HexLower).read_bytes_iterated(b"abc",3);
+    assert_eq!(upper.0.to_lowercase(),lower.0);
+    assert_eq!(upper.1.to_lowercase(),lower.1);
+}
+
+#[test]
+#[should_panic]
+fn zero_rounds_panics(){
+    ParanoidHash::default().read_bytes_iterated(b"abc",0);
+}
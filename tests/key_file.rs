@@ -0,0 +1,58 @@
+use paranoid_hash::{FileError, KeyFormat, ParanoidHash};
+use std::io::ErrorKind;
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+
+fn key() -> Vec<u8> {
+    return (0u8..32).collect()
+}
+
+#[test]
+fn raw_and_hex_key_files(){
+    let dir = tempfile::tempdir().unwrap();
+    let context = ParanoidHash::default();
+    let expected = context.read_with_key(FILE,key()).unwrap();
+
+    let raw = dir.path().join("key.bin");
+    std::fs::write(&raw,key()).unwrap();
+    assert_eq!(context.read_with_key_file(FILE,&raw,KeyFormat::Raw).unwrap(),expected);
+
+    for contents in [hex::encode(key()),hex::encode_upper(key())+"\n",format!("0x{}\r\n",hex::encode(key())),format!("  {}  \n\n",hex::encode(key()))] {
+        let hex_file = dir.path().join("key.hex");
+        std::fs::write(&hex_file,contents).unwrap();
+        assert_eq!(context.read_with_key_file(FILE,&hex_file,KeyFormat::Hex).unwrap(),expected);
+    }
+}
+
+#[test]
+fn key_errors_are_distinct(){
+    let dir = tempfile::tempdir().unwrap();
+    let context = ParanoidHash::default();
+    let key_path = dir.path().join("key.hex");
+
+    assert_eq!(context.read_with_key_file(FILE,&key_path,KeyFormat::Hex),Err(FileError::KeyFile(ErrorKind::NotFound)));
+
+    std::fs::write(&key_path,"not hex\n").unwrap();
+    assert_eq!(context.read_with_key_file(FILE,&key_path,KeyFormat::Hex),Err(FileError::InvalidKey));
+
+    std::fs::write(&key_path,"\n").unwrap();
+    assert_eq!(context.read_with_key_file(FILE,&key_path,KeyFormat::Hex),Err(FileError::InvalidKey));
+
+    std::fs::write(&key_path,"00".repeat(65)).unwrap();
+    assert_eq!(context.read_with_key_file(FILE,&key_path,KeyFormat::Hex),Err(FileError::InvalidKey));
+    assert_eq!(context.read_with_key_file(FILE,&key_path,KeyFormat::Raw),Err(FileError::InvalidKey));
+
+    std::fs::write(&key_path,"00".repeat(64)).unwrap();
+    assert!(context.read_with_key_file(FILE,&key_path,KeyFormat::Hex).is_ok());
+    assert_eq!(context.read_with_key_file("tests/fixtures/report/missing.txt",&key_path,KeyFormat::Hex),Err(FileError::FileNotFound));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn secret_key_from_hex(){
+    use paranoid_hash::{HexError, SecretKey};
+
+    let key = SecretKey::from_hex(format!("{}\n",hex::encode(key()))).unwrap();
+    assert_eq!(key.as_ref(),&self::key()[..]);
+    assert_eq!(SecretKey::from_hex("abc").unwrap_err(),HexError::OddLength);
+}
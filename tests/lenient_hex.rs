@@ -0,0 +1,37 @@
+use paranoid_hash::{HexError, OsAlgorithm, ParanoidHash};
+
+#[test]
+fn openssl_colon_fingerprint(){
+    // `openssl x509 -fingerprint -sha256` prints upper case pairs separated by colons
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    let (_,sha256) = context.read_str("certificate");
+    let fingerprint = ParanoidHash::decode_from_hex(&sha256).chunks(1).map(hex::encode_upper).collect::<Vec<_>>().join(":");
+
+    assert_eq!(ParanoidHash::decode_from_hex_lenient(&fingerprint).unwrap(),ParanoidHash::decode_from_hex(&sha256));
+    assert!(ParanoidHash::compare_hash_lenient(&fingerprint,&sha256));
+    assert!(!ParanoidHash::compare_hash(&fingerprint,&sha256));
+}
+
+#[test]
+fn whitespace_dashes_and_newlines(){
+    let expected = vec![0xDE,0xAD,0xBE,0xEF];
+    for input in ["deadbeef\n","DEADBEEF\r\n","de ad be ef","dead-beef","  0xDEAD BEEF\n","de\tad:be-ef"] {
+        assert_eq!(ParanoidHash::decode_from_hex_lenient(input).unwrap(),expected,"{:?}",input);
+    }
+    assert!(ParanoidHash::compare_hash_lenient("DE AD BE EF\n","deadbeef"));
+}
+
+#[test]
+fn invalid_input_is_still_rejected(){
+    assert_eq!(ParanoidHash::decode_from_hex_lenient("ab:cd:eg"),Err(HexError::InvalidCharacter { character: 'g', index: 7 }));
+    assert_eq!(ParanoidHash::decode_from_hex_lenient("ab:cd:e"),Err(HexError::OddLength));
+    assert_eq!(ParanoidHash::decode_from_hex_lenient("ab;cd"),Err(HexError::InvalidCharacter { character: ';', index: 2 }));
+    assert!(!ParanoidHash::compare_hash_lenient("ab:cd","abce"));
+}
+
+#[test]
+fn strict_decoder_returns_errors(){
+    assert_eq!(ParanoidHash::try_decode_from_hex("0xABcd").unwrap(),vec![0xAB,0xCD]);
+    assert_eq!(ParanoidHash::try_decode_from_hex("ab:cd"),Err(HexError::InvalidCharacter { character: ':', index: 2 }));
+    assert_eq!(ParanoidHash::try_decode_from_hex("abc"),Err(HexError::OddLength));
+}
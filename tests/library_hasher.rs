@@ -0,0 +1,60 @@
+use paranoid_hash::{Blake2bHasher,LibraryHasher,OsAlgorithm,ParanoidHash,ParanoidHashCustom,VerifyPolicy};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Records every byte it is fed and returns the count as an 8 byte big-endian digest
+struct Counter {
+    seen: Rc<RefCell<Vec<u8>>>,
+    count: u64,
+}
+
+impl LibraryHasher for Counter {
+    fn update(&mut self, bytes: &[u8]) {
+        self.seen.borrow_mut().extend_from_slice(bytes);
+        self.count += bytes.len() as u64;
+    }
+    fn finalize(self) -> Vec<u8> {
+        return self.count.to_be_bytes().to_vec()
+    }
+    fn output_len(&self) -> usize {
+        return 8
+    }
+}
+
+#[test]
+fn every_byte_is_fed_exactly_once(){
+    let expected = std::fs::read("examples/example_file.txt").unwrap();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+
+    let (count,sha256) = context.read_with_hasher("examples/example_file.txt",Counter { seen: seen.clone(), count: 0 }).unwrap();
+    assert_eq!(*seen.borrow(),expected);
+    assert_eq!(count,"000000000000023F");
+    assert_eq!(sha256,context.read("examples/example_file.txt").unwrap().1);
+
+    seen.borrow_mut().clear();
+    let (count,_) = context.read_from_reader_with_hasher(&expected[..],Counter { seen: seen.clone(), count: 0 }).unwrap();
+    assert_eq!(*seen.borrow(),expected);
+    assert_eq!(count,"000000000000023F");
+}
+
+#[test]
+fn blake2b_hasher_matches_builtin(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA512);
+    assert_eq!(Blake2bHasher::new(32).output_len(),32);
+    assert_eq!(context.read_with_hasher("examples/example_file.txt",Blake2bHasher::new(32)).unwrap(),context.read("examples/example_file.txt").unwrap());
+    assert_eq!(context.read_bytes_with_hasher(b"abc",Blake2bHasher::new(32)).unwrap(),context.read_str("abc"));
+}
+
+#[test]
+fn custom_context_verifies(){
+    let config = ParanoidHash::default();
+    let custom = ParanoidHashCustom::new(config.clone(),|| Counter { seen: Rc::new(RefCell::new(Vec::new())), count: 0 });
+
+    let (count,sha512) = custom.read("examples/example_file.txt").unwrap();
+    assert_eq!(count,"000000000000023F");
+    assert_eq!(custom.read_bytes(b"abc").unwrap().0,"0000000000000003");
+
+    assert!(custom.verify_file("examples/example_file.txt",Some(&count),Some(&sha512),VerifyPolicy::Both).unwrap().passed);
+    assert!(!custom.verify_file("examples/example_file.txt",Some("0000000000000003"),Some(&sha512),VerifyPolicy::Both).unwrap().passed);
+}
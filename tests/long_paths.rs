@@ -0,0 +1,83 @@
+#![cfg(windows)]
+
+use std::fs;
+use std::path::PathBuf;
+
+use paranoid_hash::ParanoidHash;
+
+const DATA: &[u8] = b"a file far below MAX_PATH";
+
+// A file more than 300 characters below the temporary directory
+fn nested(dir: &tempfile::TempDir) -> PathBuf {
+    let mut path = dir.path().to_path_buf();
+    for i in 0..12 {
+        path.push(format!("nested-directory-{:02}-{}", i, "x".repeat(10)));
+    }
+    fs::create_dir_all(&path).unwrap();
+    let file = path.join("long-path-file.txt");
+    fs::write(&file,DATA).unwrap();
+    assert!(file.as_os_str().len() > 300);
+    return file
+}
+
+#[test]
+fn read_long_path(){
+    let dir = tempfile::tempdir().unwrap();
+    let file = nested(&dir);
+    let context = ParanoidHash::default();
+    assert_eq!(context.read(&file).unwrap(),context.read_bytes(DATA));
+    assert_eq!(context.read_using_std(&file).unwrap(),context.read_bytes(DATA));
+    assert_eq!(context.read_parallel(&file).unwrap(),context.read_bytes(DATA));
+}
+
+#[test]
+fn read_with_key_long_path(){
+    let dir = tempfile::tempdir().unwrap();
+    let file = nested(&dir);
+    let context = ParanoidHash::default();
+    let key = [7u8; 32];
+    fs::write(dir.path().join("short.txt"),DATA).unwrap();
+    assert_eq!(context.read_with_key(&file,key).unwrap(),context.read_with_key(dir.path().join("short.txt"),key).unwrap());
+}
+
+#[test]
+fn walk_long_paths(){
+    let dir = tempfile::tempdir().unwrap();
+    let file = nested(&dir);
+    let context = ParanoidHash::default();
+    let manifest = context.manifest_dir(dir.path()).unwrap();
+    assert_eq!(manifest.entries.len(),1);
+    assert_eq!(dir.path().join(&manifest.entries[0].path),file);
+    assert!(context.read_dir(dir.path()).is_ok());
+}
+
+#[test]
+fn slashes_and_dots_are_normalised(){
+    let dir = tempfile::tempdir().unwrap();
+    let file = nested(&dir);
+    let parent = file.parent().unwrap();
+    let dotted = parent.join("..").join(parent.file_name().unwrap()).join(".").join("long-path-file.txt");
+    let slashed = PathBuf::from(dotted.to_str().unwrap().replace('\\',"/"));
+    let context = ParanoidHash::default();
+    assert_eq!(context.read(&slashed).unwrap(),context.read_bytes(DATA));
+}
+
+#[test]
+fn every_opener_long_path(){
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = nested(&dir);
+    let context = ParanoidHash::default();
+    let expected = context.read_bytes(DATA);
+    assert_eq!(context.read_cancellable(&file,&AtomicBool::new(false)).unwrap(),expected);
+    assert_eq!(context.read_with_timeout(&file,Duration::from_secs(60)).unwrap(),expected);
+    assert_eq!(context.read_with_limit(&file,1024).unwrap().0,expected);
+    assert_eq!(context.read_lines(&file).unwrap(),vec![expected.clone()]);
+    assert_eq!(context.read_concat(&[&file]).unwrap(),expected);
+    assert_eq!(context.read_ranges(&file,&[0..DATA.len() as u64]).unwrap(),vec![expected.clone()]);
+    assert_eq!(context.read_double_checked(&file).unwrap(),expected);
+    assert_eq!(context.copy_and_hash(&file,file.with_extension("copy")).unwrap(),expected);
+    assert_eq!(fs::read(file.with_extension("copy")).unwrap(),DATA);
+}
@@ -0,0 +1,117 @@
+use paranoid_hash::manifest::MANIFEST_FORMAT_VERSION;
+use paranoid_hash::{FileError, Manifest, ManifestStatus, OsAlgorithm, OutputEncoding, ParanoidHash};
+use std::fs;
+use std::path::PathBuf;
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("nested/deeper")).unwrap();
+    fs::write(dir.path().join("a.txt"),b"alpha\n").unwrap();
+    fs::write(dir.path().join("nested/b.txt"),b"beta\n").unwrap();
+    fs::write(dir.path().join("nested/deeper/with space.txt"),b"").unwrap();
+    return dir
+}
+
+#[test]
+fn round_trip(){
+    let dir = tree();
+    let manifest = ParanoidHash::new(32,OsAlgorithm::SHA256).output_encoding(OutputEncoding::Base32).manifest_dir(dir.path()).unwrap();
+
+    assert_eq!(manifest.format_version,MANIFEST_FORMAT_VERSION);
+    assert_eq!(manifest.entries.iter().map(|entry| entry.path.clone()).collect::<Vec<_>>(),vec![PathBuf::from("a.txt"),PathBuf::from("nested/b.txt"),PathBuf::from("nested/deeper/with space.txt")]);
+    assert_eq!(manifest.entries[0].size,6);
+    assert_eq!((manifest.entries[0].blake2b.clone(),manifest.entries[0].os_digest.clone()),ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes(b"alpha\n"));
+
+    let out = tempfile::tempdir().unwrap();
+    let path = out.path().join("MANIFEST");
+    manifest.save(&path).unwrap();
+    assert_eq!(Manifest::load(&path).unwrap(),manifest);
+}
+
+#[cfg(unix)]
+#[test]
+fn escaped_paths_round_trip(){
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("new\nline\\slash"),b"x").unwrap();
+    let manifest = ParanoidHash::default().manifest_dir(dir.path()).unwrap();
+
+    let out = tempfile::tempdir().unwrap();
+    let path = out.path().join("MANIFEST");
+    manifest.save(&path).unwrap();
+    let loaded = Manifest::load(&path).unwrap();
+
+    assert_eq!(loaded,manifest);
+    assert_eq!(loaded.entries[0].path,PathBuf::from("new\nline\\slash"));
+}
+
+#[test]
+fn verify_against_directory(){
+    let dir = tree();
+    // Created with a different configuration than the caller's default
+    let manifest = ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1).manifest_dir(dir.path()).unwrap();
+    assert!(manifest.verify(dir.path()).iter().all(|(_,status)| *status == ManifestStatus::Match));
+
+    fs::write(dir.path().join("a.txt"),b"tampered\n").unwrap();
+    fs::remove_file(dir.path().join("nested/b.txt")).unwrap();
+    fs::write(dir.path().join("unlisted.txt"),b"new").unwrap();
+
+    assert_eq!(manifest.verify(dir.path()),vec![
+        (PathBuf::from("a.txt"),ManifestStatus::Mismatch),
+        (PathBuf::from("nested/b.txt"),ManifestStatus::Missing),
+        (PathBuf::from("nested/deeper/with space.txt"),ManifestStatus::Match),
+    ]);
+}
+
+#[test]
+fn load_errors(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("MANIFEST");
+
+    fs::write(&path,"paranoid-hash manifest 4\ncreated-by 9.0.0\n").unwrap();
+    assert_eq!(Manifest::load(&path),Err(FileError::UnsupportedManifestVersion(4)));
+
+    fs::write(&path,"not a manifest\n").unwrap();
+    assert_eq!(Manifest::load(&path),Err(FileError::InvalidManifest(1)));
+
+    fs::write(&path,"paranoid-hash manifest 1\ncreated-by 0.5.0\nblake2b 65\nos SHA512\n").unwrap();
+    assert_eq!(Manifest::load(&path),Err(FileError::InvalidManifest(3)));
+
+    fs::write(&path,"paranoid-hash manifest 1\ncreated-by 0.5.0\nblake2b 64\nos SHA512\n6 AA BB ../escape.txt\n").unwrap();
+    assert_eq!(Manifest::load(&path),Err(FileError::InvalidManifest(5)));
+
+    fs::write(&path,"paranoid-hash manifest 1\ncreated-by 0.5.0\nblake2b 64\nos SHA512\n").unwrap();
+    assert!(Manifest::load(&path).unwrap().entries.is_empty());
+
+    fs::write(&path,"paranoid-hash manifest 2\ncreated-by 0.5.0\nblake2b 64\nos SHA512\nhidden maybe\n").unwrap();
+    assert_eq!(Manifest::load(&path),Err(FileError::InvalidManifest(5)));
+
+    fs::write(&path,"paranoid-hash manifest 3\ncreated-by 0.5.0\nblake2b 64\nos SHA512\nhidden true\n6 yesterday AA BB a.txt\n").unwrap();
+    assert_eq!(Manifest::load(&path),Err(FileError::InvalidManifest(6)));
+
+    assert_eq!(Manifest::load(dir.path().join("missing")),Err(FileError::FileNotFound));
+}
+
+#[test]
+fn modification_times(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("MANIFEST");
+
+    // Version 2 entries have no modification time
+    fs::write(&path,"paranoid-hash manifest 2\ncreated-by 0.5.0\nblake2b 64\nos SHA512\nhidden true\n6 AA BB a.txt\n").unwrap();
+    let manifest = Manifest::load(&path).unwrap();
+    assert_eq!((manifest.entries[0].size,manifest.entries[0].modified,manifest.entries[0].path.clone()),(6,None,PathBuf::from("a.txt")));
+
+    fs::write(&path,"paranoid-hash manifest 3\ncreated-by 0.5.0\nblake2b 64\nos SHA512\nhidden true\n6 1700000000000000000 AA BB a.txt\n1 - CC DD with space.txt\n").unwrap();
+    let manifest = Manifest::load(&path).unwrap();
+    assert_eq!(manifest.entries[0].modified,Some(1_700_000_000_000_000_000));
+    assert_eq!((manifest.entries[1].modified,manifest.entries[1].path.clone()),(None,PathBuf::from("with space.txt")));
+
+    // Written back unchanged
+    let saved = dir.path().join("SAVED");
+    manifest.save(&saved).unwrap();
+    assert_eq!(fs::read_to_string(&saved).unwrap(),fs::read_to_string(&path).unwrap());
+
+    let tree = tree();
+    let manifest = ParanoidHash::default().manifest_dir(tree.path()).unwrap();
+    assert!(manifest.entries.iter().all(|entry| entry.modified.is_some()));
+}
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use paranoid_hash::{LibAlgorithm, Manifest, ManifestChange, ManifestDiff, ManifestEntry, OsAlgorithm};
+
+fn entry(path: &str, size: u64, blake2b: &str, os_digest: &str) -> ManifestEntry {
+    return ManifestEntry { path: PathBuf::from(path), size, modified: None, blake2b: blake2b.to_string(), os_digest: os_digest.to_string() }
+}
+
+fn manifest(os_algorithm: OsAlgorithm, entries: Vec<ManifestEntry>) -> Manifest {
+    return Manifest {
+        format_version: 2,
+        created_by: String::from("0.5.0"),
+        library_algorithm: LibAlgorithm::BLAKE2B,
+        blake2b_digest_size: 1,
+        os_algorithm,
+        include_hidden: false,
+        entries,
+    }
+}
+
+fn yesterday() -> Manifest {
+    return manifest(OsAlgorithm::SHA1, vec![
+        entry("a.txt",1,"AA","0000000000000000000000000000000000000001"),
+        entry("b.txt",2,"BB","0000000000000000000000000000000000000002"),
+        entry("c.txt",3,"CC","0000000000000000000000000000000000000003"),
+        entry("dir/d.txt",4,"DD","0000000000000000000000000000000000000004"),
+    ])
+}
+
+fn today(os_algorithm: OsAlgorithm) -> Manifest {
+    return manifest(os_algorithm, vec![
+        // Unchanged apart from the case of the digests
+        entry("a.txt",1,"aa","0000000000000000000000000000000000000001"),
+        // Same size, different digest
+        entry("b.txt",2,"BE","0000000000000000000000000000000000000002"),
+        // Different size, same digests
+        entry("c.txt",30,"CC","0000000000000000000000000000000000000003"),
+        entry("dir/e.txt",5,"EE","0000000000000000000000000000000000000005"),
+    ])
+}
+
+#[test]
+fn added_removed_modified(){
+    let diff = yesterday().diff(&today(OsAlgorithm::SHA1));
+    assert_eq!(diff,ManifestDiff {
+        added: vec![entry("dir/e.txt",5,"EE","0000000000000000000000000000000000000005")],
+        removed: vec![entry("dir/d.txt",4,"DD","0000000000000000000000000000000000000004")],
+        modified: vec![
+            (entry("b.txt",2,"BB","0000000000000000000000000000000000000002"),entry("b.txt",2,"BE","0000000000000000000000000000000000000002")),
+            (entry("c.txt",3,"CC","0000000000000000000000000000000000000003"),entry("c.txt",30,"CC","0000000000000000000000000000000000000003")),
+        ],
+        incompatible: false,
+    });
+    assert!(!diff.is_empty());
+    assert!(yesterday().diff(&yesterday()).is_empty());
+}
+
+#[test]
+fn iterates_by_path(){
+    let diff = yesterday().diff(&today(OsAlgorithm::SHA1));
+    let paths: Vec<&str> = diff.iter().map(|change| change.path().to_str().unwrap()).collect();
+    assert_eq!(paths,vec!["b.txt","c.txt","dir/d.txt","dir/e.txt"]);
+
+    let mut changes = (&diff).into_iter();
+    assert!(matches!(changes.next(),Some(ManifestChange::Modified(older,newer)) if older.blake2b == "BB" && newer.blake2b == "BE"));
+    assert!(matches!(changes.nth(1),Some(ManifestChange::Removed(entry)) if entry.size == 4));
+    assert!(matches!(changes.next(),Some(ManifestChange::Added(entry)) if entry.size == 5));
+    assert_eq!(changes.next(),None);
+}
+
+#[test]
+fn incompatible_configurations(){
+    // The digests cannot be compared, so only the size change is reported
+    let diff = yesterday().diff(&today(OsAlgorithm::SHA256));
+    assert!(diff.incompatible);
+    assert_eq!(diff.modified.len(),1);
+    assert_eq!(diff.modified[0].1.path,PathBuf::from("c.txt"));
+    assert_eq!(diff.added.len(),1);
+    assert_eq!(diff.removed.len(),1);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_to_json(){
+    let older = manifest(OsAlgorithm::SHA1, vec![entry("a.txt",1,"AA","01")]);
+    let newer = manifest(OsAlgorithm::SHA1, vec![entry("b.txt",2,"BB","02")]);
+    let json = serde_json::to_string(&older.diff(&newer)).unwrap();
+    assert_eq!(json,r#"{"added":[{"path":"b.txt","size":2,"blake2b":"BB","os_digest":"02"}],"removed":[{"path":"a.txt","size":1,"blake2b":"AA","os_digest":"01"}],"modified":[],"incompatible":false}"#);
+}
@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use paranoid_hash::{Manifest, OsAlgorithm, ParanoidHash, UpdateOptions, UpdateSummary};
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("nested")).unwrap();
+    fs::write(dir.path().join("a.txt"),b"alpha\n").unwrap();
+    fs::write(dir.path().join("b.txt"),b"beta\n").unwrap();
+    fs::write(dir.path().join("nested/c.txt"),b"gamma\n").unwrap();
+    return dir
+}
+
+// Moves the modification time an hour back, as if the file had been written since
+fn touch(path: &Path) {
+    let file = fs::OpenOptions::new().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(3600)).unwrap();
+}
+
+fn paths(manifest: &Manifest) -> Vec<PathBuf> {
+    return manifest.entries.iter().map(|entry| entry.path.clone()).collect()
+}
+
+#[test]
+fn touch_add_and_delete(){
+    let dir = tree();
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    let mut manifest = context.manifest_dir(dir.path()).unwrap();
+
+    touch(&dir.path().join("a.txt"));
+    fs::write(dir.path().join("nested/d.txt"),b"delta\n").unwrap();
+    fs::remove_file(dir.path().join("b.txt")).unwrap();
+
+    let summary = manifest.update(dir.path(),UpdateOptions::default()).unwrap();
+    assert_eq!(summary,UpdateSummary { reused: 1, rehashed: 1, added: 1, removed: 1 });
+
+    assert_eq!(paths(&manifest),vec![PathBuf::from("a.txt"),PathBuf::from("nested/c.txt"),PathBuf::from("nested/d.txt")]);
+    // The same as a new manifest of the directory, including the new modification time
+    assert_eq!(manifest,context.manifest_dir(dir.path()).unwrap());
+    assert_eq!(manifest.config().return_os_hash_algorithm(),OsAlgorithm::SHA256);
+}
+
+#[test]
+fn changed_contents_are_rehashed(){
+    let dir = tree();
+    let mut manifest = ParanoidHash::default().manifest_dir(dir.path()).unwrap();
+
+    fs::write(dir.path().join("b.txt"),b"a longer beta\n").unwrap();
+    let summary = manifest.update(dir.path(),UpdateOptions::default()).unwrap();
+    assert_eq!(summary,UpdateSummary { reused: 2, rehashed: 1, added: 0, removed: 0 });
+
+    let entry = manifest.entries.iter().find(|entry| entry.path == Path::new("b.txt")).unwrap();
+    assert_eq!(entry.size,14);
+    assert_eq!((entry.blake2b.clone(),entry.os_digest.clone()),ParanoidHash::default().read_bytes(b"a longer beta\n"));
+}
+
+#[test]
+fn unchanged_metadata_is_trusted(){
+    let dir = tree();
+    let mut manifest = ParanoidHash::default().manifest_dir(dir.path()).unwrap();
+    let original = manifest.clone();
+
+    // Same size, with the modification time restored afterwards
+    let path = dir.path().join("a.txt");
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    fs::write(&path,b"ALPHA\n").unwrap();
+    fs::OpenOptions::new().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+
+    assert_eq!(manifest.update(dir.path(),UpdateOptions::default()).unwrap(),UpdateSummary { reused: 3, rehashed: 0, added: 0, removed: 0 });
+    assert_eq!(manifest,original);
+
+    // Paranoid mode reads every file and finds it
+    let summary = manifest.update(dir.path(),UpdateOptions { paranoid: true }).unwrap();
+    assert_eq!(summary,UpdateSummary { reused: 0, rehashed: 3, added: 0, removed: 0 });
+    assert_ne!(manifest,original);
+    assert_eq!(manifest,ParanoidHash::default().manifest_dir(dir.path()).unwrap());
+}
+
+#[test]
+fn older_entries_without_times_are_rehashed(){
+    let dir = tree();
+    let mut manifest = ParanoidHash::default().manifest_dir(dir.path()).unwrap();
+    for entry in manifest.entries.iter_mut() {
+        entry.modified = None;
+    }
+    manifest.format_version = 2;
+
+    let summary = manifest.update(dir.path(),UpdateOptions::default()).unwrap();
+    assert_eq!(summary,UpdateSummary { reused: 0, rehashed: 3, added: 0, removed: 0 });
+    assert_eq!(manifest,ParanoidHash::default().manifest_dir(dir.path()).unwrap());
+}
+
+#[test]
+fn failure_leaves_the_manifest_unchanged(){
+    let dir = tree();
+    let mut manifest = ParanoidHash::default().manifest_dir(dir.path()).unwrap();
+    let original = manifest.clone();
+
+    let missing = dir.path().join("missing");
+    assert!(manifest.update(&missing,UpdateOptions::default()).is_err());
+    assert_eq!(manifest,original);
+}
@@ -0,0 +1,72 @@
+use std::fs;
+use std::path::Path;
+
+use paranoid_hash::{FileError, ParanoidHash};
+
+const SIZE: u64 = 100;
+
+fn file() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("upload"),vec![7u8; SIZE as usize]).unwrap();
+    return dir
+}
+
+// Every way of hashing a file that honours the limit
+fn all_paths(context: &ParanoidHash, path: &Path) -> Vec<Result<(String,String),FileError>> {
+    return vec![
+        context.read(path),
+        context.read_using_std(path),
+        context.read_parallel(path),
+        context.read_from_reader(fs::File::open(path).unwrap()),
+        context.read_file(&mut fs::File::open(path).unwrap()),
+    ]
+}
+
+#[test]
+fn just_under_the_limit(){
+    let dir = file();
+    let path = dir.path().join("upload");
+    let expected = ParanoidHash::default().read(&path).unwrap();
+    for result in all_paths(&ParanoidHash::default().max_file_size(Some(SIZE + 1)),&path) {
+        assert_eq!(result.unwrap(),expected);
+    }
+}
+
+#[test]
+fn exactly_at_the_limit(){
+    let dir = file();
+    let path = dir.path().join("upload");
+    let expected = ParanoidHash::default().read(&path).unwrap();
+    for result in all_paths(&ParanoidHash::default().max_file_size(Some(SIZE)),&path) {
+        assert_eq!(result.unwrap(),expected);
+    }
+}
+
+#[test]
+fn just_over_the_limit(){
+    let dir = file();
+    let path = dir.path().join("upload");
+    for result in all_paths(&ParanoidHash::default().max_file_size(Some(SIZE - 1)),&path) {
+        assert_eq!(result,Err(FileError::FileTooLarge { size: SIZE, limit: SIZE - 1 }));
+    }
+}
+
+#[test]
+fn streaming_stops_mid_way(){
+    // An endless reader is only stopped by the limit
+    let context = ParanoidHash::default().max_file_size(Some(1024 * 1024));
+    match context.read_from_reader(std::io::repeat(0)) {
+        Err(FileError::FileTooLarge { size, limit }) => {
+            assert_eq!(limit,1024 * 1024);
+            assert!(size > limit);
+        }
+        other => panic!("unexpected result {:?}",other),
+    }
+}
+
+#[test]
+fn directories_report_the_file(){
+    let dir = file();
+    let context = ParanoidHash::default().max_file_size(Some(SIZE - 1));
+    assert_eq!(context.read_dir(dir.path()),Err(FileError::AtPath(dir.path().join("upload"),Box::new(FileError::FileTooLarge { size: SIZE, limit: SIZE - 1 }))));
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "insecure-md5")]
+
+use std::convert::TryFrom;
+use std::io::Write;
+
+use paranoid_hash::{detect_algorithm, Algo, OsAlgorithm, OsDigestArray, ParanoidHash};
+
+// RFC 1321 Appendix A.5
+const RFC_1321: [(&str, &str); 7] = [
+    ("", "D41D8CD98F00B204E9800998ECF8427E"),
+    ("a", "0CC175B9C0F1B6A831C399E269772661"),
+    ("abc", "900150983CD24FB0D6963F7D28E17F72"),
+    ("message digest", "F96B697D7CB7938D525A2F31AAF161D0"),
+    ("abcdefghijklmnopqrstuvwxyz", "C3FCD3D76192E4007DFB496CCA67E13B"),
+    ("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789", "D174AB98D277D9F5A5611C2C9F419D9F"),
+    ("12345678901234567890123456789012345678901234567890123456789012345678901234567890", "57EDF4A22BE3C955AC49DA2E2107B67A"),
+];
+
+fn context() -> ParanoidHash {
+    return ParanoidHash::new(16,OsAlgorithm::MD5)
+}
+
+#[test]
+fn rfc_1321_strings_and_bytes(){
+    for (message, expected) in RFC_1321.iter() {
+        assert_eq!(context().read_str(message).1,*expected,"{:?}",message);
+        assert_eq!(context().read_bytes(message.as_bytes()).1,*expected,"{:?}",message);
+    }
+}
+
+#[test]
+fn rfc_1321_files(){
+    for (message, expected) in RFC_1321.iter() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(message.as_bytes()).unwrap();
+        file.flush().unwrap();
+        assert_eq!(context().read(file.path()).unwrap().1,*expected,"{:?}",message);
+        assert_eq!(context().read_using_std(file.path()).unwrap().1,*expected,"{:?}",message);
+    }
+}
+
+#[test]
+fn length_detection(){
+    assert_eq!(OsAlgorithm::from_hex_digest("d41d8cd98f00b204e9800998ecf8427e"),Some(OsAlgorithm::MD5));
+    assert_eq!(detect_algorithm(&"0".repeat(32)),vec![Algo::Os(OsAlgorithm::MD5)]);
+}
+
+#[test]
+fn parse_name(){
+    assert_eq!("md5".parse::<OsAlgorithm>(),Ok(OsAlgorithm::MD5));
+    assert_eq!("MD5".parse::<OsAlgorithm>(),Ok(OsAlgorithm::MD5));
+}
+
+#[test]
+fn tagged_round_trip(){
+    let (blake2b,md5) = context().read_str("abc");
+    let tagged = context().to_tagged_string(&blake2b,&md5).unwrap();
+    assert_eq!(tagged,"blake2b-16:CF4AB791C62B8D2B2109C90275287816;md5:900150983CD24FB0D6963F7D28E17F72");
+
+    let (parsed,digests) = ParanoidHash::parse_tagged(&tagged).unwrap();
+    assert_eq!(parsed.return_os_hash_algorithm(),OsAlgorithm::MD5);
+    assert_eq!(digests,(blake2b,md5));
+}
+
+#[test]
+fn fixed_array(){
+    let (_,os) = context().read_bytes_fixed::<16>(b"abc").unwrap();
+    assert_eq!(os,OsDigestArray::MD5(<[u8; 16]>::try_from(&hex::decode("900150983CD24FB0D6963F7D28E17F72").unwrap()[..]).unwrap()));
+}
+
+#[test]
+fn not_weak_but_not_default(){
+    // The feature is the opt-in, so MD5 does not also need `new_allowing_weak()`
+    assert!(!OsAlgorithm::MD5.is_weak());
+    assert_ne!(OsAlgorithm::default(),OsAlgorithm::MD5);
+}
+
+#[test]
+fn self_test_covers_md5(){
+    assert!(ParanoidHash::self_test().is_ok());
+}
@@ -0,0 +1,118 @@
+use std::fs;
+
+use paranoid_hash::{FileError, OsAlgorithm, OutputEncoding, ParanoidHash};
+
+const FIXTURE: &str = "tests/fixtures/dir_digest";
+const FILES: [&str; 5] = ["B.txt","a-b.txt","a/b.txt","a/c/empty","z.txt"];
+
+// Computed independently with Python's hashlib following the documented construction
+#[test]
+fn fixture_roots(){
+    assert_eq!(ParanoidHash::default().merkle_root(FIXTURE).unwrap(),(
+        String::from("21E6CFDD3AFFC09238162713CD7E846ACA3C7C933D97AA0CF0164C92C82D35D0D3968E74ADB2372B4A0575F939F505359C75BF2C470001ABB44DF8603DDDB230"),
+        String::from("4A695B17FA48A04FD2B8117F21EE5C76A1F5B1C992BAD942373F75686DB83A2D2785ADB59D9A0F645737A4B211A2E4FC6DD7FB42A153C79C5127D732779268DD"),
+    ));
+    assert_eq!(ParanoidHash::new(32,OsAlgorithm::SHA256).merkle_root(FIXTURE).unwrap(),(
+        String::from("3BF0BC79ABB42B8D4581BA6DE5BDA3896047E0DAE39A0B85F7701D001E67EFA6"),
+        String::from("A352A9560C14ACFF5743F01BF64A20DEC34F67899727419449CC0BCDE03E76CA"),
+    ));
+}
+
+#[test]
+fn every_file_proves(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    let (blake2b_root, os_root) = context.merkle_root(FIXTURE).unwrap();
+
+    for (index, name) in FILES.iter().enumerate() {
+        let proof = context.merkle_proof(FIXTURE,name).unwrap();
+        assert_eq!((proof.name.as_str(),proof.index,proof.leaf_count),(*name,index,5));
+        assert_eq!(proof.digests,context.read(format!("{}/{}",FIXTURE,name)).unwrap());
+        // The fifth leaf sits directly below the root
+        assert_eq!(proof.blake2b_path.len(),if index == 4 { 1 } else { 3 });
+        assert_eq!(proof.os_path.len(),proof.blake2b_path.len());
+        assert!(context.verify_merkle_proof(&proof,&blake2b_root,&os_root).unwrap());
+    }
+}
+
+#[test]
+fn tampered_proofs(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    let (blake2b_root, os_root) = context.merkle_root(FIXTURE).unwrap();
+    let proof = context.merkle_proof(FIXTURE,"a/b.txt").unwrap();
+    let other = context.merkle_proof(FIXTURE,"z.txt").unwrap();
+
+    // Different contents
+    let mut forged = proof.clone();
+    forged.digests = other.digests.clone();
+    assert!(!context.verify_merkle_proof(&forged,&blake2b_root,&os_root).unwrap());
+
+    // Different position
+    let mut forged = proof.clone();
+    forged.index = 3;
+    assert!(!context.verify_merkle_proof(&forged,&blake2b_root,&os_root).unwrap());
+    forged.index = 5;
+    assert!(!context.verify_merkle_proof(&forged,&blake2b_root,&os_root).unwrap());
+
+    // Missing sibling
+    let mut forged = proof.clone();
+    forged.blake2b_path.pop();
+    forged.os_path.pop();
+    assert!(!context.verify_merkle_proof(&forged,&blake2b_root,&os_root).unwrap());
+
+    // Only one of the trees was tampered with
+    let mut forged = proof.clone();
+    forged.os_path.swap(0,1);
+    assert_eq!(context.verify_merkle_proof(&forged,&blake2b_root,&os_root),Err(FileError::DigestDisagreement));
+
+    assert_eq!(context.verify_merkle_proof(&proof,"not a digest",&os_root),Err(FileError::InvalidDigest));
+}
+
+#[test]
+fn changed_tree(){
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["one","two","three"] {
+        fs::write(dir.path().join(name),name).unwrap();
+    }
+    let context = ParanoidHash::default();
+    let (blake2b_root, os_root) = context.merkle_root(dir.path()).unwrap();
+    let proof = context.merkle_proof(dir.path(),"one").unwrap();
+
+    fs::write(dir.path().join("two"),"changed").unwrap();
+    let (changed_blake2b, changed_os) = context.merkle_root(dir.path()).unwrap();
+    assert_ne!(changed_blake2b,blake2b_root);
+    assert!(!context.verify_merkle_proof(&proof,&changed_blake2b,&changed_os).unwrap());
+    assert!(context.verify_merkle_proof(&proof,&blake2b_root,&os_root).unwrap());
+}
+
+#[test]
+fn small_trees(){
+    let dir = tempfile::tempdir().unwrap();
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+
+    // No files has the digests of empty input
+    assert_eq!(context.merkle_root(dir.path()).unwrap(),context.read_bytes(b""));
+    assert_eq!(context.clone().reject_empty_input(true).merkle_root(dir.path()),Err(FileError::EmptyFile));
+
+    // A single file is its own leaf and has an empty audit path
+    fs::write(dir.path().join("only"),"contents").unwrap();
+    let (blake2b_root, os_root) = context.merkle_root(dir.path()).unwrap();
+    let proof = context.merkle_proof(dir.path(),"only").unwrap();
+    assert!(proof.blake2b_path.is_empty());
+    assert!(context.verify_merkle_proof(&proof,&blake2b_root,&os_root).unwrap());
+}
+
+#[test]
+fn any_encoding(){
+    let base32 = ParanoidHash::new(32,OsAlgorithm::SHA256).output_encoding(OutputEncoding::Base32);
+    let (blake2b_root, os_root) = base32.merkle_root(FIXTURE).unwrap();
+    let proof = base32.merkle_proof(FIXTURE,"./a/c/empty").unwrap();
+    assert!(ParanoidHash::new(32,OsAlgorithm::SHA256).verify_merkle_proof(&proof,&blake2b_root,&os_root).unwrap());
+}
+
+#[test]
+fn unknown_files(){
+    let context = ParanoidHash::default();
+    assert_eq!(context.merkle_proof(FIXTURE,"missing.txt"),Err(FileError::FileNotFound));
+    assert_eq!(context.merkle_proof(FIXTURE,"a"),Err(FileError::FileNotFound));
+    assert_eq!(context.merkle_proof(FIXTURE,"../dir_digest/z.txt"),Err(FileError::FileNotFound));
+}
@@ -0,0 +1,42 @@
+use paranoid_hash::{Algo, OsAlgorithm, ParanoidHash, ParanoidHashMulti};
+
+#[test]
+fn matches_two_digest_api(){
+    let multi = ParanoidHashMulti::new(&[Algo::Blake2b(64),Algo::Os(OsAlgorithm::SHA256),Algo::Os(OsAlgorithm::SHA512)]);
+    let digests = multi.read_bytes(b"Hello World");
+
+    let (blake2b,sha256) = ParanoidHash::new(64,OsAlgorithm::SHA256).read_bytes(b"Hello World");
+    let (_,sha512) = ParanoidHash::new(64,OsAlgorithm::SHA512).read_bytes(b"Hello World");
+
+    assert_eq!(digests,vec![
+        (Algo::Blake2b(64),blake2b),
+        (Algo::Os(OsAlgorithm::SHA256),sha256),
+        (Algo::Os(OsAlgorithm::SHA512),sha512),
+    ]);
+}
+
+#[test]
+fn known_answer(){
+    let multi = ParanoidHashMulti::new(&[Algo::Os(OsAlgorithm::SHA256)]);
+    assert_eq!(multi.read_bytes(b"abc")[0].1,"BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD");
+}
+
+#[test]
+fn duplicates_are_removed(){
+    let multi = ParanoidHashMulti::new(&[Algo::Os(OsAlgorithm::SHA256),Algo::Blake2b(32),Algo::Os(OsAlgorithm::SHA256),Algo::Blake2b(32),Algo::Blake2b(64)]);
+    assert_eq!(multi.return_algorithms(),&[Algo::Os(OsAlgorithm::SHA256),Algo::Blake2b(32),Algo::Blake2b(64)]);
+    assert_eq!(multi.read_bytes(b"").len(),3);
+}
+
+#[test]
+fn read_file_matches_bytes(){
+    let multi = ParanoidHashMulti::new(&[Algo::Blake2b(48),Algo::Os(OsAlgorithm::SHA1)]);
+    let from_file = multi.read("tests/fixtures/report/alpha.txt").unwrap();
+    assert_eq!(from_file,multi.read_bytes(b"alpha\n"));
+}
+
+#[test]
+#[should_panic]
+fn empty_list_panics(){
+    ParanoidHashMulti::new(&[]);
+}
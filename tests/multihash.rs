@@ -0,0 +1,68 @@
+#![cfg(feature = "multihash")]
+
+use paranoid_hash::{Algo, HashDigest, MultihashError, OsAlgorithm, ParanoidHash};
+
+// sha2-256("hello world"), the usual example for the multihash format
+const SHA256_MULTIHASH: &str = "1220b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+const SHA256_MULTIBASE: &str = "bciqlstjhxgju2pqiuuxffv62pwv7vree57rxuu4a52iir55m4lx432i";
+// blake2b-256("hello world"), code 0xb220 as a varint is a0 e4 02
+const BLAKE2B_256_MULTIHASH: &str = "a0e40220256c83b297114d201b30179f3f0ef0cace9783622da5974326b436178aeef610";
+// sha1("hello world")
+const SHA1_MULTIHASH: &str = "11142aae6c35c94fcfb415dbe95f408b9ce91ee846ed";
+
+#[test]
+fn known_multihashes(){
+    let (blake2b,sha256) = ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes_digests(b"hello world").unwrap();
+    assert_eq!(hex::encode(sha256.to_multihash().unwrap()),SHA256_MULTIHASH);
+    assert_eq!(sha256.to_multibase().unwrap(),SHA256_MULTIBASE);
+    assert_eq!(hex::encode(blake2b.to_multihash().unwrap()),BLAKE2B_256_MULTIHASH);
+
+    let (_,sha1) = ParanoidHash::new_allowing_weak(32,OsAlgorithm::SHA1).read_bytes_digests(b"hello world").unwrap();
+    assert_eq!(hex::encode(sha1.to_multihash().unwrap()),SHA1_MULTIHASH);
+}
+
+#[test]
+fn blake2b_512_code(){
+    let (blake2b,sha512) = ParanoidHash::default().read_bytes_digests(b"").unwrap();
+    assert_eq!(&blake2b.to_multihash().unwrap()[..4],&[0xc0,0xe4,0x02,0x40]);
+    assert_eq!(&sha512.to_multihash().unwrap()[..2],&[0x13,0x40]);
+}
+
+#[test]
+fn decode_for_verification(){
+    let digest = HashDigest::from_multihash(&hex::decode(SHA256_MULTIHASH).unwrap()).unwrap();
+    assert_eq!(digest.algorithm(),&Algo::Os(OsAlgorithm::SHA256));
+    assert_eq!(digest,ParanoidHash::new(64,OsAlgorithm::SHA256).read_bytes_digests(b"hello world").unwrap().1);
+
+    let digest = HashDigest::from_multihash(&hex::decode(BLAKE2B_256_MULTIHASH).unwrap()).unwrap();
+    assert_eq!(digest.algorithm(),&Algo::Blake2b(32));
+
+    assert_eq!(HashDigest::from_multibase(SHA256_MULTIBASE).unwrap(),HashDigest::from_multihash(&hex::decode(SHA256_MULTIHASH).unwrap()).unwrap());
+    assert_eq!(HashDigest::from_multibase(SHA256_MULTIBASE.to_uppercase()).unwrap().algorithm(),&Algo::Os(OsAlgorithm::SHA256));
+}
+
+#[test]
+fn round_trip_every_algorithm(){
+    for (size,os) in [(1,OsAlgorithm::SHA1),#[cfg(feature = "insecure-md5")] (20,OsAlgorithm::MD5),(48,OsAlgorithm::SHA256),(64,OsAlgorithm::SHA512),(32,OsAlgorithm::SHA512_256),(28,OsAlgorithm::SHA224)] {
+        let (blake2b,digest) = ParanoidHash::new_allowing_weak(size,os).read_bytes_digests(b"round trip").unwrap();
+        for digest in [blake2b,digest] {
+            assert_eq!(HashDigest::from_multihash(&digest.to_multihash().unwrap()).unwrap(),digest);
+            assert_eq!(HashDigest::from_multibase(digest.to_multibase().unwrap()).unwrap(),digest);
+        }
+    }
+}
+
+#[test]
+fn decode_errors(){
+    // sha3-256 is registered but not supported
+    let mut sha3 = vec![0x16,0x20];
+    sha3.extend_from_slice(&[0u8;32]);
+    assert_eq!(HashDigest::from_multihash(&sha3),Err(MultihashError::UnknownCode(0x16)));
+
+    let mut truncated = hex::decode(SHA256_MULTIHASH).unwrap();
+    truncated.pop();
+    assert_eq!(HashDigest::from_multihash(&truncated),Err(MultihashError::InvalidLength));
+    assert_eq!(HashDigest::from_multihash(&[0x80]),Err(MultihashError::InvalidVarint));
+    assert_eq!(HashDigest::from_multibase("zQm"),Err(MultihashError::UnsupportedMultibase('z')));
+    assert_eq!(HashDigest::from_multibase("b1"),Err(MultihashError::InvalidEncoding));
+}
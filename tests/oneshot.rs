@@ -0,0 +1,22 @@
+use paranoid_hash::{FileError, OsAlgorithm, ParanoidHash};
+
+const FILE: &str = "examples/example_file.txt";
+
+#[test]
+fn matches_struct_api(){
+    for (size,os_hash) in [(64,OsAlgorithm::SHA512),(32,OsAlgorithm::SHA256),(28,OsAlgorithm::SHA224),(1,OsAlgorithm::SHA512_256)] {
+        let context = ParanoidHash::new(size,os_hash.clone());
+        assert_eq!(paranoid_hash::hash_file(FILE,size,os_hash.clone()).unwrap(),context.read(FILE).unwrap());
+        assert_eq!(paranoid_hash::hash_bytes(b"one-off",size,os_hash).unwrap(),context.read_bytes(b"one-off"));
+    }
+    assert_eq!(paranoid_hash::hash_file_default(FILE).unwrap(),ParanoidHash::default().read(FILE).unwrap());
+    assert_eq!(paranoid_hash::hash_bytes("",64,OsAlgorithm::SHA512).unwrap(),ParanoidHash::default().read_bytes(b""));
+}
+
+#[test]
+fn errors(){
+    assert_eq!(paranoid_hash::hash_file(FILE,0,OsAlgorithm::SHA256),Err(FileError::InvalidDigestSize(0)));
+    assert_eq!(paranoid_hash::hash_bytes(b"abc",65,OsAlgorithm::SHA256),Err(FileError::InvalidDigestSize(65)));
+    assert_eq!(paranoid_hash::hash_bytes(b"abc",20,OsAlgorithm::SHA1),Err(FileError::WeakAlgorithm(OsAlgorithm::SHA1)));
+    assert_eq!(paranoid_hash::hash_file_default("examples/missing.txt"),Err(FileError::FileNotFound));
+}
@@ -0,0 +1,15 @@
+#![cfg(feature = "fallback")]
+
+// The failure path itself is tested inside the crate, since the failing backend is only compiled into its own tests
+
+use paranoid_hash::ParanoidHash;
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+
+#[test]
+fn fallback_unused_when_os_works(){
+    let context = ParanoidHash::default().os_fallback(true);
+    let (digests,stats) = context.read_with_stats(FILE).unwrap();
+    assert_eq!(digests,ParanoidHash::default().read(FILE).unwrap());
+    assert!(!stats.os_fallback);
+}
@@ -0,0 +1,56 @@
+use std::thread;
+
+use paranoid_hash::{OsAlgorithm, ParanoidHash};
+
+#[test]
+fn sha256_is_available(){
+    assert!(OsAlgorithm::SHA256.is_available());
+    assert!(ParanoidHash::supported_os_algorithms().contains(&OsAlgorithm::SHA256));
+}
+
+#[test]
+fn pure_rust_algorithms_are_available(){
+    assert!(OsAlgorithm::SHA224.is_available());
+    assert!(OsAlgorithm::SHA512_256.is_available());
+}
+
+#[test]
+fn supported_matches_is_available(){
+    let all = [
+        OsAlgorithm::SHA1,OsAlgorithm::SHA224,OsAlgorithm::SHA256,OsAlgorithm::SHA512,OsAlgorithm::SHA512_256,
+        #[cfg(feature = "insecure-md5")]
+        OsAlgorithm::MD5,
+    ];
+    let expected: Vec<OsAlgorithm> = all.iter().filter(|os_hash| os_hash.is_available()).cloned().collect();
+    assert_eq!(ParanoidHash::supported_os_algorithms(),expected);
+}
+
+#[test]
+fn available_algorithms_hash(){
+    for os_hash in ParanoidHash::supported_os_algorithms() {
+        assert!(ParanoidHash::new_allowing_weak(64,os_hash).try_read_bytes(b"abc").is_ok());
+    }
+}
+
+#[test]
+fn result_is_cached(){
+    for _ in 0..10 {
+        assert!(OsAlgorithm::SHA512.is_available());
+    }
+    ParanoidHash::supported_os_algorithms();
+    assert_eq!(OsAlgorithm::SHA512.probe_count(),1);
+}
+
+#[test]
+fn probed_once_across_threads(){
+    let threads: Vec<_> = (0..8).map(|_| thread::spawn(|| OsAlgorithm::SHA1.is_available())).collect();
+    let results: Vec<bool> = threads.into_iter().map(|thread| thread.join().unwrap()).collect();
+    assert!(results.iter().all(|available| *available == results[0]));
+    assert_eq!(OsAlgorithm::SHA1.probe_count(),1);
+}
+
+#[test]
+fn require_available(){
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    assert_eq!(context.clone().require_available_os_algorithm().unwrap(),context);
+}
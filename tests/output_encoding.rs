@@ -0,0 +1,54 @@
+use paranoid_hash::{OsAlgorithm, OutputEncoding, ParanoidHash, VerifyPolicy};
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+const ENCODINGS: [OutputEncoding;3] = [OutputEncoding::HexUpper,OutputEncoding::HexLower,OutputEncoding::Base32];
+
+#[test]
+fn rfc_4648_vectors(){
+    let vectors = [("",""),("f","MY======"),("fo","MZXQ===="),("foo","MZXW6==="),("foob","MZXW6YQ="),("fooba","MZXW6YTB"),("foobar","MZXW6YTBOI======")];
+    for (input,output) in vectors.iter() {
+        assert_eq!(OutputEncoding::Base32.encode(input),*output);
+        assert_eq!(OutputEncoding::Base32.decode(output).unwrap(),input.as_bytes());
+        assert_eq!(OutputEncoding::Base32.decode(output.to_lowercase().trim_end_matches('=')).unwrap(),input.as_bytes());
+    }
+    assert_eq!(OutputEncoding::Base32.decode("MZXW6YQ1"),None);
+    assert_eq!(OutputEncoding::Base32.decode("M"),None);
+    assert_eq!(OutputEncoding::Base32.decode("MZ"),None);
+}
+
+#[test]
+fn round_trip_to_identical_bytes(){
+    let (blake2b,sha256) = ParanoidHash::new(64,OsAlgorithm::SHA256).read_digests(FILE).unwrap();
+
+    for encoding in ENCODINGS.iter() {
+        let context = ParanoidHash::new(64,OsAlgorithm::SHA256).output_encoding(*encoding);
+        assert_eq!(context.return_output_encoding(),*encoding);
+
+        let from_file = context.read(FILE).unwrap();
+        assert_eq!(context.read_bytes(b"alpha\n"),from_file);
+        assert_eq!(context.read_str("alpha\n"),from_file);
+        assert_eq!(context.read_using_std(FILE).unwrap(),from_file);
+        assert_eq!(context.read_parallel(FILE).unwrap(),from_file);
+
+        assert_eq!(encoding.decode(&from_file.0).unwrap(),blake2b.as_bytes());
+        assert_eq!(encoding.decode(&from_file.1).unwrap(),sha256.as_bytes());
+        assert_eq!(OutputEncoding::decode_any(&from_file.0).unwrap(),blake2b.as_bytes());
+        assert_eq!(OutputEncoding::decode_any(&from_file.1).unwrap(),sha256.as_bytes());
+
+        // Comparing two digests in the same encoding
+        assert!(ParanoidHash::compare_hash(&from_file.1,&context.read_bytes(b"alpha\n").1));
+        assert!(!ParanoidHash::compare_hash(&from_file.1,&context.read_bytes(b"beta\n").1));
+    }
+}
+
+#[test]
+fn verification_accepts_any_encoding(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA512);
+    for output in ENCODINGS.iter() {
+        let (blake2b,sha512) = context.clone().output_encoding(*output).read(FILE).unwrap();
+        for verifier in ENCODINGS.iter() {
+            let verification = context.clone().output_encoding(*verifier).verify_file(FILE,Some(&blake2b),Some(&sha512),VerifyPolicy::Both).unwrap();
+            assert!(verification.passed);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::io::Write;
+
+use paranoid_hash::{OsAlgorithm, OutputEncoding, ParanoidHash};
+
+const BLAKE2B_512_ABC: &str = "BA80A53F981C4D0D6A2797B69F12F6E94C212F14685AC4B74B12BB6FDBFFA2D17D87C5392AAB792DC252D5DE4533CC9518D38AA8DBF1925AB92386EDD4009923";
+const SHA512_ABC: &str = "DDAF35A193617ABACC417349AE20413112E6FA4E89A97EA20A9EEEE64B55D39A2192992A274FC1A836BA3C23A3FEEBBD454D4423643CE80E2A9AC94FA54CA49F";
+
+#[test]
+fn display_default(){
+    let output = ParanoidHash::default().read_bytes_output(b"abc").unwrap();
+    assert_eq!(format!("{}",output),format!("BLAKE2b-512: {}\nSHA-512: {}",BLAKE2B_512_ABC,SHA512_ABC));
+}
+
+#[test]
+fn display_names(){
+    for (os_hash, name) in [(OsAlgorithm::SHA1,"SHA-1"),(OsAlgorithm::SHA224,"SHA-224"),(OsAlgorithm::SHA256,"SHA-256"),(OsAlgorithm::SHA512_256,"SHA-512/256")] {
+        let output = ParanoidHash::new_allowing_weak(48,os_hash).read_bytes_output(b"abc").unwrap();
+        let text = output.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("BLAKE2b-384: "));
+        assert!(lines[1].starts_with(&format!("{}: ",name)));
+    }
+}
+
+#[test]
+fn matches_read(){
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"printed").unwrap();
+    for encoding in [OutputEncoding::HexUpper,OutputEncoding::HexLower,OutputEncoding::Base32] {
+        let context = ParanoidHash::default().output_encoding(encoding);
+        let (blake2b, os) = context.read(file.path()).unwrap();
+        let output = context.read_output(file.path()).unwrap();
+        assert_eq!(output.format_compact(),format!("{} {}",blake2b,os));
+        assert_eq!(output.to_string(),format!("BLAKE2b-512: {}\nSHA-512: {}",blake2b,os));
+    }
+}
+
+#[test]
+fn gnu_lines_follow_hex_case(){
+    let upper = ParanoidHash::default().read_bytes_output(b"abc").unwrap();
+    assert_eq!(upper.format_gnu("abc.txt"),format!("{}  abc.txt\n{}  abc.txt\n",BLAKE2B_512_ABC,SHA512_ABC));
+
+    let lower = ParanoidHash::default().output_encoding(OutputEncoding::HexLower).read_bytes_output(b"abc").unwrap();
+    assert_eq!(lower.format_gnu("abc.txt"),format!("{}  abc.txt\n{}  abc.txt\n",BLAKE2B_512_ABC.to_lowercase(),SHA512_ABC.to_lowercase()));
+
+    // Checksum files are always hexadecimal
+    let base32 = ParanoidHash::default().output_encoding(OutputEncoding::Base32).read_bytes_output(b"abc").unwrap();
+    assert_eq!(base32.format_gnu("abc.txt"),upper.format_gnu("abc.txt"));
+}
+
+#[test]
+fn gnu_lines_can_be_verified(){
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("data.txt"),"data").unwrap();
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256).output_encoding(OutputEncoding::HexLower);
+    let output = context.read_output(dir.path().join("data.txt")).unwrap();
+    // A checksum file for `sha256sum -c` holds only the Operating System line
+    let sha256_line = output.format_gnu("data.txt").lines().nth(1).unwrap().to_string();
+    std::fs::write(dir.path().join("SUMS"),sha256_line + "\n").unwrap();
+    let outcome = context.verify_against_checksum_file(dir.path().join("data.txt"),dir.path().join("SUMS")).unwrap();
+    assert_eq!(outcome,paranoid_hash::VerifyOutcome::Match);
+}
+
+#[test]
+fn tagged_lines(){
+    let output = ParanoidHash::default().read_bytes_output(b"abc").unwrap();
+    assert_eq!(output.format_tagged("abc.txt"),format!("BLAKE2b (abc.txt) = {}\nSHA512 (abc.txt) = {}\n",BLAKE2B_512_ABC,SHA512_ABC));
+
+    let tags = [(OsAlgorithm::SHA1,"SHA1"),(OsAlgorithm::SHA224,"SHA224"),(OsAlgorithm::SHA256,"SHA256"),(OsAlgorithm::SHA512_256,"SHA512/256")];
+    for (os_hash, tag) in tags {
+        let output = ParanoidHash::new_allowing_weak(32,os_hash).output_encoding(OutputEncoding::HexLower).read_bytes_output(b"abc").unwrap();
+        let text = output.format_tagged("abc.txt");
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0],format!("BLAKE2b-256 (abc.txt) = {}",output.blake2b.to_hex_lower()));
+        assert_eq!(lines[1],format!("{} (abc.txt) = {}",tag,output.os.to_hex_lower()));
+    }
+}
+
+#[test]
+fn names_are_escaped(){
+    let output = ParanoidHash::new(1,OsAlgorithm::SHA224).read_bytes_output(b"abc").unwrap();
+    assert_eq!(output.format_gnu("a\nb.txt"),"\\6B  a\\nb.txt\n\\23097D223405D8228642A477BDA255B32AADBCE4BDA0B3F7E36C9DA7  a\\nb.txt\n");
+    assert_eq!(output.format_tagged("a\\b.txt"),"\\BLAKE2b-8 (a\\\\b.txt) = 6B\n\\SHA224 (a\\\\b.txt) = 23097D223405D8228642A477BDA255B32AADBCE4BDA0B3F7E36C9DA7\n");
+}
+
+#[test]
+fn empty_input_is_rejected_when_configured(){
+    assert!(ParanoidHash::default().reject_empty_input(true).read_bytes_output(b"").is_err());
+    assert!(ParanoidHash::default().read_output("does/not/exist").is_err());
+}
@@ -0,0 +1,19 @@
+use paranoid_hash::{OsAlgorithm, ParanoidHash};
+
+#[test]
+fn parallel_matches_sequential(){
+    let data: Vec<u8> = (0..3_000_000u32).map(|i| (i % 253) as u8).collect();
+
+    for os_hash in [OsAlgorithm::SHA1,OsAlgorithm::SHA256,OsAlgorithm::SHA512] {
+        let context = ParanoidHash::new_allowing_weak(48,os_hash);
+        assert_eq!(context.read_bytes_parallel(&data),context.read_bytes(&data));
+        assert_eq!(context.read_bytes_parallel(b""),context.read_bytes(b""));
+    }
+}
+
+#[test]
+fn read_parallel_matches_read(){
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_parallel("tests/fixtures/report/alpha.txt").unwrap(),context.read("tests/fixtures/report/alpha.txt").unwrap());
+    assert!(context.read_parallel("tests/fixtures/report/missing.txt").is_err());
+}
@@ -0,0 +1,61 @@
+use paranoid_hash::{FileError,OsAlgorithm,ParanoidHash};
+
+// A small xorshift generator so the chunkings are random but repeatable
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        return self.0
+    }
+    fn below(&mut self, n: usize) -> usize {
+        return (self.next() % n as u64) as usize
+    }
+}
+
+#[test]
+fn empty(){
+    let context = ParanoidHash::default();
+    let expected = context.read_bytes(b"");
+    assert_eq!(context.read_chunks(Vec::<Vec<u8>>::new()),expected);
+    assert_eq!(context.read_chunks(vec![Vec::<u8>::new(); 3]),expected);
+    assert_eq!(context.try_read_chunks(Vec::<&[u8]>::new()).unwrap(),expected);
+}
+
+#[test]
+fn reject_empty_input(){
+    let context = ParanoidHash::default().reject_empty_input(true);
+    let empty: [&[u8]; 2] = [b"",b""];
+    assert_eq!(context.try_read_chunks(empty),Err(FileError::EmptyFile));
+    assert_eq!(context.try_read_chunks(["","a"]).unwrap(),context.read_bytes(b"a"));
+}
+
+#[test]
+fn max_file_size(){
+    let context = ParanoidHash::default().max_file_size(Some(4));
+    assert!(context.try_read_chunks(["ab","cd"]).is_ok());
+    assert_eq!(context.try_read_chunks(["ab","cd","e"]),Err(FileError::FileTooLarge { size: 5, limit: 4 }));
+}
+
+#[test]
+fn random_chunkings(){
+    let mut rng = XorShift(0x9E3779B97F4A7C15);
+    let context = ParanoidHash::new(48,OsAlgorithm::SHA512);
+
+    for _ in 0..200 {
+        let data: Vec<u8> = (0..rng.below(3000)).map(|_| rng.next() as u8).collect();
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        let mut rest = &data[..];
+        while !rest.is_empty() || rng.below(4) == 0 {
+            let (chunk, tail) = rest.split_at(rng.below(rest.len() + 1));
+            chunks.push(chunk.to_vec());
+            rest = tail;
+            if rest.is_empty() && rng.below(2) == 0 {
+                break
+            }
+        }
+        assert_eq!(context.read_chunks(&chunks),context.read_bytes(&data));
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs;
+
+use paranoid_hash::{FileError, OsAlgorithm, ParanoidHash};
+
+#[test]
+fn equals_concatenated_file(){
+    let dir = tempfile::tempdir().unwrap();
+    let parts: Vec<(&str,Vec<u8>)> = vec![
+        ("part1",vec![1u8; 3000]),
+        ("part2",Vec::new()),
+        ("part3",b"the last part".to_vec()),
+    ];
+    let mut joined = Vec::new();
+    for (name,contents) in parts.iter() {
+        fs::write(dir.path().join(name),contents).unwrap();
+        joined.extend_from_slice(contents);
+    }
+    fs::write(dir.path().join("joined"),&joined).unwrap();
+
+    let paths: Vec<_> = parts.iter().map(|(name,_)| dir.path().join(name)).collect();
+    for context in [ParanoidHash::default(),ParanoidHash::new(32,OsAlgorithm::SHA256).window_size(1024)] {
+        assert_eq!(context.read_concat(&paths).unwrap(),context.read(dir.path().join("joined")).unwrap());
+    }
+}
+
+#[test]
+fn order_matters(){
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a"),"first").unwrap();
+    fs::write(dir.path().join("b"),"second").unwrap();
+
+    let context = ParanoidHash::default();
+    let forward = context.read_concat(&[dir.path().join("a"),dir.path().join("b")]).unwrap();
+    let backward = context.read_concat(&[dir.path().join("b"),dir.path().join("a")]).unwrap();
+    assert_ne!(forward,backward);
+}
+
+#[test]
+fn missing_part_is_identified(){
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("part1"),"data").unwrap();
+    let missing = dir.path().join("part2");
+
+    let result = ParanoidHash::default().read_concat(&[dir.path().join("part1"),missing.clone()]);
+    assert_eq!(result,Err(FileError::AtPath(missing,Box::new(FileError::FileNotFound))));
+}
+
+#[test]
+fn empty_parts_and_reject_empty(){
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("empty"),"").unwrap();
+
+    let context = ParanoidHash::default().reject_empty_input(true);
+    assert_eq!(context.read_concat(&[dir.path().join("empty")]),Err(FileError::EmptyFile));
+    assert_eq!(ParanoidHash::default().read_concat(&[dir.path().join("empty")]).unwrap(),ParanoidHash::default().read_str(""));
+}
@@ -0,0 +1,70 @@
+use std::fs;
+
+use paranoid_hash::{FileError, ParanoidHash};
+
+const FIXTURE: &str = "tests/fixtures/dir_digest";
+
+// Computed independently from the documented framing over
+// B.txt, a-b.txt, a/b.txt, a/c/empty, z.txt (in that order)
+const PINNED_BLAKE2B: &str = "2FCE37483ADD75A7C1DD9FF992462475DE98C65BD253C49E76CD81CCE6EE93748D6EEA436B9484FC1ACD02CCC63A003230B5FE7468B52E3AD3CEFABFBEB113CC";
+const PINNED_SHA512: &str = "40168E15F50A0B099B964D25BE25A9FEEEB69626325CCA44F393245564CE26079323E8DFF3442BDBAAE5F64D2373B17B46CF262BDFC595C1915DF01737491A0A";
+
+#[test]
+fn pinned_fixture_digest(){
+    let (blake2b,sha512) = ParanoidHash::default().read_dir(FIXTURE).unwrap();
+    assert_eq!(blake2b,PINNED_BLAKE2B);
+    assert_eq!(sha512,PINNED_SHA512);
+}
+
+#[test]
+fn windowed_reads_give_the_same_digest(){
+    let context = ParanoidHash::default().window_size(3);
+    assert_eq!(context.read_dir(FIXTURE).unwrap(),(PINNED_BLAKE2B.to_string(),PINNED_SHA512.to_string()));
+}
+
+#[test]
+fn renaming_a_file_changes_the_digest(){
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a"),"data").unwrap();
+    let context = ParanoidHash::default();
+    let before = context.read_dir(dir.path()).unwrap();
+
+    fs::rename(dir.path().join("a"),dir.path().join("b")).unwrap();
+    assert_ne!(context.read_dir(dir.path()).unwrap(),before);
+}
+
+#[test]
+fn moving_bytes_between_files_changes_the_digest(){
+    let first = tempfile::tempdir().unwrap();
+    fs::write(first.path().join("a"),"ab").unwrap();
+    fs::write(first.path().join("b"),"c").unwrap();
+
+    let second = tempfile::tempdir().unwrap();
+    fs::write(second.path().join("a"),"a").unwrap();
+    fs::write(second.path().join("b"),"bc").unwrap();
+
+    let context = ParanoidHash::default();
+    assert_ne!(context.read_dir(first.path()).unwrap(),context.read_dir(second.path()).unwrap());
+}
+
+#[test]
+fn empty_and_missing_directories(){
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(ParanoidHash::default().read_dir(dir.path()).unwrap(),ParanoidHash::default().read_str(""));
+    assert_eq!(ParanoidHash::default().reject_empty_input(true).read_dir(dir.path()),Err(FileError::EmptyFile));
+    assert_eq!(ParanoidHash::default().read_dir(dir.path().join("missing")),Err(FileError::FileNotFound));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn non_utf8_names_are_rejected(){
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(OsStr::from_bytes(b"invalid\xFF"));
+    fs::write(&path,"data").unwrap();
+
+    let result = ParanoidHash::default().read_dir(dir.path());
+    assert_eq!(result,Err(FileError::AtPath(path,Box::new(FileError::Io(std::io::ErrorKind::InvalidData)))));
+}
@@ -0,0 +1,44 @@
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use paranoid_hash::{OsAlgorithm, ParanoidHash};
+
+const FILE: &str = "examples/example_file.txt";
+
+#[test]
+fn pre_opened_handle_matches_read(){
+    for context in [ParanoidHash::default(),ParanoidHash::new(32,OsAlgorithm::SHA256)] {
+        let mut file = fs::File::open(FILE).unwrap();
+        assert_eq!(context.read_file(&mut file).unwrap(),context.read(FILE).unwrap());
+
+        // The cursor is left at the end
+        let mut rest = Vec::new();
+        assert_eq!(file.read_to_end(&mut rest).unwrap(),0);
+    }
+}
+
+#[test]
+fn hashes_from_the_current_position(){
+    let context = ParanoidHash::default();
+    let contents = fs::read(FILE).unwrap();
+
+    let mut file = fs::File::open(FILE).unwrap();
+    file.seek(SeekFrom::Start(4)).unwrap();
+    assert_eq!(context.read_file(&mut file).unwrap(),context.read_bytes(&contents[4..]));
+
+    // Hashing again from the start ignores the cursor
+    assert_eq!(context.read_file_from_start(&mut file).unwrap(),context.read(FILE).unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn unlinked_file(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("unlinked");
+    let mut file = fs::OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path).unwrap();
+    file.write_all(b"still here").unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_file_from_start(&mut file).unwrap(),context.read_str("still here"));
+}
@@ -0,0 +1,50 @@
+use paranoid_hash::{FileError, ParanoidHash};
+use std::path::PathBuf;
+
+#[test]
+fn partial_failure_keeps_order(){
+    let context = ParanoidHash::default();
+    let paths = ["tests/fixtures/report/nested/beta.txt","tests/fixtures/report/missing.txt","tests/fixtures/report/alpha.txt"];
+    let results = context.read_files(&paths);
+
+    assert_eq!(results.len(),3);
+    for ((path,_),expected) in results.iter().zip(paths.iter()) {
+        assert_eq!(path,&PathBuf::from(expected));
+    }
+    assert_eq!(results[0].1,context.read(paths[0]));
+    assert_eq!(results[1].1,Err(FileError::FileNotFound));
+    assert_eq!(results[2].1,context.read(paths[2]));
+}
+
+#[test]
+fn windowed_files_share_a_buffer(){
+    // Forces every file through the windowed path
+    let context = ParanoidHash::default().window_size(3);
+    let paths = ["tests/fixtures/report/alpha.txt","tests/fixtures/report/nested/beta.txt","tests/fixtures/report/alpha.txt"];
+    let results = context.read_files(&paths);
+
+    let expected = ParanoidHash::default();
+    for (path,result) in results {
+        assert_eq!(result,expected.read(path));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn permission_denied(){
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let locked = dir.path().join("locked.txt");
+    std::fs::write(&locked,b"secret").unwrap();
+    std::fs::set_permissions(&locked,std::fs::Permissions::from_mode(0o000)).unwrap();
+
+    // Root can read the file anyway
+    if std::fs::read(&locked).is_ok() {
+        return
+    }
+
+    let results = ParanoidHash::default().read_files(&[locked.as_path(),std::path::Path::new("tests/fixtures/report/alpha.txt")]);
+    assert_eq!(results[0].1,Err(FileError::Io(std::io::ErrorKind::PermissionDenied)));
+    assert!(results[1].1.is_ok());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "glob")]
+
+use std::fs;
+use std::path::PathBuf;
+
+use paranoid_hash::{GlobError,ParanoidHash};
+
+fn releases() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for path in ["releases/v1/app-1.0.tar.gz","releases/v1/app-1.0.zip","releases/v2/app-2.0.tar.gz","releases/v2/beta/app-2.1b.tar.gz","releases/app-0.9.tar.gz","releases/notes.txt","releases/v3.tar.gz/readme.txt"] {
+        let path = dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path,path.file_name().unwrap().to_str().unwrap()).unwrap();
+    }
+    return dir
+}
+
+fn matched(dir: &tempfile::TempDir, pattern: &str) -> Vec<String> {
+    let results = ParanoidHash::default().read_glob(&format!("{}/{}",dir.path().display(),pattern)).unwrap();
+    for (path,result) in results.iter() {
+        assert_eq!(result,&ParanoidHash::default().read(path));
+    }
+    return results.into_iter().map(|(path,_)| path.strip_prefix(dir.path()).unwrap().to_str().unwrap().replace('\\',"/")).collect()
+}
+
+#[test]
+fn recursive_wildcard(){
+    let dir = releases();
+    // The `v3.tar.gz` directory is skipped
+    assert_eq!(matched(&dir,"releases/**/*.tar.gz"),vec!["releases/app-0.9.tar.gz","releases/v1/app-1.0.tar.gz","releases/v2/app-2.0.tar.gz","releases/v2/beta/app-2.1b.tar.gz"]);
+}
+
+#[test]
+fn single_component_wildcard(){
+    let dir = releases();
+    assert_eq!(matched(&dir,"releases/*/*.tar.gz"),vec!["releases/v1/app-1.0.tar.gz","releases/v2/app-2.0.tar.gz"]);
+    assert_eq!(matched(&dir,"releases/*"),vec!["releases/app-0.9.tar.gz","releases/notes.txt"]);
+    assert_eq!(matched(&dir,"releases/v?/app-*"),vec!["releases/v1/app-1.0.tar.gz","releases/v1/app-1.0.zip","releases/v2/app-2.0.tar.gz"]);
+}
+
+#[test]
+fn character_classes(){
+    let dir = releases();
+    assert_eq!(matched(&dir,"releases/v[12]/*.zip"),vec!["releases/v1/app-1.0.zip"]);
+    assert_eq!(matched(&dir,"releases/v[!1]/*.tar.gz"),vec!["releases/v2/app-2.0.tar.gz"]);
+    assert_eq!(matched(&dir,"releases/**/app-[0-1].*.tar.gz"),vec!["releases/app-0.9.tar.gz","releases/v1/app-1.0.tar.gz"]);
+}
+
+#[test]
+fn no_match(){
+    let dir = releases();
+    let pattern = format!("{}/releases/**/*.rpm",dir.path().display());
+    assert_eq!(ParanoidHash::default().read_glob(&pattern).unwrap(),Vec::<(PathBuf,_)>::new());
+    assert_eq!(ParanoidHash::default().require_glob_match(true).read_glob(&pattern),Err(GlobError::NoMatch));
+    assert_eq!(ParanoidHash::default().require_glob_match(true).read_glob(&format!("{}/releases/*.txt",dir.path().display())).unwrap().len(),1);
+}
+
+#[test]
+fn invalid_pattern(){
+    assert!(matches!(ParanoidHash::default().read_glob("releases/***/x"),Err(GlobError::InvalidPattern { .. })));
+    assert!(matches!(ParanoidHash::default().read_glob("releases/[a"),Err(GlobError::InvalidPattern { .. })));
+}
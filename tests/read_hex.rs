@@ -0,0 +1,26 @@
+use paranoid_hash::{FileError, HexError, ParanoidHash};
+
+#[test]
+fn hashes_decoded_bytes(){
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_hex("48656c6c6f").unwrap(),context.read_bytes(b"Hello"));
+    assert_eq!(context.read_hex("0x48656C6c6F").unwrap(),context.read_bytes(b"Hello"));
+    assert_eq!(context.read_hex("0X48656C6C6F").unwrap(),context.read_bytes(b"Hello"));
+    assert_ne!(context.read_hex("48656c6c6f").unwrap(),context.read_str("48656c6c6f"));
+    assert_eq!(context.read_hex("").unwrap(),context.read_bytes(b""));
+}
+
+#[test]
+fn invalid_input(){
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_hex("48656c6c6"),Err(HexError::OddLength));
+    assert_eq!(context.read_hex("0x4865zc"),Err(HexError::InvalidCharacter { character: 'z', index: 6 }));
+    assert_eq!(context.read_hex("48 65"),Err(HexError::InvalidCharacter { character: ' ', index: 2 }));
+    assert_eq!(context.read_hex("4é"),Err(HexError::InvalidCharacter { character: 'é', index: 1 }));
+}
+
+#[test]
+fn hashing_errors_are_kept(){
+    let context = ParanoidHash::default().reject_empty_input(true);
+    assert_eq!(context.read_hex("0x"),Err(HexError::Hashing(FileError::EmptyFile)));
+}
@@ -0,0 +1,53 @@
+use paranoid_hash::ParanoidHash;
+use std::io::Write;
+
+fn file_with(contents: &[u8]) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(contents).unwrap();
+    file.flush().unwrap();
+    return file
+}
+
+#[test]
+fn terminators_are_excluded(){
+    let context = ParanoidHash::default();
+    let expected = context.read_str_lines(["one","","three"]).unwrap();
+
+    for contents in [&b"one\n\nthree\n"[..],b"one\r\n\r\nthree\r\n",b"one\n\nthree",b"one\r\n\nthree"] {
+        let file = file_with(contents);
+        assert_eq!(context.read_lines(file.path()).unwrap(),expected);
+    }
+}
+
+#[test]
+fn lone_carriage_return_is_kept(){
+    let context = ParanoidHash::default();
+    let file = file_with(b"a\rb\nc\r");
+    assert_eq!(context.read_lines(file.path()).unwrap(),context.read_str_lines(["a\rb","c\r"]).unwrap());
+}
+
+#[test]
+fn empty_file_has_no_lines(){
+    let context = ParanoidHash::default().reject_empty_input(true);
+    let file = file_with(b"");
+    assert_eq!(context.read_lines(file.path()).unwrap(),Vec::new());
+
+    let file = file_with(b"\n");
+    assert_eq!(context.read_lines(file.path()).unwrap(),vec![ParanoidHash::default().read_str("")]);
+}
+
+#[test]
+fn long_line(){
+    let line = vec![b'x';3_000_000];
+    let mut contents = line.clone();
+    contents.extend_from_slice(b"\nshort");
+    let file = file_with(&contents);
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_lines(file.path()).unwrap(),vec![context.read_bytes(&line),context.read_str("short")]);
+}
+
+#[test]
+fn missing_file(){
+    assert!(ParanoidHash::default().read_lines("tests/fixtures/report/missing.txt").is_err());
+}
@@ -0,0 +1,69 @@
+use paranoid_hash::{FileError, OsAlgorithm, ParanoidHash};
+use std::fs;
+
+fn fixture(dir: &tempfile::TempDir) -> (std::path::PathBuf,Vec<u8>) {
+    let path = dir.path().join("blocks");
+    let data: Vec<u8> = (0..200_000u32).map(|i| (i % 241) as u8).collect();
+    fs::write(&path,&data).unwrap();
+    return (path,data)
+}
+
+#[test]
+fn each_range_matches_the_slice(){
+    let dir = tempfile::tempdir().unwrap();
+    let (path,data) = fixture(&dir);
+    let ranges = [150_000..200_000,0..4096,8192..8192,70_000..140_001];
+
+    for context in [ParanoidHash::default(),ParanoidHash::new(32,OsAlgorithm::SHA256)] {
+        let digests = context.read_ranges(&path,&ranges).unwrap();
+        assert_eq!(digests.len(),ranges.len());
+        for (range,digest) in ranges.iter().zip(digests.iter()) {
+            assert_eq!(*digest,context.read_bytes(&data[range.start as usize..range.end as usize]));
+        }
+    }
+}
+
+#[test]
+fn combined_matches_the_concatenation(){
+    let dir = tempfile::tempdir().unwrap();
+    let (path,data) = fixture(&dir);
+    let ranges = [100_000..150_000,10..20,0..5];
+
+    let mut joined = Vec::new();
+    for range in ranges.iter() {
+        joined.extend_from_slice(&data[range.start as usize..range.end as usize]);
+    }
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_ranges_combined(&path,&ranges).unwrap(),context.read_bytes(&joined));
+    assert_eq!(context.read_ranges_combined(&path,&[0..100_000,100_000..200_000]).unwrap(),context.read(&path).unwrap());
+}
+
+#[test]
+fn invalid_ranges_name_their_index(){
+    let dir = tempfile::tempdir().unwrap();
+    let (path,_) = fixture(&dir);
+    let context = ParanoidHash::default();
+
+    // Past the end of the file
+    assert_eq!(context.read_ranges(&path,&[0..10,199_990..200_001]),Err(FileError::InvalidRange(1)));
+    // Overlapping, in either order
+    assert_eq!(context.read_ranges(&path,&[0..10,50..60,5..15]),Err(FileError::InvalidRange(2)));
+    assert_eq!(context.read_ranges_combined(&path,&[100..200,300..400,0..150]),Err(FileError::InvalidRange(2)));
+    // Reversed
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = [0..5,20..10];
+    assert_eq!(context.read_ranges(&path,&reversed),Err(FileError::InvalidRange(1)));
+    // Touching ranges do not overlap
+    assert!(context.read_ranges(&path,&[10..20,0..10,20..30]).is_ok());
+}
+
+#[test]
+fn limits_apply_per_range_or_in_total(){
+    let dir = tempfile::tempdir().unwrap();
+    let (path,_) = fixture(&dir);
+    let context = ParanoidHash::default().max_file_size(Some(100)).reject_empty_input(true);
+
+    assert!(context.read_ranges(&path,&[0..100,100..200]).is_ok());
+    assert_eq!(context.read_ranges(&path,&[0..100,100..100]),Err(FileError::EmptyFile));
+    assert!(matches!(context.read_ranges_combined(&path,&[0..100,100..200]),Err(FileError::FileTooLarge { limit: 100, .. })));
+}
@@ -0,0 +1,63 @@
+use paranoid_hash::{ParanoidHash, ReadTuning};
+use std::io::Write;
+
+const HINTS: ReadTuning = ReadTuning { sequential: true, drop_cache: true };
+
+fn large_file() -> tempfile::NamedTempFile {
+    let data: Vec<u8> = (0..3 * 1024 * 1024u32).map(|i| (i % 251) as u8).collect();
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&data).unwrap();
+    file.flush().unwrap();
+    return file
+}
+
+#[test]
+fn default_gives_no_hints(){
+    assert_eq!(ParanoidHash::default().return_read_tuning(),ReadTuning { sequential: false, drop_cache: false });
+    assert_eq!(ParanoidHash::default().read_tuning(HINTS).return_read_tuning(),HINTS);
+}
+
+#[test]
+fn same_digests_with_std(){
+    let file = large_file();
+    let expected = ParanoidHash::default().read(file.path()).unwrap();
+
+    let context = ParanoidHash::default().read_tuning(HINTS);
+    assert_eq!(context.read_using_std(file.path()).unwrap(),expected);
+    // Again now that the pages may have been dropped
+    assert_eq!(context.read_using_std(file.path()).unwrap(),expected);
+}
+
+#[test]
+fn same_digests_in_windows(){
+    let file = large_file();
+    let expected = ParanoidHash::default().read(file.path()).unwrap();
+
+    for tuning in [HINTS, ReadTuning { sequential: true, drop_cache: false }, ReadTuning { sequential: false, drop_cache: true }] {
+        let context = ParanoidHash::default().window_size(64 * 1024).read_tuning(tuning);
+        assert_eq!(context.read(file.path()).unwrap(),expected);
+    }
+}
+
+// Advising a pipe fails, which is ignored
+#[cfg(unix)]
+#[test]
+fn hints_on_a_fifo_are_ignored(){
+    use std::process::Command;
+
+    let dir = tempfile::tempdir().unwrap();
+    let fifo = dir.path().join("pipe");
+    assert!(Command::new("mkfifo").arg(&fifo).status().unwrap().success());
+
+    let writer_path = fifo.clone();
+    let writer = std::thread::spawn(move || {
+        let mut pipe = std::fs::OpenOptions::new().write(true).open(writer_path).unwrap();
+        pipe.write_all(b"piped").unwrap();
+    });
+
+    let context = ParanoidHash::default().read_tuning(HINTS);
+    let digests = context.read(&fifo).unwrap();
+    writer.join().unwrap();
+
+    assert_eq!(digests,context.read_bytes(b"piped"));
+}
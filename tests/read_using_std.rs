@@ -0,0 +1,24 @@
+use paranoid_hash::{OsAlgorithm, ParanoidHash};
+use std::io::Write;
+
+#[test]
+fn streaming_matches_filebuffer(){
+    // Several megabytes, not a multiple of the internal buffer size
+    let data: Vec<u8> = (0..5_000_003u32).map(|i| (i.wrapping_mul(31) % 256) as u8).collect();
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&data).unwrap();
+    file.flush().unwrap();
+
+    for os_hash in [OsAlgorithm::SHA1,OsAlgorithm::SHA256,OsAlgorithm::SHA512] {
+        let context = ParanoidHash::new_allowing_weak(64,os_hash);
+        let streamed = context.read_using_std(file.path()).unwrap();
+        assert_eq!(streamed,context.read(file.path()).unwrap());
+        assert_eq!(streamed,context.read_bytes(&data));
+    }
+}
+
+#[test]
+fn missing_file(){
+    let context = ParanoidHash::default();
+    assert!(context.read_using_std("tests/fixtures/report/missing.txt").is_err());
+}
@@ -0,0 +1,70 @@
+use std::fs;
+use std::io::{Cursor, Read};
+
+use paranoid_hash::{FileError,ParanoidHash};
+
+const LIMIT: u64 = 64;
+
+fn file(length: usize) -> (tempfile::TempDir,Vec<u8>) {
+    let dir = tempfile::tempdir().unwrap();
+    let contents: Vec<u8> = (0..length).map(|i| (i % 251) as u8).collect();
+    fs::write(dir.path().join("file"),&contents).unwrap();
+    return (dir,contents)
+}
+
+#[test]
+fn shorter_than_limit(){
+    let (dir,contents) = file(LIMIT as usize - 1);
+    let context = ParanoidHash::default();
+    let (digests,hashed) = context.read_with_limit(dir.path().join("file"),LIMIT).unwrap();
+    assert_eq!(hashed,LIMIT - 1);
+    assert_eq!(digests,context.read_bytes(&contents));
+}
+
+#[test]
+fn equal_to_limit(){
+    let (dir,contents) = file(LIMIT as usize);
+    let context = ParanoidHash::default();
+    let (digests,hashed) = context.read_with_limit(dir.path().join("file"),LIMIT).unwrap();
+    assert_eq!(hashed,LIMIT);
+    assert_eq!(digests,context.read(dir.path().join("file")).unwrap());
+    assert_eq!(digests,context.read_bytes(&contents));
+}
+
+#[test]
+fn longer_than_limit(){
+    let (dir,contents) = file(10_000);
+    let context = ParanoidHash::default();
+    let (digests,hashed) = context.read_with_limit(dir.path().join("file"),LIMIT).unwrap();
+    assert_eq!(hashed,LIMIT);
+    assert_eq!(digests,context.read_bytes(&contents[..LIMIT as usize]));
+}
+
+#[test]
+fn limits_apply_to_hashed_bytes(){
+    let (dir,_) = file(10_000);
+    let path = dir.path().join("file");
+    assert!(ParanoidHash::default().max_file_size(Some(LIMIT)).read_with_limit(&path,LIMIT).is_ok());
+    assert_eq!(ParanoidHash::default().reject_empty_input(true).read_with_limit(&path,0),Err(FileError::EmptyFile));
+    assert_eq!(ParanoidHash::default().read_with_limit(dir.path().join("missing"),LIMIT),Err(FileError::FileNotFound));
+}
+
+#[test]
+fn reader(){
+    let contents: Vec<u8> = (0..200u8).collect();
+    let context = ParanoidHash::default();
+
+    let mut cursor = Cursor::new(&contents);
+    let (digests,hashed) = context.read_reader_with_limit(&mut cursor,LIMIT).unwrap();
+    assert_eq!(hashed,LIMIT);
+    assert_eq!(digests,context.read_bytes(&contents[..LIMIT as usize]));
+
+    // The rest of the reader is left unread
+    let mut rest = Vec::new();
+    cursor.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest,&contents[LIMIT as usize..]);
+
+    let (digests,hashed) = context.read_reader_with_limit(&contents[..10],LIMIT).unwrap();
+    assert_eq!(hashed,10);
+    assert_eq!(digests,context.read_bytes(&contents[..10]));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "serde")]
+
+use paranoid_hash::{FileError, HashReport, OsAlgorithm, ParanoidHash};
+use std::time::Duration;
+
+fn to_json(mut report: HashReport) -> String {
+    // Durations vary between runs
+    for entry in report.entries.iter_mut() {
+        entry.duration = Duration::from_micros(0);
+    }
+    let mut out: Vec<u8> = Vec::new();
+    report.to_json_writer(&mut out).unwrap();
+    return String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn report_dir_json(){
+    let context = ParanoidHash::new(16,OsAlgorithm::SHA256).canonicalize_paths(false);
+    let report = context.report_dir("tests/fixtures/report").unwrap();
+    assert_eq!(report.has_errors(),false);
+
+    let expected = concat!(
+        r#"{"format_version":1,"library_algorithm":"BLAKE2B","blake2b_digest_size":16,"entries":["#,
+        r#"{"path":"tests/fixtures/report/alpha.txt","resolved_path":"tests/fixtures/report/alpha.txt","canonical":false,"size":6,"blake2b":"08343DBF4CD1C2662F2F498F2DF531A7","os_algorithm":"SHA256","os_digest":"B6A98D9CE9A2D9149288FA3DF42D377C3E42737AFDCDAF714E33C0A100B51060","duration_us":0,"error":null},"#,
+        r#"{"path":"tests/fixtures/report/nested/beta.txt","resolved_path":"tests/fixtures/report/nested/beta.txt","canonical":false,"size":5,"blake2b":"CE0AC25E23FE3D5C56512C6054A1C399","os_algorithm":"SHA256","os_digest":"F2C82DECDD7181CF98945929A62598DB7E6B477E11F6E0EB0AE97020EFF151AD","duration_us":0,"error":null}"#,
+        r#"]}"#,
+    );
+    assert_eq!(to_json(report),expected);
+}
+
+#[test]
+fn report_files_keeps_errors(){
+    let context = ParanoidHash::new(16,OsAlgorithm::SHA256).canonicalize_paths(false);
+    let report = context.report_files(&["tests/fixtures/report/missing.txt","tests/fixtures/report/alpha.txt"]);
+    assert_eq!(report.has_errors(),true);
+    assert_eq!(report.entries.len(),2);
+    assert_eq!(report.entries[0].error,Some(FileError::FileNotFound));
+
+    let expected = concat!(
+        r#"{"format_version":1,"library_algorithm":"BLAKE2B","blake2b_digest_size":16,"entries":["#,
+        r#"{"path":"tests/fixtures/report/missing.txt","resolved_path":"tests/fixtures/report/missing.txt","canonical":false,"size":null,"blake2b":null,"os_algorithm":"SHA256","os_digest":null,"duration_us":0,"error":"file not found"},"#,
+        r#"{"path":"tests/fixtures/report/alpha.txt","resolved_path":"tests/fixtures/report/alpha.txt","canonical":false,"size":6,"blake2b":"08343DBF4CD1C2662F2F498F2DF531A7","os_algorithm":"SHA256","os_digest":"B6A98D9CE9A2D9149288FA3DF42D377C3E42737AFDCDAF714E33C0A100B51060","duration_us":0,"error":null}"#,
+        r#"]}"#,
+    );
+    assert_eq!(to_json(report),expected);
+}
+
+#[test]
+fn report_json_canonical_path(){
+    let report = ParanoidHash::default().report_files(&["tests/fixtures/report/nested/../alpha.txt"]);
+    let canonical = std::fs::canonicalize("tests/fixtures/report/alpha.txt").unwrap();
+    let json = to_json(report);
+    assert!(json.contains(&format!(r#""path":"tests/fixtures/report/nested/../alpha.txt","resolved_path":{},"canonical":true,"#,serde_json::to_string(&canonical.to_string_lossy()).unwrap())));
+}
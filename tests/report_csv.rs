@@ -0,0 +1,73 @@
+use std::fs;
+
+use paranoid_hash::ParanoidHash;
+
+fn csv(report: &paranoid_hash::HashReport, header: bool) -> String {
+    let mut csv = Vec::new();
+    report.to_csv_writer(&mut csv,header).unwrap();
+    return String::from_utf8(csv).unwrap()
+}
+
+#[test]
+fn comma_and_quote_are_escaped(){
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("inventory, \"final\".txt");
+    fs::write(&file,"contents").unwrap();
+
+    let context = ParanoidHash::default();
+    let (blake2b, sha512) = context.read(&file).unwrap();
+    let report = context.report_files(&[&file]);
+
+    let quoted = format!("\"{}\"",file.to_str().unwrap().replace('"',"\"\""));
+    assert!(quoted.ends_with("inventory, \"\"final\"\".txt\""));
+    assert_eq!(csv(&report,false),format!("{},8,64,{},SHA512,{},\r\n",quoted,blake2b,sha512));
+}
+
+#[test]
+fn header_is_optional(){
+    let report = ParanoidHash::default().report_files(&["examples/example_file.txt"]);
+    let with_header = csv(&report,true);
+    let without_header = csv(&report,false);
+    assert_eq!(with_header,format!("path,size,blake2b_digest_size,blake2b,os_algorithm,os_digest,error\r\n{}",without_header));
+    assert_eq!(without_header.matches("\r\n").count(),1);
+}
+
+#[test]
+fn errors_have_empty_digests(){
+    let report = ParanoidHash::new(32,paranoid_hash::OsAlgorithm::SHA256).report_files(&["does/not/exist"]);
+    assert_eq!(csv(&report,false),"does/not/exist,,32,,SHA256,,file not found\r\n");
+}
+
+#[test]
+fn rows_follow_the_report(){
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["a.txt","b.txt","c.txt"] {
+        fs::write(dir.path().join(name),name).unwrap();
+    }
+    let report = ParanoidHash::default().report_dir(dir.path()).unwrap();
+    let rows: Vec<String> = csv(&report,false).split_terminator("\r\n").map(String::from).collect();
+    assert_eq!(rows.len(),3);
+    for (row, entry) in rows.iter().zip(report.entries.iter()) {
+        assert!(row.starts_with(&format!("{},5,64,{},SHA512,",entry.path.display(),entry.blake2b.as_ref().unwrap())));
+    }
+}
+
+#[test]
+fn empty_report(){
+    let report = ParanoidHash::default().report_files::<&str>(&[]);
+    assert_eq!(csv(&report,false),"");
+    assert_eq!(csv(&report,true),"path,size,blake2b_digest_size,blake2b,os_algorithm,os_digest,error\r\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn newline_is_quoted(){
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("two\nlines.txt");
+    fs::write(&file,"contents").unwrap();
+
+    let report = ParanoidHash::default().report_files(&[&file]);
+    let csv = csv(&report,false);
+    assert!(csv.starts_with(&format!("\"{}\",8,64,",file.to_str().unwrap())));
+    assert!(csv.contains("two\nlines.txt\","));
+}
@@ -0,0 +1,146 @@
+use paranoid_hash::{FileError, OsAlgorithm, ParanoidHash, STATE_FORMAT_VERSION};
+
+// Crosses several 64 and 128 byte block boundaries
+fn data() -> Vec<u8> {
+    return (0..1000u32).map(|i| (i * 7 + 3) as u8).collect()
+}
+
+// Hashes `data`, saving the state after `offset` bytes and finishing in a fresh hasher
+fn resumed(context: &ParanoidHash, data: &[u8], offset: usize) -> (String,String) {
+    let mut hasher = context.hasher().unwrap();
+    hasher.update(&data[..offset]);
+    let saved = hasher.save_state();
+    drop(hasher);
+
+    let mut hasher = context.hasher().unwrap();
+    hasher.restore_state(&saved).unwrap();
+    assert_eq!(hasher.return_bytes_hashed(),offset as u64);
+    hasher.update(&data[offset..]);
+    return hasher.finalize().unwrap()
+}
+
+#[test]
+fn resumed_digests_match_uninterrupted(){
+    let data = data();
+    let contexts = [
+        ParanoidHash::default(),
+        ParanoidHash::new(32,OsAlgorithm::SHA256),
+        ParanoidHash::new(28,OsAlgorithm::SHA224),
+        ParanoidHash::new(1,OsAlgorithm::SHA512_256),
+    ];
+    for context in contexts.iter() {
+        let expected = context.read_bytes(&data);
+        for &offset in [0,1,63,64,65,127,128,129,256,999,1000].iter() {
+            assert_eq!(resumed(context,&data,offset),expected,"{:?} at {}",context.return_os_hash_algorithm(),offset);
+        }
+    }
+}
+
+// BLAKE2B digests of "abc" and of `data()` at digest sizes other than 64
+const BLAKE2B_VECTORS: [(usize, &str, &str); 5] = [
+    (1, "6B", "CE"),
+    (20, "384264F676F39536840523F284921CDC68B6846B", "7347A87D12C5B902EEFD08B8B5C14816494C2639"),
+    (32, "BDDD813C634239723171EF3FEE98579B94964E3BB1CB3E427262C8C068D52319", "D62B6C768CE1AFC8367E0498AB2F8E3F7C178C35B1429F14C4604B545D200F52"),
+    (48, "6F56A82C8E7EF526DFE182EB5212F7DB9DF1317E57815DBDA46083FC30F54EE6C66BA83BE64B302D7CBA6CE15BB556F4", "3910B84A1FE7F38F69F3CF3B0A217A0AC1A8865DD72E74A315D615E475039F6A7B7299BBFE070081F141F544620926B8"),
+    (63, "EB5324BB0B0F9CA27381F22F5E49604D7C341B77371FE5BF61FB643C8AB481C7555EF17C9B9E7C92F0DAAFFF6C0D748CAB97D2B267BF53F8225C173EA26F3E", "7BC6D409607EC3B05B8A40CBDEF27EFDC551A15E76371E7972AD6D13B15FA55872FAC007F5E478DE57123DF4001A368D3BD7A2AE3D92A0341BE36BFCEF10A9"),
+];
+
+#[test]
+fn blake2b_known_answers(){
+    let data = data();
+    for (size,abc,long) in BLAKE2B_VECTORS.iter() {
+        let context = ParanoidHash::new(*size,OsAlgorithm::SHA256);
+        let mut hasher = context.hasher().unwrap();
+        hasher.update(b"abc");
+        assert_eq!(hasher.finalize().unwrap().0,*abc,"size {}",size);
+
+        assert_eq!(resumed(&context,&data,129).0,*long,"size {}",size);
+    }
+
+    // The empty message and a single full block, which is only compressed when finalizing
+    let context = ParanoidHash::new(20,OsAlgorithm::SHA256);
+    assert_eq!(context.hasher().unwrap().finalize().unwrap().0,"3345524ABF6BBE1809449224B5972C41790B6CF2");
+    let mut hasher = ParanoidHash::new(32,OsAlgorithm::SHA256).hasher().unwrap();
+    hasher.update(&[0u8; 128]);
+    assert_eq!(hasher.finalize().unwrap().0,"378D0CAAAA3855F1B38693C1D6EF004FD118691C95C959D4EFA950D6D6FCF7C1");
+}
+
+#[test]
+fn uneven_updates(){
+    let context = ParanoidHash::default();
+    let data = data();
+    let mut hasher = context.hasher().unwrap();
+    for chunk in data.chunks(37) {
+        hasher.update(chunk);
+    }
+    assert_eq!(hasher.finalize().unwrap(),context.read_bytes(&data));
+}
+
+#[test]
+fn saved_more_than_once(){
+    let context = ParanoidHash::default();
+    let data = data();
+    let mut saved = context.hasher().unwrap().save_state();
+    for chunk in data.chunks(300) {
+        let mut hasher = context.hasher().unwrap();
+        hasher.restore_state(&saved).unwrap();
+        hasher.update(chunk);
+        saved = hasher.save_state();
+    }
+    let mut hasher = context.hasher().unwrap();
+    hasher.restore_state(&saved).unwrap();
+    assert_eq!(hasher.finalize().unwrap(),context.read_bytes(&data));
+}
+
+#[test]
+fn empty_input(){
+    let context = ParanoidHash::default();
+    assert_eq!(context.hasher().unwrap().finalize().unwrap(),context.read_bytes(b""));
+
+    let context = ParanoidHash::default().reject_empty_input(true);
+    assert_eq!(context.hasher().unwrap().finalize(),Err(FileError::EmptyFile));
+}
+
+#[test]
+fn version_byte(){
+    let context = ParanoidHash::default();
+    let mut saved = context.hasher().unwrap().save_state();
+    assert_eq!(saved[0],STATE_FORMAT_VERSION);
+
+    saved[0] = STATE_FORMAT_VERSION + 1;
+    let mut hasher = context.hasher().unwrap();
+    assert_eq!(hasher.restore_state(&saved),Err(FileError::UnsupportedStateVersion(STATE_FORMAT_VERSION + 1)));
+}
+
+#[test]
+fn different_configuration_is_rejected(){
+    let mut hasher = ParanoidHash::default().hasher().unwrap();
+    hasher.update(b"progress");
+    let saved = hasher.save_state();
+
+    let mut other = ParanoidHash::new(32,OsAlgorithm::SHA256).hasher().unwrap();
+    assert_eq!(other.restore_state(&saved),Err(FileError::InvalidHasherState));
+}
+
+#[test]
+fn malformed_state_is_rejected(){
+    let context = ParanoidHash::default();
+    let mut hasher = context.hasher().unwrap();
+    hasher.update(&data());
+    let saved = hasher.save_state();
+
+    let mut fresh = context.hasher().unwrap();
+    assert_eq!(fresh.restore_state(&saved[..saved.len() - 1]),Err(FileError::InvalidHasherState));
+    assert_eq!(fresh.restore_state(&[saved.as_slice(),&[0]].concat()),Err(FileError::InvalidHasherState));
+    assert_eq!(fresh.restore_state(&[]),Err(FileError::InvalidHasherState));
+
+    // The hasher is unchanged after a failed restore
+    assert_eq!(fresh.return_bytes_hashed(),0);
+    assert_eq!(fresh.finalize().unwrap(),context.read_bytes(b""));
+}
+
+#[test]
+fn not_resumable(){
+    let context = ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1);
+    assert_eq!(context.hasher().unwrap_err(),FileError::NotResumable);
+}
@@ -0,0 +1,118 @@
+use std::io::{self, ErrorKind, Read};
+use std::time::Duration;
+
+use paranoid_hash::{Blake2bHasher,FileError,ParanoidHash,RetryPolicy};
+
+const DATA: &[u8] = b"data read from a flaky network filesystem";
+
+// Fails the first `failures` calls to `read()` with `kind`, then reads `data` a few bytes at a time
+struct Flaky<'a> {
+    data: &'a [u8],
+    failures: u32,
+    kind: ErrorKind,
+    calls: u32,
+}
+
+impl<'a> Flaky<'a> {
+    fn new(failures: u32, kind: ErrorKind) -> Self {
+        return Flaky { data: DATA, failures, kind, calls: 0 }
+    }
+}
+
+impl<'a> Read for Flaky<'a> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        self.calls += 1;
+        if self.calls <= self.failures {
+            return Err(io::Error::from(self.kind))
+        }
+        let length = buffer.len().min(self.data.len()).min(7);
+        buffer[..length].copy_from_slice(&self.data[..length]);
+        self.data = &self.data[length..];
+        return Ok(length)
+    }
+}
+
+fn policy(max_attempts: u32) -> ParanoidHash {
+    return ParanoidHash::default().retry_policy(Some(RetryPolicy { max_attempts, backoff: Duration::ZERO }))
+}
+
+#[test]
+fn no_policy_by_default(){
+    let context = ParanoidHash::default();
+    assert_eq!(context.return_retry_policy(),None);
+    assert_eq!(context.read_from_reader(Flaky::new(1,ErrorKind::TimedOut)),Err(FileError::Io(ErrorKind::TimedOut)));
+}
+
+#[test]
+fn interrupted_always_retried(){
+    let expected = ParanoidHash::default().read_bytes(DATA);
+    assert_eq!(ParanoidHash::default().read_from_reader(Flaky::new(10,ErrorKind::Interrupted)).unwrap(),expected);
+    assert_eq!(policy(1).read_from_reader(Flaky::new(10,ErrorKind::Interrupted)).unwrap(),expected);
+}
+
+#[test]
+fn recovers_within_attempts(){
+    let expected = ParanoidHash::default().read_bytes(DATA);
+    let mut reader = Flaky::new(2,ErrorKind::TimedOut);
+    assert_eq!(policy(3).read_from_reader(&mut reader).unwrap(),expected);
+    assert!(reader.calls > 3);
+}
+
+#[test]
+fn reports_attempts_when_exhausted(){
+    let mut reader = Flaky::new(5,ErrorKind::TimedOut);
+    assert_eq!(policy(3).read_from_reader(&mut reader),Err(FileError::RetriesExhausted { kind: ErrorKind::TimedOut, attempts: 3 }));
+    assert_eq!(reader.calls,3);
+}
+
+#[test]
+fn permanent_errors_not_retried(){
+    let mut reader = Flaky::new(5,ErrorKind::PermissionDenied);
+    assert_eq!(policy(3).read_from_reader(&mut reader),Err(FileError::Io(ErrorKind::PermissionDenied)));
+    assert_eq!(reader.calls,1);
+}
+
+#[test]
+fn unknown_errors_not_retried(){
+    for kind in [ErrorKind::UnexpectedEof,ErrorKind::Other] {
+        let mut reader = Flaky::new(5,kind);
+        assert_eq!(policy(3).read_from_reader(&mut reader),Err(FileError::Io(kind)));
+        assert_eq!(reader.calls,1);
+    }
+}
+
+#[test]
+fn network_errors_retried(){
+    let expected = ParanoidHash::default().read_bytes(DATA);
+    for kind in [ErrorKind::WouldBlock,ErrorKind::ResourceBusy,ErrorKind::StaleNetworkFileHandle,ErrorKind::ConnectionReset] {
+        assert_eq!(policy(3).read_from_reader(Flaky::new(2,kind)).unwrap(),expected);
+    }
+}
+
+#[test]
+fn files_unchanged(){
+    let path = "examples/example_file.txt";
+    assert_eq!(policy(3).read(path).unwrap(),ParanoidHash::default().read(path).unwrap());
+    assert_eq!(policy(3).window_size(1024).read(path).unwrap(),ParanoidHash::default().read(path).unwrap());
+}
+
+#[test]
+#[should_panic]
+fn zero_attempts(){
+    let _ = ParanoidHash::default().retry_policy(Some(RetryPolicy { max_attempts: 0, backoff: Duration::ZERO }));
+}
+
+#[test]
+fn every_reader_function_retries(){
+    use std::sync::atomic::AtomicBool;
+
+    let context = policy(3);
+    let expected = ParanoidHash::default().read_bytes(DATA);
+    assert_eq!(context.read_from_reader_cancellable(Flaky::new(2,ErrorKind::TimedOut),&AtomicBool::new(false)).unwrap(),expected);
+    assert_eq!(context.read_from_reader_with_timeout(Flaky::new(2,ErrorKind::TimedOut),Duration::from_secs(60)).unwrap(),expected);
+    assert_eq!(context.read_reader_with_limit(Flaky::new(2,ErrorKind::TimedOut),1024).unwrap().0,expected);
+    assert_eq!(context.read_from_reader_with_hasher(Flaky::new(2,ErrorKind::TimedOut),Blake2bHasher::new(64)).unwrap(),expected);
+
+    let result = context.read_from_reader_with_hasher(Flaky::new(5,ErrorKind::TimedOut),Blake2bHasher::new(64));
+    assert_eq!(result,Err(FileError::RetriesExhausted { kind: ErrorKind::TimedOut, attempts: 3 }));
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "zeroize")]
+
+use paranoid_hash::{ParanoidHash, SecretKey};
+use zeroize::Zeroize;
+
+#[test]
+fn secret_key_matches_slice_key(){
+    let context = ParanoidHash::default();
+    let raw: Vec<u8> = (0..32u8).collect();
+    let key = SecretKey::from_slice(&raw);
+
+    assert_eq!(context.read_with_key("tests/fixtures/report/alpha.txt",&key),context.read_with_key("tests/fixtures/report/alpha.txt",&raw[..]));
+}
+
+#[test]
+fn zeroize_wipes_key(){
+    let mut key = SecretKey::new(vec![0xAA; 64]);
+    key.zeroize();
+    assert!(key.is_empty());
+    assert_eq!(format!("{:?}",key),"SecretKey([REDACTED; 0])");
+}
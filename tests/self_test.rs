@@ -0,0 +1,6 @@
+use paranoid_hash::ParanoidHash;
+
+#[test]
+fn known_answers_pass(){
+    assert_eq!(ParanoidHash::self_test(),Ok(()));
+}
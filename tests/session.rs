@@ -0,0 +1,20 @@
+use paranoid_hash::{FileError, OsAlgorithm, OutputEncoding, ParanoidHash};
+
+#[test]
+fn matches_read_bytes(){
+    for context in [ParanoidHash::default(),ParanoidHash::new(20,OsAlgorithm::SHA224),ParanoidHash::new(32,OsAlgorithm::SHA256).output_encoding(OutputEncoding::Base32)] {
+        let mut session = context.session();
+        for length in [0usize,1,63,64,65,1000] {
+            let data: Vec<u8> = (0..length).map(|i| i as u8).collect();
+            let (blake2b,os) = session.hash_bytes_into(&data).unwrap();
+            assert_eq!((blake2b.to_string(),os.to_string()),context.read_bytes(&data));
+        }
+    }
+}
+
+#[test]
+fn honours_configuration(){
+    let mut session = ParanoidHash::default().reject_empty_input(true).session();
+    assert_eq!(session.hash_bytes_into(b""),Err(FileError::EmptyFile));
+    assert!(session.hash_bytes_into(b"x").is_ok());
+}
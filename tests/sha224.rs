@@ -0,0 +1,56 @@
+use paranoid_hash::{detect_algorithm, Algo, DigestKind, Manifest, OsAlgorithm, OsDigestArray, ParanoidHash, ParanoidHashMulti};
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+
+// sha224 from Python's hashlib
+const FILE_SHA224: &str = "DE83F7A1E5142382528E31D7473BA6B5C81A2A8A1175CD8E8A9BA8EC";
+
+#[test]
+fn nist_vectors(){
+    // FIPS 180-4 examples
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA224);
+    assert_eq!(context.read_str("").1,"D14A028C2A3A2BC9476102BB288234C415A2B01F828EA62AC5B3E42F");
+    assert_eq!(context.read_str("abc").1,"23097D223405D8228642A477BDA255B32AADBCE4BDA0B3F7E36C9DA7");
+    assert_eq!(context.read_str("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").1,"75388B16512776CC5DBA5DA1FD890150B0C6455CB4F58B1952522525");
+}
+
+#[test]
+fn read_file(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA224);
+    let (_,os) = context.read(FILE).unwrap();
+    assert_eq!(os,FILE_SHA224);
+    assert_eq!(context.read_using_std(FILE).unwrap().1,os);
+    assert_eq!(context.read_parallel(FILE).unwrap().1,os);
+
+    let multi = ParanoidHashMulti::new(&[Algo::Os(OsAlgorithm::SHA224)]).read(FILE).unwrap();
+    assert_eq!(multi[0].1,os);
+
+    let (_,sha224): ([u8; 32],OsDigestArray) = context.read_bytes_fixed(b"abc").unwrap();
+    assert!(matches!(sha224,OsDigestArray::SHA224(_)));
+    assert_eq!(sha224.as_bytes().len(),28);
+}
+
+#[test]
+fn digest_length(){
+    assert_eq!(OsAlgorithm::from_hex_digest(FILE_SHA224),Some(OsAlgorithm::SHA224));
+    assert_eq!(detect_algorithm(FILE_SHA224),vec![Algo::Os(OsAlgorithm::SHA224)]);
+
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA224);
+    assert!(context.is_valid_hex_digest(FILE_SHA224,DigestKind::Os).is_ok());
+    assert!(context.is_valid_hex_digest(&FILE_SHA224[..54],DigestKind::Os).is_err());
+    assert!(ParanoidHash::verify_file_unlabeled(FILE,FILE_SHA224).unwrap().passed);
+}
+
+#[test]
+fn tagged_and_manifest_round_trip(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA224);
+    let tagged = context.to_tagged_string_for_file(FILE).unwrap();
+    assert!(tagged.ends_with(&format!(";sha224:{}",FILE_SHA224)));
+    let (parsed,_) = ParanoidHash::parse_tagged(&tagged).unwrap();
+    assert_eq!(parsed.return_os_hash_algorithm(),OsAlgorithm::SHA224);
+
+    let manifest = context.manifest_dir("tests/fixtures/report").unwrap();
+    let out = tempfile::tempdir().unwrap();
+    manifest.save(out.path().join("MANIFEST")).unwrap();
+    assert_eq!(Manifest::load(out.path().join("MANIFEST")).unwrap(),manifest);
+}
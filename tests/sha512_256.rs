@@ -0,0 +1,48 @@
+use paranoid_hash::{Algo, DigestStatus, OsAlgorithm, ParanoidHash, ParanoidHashMulti};
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+
+#[test]
+fn nist_vectors(){
+    // FIPS 180-4 examples
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA512_256);
+    assert_eq!(context.read_str("").1,"C672B8D1EF56ED28AB87C3622C5114069BDD3AD7B8F9737498D0C01ECEF0967A");
+    assert_eq!(context.read_str("abc").1,"53048E2681941EF99B2E29B76B4C7DABE4C2D0C634FC6D46E0E2F13107E7AF23");
+    assert_eq!(context.read_str("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu").1,"3928E184FB8690F840DA3988121D31BE65CB9D3EF83EE6146FEAC861E19B563A");
+}
+
+#[test]
+fn read_file(){
+    // sha512_256 from Python's hashlib
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA512_256);
+    let (_,os) = context.read(FILE).unwrap();
+    assert_eq!(os,"B9D56C98A3408E1E725A520D8B435350EE92D0144A2D08AF92A58821EDAACBF1");
+    assert_eq!(os.len(),64);
+    assert_eq!(context.read_using_std(FILE).unwrap().1,os);
+    assert_eq!(context.read_parallel(FILE).unwrap().1,os);
+
+    let multi = ParanoidHashMulti::new(&[Algo::Os(OsAlgorithm::SHA512_256)]).read(FILE).unwrap();
+    assert_eq!(multi[0].1,os);
+}
+
+#[test]
+fn tagged_round_trip(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA512_256);
+    let tagged = context.to_tagged_string_for_file(FILE).unwrap();
+    assert!(tagged.ends_with(";sha512_256:B9D56C98A3408E1E725A520D8B435350EE92D0144A2D08AF92A58821EDAACBF1"));
+
+    let (parsed,_) = ParanoidHash::parse_tagged(&tagged).unwrap();
+    assert_eq!(parsed.return_os_hash_algorithm(),OsAlgorithm::SHA512_256);
+}
+
+#[test]
+fn detected_when_unlabeled(){
+    let sha512_256 = ParanoidHash::new(64,OsAlgorithm::SHA512_256).read(FILE).unwrap().1;
+    let verification = ParanoidHash::verify_file_unlabeled(FILE,&sha512_256).unwrap();
+    assert!(verification.passed);
+    assert_eq!(verification.os,DigestStatus::Match);
+
+    // SHA256 is still found when SHA512_256 is checked after it
+    let sha256 = ParanoidHash::new(64,OsAlgorithm::SHA256).read(FILE).unwrap().1;
+    assert_eq!(ParanoidHash::verify_file_unlabeled(FILE,&sha256).unwrap().os,DigestStatus::Match);
+}
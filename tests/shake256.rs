@@ -0,0 +1,97 @@
+#![cfg(feature = "xof")]
+
+use paranoid_hash::{Algo, FileError, LibAlgorithm, OsAlgorithm, ParanoidHash, ParanoidHashMulti};
+use std::fs;
+
+// Known answers from Python's hashlib.shake_256 (FIPS 202)
+const SHAKE256_EMPTY_128: &str = "46B9DD2B0BA88D13233B3FEB743EEB243FCD52EA62B81B82B50C27646ED5762FD75DC4DDD8C0F200CB05019D67B592F6FC821C49479AB48640292EACB3B7C4BE141E96616FB13957692CC7EDD0B45AE3DC07223C8E92937BEF84BC0EAB862853349EC75546F58FB7C2775C38462C5010D846C185C15111E595522A6BCD16CF86";
+const SHAKE256_ABC_128: &str = "483366601360A8771C6863080CC4114D8DB44530F8F1E1EE4F94EA37E78B5739D5A15BEF186A5386C75744C0527E1FAA9F8726E462A12A4FEB06BD8801E751E41385141204F329979FD3047A13C5657724ADA64D2470157B3CDC288620944D78DBCDDBD912993F0913F164FB2CE95131A2D09A3E6D51CBFC622720D7A75C6334";
+const SHA512_ABC: &str = "DDAF35A193617ABACC417349AE20413112E6FA4E89A97EA20A9EEEE64B55D39A2192992A274FC1A836BA3C23A3FEEBBD454D4423643CE80E2A9AC94FA54CA49F";
+
+// 200000 bytes of `i % 251`, squeezed to the largest digest size (1024 bytes)
+const LARGE_SIZE: usize = 200_000;
+const SHAKE256_LARGE_FIRST: &str = "E752C755B925E735F1F348646C6BE4DB";
+const SHAKE256_LARGE_LAST: &str = "1B51ADF7824BF4324FDE29F042982A4AEB96473A2E6A079A1375B6D736E748B4";
+
+fn shake256(digest_size: usize) -> ParanoidHash {
+    return ParanoidHash::new_with_library(LibAlgorithm::SHAKE256,digest_size,OsAlgorithm::SHA512)
+}
+
+#[test]
+fn known_answers(){
+    assert_eq!(shake256(128).read_bytes(b"").0,SHAKE256_EMPTY_128);
+    assert_eq!(shake256(128).read_bytes(b"abc"),(SHAKE256_ABC_128.to_string(),SHA512_ABC.to_string()));
+
+    // Shorter digests are prefixes of longer ones
+    assert_eq!(shake256(32).read_str("abc").0,&SHAKE256_ABC_128[..64]);
+    assert_eq!(shake256(1).read_str("abc").0,"48");
+}
+
+#[test]
+fn largest_digest_from_file(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("large");
+    let contents: Vec<u8> = (0..LARGE_SIZE).map(|i| (i % 251) as u8).collect();
+    fs::write(&path,&contents).unwrap();
+
+    let (digest,_) = shake256(1024).read(&path).unwrap();
+    assert_eq!(digest.len(),2048);
+    assert!(digest.starts_with(SHAKE256_LARGE_FIRST));
+    assert!(digest.ends_with(SHAKE256_LARGE_LAST));
+    assert_eq!(shake256(1024).read_from_reader(&contents[..]).unwrap().0,digest);
+}
+
+#[test]
+fn digest_size_bounds(){
+    assert!(std::panic::catch_unwind(|| shake256(0)).is_err());
+    assert!(std::panic::catch_unwind(|| shake256(1025)).is_err());
+    assert!(std::panic::catch_unwind(|| ParanoidHash::new_with_library(LibAlgorithm::BLAKE2B,65,OsAlgorithm::SHA512)).is_err());
+    assert_eq!(shake256(1024).return_digest_size(),1024);
+}
+
+#[test]
+fn cannot_be_keyed(){
+    assert_eq!(shake256(128).read_with_key("examples/example_file.txt",b"key"),Err(FileError::InvalidKey));
+}
+
+#[test]
+fn multi(){
+    let digests = ParanoidHashMulti::new(&[Algo::Shake256(128),Algo::Os(OsAlgorithm::SHA512)]).read_bytes(b"abc");
+    assert_eq!(digests[0].1,SHAKE256_ABC_128);
+    assert!(std::panic::catch_unwind(|| ParanoidHashMulti::new(&[Algo::Shake256(2000)])).is_err());
+}
+
+#[test]
+fn tagged_round_trip(){
+    let context = shake256(128);
+    let tagged = context.to_tagged_string_for_file("examples/example_file.txt").unwrap();
+    assert!(tagged.starts_with("shake256-128:"));
+
+    let (parsed,digests) = ParanoidHash::parse_tagged(&tagged).unwrap();
+    assert_eq!(parsed.return_library_algorithm(),LibAlgorithm::SHAKE256);
+    assert_eq!(parsed.return_digest_size(),128);
+    assert_eq!(digests,context.read("examples/example_file.txt").unwrap());
+}
+
+#[cfg(feature = "multihash")]
+#[test]
+fn multihash_round_trip(){
+    use paranoid_hash::HashDigest;
+
+    let (shake,_) = shake256(100).read_bytes_digests(b"abc").unwrap();
+    let multihash = shake.to_multihash().unwrap();
+    assert_eq!(&multihash[..3],&[0x19,100,0x48]);
+    assert_eq!(HashDigest::from_multihash(&multihash).unwrap(),shake);
+}
+
+#[test]
+fn manifest_round_trip(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("manifest");
+    let manifest = shake256(128).manifest_dir("tests/fixtures/dir_digest").unwrap();
+    manifest.save(&path).unwrap();
+
+    let loaded = paranoid_hash::Manifest::load(&path).unwrap();
+    assert_eq!(loaded.config().return_digest_size(),128);
+    assert!(loaded.verify("tests/fixtures/dir_digest").iter().all(|(_,status)| *status == paranoid_hash::ManifestStatus::Match));
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use paranoid_hash::{FileError,OsAlgorithm,ParanoidHash,SidecarOutcome};
+
+fn file() -> (tempfile::TempDir,PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("image.iso");
+    fs::write(&path,b"not really an iso image").unwrap();
+    return (dir,path)
+}
+
+fn sidecar(path: &Path, extension: &str) -> PathBuf {
+    return PathBuf::from(format!("{}.{}",path.display(),extension))
+}
+
+fn os_digest(path: &Path, os_hash: OsAlgorithm) -> String {
+    return ParanoidHash::new_allowing_weak(64,os_hash).read(path).unwrap().1.to_lowercase()
+}
+
+#[test]
+fn no_sidecar(){
+    let (_dir,path) = file();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::NoSidecarFound);
+}
+
+#[test]
+fn missing_file(){
+    let (dir,_) = file();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(dir.path().join("missing.iso")),Err(FileError::FileNotFound));
+}
+
+#[test]
+fn sha256_with_name(){
+    let (_dir,path) = file();
+    let sidecar = sidecar(&path,"sha256");
+    fs::write(&sidecar,format!("{}  image.iso\n",os_digest(&path,OsAlgorithm::SHA256))).unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Match(vec![sidecar.clone()]));
+
+    // Binary mode marker and a leading ./
+    fs::write(&sidecar,format!("{} *./image.iso\r\n",os_digest(&path,OsAlgorithm::SHA256))).unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Match(vec![sidecar]));
+}
+
+#[test]
+fn sha512_mismatch(){
+    let (_dir,path) = file();
+    let sidecar = sidecar(&path,"sha512");
+    fs::write(&sidecar,format!("{}  image.iso\n",os_digest(&path,OsAlgorithm::SHA512))).unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Match(vec![sidecar.clone()]));
+
+    fs::write(&path,b"tampered").unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Mismatch(vec![sidecar]));
+}
+
+#[test]
+fn bare_hash(){
+    let (_dir,path) = file();
+    let sidecar = sidecar(&path,"sha256");
+    fs::write(&sidecar,format!("\n  {}  \n\n",os_digest(&path,OsAlgorithm::SHA256).to_uppercase())).unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Match(vec![sidecar]));
+}
+
+#[test]
+fn b2_digest_size_from_length(){
+    let (_dir,path) = file();
+    let sidecar = sidecar(&path,"b2");
+    for size in [32,64] {
+        let blake2b = ParanoidHash::new(size,OsAlgorithm::SHA256).read(&path).unwrap().0;
+        fs::write(&sidecar,format!("{}  image.iso\n",blake2b)).unwrap();
+        assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Match(vec![sidecar.clone()]));
+    }
+}
+
+#[test]
+fn every_sidecar_checked(){
+    let (_dir,path) = file();
+    let sha256 = sidecar(&path,"sha256");
+    let sha512 = sidecar(&path,"sha512");
+    let b2 = sidecar(&path,"b2");
+    fs::write(&sha256,os_digest(&path,OsAlgorithm::SHA256)).unwrap();
+    fs::write(&sha512,os_digest(&path,OsAlgorithm::SHA512)).unwrap();
+    fs::write(&b2,ParanoidHash::default().read(&path).unwrap().0).unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Match(vec![sha256.clone(),sha512.clone(),b2.clone()]));
+
+    // Only the SHA512 sidecar is wrong
+    fs::write(&sha512,"0".repeat(128)).unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Mismatch(vec![sha512]));
+}
+
+#[test]
+fn several_names_listed(){
+    let (_dir,path) = file();
+    let sidecar = sidecar(&path,"sha256");
+    let listing = format!("{}  other.iso\n{}  image.iso\n","0".repeat(64),os_digest(&path,OsAlgorithm::SHA256));
+    fs::write(&sidecar,listing).unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path).unwrap(),SidecarOutcome::Match(vec![sidecar]));
+}
+
+#[test]
+fn invalid_sidecar(){
+    let (_dir,path) = file();
+    let sidecar = sidecar(&path,"sha256");
+    let invalid = |sidecar: &Path| Err(FileError::AtPath(sidecar.to_path_buf(),Box::new(FileError::InvalidDigest)));
+
+    // A SHA1 digest in a SHA256 sidecar
+    fs::write(&sidecar,os_digest(&path,OsAlgorithm::SHA1)).unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path),invalid(&sidecar));
+
+    fs::write(&sidecar,"not a digest at all\n").unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path),invalid(&sidecar));
+
+    fs::write(&sidecar,"").unwrap();
+    assert_eq!(ParanoidHash::default().verify_with_sidecar(&path),invalid(&sidecar));
+}
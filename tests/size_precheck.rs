@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::Path;
+
+use paranoid_hash::ParanoidHash;
+
+fn write(path: &Path, contents: &[u8]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path,contents).unwrap();
+}
+
+#[test]
+fn default_is_enabled(){
+    assert!(ParanoidHash::default().return_size_precheck());
+    assert!(!ParanoidHash::default().size_precheck(false).return_size_precheck());
+}
+
+#[test]
+fn different_sizes_shortcut(){
+    let dir = tempfile::tempdir().unwrap();
+    let (a,b) = (dir.path().join("a"),dir.path().join("b"));
+    write(&a,b"short");
+    write(&b,b"longer");
+
+    let comparison = ParanoidHash::default().compare_files_detailed(&a,&b).unwrap();
+    assert!(!comparison.equal);
+    assert!(comparison.size_shortcut);
+    assert_eq!((comparison.size_a,comparison.size_b),(5,6));
+    assert_eq!((comparison.digests_a,comparison.digests_b),(None,None));
+}
+
+#[test]
+fn different_sizes_without_precheck(){
+    let dir = tempfile::tempdir().unwrap();
+    let (a,b) = (dir.path().join("a"),dir.path().join("b"));
+    write(&a,b"short");
+    write(&b,b"longer");
+
+    let context = ParanoidHash::default().size_precheck(false);
+    let comparison = context.compare_files_detailed(&a,&b).unwrap();
+    assert!(!comparison.equal);
+    assert!(!comparison.size_shortcut);
+    assert_eq!(comparison.digests_a,Some(context.read(&a).unwrap()));
+    assert_eq!(comparison.digests_b,Some(context.read(&b).unwrap()));
+    assert!(!context.compare_files(&a,&b).unwrap());
+}
+
+#[test]
+fn same_size_is_hashed(){
+    let dir = tempfile::tempdir().unwrap();
+    let (a,b) = (dir.path().join("a"),dir.path().join("b"));
+    write(&a,b"paranoid");
+    write(&b,b"paranoiD");
+
+    let comparison = ParanoidHash::default().compare_files_detailed(&a,&b).unwrap();
+    assert!(!comparison.equal);
+    assert!(!comparison.size_shortcut);
+    assert!(comparison.digests_a.is_some() && comparison.digests_b.is_some());
+    assert_ne!(comparison.digests_a,comparison.digests_b);
+
+    write(&b,b"paranoid");
+    assert!(ParanoidHash::default().compare_files_detailed(&a,&b).unwrap().equal);
+}
+
+#[test]
+fn compare_dirs_records_shortcut(){
+    let dir = tempfile::tempdir().unwrap();
+    let (a,b) = (dir.path().join("a"),dir.path().join("b"));
+    write(&a.join("resized.txt"),b"short");
+    write(&b.join("resized.txt"),b"longer");
+
+    let report = ParanoidHash::default().compare_dirs(&a,&b).unwrap();
+    assert_eq!(report.mismatched.len(),1);
+    assert!(report.mismatched[0].size_shortcut);
+    assert_eq!(report.mismatched[0].digests_a,None);
+
+    let report = ParanoidHash::default().size_precheck(false).compare_dirs(&a,&b).unwrap();
+    assert_eq!(report.mismatched.len(),1);
+    assert!(!report.mismatched[0].size_shortcut);
+    assert!(report.mismatched[0].digests_a.is_some() && report.mismatched[0].digests_b.is_some());
+}
+
+#[test]
+fn duplicates_unique_size(){
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(&root.join("a"),b"photo one");
+    write(&root.join("a_copy"),b"photo one");
+    write(&root.join("unique"),b"something else entirely");
+
+    let duplicates = ParanoidHash::default().find_duplicates_with_errors(root).unwrap();
+    assert_eq!(duplicates.groups,vec![vec![root.join("a"),root.join("a_copy")]]);
+    assert_eq!(duplicates.unique_size,vec![root.join("unique")]);
+
+    // Without the precheck every file is hashed and the groups stay the same
+    let duplicates = ParanoidHash::default().size_precheck(false).find_duplicates_with_errors(root).unwrap();
+    assert_eq!(duplicates.groups,vec![vec![root.join("a"),root.join("a_copy")]]);
+    assert!(duplicates.unique_size.is_empty());
+}
@@ -0,0 +1,78 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::io::Write;
+use std::process::Command;
+use std::thread;
+
+use paranoid_hash::{Algo, FileError, OsAlgorithm, ParanoidHash, ParanoidHashMulti};
+
+const DATA: &[u8] = b"data arriving through a pipe\n";
+
+#[test]
+fn fifo_is_streamed(){
+    let dir = tempfile::tempdir().unwrap();
+    let fifo = dir.path().join("pipe");
+    assert!(Command::new("mkfifo").arg(&fifo).status().unwrap().success());
+
+    let writer_path = fifo.clone();
+    let writer = thread::spawn(move || {
+        let mut pipe = fs::OpenOptions::new().write(true).open(writer_path).unwrap();
+        for _ in 0..1000 {
+            pipe.write_all(DATA).unwrap();
+        }
+    });
+
+    let context = ParanoidHash::default();
+    let digests = context.read(&fifo).unwrap();
+    writer.join().unwrap();
+
+    assert_eq!(digests,context.read_bytes(&DATA.repeat(1000)));
+}
+
+#[test]
+fn fifo_in_parallel_mode(){
+    let dir = tempfile::tempdir().unwrap();
+    let fifo = dir.path().join("pipe");
+    assert!(Command::new("mkfifo").arg(&fifo).status().unwrap().success());
+
+    let writer_path = fifo.clone();
+    let writer = thread::spawn(move || {
+        fs::write(writer_path,DATA).unwrap();
+    });
+
+    let context = ParanoidHash::default();
+    let digests = context.read_parallel(&fifo).unwrap();
+    writer.join().unwrap();
+
+    assert_eq!(digests,context.read_bytes(DATA));
+}
+
+#[test]
+fn fifo_with_multi(){
+    let dir = tempfile::tempdir().unwrap();
+    let fifo = dir.path().join("pipe");
+    assert!(Command::new("mkfifo").arg(&fifo).status().unwrap().success());
+
+    // The writer connects once, so a second open of the FIFO would block forever
+    let writer_path = fifo.clone();
+    let writer = thread::spawn(move || {
+        fs::write(writer_path,DATA).unwrap();
+    });
+
+    let context = ParanoidHashMulti::new(&[Algo::Blake2b(64),Algo::Os(OsAlgorithm::SHA256)]);
+    let digests = context.read(&fifo).unwrap();
+    writer.join().unwrap();
+
+    assert_eq!(digests,context.read_bytes(DATA));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn endless_device_needs_a_limit(){
+    let context = ParanoidHash::default().max_file_size(Some(1024 * 1024));
+    match context.read("/dev/zero") {
+        Err(FileError::FileTooLarge { limit, .. }) => assert_eq!(limit,1024 * 1024),
+        other => panic!("unexpected result {:?}",other),
+    }
+}
@@ -0,0 +1,59 @@
+use paranoid_hash::ParanoidHash;
+use std::io::Write;
+
+#[test]
+fn stats_do_not_change_digests(){
+    let data: Vec<u8> = (0..2_000_000u32).map(|i| (i % 199) as u8).collect();
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&data).unwrap();
+    file.flush().unwrap();
+
+    let context = ParanoidHash::default();
+    let expected = context.read(file.path()).unwrap();
+
+    let (digests,stats) = context.read_with_stats(file.path()).unwrap();
+    assert_eq!(digests,expected);
+    assert_eq!(stats.bytes,2_000_000);
+    assert!(stats.mb_per_sec >= 0.0);
+
+    let (digests,stats) = context.read_parallel_with_stats(file.path()).unwrap();
+    assert_eq!(digests,expected);
+    assert_eq!(stats.bytes,2_000_000);
+
+    let (digests,stats) = context.read_bytes_with_stats(&data);
+    assert_eq!(digests,expected);
+    assert_eq!(stats.bytes,2_000_000);
+}
+
+#[test]
+fn report_entries_carry_stats(){
+    let report = ParanoidHash::default().report_files(&["tests/fixtures/report/alpha.txt","tests/fixtures/report/missing.txt"]);
+    assert_eq!(report.entries[0].stats.unwrap().bytes,6);
+    assert!(report.entries[1].stats.is_none());
+}
+
+// Yields the data 7 bytes at a time, then 13, alternating
+struct OddChunks<'a> {
+    data: &'a [u8],
+    turn: bool,
+}
+
+impl std::io::Read for OddChunks<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.turn = !self.turn;
+        let n = (if self.turn { 7 } else { 13 }).min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        return Ok(n)
+    }
+}
+
+#[test]
+fn reader_stats_count_every_byte(){
+    let data: Vec<u8> = (0..100_003u32).map(|i| (i % 251) as u8).collect();
+    let context = ParanoidHash::default();
+
+    let (digests,stats) = context.read_from_reader_with_stats(OddChunks { data: &data, turn: false }).unwrap();
+    assert_eq!(digests,context.read_bytes(&data));
+    assert_eq!(stats.bytes,100_003);
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "subtle")]
+
+use paranoid_hash::{Algo, HashDigest, OsAlgorithm, ParanoidHash};
+use subtle::ConstantTimeEq;
+
+const DIGESTS: [&str; 7] = [
+    "",
+    "ab",
+    "AB",
+    "abcd",
+    "ABCD",
+    "abce",
+    "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD",
+];
+
+#[test]
+fn choice_agrees_with_bool(){
+    for a in DIGESTS.iter() {
+        for b in DIGESTS.iter() {
+            assert_eq!(bool::from(ParanoidHash::compare_hash_ct(a,b)),ParanoidHash::compare_hash(a,b),"{:?} {:?}",a,b);
+            assert_eq!(bool::from(a.as_bytes().ct_eq(b.as_bytes())),ParanoidHash::compare_hash_bytes(a,b),"{:?} {:?}",a,b);
+        }
+    }
+}
+
+#[test]
+fn combined_checks(){
+    let (blake2b, sha512) = ParanoidHash::default().read_bytes(b"abc");
+    let both = ParanoidHash::compare_hash_ct(&blake2b,&blake2b) & ParanoidHash::compare_hash_ct(&sha512,sha512.to_lowercase());
+    assert!(bool::from(both));
+    let one = ParanoidHash::compare_hash_ct(&blake2b,&blake2b) & ParanoidHash::compare_hash_ct(&sha512,&blake2b);
+    assert!(!bool::from(one));
+}
+
+#[test]
+fn hash_digest(){
+    let (blake2b, sha256) = ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes_digests(b"abc").unwrap();
+    let same = HashDigest::new(Algo::Os(OsAlgorithm::SHA256),sha256.as_bytes().to_vec()).unwrap();
+    let other = HashDigest::new(Algo::Os(OsAlgorithm::SHA256),vec![0; 32]).unwrap();
+
+    for (a, b) in [(&sha256,&same),(&sha256,&other),(&sha256,&blake2b),(&blake2b,&blake2b)] {
+        assert_eq!(bool::from(a.ct_eq(b)),a == b);
+    }
+    // Same bytes from a different hash function
+    assert!(!bool::from(sha256.ct_eq(&HashDigest::new(Algo::Blake2b(32),sha256.as_bytes().to_vec()).unwrap())));
+}
@@ -0,0 +1,59 @@
+use paranoid_hash::{OsAlgorithm, OutputEncoding, ParanoidHash, TaggedParseError, VerifyPolicy};
+
+const FILE: &str = "examples/example_file.txt";
+
+#[test]
+fn round_trip_every_os_algorithm(){
+    for (size,os,tag) in [(64,OsAlgorithm::SHA512,"sha512"),(32,OsAlgorithm::SHA256,"sha256"),(20,OsAlgorithm::SHA1,"sha1"),(28,OsAlgorithm::SHA224,"sha224")] {
+        let context = ParanoidHash::new_allowing_weak(size,os);
+        let (blake2b,digest) = context.read_str("Hello");
+        let tagged = context.to_tagged_string(&blake2b,&digest).unwrap();
+        assert_eq!(tagged,format!("blake2b-{}:{};{}:{}",size,blake2b,tag,digest));
+
+        let (parsed,expected) = ParanoidHash::parse_tagged(&tagged).unwrap();
+        assert_eq!(parsed,context);
+        assert_eq!(expected,(blake2b,digest));
+    }
+}
+
+#[test]
+fn tagged_digests_are_hex_in_every_encoding(){
+    let context = ParanoidHash::new(48,OsAlgorithm::SHA256).output_encoding(OutputEncoding::Base32);
+    let (blake2b,sha256) = context.read_str("Hello");
+    let tagged = context.to_tagged_string(&blake2b,&sha256).unwrap();
+
+    let hex_context = ParanoidHash::new(48,OsAlgorithm::SHA256);
+    let (hex_blake2b,hex_sha256) = hex_context.read_str("Hello");
+    assert_eq!(tagged,hex_context.to_tagged_string(&hex_blake2b,&hex_sha256).unwrap());
+}
+
+#[test]
+fn verify_from_string(){
+    let tagged = ParanoidHash::new(32,OsAlgorithm::SHA256).to_tagged_string_for_file(FILE).unwrap();
+
+    // Only the string is needed to verify later
+    let (context,(blake2b,os)) = ParanoidHash::parse_tagged(format!("  {}\n",tagged.to_lowercase())).unwrap();
+    assert_eq!(context.return_digest_size(),32);
+    assert_eq!(context.return_os_hash_algorithm(),OsAlgorithm::SHA256);
+    assert!(context.verify_file(FILE,Some(&blake2b),Some(&os),VerifyPolicy::Both).unwrap().passed);
+    assert!(!context.verify_file("tests/fixtures/report/alpha.txt",Some(&blake2b),Some(&os),VerifyPolicy::Both).unwrap().passed);
+}
+
+#[test]
+fn parse_errors(){
+    let (blake2b,sha512) = ParanoidHash::default().read_str("Hello");
+
+    assert_eq!(ParanoidHash::parse_tagged(format!("blake3-64:{};sha512:{}",blake2b,sha512)),Err(TaggedParseError::UnknownAlgorithm("blake3-64".to_string())));
+    assert_eq!(ParanoidHash::parse_tagged(format!("blake2b-64:{};sha3:{}",blake2b,sha512)),Err(TaggedParseError::UnknownAlgorithm("sha3".to_string())));
+    assert_eq!(ParanoidHash::parse_tagged(format!("blake2b-65:{};sha512:{}",blake2b,sha512)),Err(TaggedParseError::InvalidDigestSize));
+    assert_eq!(ParanoidHash::parse_tagged(format!("blake2b-32:{};sha512:{}",blake2b,sha512)),Err(TaggedParseError::InvalidDigest));
+    assert_eq!(ParanoidHash::parse_tagged(format!("blake2b-64:{};sha256:{}",blake2b,sha512)),Err(TaggedParseError::InvalidDigest));
+    assert_eq!(ParanoidHash::parse_tagged(format!("blake2b-64:{}",blake2b)),Err(TaggedParseError::Malformed));
+    assert_eq!(ParanoidHash::parse_tagged(""),Err(TaggedParseError::Malformed));
+}
+
+#[test]
+fn digests_from_another_config_are_rejected(){
+    let (blake2b,sha512) = ParanoidHash::default().read_str("Hello");
+    assert_eq!(ParanoidHash::new(32,OsAlgorithm::SHA512).to_tagged_string(&blake2b,&sha512),Err(TaggedParseError::InvalidDigest));
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "tar")]
+
+use paranoid_hash::{FileError, ParanoidHash};
+use std::fs::File;
+use std::path::PathBuf;
+use tar::{Builder, EntryType, Header};
+
+// BLAKE2B-512 and SHA512 from Python's hashlib
+const FIRST_BLAKE2B: &str = "A81AA5CC97A4548372522A6EFD618FD8DED3F0D0B26C6B816FF7764A5B1A1B797FA4294B7A5DBB1DDC4675DAFA604E5D4D0C7BD23E83FCA9AC55D76EB5F2F191";
+const FIRST_SHA512: &str = "9FB41C0DB7133818BD20A19A71B999AA47042F662CC8B44630871ACE79BDBBBE5D4885EFBFB8FFC2E7FD8271FBF736695727EBC9F0DB57761AB361791A9158B1";
+const SECOND_BLAKE2B: &str = "1E87E0B8C0A817C20FA6DBB0197ACF8615973F5E1D4880C262914C315A9EFEAC49DCAEC62738D2CE5C2CECF5D8E77CF8502EE7117AC9BCAC9FAD3B2CF3AA4E55";
+const SECOND_SHA512: &str = "F86F992FF48881464EC0081FC5F4AC38DD7496AEB1312F78DFA7B19E377C57EF7104A8ACF826CF23997D87F6A2DFF8D21F94792DFE79E7231D31D31F73578AC5";
+
+fn header(entry_type: EntryType, size: u64) -> Header {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(entry_type);
+    header.set_size(size);
+    header.set_mode(0o644);
+    return header
+}
+
+// A directory, two regular files, a symbolic link and a hard link
+fn fixture(dir: &tempfile::TempDir) -> PathBuf {
+    let path = dir.path().join("fixture.tar");
+    let mut builder = Builder::new(File::create(&path).unwrap());
+
+    builder.append_data(&mut header(EntryType::Directory, 0), "docs/", &[][..]).unwrap();
+    let first = b"first member\n";
+    builder.append_data(&mut header(EntryType::Regular, first.len() as u64), "docs/first.txt", &first[..]).unwrap();
+    builder.append_link(&mut header(EntryType::Symlink, 0), "docs/link.txt", "first.txt").unwrap();
+    builder.append_link(&mut header(EntryType::Link, 0), "docs/hard.txt", "docs/first.txt").unwrap();
+    let second = b"second member, a little longer\n";
+    builder.append_data(&mut header(EntryType::Regular, second.len() as u64), "second.txt", &second[..]).unwrap();
+
+    builder.into_inner().unwrap();
+    return path
+}
+
+#[test]
+fn regular_members_are_hashed(){
+    let dir = tempfile::tempdir().unwrap();
+    let members = ParanoidHash::default().read_tar_members(fixture(&dir)).unwrap();
+    assert_eq!(members,vec![
+        ("docs/first.txt".to_string(),FIRST_BLAKE2B.to_string(),FIRST_SHA512.to_string()),
+        ("second.txt".to_string(),SECOND_BLAKE2B.to_string(),SECOND_SHA512.to_string()),
+    ]);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_compressed_archives(){
+    let dir = tempfile::tempdir().unwrap();
+    let tar = std::fs::read(fixture(&dir)).unwrap();
+    let path = dir.path().join("fixture.tar.gz");
+    let mut encoder = flate2::write::GzEncoder::new(File::create(&path).unwrap(), flate2::Compression::default());
+    std::io::Write::write_all(&mut encoder, &tar).unwrap();
+    encoder.finish().unwrap();
+
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_tar_members(&path).unwrap(),context.read_tar_members(dir.path().join("fixture.tar")).unwrap());
+
+    let mut corrupt = std::fs::read(&path).unwrap();
+    let middle = corrupt.len() / 2;
+    corrupt[middle] ^= 0xff;
+    std::fs::write(&path,corrupt).unwrap();
+    assert!(context.read_tar_members(&path).is_err());
+}
+
+#[test]
+fn member_errors_carry_the_member_path(){
+    let dir = tempfile::tempdir().unwrap();
+    let context = ParanoidHash::default().max_file_size(Some(16));
+    assert_eq!(context.read_tar_members(fixture(&dir)),Err(FileError::AtPath(PathBuf::from("second.txt"),Box::new(FileError::FileTooLarge { size: 31, limit: 16 }))));
+}
+
+#[test]
+fn missing_and_malformed_archives(){
+    let dir = tempfile::tempdir().unwrap();
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_tar_members(dir.path().join("missing.tar")),Err(FileError::FileNotFound));
+
+    let garbage = dir.path().join("garbage.tar");
+    std::fs::write(&garbage,vec![0x55u8; 1024]).unwrap();
+    assert!(matches!(context.read_tar_members(&garbage),Err(FileError::Io(_))));
+}
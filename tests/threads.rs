@@ -0,0 +1,71 @@
+use std::fs;
+
+use paranoid_hash::{FileError,ParanoidHash};
+
+fn files() -> (tempfile::TempDir,Vec<std::path::PathBuf>) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut paths = Vec::new();
+    for i in 0..20 {
+        let path = dir.path().join(format!("file_{:02}",i));
+        let contents: Vec<u8> = (0..i * 1000).map(|j| (j % 251) as u8).collect();
+        fs::write(&path,&contents).unwrap();
+        paths.push(path);
+    }
+    paths.insert(7,dir.path().join("missing"));
+    return (dir,paths)
+}
+
+#[test]
+fn default_is_logical_cpus(){
+    let cpus = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    assert_eq!(ParanoidHash::default().return_threads(),cpus);
+    assert_eq!(ParanoidHash::default().threads(3).return_threads(),3);
+}
+
+#[test]
+#[should_panic]
+fn zero_threads(){
+    let _ = ParanoidHash::default().threads(0);
+}
+
+#[test]
+fn read_files_same_for_any_thread_count(){
+    let (_dir,paths) = files();
+    let serial: Vec<_> = paths.iter().map(|path| (path.clone(),ParanoidHash::default().read(path))).collect();
+    assert_eq!(serial[7].1,Err(FileError::FileNotFound));
+
+    for threads in [1,2,8] {
+        assert_eq!(ParanoidHash::default().threads(threads).read_files(&paths),serial);
+    }
+}
+
+#[test]
+fn report_same_for_any_thread_count(){
+    let (dir,paths) = files();
+    let serial = ParanoidHash::default().threads(1).report_files(&paths);
+
+    for threads in [2,8] {
+        let report = ParanoidHash::default().threads(threads).report_files(&paths);
+        assert_eq!(report.entries.len(),serial.entries.len());
+        for (entry,expected) in report.entries.iter().zip(serial.entries.iter()) {
+            assert_eq!(entry.path,expected.path);
+            assert_eq!(entry.blake2b,expected.blake2b);
+            assert_eq!(entry.os_digest,expected.os_digest);
+            assert_eq!(entry.error,expected.error);
+        }
+
+        let report = ParanoidHash::default().threads(threads).report_dir(dir.path()).unwrap();
+        let paths: Vec<_> = report.entries.iter().map(|entry| entry.path.clone()).collect();
+        assert_eq!(paths,ParanoidHash::default().threads(1).report_dir(dir.path()).unwrap().entries.iter().map(|entry| entry.path.clone()).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn read_parallel_same_for_any_thread_count(){
+    let data: Vec<u8> = (0..1_000_000).map(|i| (i % 251) as u8).collect();
+    let expected = ParanoidHash::default().read_bytes(&data);
+
+    for threads in [1,2,8] {
+        assert_eq!(ParanoidHash::default().threads(threads).read_bytes_parallel(&data),expected);
+    }
+}
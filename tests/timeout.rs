@@ -0,0 +1,77 @@
+use paranoid_hash::{FileError, ParanoidHash, RetryPolicy};
+use std::io::{self, Read};
+use std::thread;
+use std::time::Duration;
+
+// Returns 100 bytes per read and stalls on the third read, like a network filesystem losing its server
+struct StallingReader {
+    reads: u32,
+}
+
+impl Read for StallingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reads += 1;
+        if self.reads == 3 {
+            thread::sleep(Duration::from_millis(200));
+        }
+        let length = buf.len().min(100);
+        buf[..length].iter_mut().for_each(|byte| *byte = 0x5A);
+        return Ok(length)
+    }
+}
+
+#[test]
+fn slow_reader_times_out(){
+    let context = ParanoidHash::default();
+    let result = context.read_from_reader_with_timeout(StallingReader { reads: 0 },Duration::from_millis(100));
+    // The stalled read finishes, then the deadline is noticed before the next one
+    assert_eq!(result,Err(FileError::TimedOut { bytes: 300 }));
+}
+
+// Fails the first read with a transient error, then returns `data`
+struct FlakyReader<'a> {
+    data: &'a [u8],
+    failed: bool,
+}
+
+impl<'a> Read for FlakyReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.failed {
+            self.failed = true;
+            return Err(io::Error::from(io::ErrorKind::TimedOut))
+        }
+        return self.data.read(buf)
+    }
+}
+
+#[test]
+fn transient_errors_retried(){
+    let data = b"read with a timeout from a flaky mount";
+    let context = ParanoidHash::default().retry_policy(Some(RetryPolicy { max_attempts: 2, backoff: Duration::ZERO }));
+    let digests = context.read_from_reader_with_timeout(FlakyReader { data, failed: false },Duration::from_secs(60)).unwrap();
+    assert_eq!(digests,context.read_bytes(data));
+
+    let result = ParanoidHash::default().read_from_reader_with_timeout(FlakyReader { data, failed: false },Duration::from_secs(60));
+    assert_eq!(result,Err(FileError::Io(io::ErrorKind::TimedOut)));
+}
+
+#[test]
+fn max_file_size_checked_before_reading(){
+    let path = "tests/fixtures/report/alpha.txt";
+    let context = ParanoidHash::default().max_file_size(Some(1));
+    assert_eq!(context.read_with_timeout(path,Duration::from_secs(60)),context.read_using_std(path));
+    assert!(context.read_with_timeout(path,Duration::from_secs(60)).is_err());
+}
+
+#[test]
+fn finishes_within_timeout(){
+    let context = ParanoidHash::default();
+    let path = "tests/fixtures/report/alpha.txt";
+    assert_eq!(context.read_with_timeout(path,Duration::from_secs(60)),context.read(path));
+}
+
+#[test]
+fn missing_file(){
+    let context = ParanoidHash::default();
+    assert_eq!(context.read_with_timeout("tests/fixtures/missing.txt",Duration::from_secs(60)),Err(FileError::FileNotFound));
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "tracing")]
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use paranoid_hash::{OsAlgorithm, ParanoidHash, VerifyPolicy};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+
+// Collects everything the subscriber writes
+#[derive(Clone,Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(bytes);
+        return Ok(bytes.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        return Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Captured {
+    type Writer = Captured;
+    fn make_writer(&'a self) -> Self::Writer {
+        return self.clone()
+    }
+}
+
+// Runs `f` with a subscriber logging closed spans and events at the DEBUG level and returns the output
+fn traced<F: FnOnce()>(f: F) -> String {
+    let captured = Captured::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(captured.clone())
+        .with_max_level(tracing::Level::DEBUG)
+        .with_span_events(FmtSpan::CLOSE)
+        .without_time()
+        .finish();
+    tracing::subscriber::with_default(subscriber, f);
+    let output = captured.0.lock().unwrap().clone();
+    return String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn read_span(){
+    let output = traced(|| { ParanoidHash::default().read("examples/example_file.txt").unwrap(); });
+
+    assert!(output.contains("read{path=examples/example_file.txt algorithm=blake2b-64+sha512 backend=\"mmap\" bytes=575 duration_us="),"{}",output);
+    assert!(output.contains("close"),"{}",output);
+}
+
+#[test]
+fn read_backends(){
+    let output = traced(|| {
+        ParanoidHash::default().window_size(100).read("examples/example_file.txt").unwrap();
+        ParanoidHash::default().read_using_std("examples/example_file.txt").unwrap();
+        ParanoidHash::default().read_from_reader(&b"abc"[..]).unwrap();
+    });
+
+    assert!(output.contains("backend=\"window\" bytes=575"),"{}",output);
+    assert!(output.contains("backend=\"std\" bytes=575"),"{}",output);
+    assert!(output.contains("read_from_reader{algorithm=blake2b-64+sha512 backend=\"stream\" bytes=3"),"{}",output);
+}
+
+#[test]
+fn read_dir_and_verify_spans(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    let (blake2b,sha256) = context.read("examples/example_file.txt").unwrap();
+    let output = traced(|| {
+        context.read_dir("tests/fixtures/dir_digest").unwrap();
+        context.verify_file("examples/example_file.txt",Some(&blake2b),Some(&sha256),VerifyPolicy::Both).unwrap();
+    });
+
+    assert!(output.contains("read_dir{path=tests/fixtures/dir_digest algorithm=blake2b-32+sha256 bytes="),"{}",output);
+    assert!(output.contains("verify_file{path=examples/example_file.txt algorithm=blake2b-32+sha256 bytes=575 duration_us="),"{}",output);
+    assert!(output.contains("passed=true"),"{}",output);
+}
+
+#[test]
+fn errors(){
+    let output = traced(|| {
+        assert!(ParanoidHash::default().read("tests/fixtures/does_not_exist").is_err());
+    });
+
+    assert!(output.contains("hashing failed error=file not found"),"{}",output);
+}
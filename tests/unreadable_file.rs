@@ -0,0 +1,46 @@
+use std::io::ErrorKind;
+use std::path::Path;
+
+use paranoid_hash::{Algo, FileError, OsAlgorithm, ParanoidHash, ParanoidHashMulti};
+
+// Every function that opens a file by path
+fn open_results(path: &Path) -> Vec<Result<(),FileError>> {
+    let context = ParanoidHash::default();
+    let key = [7u8; 32];
+    return vec![
+        context.read(path).map(|_| ()),
+        context.read_with_key(path,key).map(|_| ()),
+        context.read_using_std(path).map(|_| ()),
+        context.read_parallel(path).map(|_| ()),
+        context.read_with_stats(path).map(|_| ()),
+        ParanoidHashMulti::new(&[Algo::Blake2b(64),Algo::Os(OsAlgorithm::SHA256)]).read(path).map(|_| ()),
+    ]
+}
+
+#[cfg(unix)]
+#[test]
+fn permission_denied_is_an_error(){
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let locked = dir.path().join("locked.txt");
+    std::fs::write(&locked,b"secret").unwrap();
+    std::fs::set_permissions(&locked,std::fs::Permissions::from_mode(0o000)).unwrap();
+
+    // Root can read the file anyway
+    if std::fs::read(&locked).is_ok() {
+        return
+    }
+
+    for result in open_results(&locked) {
+        assert_eq!(result,Err(FileError::Io(ErrorKind::PermissionDenied)));
+    }
+}
+
+#[test]
+fn directory_is_an_error(){
+    let dir = tempfile::tempdir().unwrap();
+    for result in open_results(dir.path()) {
+        assert!(matches!(result,Err(FileError::Io(_))),"{:?}",result);
+    }
+}
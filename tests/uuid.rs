@@ -0,0 +1,50 @@
+use paranoid_hash::{uuid_from_digest, HexError, OsAlgorithm, ParanoidHash};
+
+fn assert_version_and_variant(uuid: &str) {
+    let bytes = hex::decode(uuid.replace('-', "")).unwrap();
+    assert_eq!(bytes.len(),16);
+    assert_eq!(bytes[6] >> 4,8,"{}",uuid);
+    assert_eq!(bytes[8] >> 6,0b10,"{}",uuid);
+}
+
+#[test]
+fn known_inputs(){
+    let output = ParanoidHash::default().read_bytes_output(b"abc").unwrap();
+    assert_eq!(output.to_uuid().unwrap(),"ba80a53f-981c-8d0d-aa27-97b69f12f6e9");
+    assert_eq!(output.to_uuid_combined().unwrap(),"672f909e-0b7d-87b7-a666-e4ff3132b7d8");
+
+    let output = ParanoidHash::default().read_bytes_output(b"").unwrap();
+    assert_eq!(output.to_uuid().unwrap(),"786a02f7-4201-8903-86c6-fd852552d272");
+    assert_eq!(output.to_uuid_combined().unwrap(),"b7e9e3c2-3cee-81be-b792-d5d5f33f5275");
+
+    let output = ParanoidHash::new(32,OsAlgorithm::SHA256).read_bytes_output(b"abc").unwrap();
+    assert_eq!(output.to_uuid().unwrap(),"bddd813c-6342-8972-b171-ef3fee98579b");
+    assert_eq!(output.to_uuid_combined().unwrap(),"07a59783-ec43-8698-b030-afe1b33675b8");
+}
+
+#[test]
+fn version_and_variant_bits(){
+    for input in [&b""[..], b"abc", b"paranoid"] {
+        let output = ParanoidHash::default().read_bytes_output(input).unwrap();
+        assert_version_and_variant(&output.to_uuid().unwrap());
+        assert_version_and_variant(&output.to_uuid_combined().unwrap());
+    }
+    assert_eq!(uuid_from_digest(&[0x00; 16]).unwrap(),"00000000-0000-8000-8000-000000000000");
+    assert_eq!(uuid_from_digest(&[0xFF; 64]).unwrap(),"ffffffff-ffff-8fff-bfff-ffffffffffff");
+}
+
+#[test]
+fn same_as_digest(){
+    let output = ParanoidHash::default().read_bytes_output(b"abc").unwrap();
+    assert_eq!(output.blake2b.to_uuid(),output.to_uuid());
+    assert_eq!(uuid_from_digest(output.blake2b.as_bytes()),output.to_uuid());
+    assert_ne!(output.to_uuid(),output.to_uuid_combined());
+}
+
+#[test]
+fn short_digests(){
+    let output = ParanoidHash::new(8,OsAlgorithm::SHA256).read_bytes_output(b"abc").unwrap();
+    assert_eq!(output.to_uuid(),Err(HexError::TooShort { required: 16, actual: 8 }));
+    assert_eq!(output.to_uuid_combined(),Err(HexError::TooShort { required: 16, actual: 8 }));
+    assert_eq!(uuid_from_digest(&[]),Err(HexError::TooShort { required: 16, actual: 0 }));
+}
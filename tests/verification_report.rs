@@ -0,0 +1,79 @@
+use paranoid_hash::{DigestStatus, OsAlgorithm, ParanoidHash, Verification, VerifyPolicy};
+
+const DATA: &[u8] = b"a release tarball";
+// The length of both a 32 byte BLAKE2B digest and SHA256
+const WRONG: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+fn context() -> ParanoidHash {
+    return ParanoidHash::new(32,OsAlgorithm::SHA256)
+}
+
+#[test]
+fn both_match(){
+    let (blake2b, sha256) = context().read_bytes(DATA);
+    let report = context().verify_bytes_report(DATA,Some(&blake2b),Some(&sha256)).unwrap();
+    assert!(report.passed);
+    assert_eq!((report.blake2b,report.os),(DigestStatus::Match,DigestStatus::Match));
+    assert!(!report.is_split());
+    assert_eq!((report.blake2b_digest,report.os_digest),(blake2b,sha256));
+}
+
+#[test]
+fn both_mismatch(){
+    let (blake2b, sha256) = context().read_bytes(DATA);
+    let report = context().verify_bytes_report(DATA,Some(WRONG),Some(WRONG)).unwrap();
+    assert!(!report.passed);
+    assert_eq!((report.blake2b,report.os),(DigestStatus::Mismatch,DigestStatus::Mismatch));
+    assert!(!report.is_split());
+    // The computed digests are reported even when nothing matched
+    assert_eq!((report.blake2b_digest,report.os_digest),(blake2b,sha256));
+}
+
+#[test]
+fn split(){
+    let (blake2b, sha256) = context().read_bytes(DATA);
+
+    let report = context().verify_bytes_report(DATA,Some(&blake2b),Some(WRONG)).unwrap();
+    assert!(!report.passed);
+    assert_eq!((report.blake2b,report.os),(DigestStatus::Match,DigestStatus::Mismatch));
+    assert!(report.is_split());
+
+    let report = context().verify_bytes_report(DATA,Some(WRONG),Some(&sha256)).unwrap();
+    assert!(!report.passed);
+    assert_eq!((report.blake2b,report.os),(DigestStatus::Mismatch,DigestStatus::Match));
+    assert!(report.is_split());
+}
+
+#[test]
+fn not_checked(){
+    let (blake2b, sha256) = context().read_bytes(DATA);
+
+    let report = context().verify_bytes_report(DATA,Some(&blake2b),None).unwrap();
+    assert!(report.passed);
+    assert_eq!((report.blake2b,report.os),(DigestStatus::Match,DigestStatus::NotChecked));
+    assert!(!report.is_split());
+    assert_eq!(report.os_digest,sha256);
+
+    let report = context().verify_bytes_report(DATA,None,Some(WRONG)).unwrap();
+    assert!(!report.passed);
+    assert_eq!((report.blake2b,report.os),(DigestStatus::NotChecked,DigestStatus::Mismatch));
+    assert!(!report.is_split());
+}
+
+#[test]
+fn file_report(){
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("release.tar");
+    std::fs::write(&path,DATA).unwrap();
+    let (blake2b, sha256) = context().read(&path).unwrap();
+
+    // A split still passes under `Either`, but the report shows which algorithm failed
+    let report = context().verify_file_report(&path,Some(WRONG),Some(&sha256),VerifyPolicy::Either).unwrap();
+    assert!(report.passed);
+    assert!(report.is_split());
+    assert_eq!(report.blake2b,DigestStatus::Mismatch);
+    assert_eq!((report.blake2b_digest.as_str(),report.os_digest.as_str()),(blake2b.as_str(),sha256.as_str()));
+
+    let verification = context().verify_file(&path,Some(WRONG),Some(&sha256),VerifyPolicy::Either).unwrap();
+    assert_eq!(verification,Verification::from(report));
+}
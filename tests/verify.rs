@@ -0,0 +1,58 @@
+use paranoid_hash::{DigestStatus, OsAlgorithm, ParanoidHash, VerifyPolicy};
+
+const FILE: &str = "tests/fixtures/report/alpha.txt";
+// The length of both a 32 byte BLAKE2B digest and SHA256
+const WRONG: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// (BLAKE2B matches, OS matches) -> expected result for (Both, Either, LibraryOnly, OsOnly)
+const MATRIX: &[((bool,bool),[bool;4])] = &[
+    ((true,true),[true,true,true,true]),
+    ((false,true),[false,true,false,true]),
+    ((true,false),[false,true,true,false]),
+    ((false,false),[false,false,false,false]),
+];
+
+const POLICIES: [VerifyPolicy;4] = [VerifyPolicy::Both,VerifyPolicy::Either,VerifyPolicy::LibraryOnly,VerifyPolicy::OsOnly];
+
+fn status(matches: bool) -> DigestStatus {
+    if matches { DigestStatus::Match } else { DigestStatus::Mismatch }
+}
+
+#[test]
+fn policy_mismatch_matrix(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    let (blake2b,sha256) = context.read(FILE).unwrap();
+
+    for ((blake2b_matches,os_matches),expected) in MATRIX {
+        let expected_blake2b = if *blake2b_matches { blake2b.as_str() } else { WRONG };
+        let expected_os = if *os_matches { sha256.as_str() } else { WRONG };
+
+        for (policy,passed) in POLICIES.iter().zip(expected.iter()) {
+            let verification = context.verify_file(FILE,Some(expected_blake2b),Some(expected_os),*policy).unwrap();
+            assert_eq!(verification.passed,*passed,"{:?} with blake2b match {} and os match {}",policy,blake2b_matches,os_matches);
+            assert_eq!(verification.policy,*policy);
+            assert_eq!(verification.blake2b,status(*blake2b_matches));
+            assert_eq!(verification.os,status(*os_matches));
+        }
+    }
+}
+
+#[test]
+fn missing_digest_is_not_checked(){
+    let context = ParanoidHash::new(32,OsAlgorithm::SHA256);
+    let sha256 = context.read(FILE).unwrap().1.to_lowercase();
+
+    let verification = context.verify_file(FILE,None,Some(&sha256),VerifyPolicy::OsOnly).unwrap();
+    assert!(verification.passed);
+    assert_eq!(verification.blake2b,DigestStatus::NotChecked);
+
+    assert!(context.verify_file(FILE,None,Some(&sha256),VerifyPolicy::Either).unwrap().passed);
+    assert!(!context.verify_file(FILE,None,Some(&sha256),VerifyPolicy::Both).unwrap().passed);
+    assert!(!context.verify_file(FILE,None,Some(&sha256),VerifyPolicy::LibraryOnly).unwrap().passed);
+}
+
+#[test]
+fn missing_file(){
+    let context = ParanoidHash::default();
+    assert!(context.verify_file("tests/fixtures/report/missing.txt",None,None,VerifyPolicy::default()).is_err());
+}
@@ -0,0 +1,85 @@
+use paranoid_hash::{DigestStatus, FileError, OsAlgorithm, OutputEncoding, ParanoidHash, VerifyPolicy};
+
+const DATA: &[u8] = b"a downloaded blob";
+// The length of both a 32 byte BLAKE2B digest and SHA256
+const WRONG: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+fn context() -> ParanoidHash {
+    return ParanoidHash::new(32,OsAlgorithm::SHA256)
+}
+
+#[test]
+fn only_blake2b(){
+    let (blake2b, _) = context().read_bytes(DATA);
+    let verification = context().verify_bytes(DATA,Some(&blake2b),None).unwrap();
+    assert!(verification.passed);
+    assert_eq!(verification.policy,VerifyPolicy::LibraryOnly);
+    assert_eq!((verification.blake2b,verification.os),(DigestStatus::Match,DigestStatus::NotChecked));
+
+    let verification = context().verify_bytes(DATA,Some(WRONG),None).unwrap();
+    assert!(!verification.passed);
+    assert_eq!((verification.blake2b,verification.os),(DigestStatus::Mismatch,DigestStatus::NotChecked));
+}
+
+#[test]
+fn only_os(){
+    let (_, sha256) = context().read_bytes(DATA);
+    let verification = context().verify_bytes(DATA,None,Some(&sha256)).unwrap();
+    assert!(verification.passed);
+    assert_eq!(verification.policy,VerifyPolicy::OsOnly);
+    assert_eq!((verification.blake2b,verification.os),(DigestStatus::NotChecked,DigestStatus::Match));
+
+    let verification = context().verify_bytes(DATA,None,Some(WRONG)).unwrap();
+    assert!(!verification.passed);
+    assert_eq!((verification.blake2b,verification.os),(DigestStatus::NotChecked,DigestStatus::Mismatch));
+}
+
+#[test]
+fn both(){
+    let (blake2b, sha256) = context().read_bytes(DATA);
+    let verification = context().verify_bytes(DATA,Some(&blake2b),Some(&sha256)).unwrap();
+    assert!(verification.passed);
+    assert_eq!(verification.policy,VerifyPolicy::Both);
+    assert_eq!((verification.blake2b,verification.os),(DigestStatus::Match,DigestStatus::Match));
+
+    // One mismatch fails the verification but both comparisons are recorded
+    let verification = context().verify_bytes(DATA,Some(&blake2b),Some(WRONG)).unwrap();
+    assert!(!verification.passed);
+    assert_eq!((verification.blake2b,verification.os),(DigestStatus::Match,DigestStatus::Mismatch));
+}
+
+#[test]
+fn neither_is_an_error(){
+    assert_eq!(context().verify_bytes(DATA,None,None),Err(FileError::NoExpectedDigest));
+    assert_eq!(context().verify_str("payload",None,None),Err(FileError::NoExpectedDigest));
+}
+
+#[test]
+fn verify_str_matches_bytes(){
+    let (blake2b, sha256) = context().read_str("message body");
+    let verification = context().verify_str("message body",Some(&blake2b),Some(&sha256)).unwrap();
+    assert_eq!(verification,context().verify_bytes(b"message body",Some(&blake2b),Some(&sha256)).unwrap());
+    assert!(verification.passed);
+    assert!(!context().verify_str(String::from("message body!"),Some(&blake2b),Some(&sha256)).unwrap().passed);
+}
+
+#[test]
+fn expected_digests_in_any_encoding(){
+    let (blake2b, sha256) = context().output_encoding(OutputEncoding::Base32).read_bytes(DATA);
+    assert!(context().verify_bytes(DATA,Some(&blake2b),Some(&format!(" {}\n",sha256))).unwrap().passed);
+    let (blake2b, _) = context().read_bytes(DATA);
+    assert!(context().verify_bytes(DATA,Some(&blake2b.to_lowercase()),None).unwrap().passed);
+}
+
+#[test]
+fn invalid_expected_digest(){
+    assert_eq!(context().verify_bytes(DATA,Some("not a digest"),None),Err(FileError::InvalidDigest));
+    assert_eq!(context().verify_bytes(DATA,None,Some("ABCD")),Err(FileError::InvalidDigest));
+}
+
+#[test]
+fn empty_input(){
+    let (blake2b, _) = context().read_bytes(b"");
+    assert!(context().verify_bytes(b"",Some(&blake2b),None).unwrap().passed);
+    assert_eq!(context().reject_empty_input(true).verify_bytes(b"",Some(&blake2b),None),Err(FileError::EmptyFile));
+}
@@ -0,0 +1,75 @@
+use std::io::{self, Cursor, Read};
+
+use paranoid_hash::{DigestStatus, FileError, OsAlgorithm, ParanoidHash, VerifyPolicy};
+
+const DATA: &[u8] = b"a large artifact streaming in";
+// The length of both a 32 byte BLAKE2B digest and SHA256
+const WRONG: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+fn context() -> ParanoidHash {
+    return ParanoidHash::new(32,OsAlgorithm::SHA256)
+}
+
+// Returns some bytes and then fails, like a dropped connection
+struct FailingReader {
+    sent: bool,
+}
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.sent {
+            return Err(io::Error::new(io::ErrorKind::ConnectionReset,"connection reset"))
+        }
+        self.sent = true;
+        buf[..4].copy_from_slice(b"part");
+        return Ok(4)
+    }
+}
+
+#[test]
+fn matches(){
+    let (blake2b, sha256) = context().read_bytes(DATA);
+    let report = context().verify_reader(Cursor::new(DATA),Some(&blake2b),Some(&sha256)).unwrap();
+    assert!(report.passed);
+    assert_eq!(report.policy,VerifyPolicy::Both);
+    assert_eq!((report.blake2b,report.os),(DigestStatus::Match,DigestStatus::Match));
+    assert_eq!(report.bytes,DATA.len() as u64);
+    assert_eq!((report.blake2b_digest,report.os_digest),(blake2b,sha256));
+}
+
+#[test]
+fn mismatches(){
+    let (blake2b, _) = context().read_bytes(DATA);
+    let report = context().verify_reader(Cursor::new(DATA),Some(&blake2b),Some(WRONG)).unwrap();
+    assert!(!report.passed);
+    assert!(report.is_split());
+    assert_eq!(report.bytes,DATA.len() as u64);
+
+    let report = context().verify_reader(Cursor::new(DATA),None,Some(WRONG)).unwrap();
+    assert!(!report.passed);
+    assert_eq!(report.policy,VerifyPolicy::OsOnly);
+    assert_eq!((report.blake2b,report.os),(DigestStatus::NotChecked,DigestStatus::Mismatch));
+}
+
+#[test]
+fn reader_error(){
+    let (blake2b, sha256) = context().read_bytes(DATA);
+    let result = context().verify_reader(FailingReader { sent: false },Some(&blake2b),Some(&sha256));
+    assert_eq!(result,Err(FileError::Io(io::ErrorKind::ConnectionReset)));
+}
+
+#[test]
+fn expected_checked_first(){
+    // Nothing is read when the expected digests cannot match
+    let reader = FailingReader { sent: true };
+    assert_eq!(context().verify_reader(reader,None,None),Err(FileError::NoExpectedDigest));
+    let reader = FailingReader { sent: true };
+    assert_eq!(context().verify_reader(reader,Some("abc"),None),Err(FileError::InvalidDigest));
+}
+
+#[test]
+fn matches_other_reports(){
+    let (blake2b, sha256) = context().read_bytes(DATA);
+    let from_reader = context().verify_reader(Cursor::new(DATA),Some(&blake2b),Some(&sha256)).unwrap();
+    assert_eq!(from_reader,context().verify_bytes_report(DATA,Some(&blake2b),Some(&sha256)).unwrap());
+}
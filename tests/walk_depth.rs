@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use paranoid_hash::{FileError, ParanoidHash, WalkOptions};
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for file in ["top.txt","a/one.txt","a/b/two.txt","a/b/c/three.txt"] {
+        let path = dir.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path,file).unwrap();
+    }
+    return dir
+}
+
+fn visited(dir: &Path, options: WalkOptions) -> Result<Vec<PathBuf>,FileError> {
+    let manifest = ParanoidHash::default().walk_options(options).manifest_dir(dir)?;
+    return Ok(manifest.entries.into_iter().map(|entry| entry.path).collect())
+}
+
+fn paths(files: &[&str]) -> Vec<PathBuf> {
+    return files.iter().map(PathBuf::from).collect()
+}
+
+fn depth(max_depth: Option<usize>) -> WalkOptions {
+    return WalkOptions { max_depth, ..WalkOptions::default() }
+}
+
+#[test]
+fn depth_zero_is_immediate_entries(){
+    let dir = tree();
+    assert_eq!(visited(dir.path(),depth(Some(0))).unwrap(),paths(&["top.txt"]));
+}
+
+#[test]
+fn depth_limits_levels(){
+    let dir = tree();
+    assert_eq!(visited(dir.path(),depth(Some(1))).unwrap(),paths(&["a/one.txt","top.txt"]));
+    assert_eq!(visited(dir.path(),depth(Some(2))).unwrap(),paths(&["a/b/two.txt","a/one.txt","top.txt"]));
+    assert_eq!(visited(dir.path(),depth(Some(3))).unwrap(),visited(dir.path(),depth(None)).unwrap());
+    assert_eq!(visited(dir.path(),depth(None)).unwrap().len(),4);
+}
+
+#[test]
+fn depth_applies_to_read_dir(){
+    let dir = tree();
+    let top = tempfile::tempdir().unwrap();
+    fs::write(top.path().join("top.txt"),"top.txt").unwrap();
+
+    let context = ParanoidHash::default().walk_options(depth(Some(0)));
+    assert_eq!(context.read_dir(dir.path()).unwrap(),ParanoidHash::default().read_dir(top.path()).unwrap());
+    assert_ne!(ParanoidHash::default().read_dir(dir.path()).unwrap(),ParanoidHash::default().read_dir(top.path()).unwrap());
+}
+
+#[cfg(unix)]
+mod symlinks {
+    use std::os::unix::fs::symlink;
+    use std::time::{Duration, Instant};
+
+    use paranoid_hash::{FileError, WalkOptions};
+
+    use super::{paths, tree, visited};
+
+    fn follow(max_depth: Option<usize>) -> WalkOptions {
+        return WalkOptions { follow_symlinks: true, max_depth, ..WalkOptions::default() }
+    }
+
+    #[test]
+    fn links_are_skipped_by_default(){
+        let dir = tree();
+        symlink(dir.path().join("a/b"),dir.path().join("linked")).unwrap();
+        symlink(dir.path().join("top.txt"),dir.path().join("linked.txt")).unwrap();
+        assert_eq!(visited(dir.path(),WalkOptions::default()).unwrap().len(),4);
+    }
+
+    #[test]
+    fn links_are_followed(){
+        let dir = tree();
+        symlink(dir.path().join("a/b"),dir.path().join("linked")).unwrap();
+        symlink(dir.path().join("top.txt"),dir.path().join("linked.txt")).unwrap();
+        symlink(dir.path().join("missing"),dir.path().join("broken")).unwrap();
+        assert_eq!(visited(dir.path(),follow(None)).unwrap(),paths(&["a/b/c/three.txt","a/b/two.txt","a/one.txt","linked.txt","linked/c/three.txt","linked/two.txt","top.txt"]));
+        assert_eq!(visited(dir.path(),follow(Some(1))).unwrap(),paths(&["a/one.txt","linked.txt","linked/two.txt","top.txt"]));
+    }
+
+    #[test]
+    fn loop_is_an_error(){
+        let dir = tree();
+        symlink("../..",dir.path().join("a/b/loop")).unwrap();
+
+        let start = Instant::now();
+        let error = visited(dir.path(),follow(None)).unwrap_err();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(error,FileError::AtPath(dir.path().join("a/b/loop"),Box::new(FileError::SymlinkLoop)));
+    }
+
+    #[test]
+    fn link_to_itself_is_an_error(){
+        let dir = tree();
+        symlink(".",dir.path().join("a/self")).unwrap();
+        assert_eq!(visited(dir.path(),follow(None)).unwrap_err(),FileError::AtPath(dir.path().join("a/self"),Box::new(FileError::SymlinkLoop)));
+    }
+
+    #[test]
+    fn loop_below_max_depth_is_not_reached(){
+        let dir = tree();
+        symlink("../..",dir.path().join("a/b/loop")).unwrap();
+        assert_eq!(visited(dir.path(),follow(Some(1))).unwrap(),paths(&["a/one.txt","top.txt"]));
+    }
+
+    #[test]
+    fn loop_is_not_followed_by_default(){
+        let dir = tree();
+        symlink("../..",dir.path().join("a/b/loop")).unwrap();
+        assert_eq!(visited(dir.path(),WalkOptions::default()).unwrap().len(),4);
+    }
+}
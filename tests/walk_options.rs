@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use paranoid_hash::{ParanoidHash, WalkOptions};
+
+const FILES: &[&str] = &[
+    "Cargo.toml",
+    "src/lib.rs",
+    "src/.lib.rs.swp",
+    "src/nested/deep/mod.rs",
+    "target/debug/build.log",
+    "docs/target/guide.md",
+    ".git/HEAD",
+    "notes.txt",
+];
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for file in FILES {
+        let path = dir.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path,file).unwrap();
+    }
+    return dir
+}
+
+fn visited(dir: &Path, include: &[&str], exclude: &[&str]) -> Vec<PathBuf> {
+    let options = WalkOptions {
+        include: include.iter().map(|p| p.to_string()).collect(),
+        exclude: exclude.iter().map(|p| p.to_string()).collect(),
+        ..WalkOptions::default()
+    };
+    let manifest = ParanoidHash::default().walk_options(options).manifest_dir(dir).unwrap();
+    return manifest.entries.into_iter().map(|entry| entry.path).collect()
+}
+
+fn paths(files: &[&str]) -> Vec<PathBuf> {
+    return files.iter().map(PathBuf::from).collect()
+}
+
+#[test]
+fn default_visits_everything(){
+    let dir = tree();
+    assert_eq!(visited(dir.path(),&[],&[]).len(),FILES.len());
+}
+
+#[test]
+fn nested_excludes(){
+    let dir = tree();
+    // `target/` matches at any depth, `/target/` would only match at the root
+    assert_eq!(visited(dir.path(),&[],&["target/",".git/","*.swp"]),paths(&["Cargo.toml","notes.txt","src/lib.rs","src/nested/deep/mod.rs"]));
+    assert_eq!(visited(dir.path(),&[],&["/target/",".git/","*.swp"]),paths(&["Cargo.toml","docs/target/guide.md","notes.txt","src/lib.rs","src/nested/deep/mod.rs"]));
+    assert_eq!(visited(dir.path(),&[],&["src/nested"]),paths(&[".git/HEAD","Cargo.toml","docs/target/guide.md","notes.txt","src/.lib.rs.swp","src/lib.rs","target/debug/build.log"]));
+}
+
+#[test]
+fn exclude_wins_over_include(){
+    let dir = tree();
+    assert_eq!(visited(dir.path(),&["*.rs","*.toml"],&["lib.rs"]),paths(&["Cargo.toml","src/nested/deep/mod.rs"]));
+}
+
+#[test]
+fn double_star_patterns(){
+    let dir = tree();
+    assert_eq!(visited(dir.path(),&["src/**/*.rs"],&[]),paths(&["src/lib.rs","src/nested/deep/mod.rs"]));
+    assert_eq!(visited(dir.path(),&["**/deep/**"],&[]),paths(&["src/nested/deep/mod.rs"]));
+    assert_eq!(visited(dir.path(),&[],&["**/target/**"]),paths(&[".git/HEAD","Cargo.toml","notes.txt","src/.lib.rs.swp","src/lib.rs","src/nested/deep/mod.rs"]));
+}
+
+#[test]
+fn include_directory_covers_its_files(){
+    let dir = tree();
+    assert_eq!(visited(dir.path(),&["docs"],&[]),paths(&["docs/target/guide.md"]));
+    assert_eq!(visited(dir.path(),&["[CN]*"],&[]),paths(&["Cargo.toml"]));
+    assert_eq!(visited(dir.path(),&["[!C]*.t?t"],&[]),paths(&["notes.txt"]));
+}
+
+#[test]
+fn directory_digest_honours_patterns(){
+    let dir = tree();
+    let options = WalkOptions { exclude: vec!["target/".to_string()], ..WalkOptions::default() };
+    let filtered = ParanoidHash::default().walk_options(options).read_dir(dir.path()).unwrap();
+
+    fs::remove_dir_all(dir.path().join("target")).unwrap();
+    fs::remove_dir_all(dir.path().join("docs/target")).unwrap();
+    assert_eq!(filtered,ParanoidHash::default().read_dir(dir.path()).unwrap());
+}
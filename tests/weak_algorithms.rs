@@ -0,0 +1,50 @@
+use paranoid_hash::{FileError, LibAlgorithm, OsAlgorithm, ParanoidHash, VerifyPolicy};
+
+const FILE: &str = "examples/example_file.txt";
+
+#[test]
+fn weak_algorithms(){
+    assert!(OsAlgorithm::SHA1.is_weak());
+    for os_hash in [OsAlgorithm::SHA224,OsAlgorithm::SHA256,OsAlgorithm::SHA512,OsAlgorithm::SHA512_256] {
+        assert!(!os_hash.is_weak());
+    }
+}
+
+#[test]
+#[should_panic]
+fn new_rejects_sha1(){
+    let _ = ParanoidHash::new(20,OsAlgorithm::SHA1);
+}
+
+#[test]
+#[should_panic]
+fn new_with_library_rejects_sha1(){
+    let _ = ParanoidHash::new_with_library(LibAlgorithm::BLAKE2B,20,OsAlgorithm::SHA1);
+}
+
+#[test]
+fn try_new(){
+    assert_eq!(ParanoidHash::try_new(20,OsAlgorithm::SHA1).unwrap_err(),FileError::WeakAlgorithm(OsAlgorithm::SHA1));
+    assert_eq!(ParanoidHash::try_new(0,OsAlgorithm::SHA256).unwrap_err(),FileError::InvalidDigestSize(0));
+    assert_eq!(ParanoidHash::try_new(65,OsAlgorithm::SHA256).unwrap_err(),FileError::InvalidDigestSize(65));
+    assert_eq!(ParanoidHash::try_new(32,OsAlgorithm::SHA256).unwrap(),ParanoidHash::new(32,OsAlgorithm::SHA256));
+}
+
+#[test]
+fn allowing_weak_verifies_existing_digests(){
+    // SHA1 of "abc" from FIPS 180
+    let context = ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1);
+    let verification = context.verify_bytes(b"abc",None,Some("A9993E364706816ABA3E25717850C26C9CD0D89D")).unwrap();
+    assert!(verification.passed);
+
+    // Strong algorithms are unaffected
+    assert_eq!(ParanoidHash::new_allowing_weak(64,OsAlgorithm::SHA512),ParanoidHash::new(64,OsAlgorithm::SHA512));
+}
+
+#[test]
+fn recorded_sha1_digests_load(){
+    let context = ParanoidHash::new_allowing_weak(20,OsAlgorithm::SHA1);
+    let tagged = context.to_tagged_string_for_file(FILE).unwrap();
+    let (parsed,(blake2b,sha1)) = ParanoidHash::parse_tagged(&tagged).unwrap();
+    assert!(parsed.verify_file(FILE,Some(&blake2b),Some(&sha1),VerifyPolicy::Both).unwrap().passed);
+}
@@ -0,0 +1,21 @@
+use paranoid_hash::{OsAlgorithm, ParanoidHash};
+use std::io::Write;
+
+#[test]
+fn windowed_read_matches_mapped_read(){
+    let data: Vec<u8> = (0..100_003u32).map(|i| (i % 241) as u8).collect();
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(&data).unwrap();
+    file.flush().unwrap();
+
+    let context = ParanoidHash::new(64,OsAlgorithm::SHA256);
+    let expected = context.read_bytes(&data);
+    assert_eq!(context.read(file.path()).unwrap(),expected);
+
+    for window in [1usize,7,4096,100_002] {
+        let windowed = context.clone().window_size(window);
+        assert_eq!(windowed.read(file.path()).unwrap(),expected);
+        assert_eq!(windowed.read_parallel(file.path()).unwrap(),expected);
+        assert_eq!(windowed.read_with_key(file.path(),b"key").unwrap(),context.read_with_key(file.path(),b"key").unwrap());
+    }
+}
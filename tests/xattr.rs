@@ -0,0 +1,77 @@
+#![cfg(all(feature = "xattr", unix))]
+
+use std::fs;
+use std::io::Write;
+
+use paranoid_hash::{FileError, OsAlgorithm, OutputEncoding, ParanoidHash, XattrOutcome, XATTR_NAME};
+
+// A stamped file, or `None` if the temporary directory is on a filesystem without user extended attributes
+fn stamped(context: &ParanoidHash, contents: &[u8]) -> Option<tempfile::NamedTempFile> {
+    let mut file = tempfile::NamedTempFile::new_in(env!("CARGO_TARGET_TMPDIR")).unwrap();
+    file.write_all(contents).unwrap();
+    match context.store_xattr(file.path()) {
+        Ok(()) => return Some(file),
+        Err(FileError::XattrUnsupported) => return None,
+        Err(e) => panic!("[Error] {}", e),
+    }
+}
+
+#[test]
+fn store_and_verify(){
+    let context = ParanoidHash::default();
+    let file = match stamped(&context,b"stamped contents") { Some(file) => file, None => return };
+    assert_eq!(context.verify_xattr(file.path()).unwrap(),XattrOutcome::Match);
+
+    let (library, os) = context.read(file.path()).unwrap();
+    let stamp = String::from_utf8(xattr::get(file.path(),XATTR_NAME).unwrap().unwrap()).unwrap();
+    assert_eq!(stamp,format!("paranoid-hash-xattr v1 blake2b-64+sha512 {} {}",library.to_lowercase(),os.to_lowercase()));
+}
+
+#[test]
+fn modified_file_mismatches(){
+    let context = ParanoidHash::default();
+    let file = match stamped(&context,b"stamped contents") { Some(file) => file, None => return };
+    // Rewriting in place keeps the attribute
+    fs::OpenOptions::new().write(true).open(file.path()).unwrap().write_all(b"S").unwrap();
+    assert_eq!(context.verify_xattr(file.path()).unwrap(),XattrOutcome::Mismatch);
+
+    context.store_xattr(file.path()).unwrap();
+    assert_eq!(context.verify_xattr(file.path()).unwrap(),XattrOutcome::Match);
+}
+
+#[test]
+fn not_stamped(){
+    let file = tempfile::NamedTempFile::new_in(env!("CARGO_TARGET_TMPDIR")).unwrap();
+    let outcome = ParanoidHash::default().verify_xattr(file.path()).unwrap();
+    assert!(outcome == XattrOutcome::NotStamped || outcome == XattrOutcome::UnsupportedFilesystem);
+}
+
+#[test]
+fn output_encoding_does_not_matter(){
+    let file = match stamped(&ParanoidHash::default(),b"encoded") { Some(file) => file, None => return };
+    assert_eq!(ParanoidHash::default().output_encoding(OutputEncoding::Base32).verify_xattr(file.path()).unwrap(),XattrOutcome::Match);
+}
+
+#[test]
+fn other_configuration_is_invalid(){
+    let file = match stamped(&ParanoidHash::default(),b"configured") { Some(file) => file, None => return };
+    assert_eq!(ParanoidHash::new(32,OsAlgorithm::SHA512).verify_xattr(file.path()),Err(FileError::InvalidDigest));
+    assert_eq!(ParanoidHash::new(64,OsAlgorithm::SHA256).verify_xattr(file.path()),Err(FileError::InvalidDigest));
+}
+
+#[test]
+fn malformed_stamp_is_invalid(){
+    let context = ParanoidHash::default();
+    let file = match stamped(&context,b"malformed") { Some(file) => file, None => return };
+    for stamp in ["", "paranoid-hash-xattr v2 blake2b-64+sha512 00 00", "paranoid-hash-xattr v1 blake2b-64+sha512 zz 00", "garbage"] {
+        xattr::set(file.path(),XATTR_NAME,stamp.as_bytes()).unwrap();
+        assert_eq!(context.verify_xattr(file.path()),Err(FileError::InvalidDigest));
+    }
+}
+
+#[test]
+fn missing_file(){
+    let context = ParanoidHash::default();
+    assert_eq!(context.store_xattr("does/not/exist"),Err(FileError::FileNotFound));
+    assert_eq!(context.verify_xattr("does/not/exist"),Err(FileError::FileNotFound));
+}